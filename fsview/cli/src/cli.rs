@@ -7,23 +7,34 @@
 //! The CLI is built with `clap` and uses *`derive`* code mark up to define the
 //! commands and their arguments. It also makes use of modules in the `toolslib`
 //! library to provide timing information and initialize the logging subsystem.
-use clap::{AppSettings, ArgAction, Parser, Subcommand};
+use clap::{AppSettings, ArgAction, Args, Parser, Subcommand};
 use fs as lib;
 use lib::domain::{get_session, FolderMd, Metadata, Session};
-use log4rs::Handle;
+use serde::Serialize;
 use std::{fmt, io, path::PathBuf, result};
 use toolslib::{
     fmt::commafy,
-    logs::{initialize as log_initialize, LogError, LogProperties},
+    logs::{initialize as log_initialize, LogError, LogFormat, LogGuard, LogProperties},
     mbufmt, rptcols, rptrow,
     stopwatch::StopWatch,
     text,
 };
 
+mod compare_databases;
+mod empty_files;
 mod file_duplicates;
+mod filename_folders;
+mod folder_depth;
+mod folder_fingerprint;
+mod folder_tree;
 mod init_database;
+mod largest_folders;
 mod list_folders;
 mod load_database;
+mod maintenance;
+mod orphaned_problems;
+mod recent_roots;
+mod schema_version;
 
 /// The result of calling a CLI function.
 type Result<T> = result::Result<T, Error>;
@@ -79,6 +90,18 @@ impl From<text::Error> for Error {
         Error::from(format!("toolslib{error}").as_str())
     }
 }
+/// Create a CLI error from a `serde_json::Error`.
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::from(format!("JSON: {error}").as_str())
+    }
+}
+/// Create a CLI error from a `csv::Error`.
+impl From<csv::Error> for Error {
+    fn from(error: csv::Error) -> Self {
+        Error::from(format!("CSV: {error}").as_str())
+    }
+}
 
 /// The command line interface (CLI) properties and commands.
 #[derive(Parser, Debug)]
@@ -142,6 +165,88 @@ fn parse_db_name(db_name: &str) -> result::Result<PathBuf, String> {
     }
 }
 
+/// The `JSON` report arguments shared by commands.
+#[derive(Args, Debug)]
+pub struct JsonArgs {
+    /// The report will be in JSON format.
+    #[clap(long, group = "format")]
+    json: bool,
+    /// For JSON reports output will be pretty printed.
+    #[clap(short = 'P', long, requires = "json")]
+    pretty: bool,
+}
+impl JsonArgs {
+    /// Identifies if the report will be `JSON`.
+    pub fn json(&self) -> bool {
+        self.json
+    }
+    /// Identifies if the `JSON` report will be pretty printed.
+    pub fn pretty(&self) -> bool {
+        self.pretty
+    }
+}
+
+/// The report format arguments shared by commands, text is the default.
+#[derive(Args, Debug)]
+pub struct ReportArgs {
+    /// The `JSON` report arguments.
+    #[clap(flatten)]
+    json_args: JsonArgs,
+    /// The report will be in CSV format.
+    #[clap(long, group = "format")]
+    csv: bool,
+}
+impl ReportArgs {
+    /// Identifies if the report will be plain text.
+    pub fn text(&self) -> bool {
+        !(self.json() || self.csv)
+    }
+    /// Identifies if the report will be `JSON`.
+    pub fn json(&self) -> bool {
+        self.json_args.json()
+    }
+    /// Identifies if the report will be `CSV`.
+    pub fn csv(&self) -> bool {
+        self.csv
+    }
+    /// Identifies if the `JSON` report will be pretty printed.
+    pub fn pretty(&self) -> bool {
+        self.json_args.pretty()
+    }
+}
+
+/// Writes a `JSON` report.
+///
+/// # Arguments
+///
+/// * `writer` is where the report will be written.
+/// * `document` is what will be reported.
+/// * `pretty` controls if the document will be pretty printed or not.
+fn write_json<T: Serialize + ?Sized>(writer: &mut dyn io::Write, document: &T, pretty: bool) -> Result<()> {
+    match pretty {
+        true => serde_json::to_writer_pretty(&mut *writer, document)?,
+        false => serde_json::to_writer(&mut *writer, document)?,
+    }
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes a `CSV` report, the field names of the first record will be used as the header.
+///
+/// # Arguments
+///
+/// * `writer` is where the report will be written.
+/// * `records` are the rows of the report.
+fn write_csv<T: Serialize>(writer: &mut dyn io::Write, records: impl IntoIterator<Item = T>) -> Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    for record in records {
+        csv_writer.serialize(record)?;
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
+
 /// The commands supported by the CLI.
 #[derive(Subcommand, Debug)]
 pub enum Commands {
@@ -173,6 +278,83 @@ pub enum Commands {
         #[clap(flatten)]
         args: file_duplicates::CommandArgs,
     },
+    /// Reports files unique to the database or another database.
+    #[clap(name="compare", setting=AppSettings::DeriveDisplayOrder)]
+    CompareDatabases {
+        /// The [`command arguments`](compare_databases::CommandArgs) used to compare the databases.
+        #[clap(flatten)]
+        args: compare_databases::CommandArgs,
+    },
+    /// Reports filenames found in more than one folder.
+    #[clap(name="names", setting=AppSettings::DeriveDisplayOrder)]
+    FilenameFolders {
+        /// The [`command arguments`](filename_folders::CommandArgs) used to find filenames in multiple folders.
+        #[clap(flatten)]
+        args: filename_folders::CommandArgs,
+    },
+    /// Reports problems whose folder is no longer in the database.
+    #[clap(name="orphans", setting=AppSettings::DeriveDisplayOrder)]
+    OrphanedProblems {
+        /// The [`command arguments`](orphaned_problems::CommandArgs) used to find orphaned problems.
+        #[clap(flatten)]
+        args: orphaned_problems::CommandArgs,
+    },
+    /// Reports files that are 0 bytes in size.
+    #[clap(name="empty", setting=AppSettings::DeriveDisplayOrder)]
+    EmptyFiles {
+        /// The [`command arguments`](empty_files::CommandArgs) used to find empty files.
+        #[clap(flatten)]
+        args: empty_files::CommandArgs,
+    },
+    /// Reports the most deeply nested folder.
+    #[clap(name="depth", setting=AppSettings::DeriveDisplayOrder)]
+    FolderDepth {
+        /// The [`command arguments`](folder_depth::CommandArgs) used to find the deepest folder.
+        #[clap(flatten)]
+        args: folder_depth::CommandArgs,
+    },
+    /// Reports the folders that directly contain the most files.
+    #[clap(name="largest", setting=AppSettings::DeriveDisplayOrder)]
+    LargestFolders {
+        /// The [`command arguments`](largest_folders::CommandArgs) used to find the folders with the most files.
+        #[clap(flatten)]
+        args: largest_folders::CommandArgs,
+    },
+    /// Reports the root folders most recently added to the database.
+    #[clap(name="recent", setting=AppSettings::DeriveDisplayOrder)]
+    RecentRoots {
+        /// The [`command arguments`](recent_roots::CommandArgs) used to report the recently added root folders.
+        #[clap(flatten)]
+        args: recent_roots::CommandArgs,
+    },
+    /// Reports a fingerprint of a folders content.
+    #[clap(name="fingerprint", setting=AppSettings::DeriveDisplayOrder)]
+    FolderFingerprint {
+        /// The [`command arguments`](folder_fingerprint::CommandArgs) used to fingerprint the folder.
+        #[clap(flatten)]
+        args: folder_fingerprint::CommandArgs,
+    },
+    /// Exports a folder hierarchy as JSON.
+    #[clap(name="tree", setting=AppSettings::DeriveDisplayOrder)]
+    FolderTree {
+        /// The [`command arguments`](folder_tree::CommandArgs) used to export the folder hierarchy.
+        #[clap(flatten)]
+        args: folder_tree::CommandArgs,
+    },
+    /// Reports the database schema version.
+    #[clap(name="schema", setting=AppSettings::DeriveDisplayOrder)]
+    SchemaVersion {
+        /// The [`command arguments`](schema_version::CommandArgs) used to report the schema version.
+        #[clap(flatten)]
+        args: schema_version::CommandArgs,
+    },
+    /// Reclaims unused database space.
+    #[clap(name="maint", setting=AppSettings::DeriveDisplayOrder)]
+    Maintenance {
+        /// The [`command arguments`](maintenance::CommandArgs) used to maintain the database.
+        #[clap(flatten)]
+        args: maintenance::CommandArgs,
+    },
 }
 
 /// Prepares the CLI for execution of commands. This really needs to go somewhere else but
@@ -181,21 +363,22 @@ pub enum Commands {
 /// # Arguments
 ///
 /// * `cli` arguments will be used to initialize the CLI.
-pub fn initialize(cli: &Cli) -> Result<Handle> {
-    let handle = log_initialize(LogProperties {
+pub fn initialize(cli: &Cli) -> Result<LogGuard> {
+    let log_guard = log_initialize(LogProperties {
         level: match cli.verbosity {
             0 => log::LevelFilter::Warn,
             1 => log::LevelFilter::Info,
             2 => log::LevelFilter::Debug,
             _ => log::LevelFilter::Trace,
         },
+        format: LogFormat::Pattern,
         console_pattern: None,
         logfile_pattern: None,
         logfile_path: cli.logfile_path.clone(),
         logfile_append: cli.append_log,
         file_loggers: vec![String::from("toolslib"), String::from("fsview")],
     })?;
-    Ok(handle)
+    Ok(log_guard)
 }
 
 /// Executes the CLI command.
@@ -229,6 +412,50 @@ pub fn execute(cli: Cli) -> Result<()> {
             let file_duplicates = file_duplicates::Command::new(args);
             file_duplicates.execute(&session)
         }
+        Some(Commands::FilenameFolders { args }) => {
+            let filename_folders = filename_folders::Command::new(args);
+            filename_folders.execute(&session)
+        }
+        Some(Commands::CompareDatabases { args }) => {
+            let compare_databases = compare_databases::Command::new(args);
+            compare_databases.execute(&session)
+        }
+        Some(Commands::EmptyFiles { args }) => {
+            let empty_files = empty_files::Command::new(args);
+            empty_files.execute(&session)
+        }
+        Some(Commands::FolderDepth { args }) => {
+            let folder_depth = folder_depth::Command::new(args);
+            folder_depth.execute(&session)
+        }
+        Some(Commands::LargestFolders { args }) => {
+            let largest_folders = largest_folders::Command::new(args);
+            largest_folders.execute(&session)
+        }
+        Some(Commands::RecentRoots { args }) => {
+            let recent_roots = recent_roots::Command::new(args);
+            recent_roots.execute(&session)
+        }
+        Some(Commands::FolderFingerprint { args }) => {
+            let folder_fingerprint = folder_fingerprint::Command::new(args);
+            folder_fingerprint.execute(&session)
+        }
+        Some(Commands::FolderTree { args }) => {
+            let folder_tree = folder_tree::Command::new(args);
+            folder_tree.execute(&session)
+        }
+        Some(Commands::Maintenance { args }) => {
+            let maintenance = maintenance::Command::new(args);
+            maintenance.execute(&session)
+        }
+        Some(Commands::SchemaVersion { args }) => {
+            let schema_version = schema_version::Command::new(args);
+            schema_version.execute(&session)
+        }
+        Some(Commands::OrphanedProblems { args }) => {
+            let orphaned_problems = orphaned_problems::Command::new(args);
+            orphaned_problems.execute(&session)
+        }
         _ => Err(Error::from("Command not recognized!!!")),
     }
 }
@@ -0,0 +1,71 @@
+//! # The subcommand that compares the files in two databases.
+//!
+use clap::Args;
+
+use super::{
+    lib::domain::FileMd,
+    mbufmt, rptcols, rptrow,
+    text::{get_writer, write_strings, Report},
+    PathBuf, Result, Session, StopWatch,
+};
+
+/// The compare databases command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {
+    /// The database that will be compared to the current database.
+    #[clap(value_name = "OTHER_DB", forbid_empty_values = true, parse(try_from_str = super::parse_db_name))]
+    other_db: PathBuf,
+}
+
+/// The compare databases command definition.
+pub struct Command {
+    /// The commands arguments.
+    args: CommandArgs,
+}
+
+impl Command {
+
+    /// Creates an instance of the command.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that will find files unique to each database.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `session` - the `domain` session that will be compared to the other database.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        let database_compare = session.compare_databases(&self.args.other_db)?;
+        let mut report = Report::from(rptcols!(<=(2), >, =));
+        let other_db = self.args.other_db.as_path().display().to_string();
+        add_files(&mut report, &session.db(), &database_compare.db_only);
+        add_files(&mut report, &other_db, &database_compare.other_db_only);
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, report.into_iter())?;
+        log::info!("compare databases took {elapsed}");
+        Ok(())
+    }
+}
+
+/// Add the files only found in a database to the report.
+///
+/// # Arguments
+///
+/// * `report` - the report that will be updated.
+/// * `db` - the name of the database the files were found in.
+/// * `files` - the files only found in the database.
+fn add_files(report: &mut Report, db: &str, files: &[FileMd]) {
+    report.text(rptrow!(= format!("Only in {db}:")));
+    if files.is_empty() {
+        report.text(rptrow!(_, _, "None"));
+    }
+    for file_md in files {
+        report.text(rptrow!(_, mbufmt!(file_md.size), file_md.pathname.as_str()));
+    }
+}
@@ -0,0 +1,53 @@
+//! # The subcommand that reports files that are 0 bytes in size.
+//!
+use clap::Args;
+
+use super::{
+    rptcols, rptrow,
+    text::{get_writer, write_strings, Report},
+    Result, Session, StopWatch,
+};
+
+/// The empty files command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {}
+
+/// The empty files command definition.
+pub struct Command {
+    /// The commands arguments.
+    #[allow(unused)]
+    args: CommandArgs,
+}
+
+impl Command {
+    /// Creates an instance of the command.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that will find empty files.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - the `domain` session that will be checked for empty files.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        let empty_files = session.empty_files()?;
+        let mut report = Report::from(rptcols!(>, >, <));
+        report.header(rptrow!(^ "Id", ^ "Folder Id", ^ "Pathname")).separator("-");
+        if empty_files.is_empty() {
+            report.text(rptrow!(_, _, "None"));
+        }
+        for file_md in &empty_files {
+            report.text(rptrow!(file_md.id, file_md.parent_id, file_md.pathname.as_str()));
+        }
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, report.into_iter())?;
+        log::info!("empty files took {elapsed}");
+        Ok(())
+    }
+}
@@ -7,18 +7,23 @@
 //! what other reports will be built from.
 //! * List all folders that have matching files and what files matched. It will also indicate if
 //! one of the matching folders did not not have matching files.
+//! * List all folders that are exact duplicates, every filename is present and every file matches.
 //! * List all folders that did not have a matching file. It will also indicate if a folder had
 //! a file match with another folder.
+//!
+//! The folder match, exact duplicate, and no match reports can also be generated as `JSON`.
 use super::{
     commafy,
     lib::domain::{
-        DuplicateFolders, DuplicateFoldersMatch, FolderAnalysisMd, FolderGroupMd, FoldersMatchMd, FoldersNoMatch,
+        DuplicateFolders, DuplicateFoldersMatch, FolderAnalysisMd, FolderGroupMd, FolderNoMatchMd, FoldersMatchMd,
+        FoldersNoMatch, SimilarFolders,
     },
     mbufmt, rptcols, rptrow,
     text::{get_writer, write_strings, Report},
-    FolderMd, PathBuf, Result, Session, StopWatch,
+    write_json, Error, FolderMd, JsonArgs, PathBuf, Result, Session, StopWatch,
 };
 use clap::Args;
+use std::{fs::File, io};
 
 /// The duplicate files command arguments.
 #[derive(Args, Debug)]
@@ -32,14 +37,29 @@ pub struct CommandArgs {
     /// Generate a report of folder groups with duplicate files.
     #[clap(short = 'm', long = "match", group = "cmd")]
     matches: bool,
+    /// Generate a report of folder groups that are exact duplicates of each other.
+    #[clap(short = 'x', long, group = "cmd")]
+    exact: bool,
     /// Generate a report of folders that were not in a match group.
     #[clap(short = 'n', long = "no_match", group = "cmd")]
     none: bool,
+    /// Generate a report of folders whose filenames are at least THRESHOLD (0.0 to 1.0) similar.
+    #[clap(long, value_name = "THRESHOLD", parse(try_from_str = parse_threshold), group = "cmd")]
+    similar: Option<f64>,
+    /// Only report folder groups that have at least N folders.
+    #[clap(long = "min-folders", value_name = "N", default_value_t = 2)]
+    min_folders: usize,
     /// Summarize the duplicate files metadata (default).
     #[clap(short, long = "sum", group = "cmd")]
     summary: bool,
+    /// Export the duplicate files metadata as CSV.
+    #[clap(
+        long = "csv", value_name="FILE", forbid_empty_values = true,
+        parse(try_from_str = super::parse_filename), group = "cmd"
+    )]
+    pub csv_path: Option<PathBuf>,
     #[clap(
-        short = 'r', long = "report", value_name="FILE", forbid_empty_values = true,
+        short = 'r', long = "report", visible_alias = "file", value_name="FILE", forbid_empty_values = true,
         parse(try_from_str = super::parse_filename), requires = "list",
         group = "out"
     )]
@@ -48,6 +68,21 @@ pub struct CommandArgs {
     /// Append to the report file, otherwise overwrite
     #[clap(short = 'A', long = "append", requires("out"))]
     pub append_log: bool,
+    /// The report format.
+    #[clap(flatten)]
+    json_args: JsonArgs,
+}
+
+/// Used by `clap` to validate the similarity threshold.
+///
+/// # Arguments
+///
+/// * `threshold` is the minimum similarity of folder filenames.
+fn parse_threshold(threshold: &str) -> std::result::Result<f64, String> {
+    match threshold.parse::<f64>() {
+        Ok(value) if (0.0..=1.0).contains(&value) => Ok(value),
+        _ => Err("The threshold must be a number between 0.0 and 1.0...".to_string()),
+    }
 }
 
 /// The initialize database command definition.
@@ -70,18 +105,30 @@ impl Command {
     ///
     /// * `session` provides the domain API used to implement each command.
     pub fn execute(&self, session: &Session) -> Result<()> {
+        if let Some(csv_path) = &self.args.csv_path {
+            return export_csv(session, csv_path, self.args.min_folders);
+        }
+        if self.args.json_args.json() {
+            return self.execute_document(session);
+        }
         let mut report_build = StopWatch::start_new();
         let report = if self.args.init {
             initialize(session)?
         } else if self.args.list {
-            let duplicate_folders = session.duplicate_folders_files()?;
+            let duplicate_folders = session.duplicate_folders_files(self.args.min_folders)?;
             list::report(duplicate_folders)
         } else if self.args.matches {
-            let folders_match = session.duplicate_folders_files_match()?;
+            let folders_match = session.duplicate_folders_files_match(self.args.min_folders)?;
+            matches::report(folders_match)
+        } else if self.args.exact {
+            let folders_match = session.duplicate_folders_exact_match(self.args.min_folders)?;
             matches::report(folders_match)
         } else if self.args.none {
-            let folders_no_match = session.duplicate_folders_no_match()?;
+            let folders_no_match = session.duplicate_folders_no_match(self.args.min_folders)?;
             no_matches::report(folders_no_match)
+        } else if let Some(threshold) = self.args.similar {
+            let similar_folders = session.duplicate_folders_similar(threshold)?;
+            similar::report(similar_folders)
         } else {
             summary(session)?
         };
@@ -91,6 +138,28 @@ impl Command {
         write_strings(&mut writer, report.into_iter())?;
         Ok(())
     }
+    /// Creates a `JSON` report.
+    ///
+    /// Only the folder match, exact duplicate, and no match reports are available as documents.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` provides the domain API used to implement each command.
+    fn execute_document(&self, session: &Session) -> Result<()> {
+        let mut writer = get_writer(&self.args.output_path, self.args.append_log)?;
+        if self.args.matches {
+            let folders_match = session.duplicate_folders_files_match(self.args.min_folders)?;
+            matches::document(&mut *writer, &folders_match, &self.args.json_args)
+        } else if self.args.exact {
+            let folders_match = session.duplicate_folders_exact_match(self.args.min_folders)?;
+            matches::document(&mut *writer, &folders_match, &self.args.json_args)
+        } else if self.args.none {
+            let folders_no_match = session.duplicate_folders_no_match(self.args.min_folders)?;
+            no_matches::document(&mut *writer, &folders_no_match, &self.args.json_args)
+        } else {
+            Err(Error::from("JSON reports are only available for the match, exact, and no match reports."))
+        }
+    }
 }
 
 /// Reloads the duplicate files metadata.
@@ -108,10 +177,69 @@ fn initialize(session: &Session) -> Result<Report> {
     Ok(report)
 }
 
+/// Exports the duplicate files metadata to a CSV file.
+///
+/// # Arguments
+///
+/// * `session` provides the domain API used to implement the command.
+/// * `csv_path` is the file the CSV document will be written to.
+/// * `min_folders` is the minimum number of folders in an exported folder group.
+fn export_csv(session: &Session, csv_path: &PathBuf, min_folders: usize) -> Result<()> {
+    let elapsed = StopWatch::start_new();
+    let rows = session.duplicate_files_csv(File::create(csv_path)?, min_folders)?;
+    log::info!("CSV export of {} rows took {elapsed}", commafy(rows));
+    Ok(())
+}
+
+mod similar {
+    //! This module consolidates the implementation of the similar folders report.
+
+    use super::{commafy, rptcols, rptrow, Report, SimilarFolders, StopWatch};
+
+    /// Generates the report showing folders that have similar filenames.
+    ///
+    /// # Arguments
+    ///
+    /// * `similar_folders` is the metadata the report will be built from.
+    pub fn report(similar_folders: SimilarFolders) -> Report {
+        let overall = StopWatch::start_new();
+        let mut report = Report::from(rptcols!(<=(2), <=(2), =));
+        for similar_md in similar_folders.get_md() {
+            report.text(rptrow!(= format!("Folders similarity {:.1}%", similar_md.similarity * 100.0)));
+            for folder_md in [similar_md.lhs, similar_md.rhs] {
+                report.text(rptrow!(_, = &folder_md.pathname, = format!("({} files)", commafy(folder_md.children.len()))));
+            }
+            report.text(rptrow!(_, = format!("Common filenames: ({} files)", commafy(similar_md.common.len()))));
+            report.text(rptrow!(_, _, = similar_md.common.join(", ")));
+        }
+        log::info!("similar report elapsed: {}", overall);
+        report
+    }
+}
+
 mod matches {
     //! This module consolidates the implementation of folder file matching report.
 
-    use super::{commafy, mbufmt, rptcols, rptrow, DuplicateFoldersMatch, FoldersMatchMd, Report, StopWatch};
+    use super::{
+        commafy, io, mbufmt, rptcols, rptrow, write_json, DuplicateFoldersMatch, FoldersMatchMd, JsonArgs, Report,
+        Result, StopWatch,
+    };
+
+    /// Writes the folders with matching files as a `JSON` document.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` is where the document will be written.
+    /// * `folders_match` is the metadata the document will be built from.
+    /// * `json_args` controls how the document is formatted.
+    pub fn document(
+        writer: &mut dyn io::Write,
+        folders_match: &DuplicateFoldersMatch,
+        json_args: &JsonArgs,
+    ) -> Result<()> {
+        let folders_match_md: Vec<FoldersMatchMd> = folders_match.into_iter().collect();
+        write_json(writer, &folders_match_md, json_args.pretty())
+    }
 
     /// Generates the report showing what folders had matching files.
     ///
@@ -180,7 +308,19 @@ mod matches {
 mod no_matches {
     //! This module consolidates the implementation of folders that did not have matching files report.
 
-    use super::{commafy, rptcols, rptrow, FoldersNoMatch, Report};
+    use super::{commafy, io, rptcols, rptrow, write_json, FolderNoMatchMd, FoldersNoMatch, JsonArgs, Report, Result};
+
+    /// Writes the folders that did not have matches as a `JSON` document.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` is where the document will be written.
+    /// * `folders_no_match` is the metadata about folders that did not match.
+    /// * `json_args` controls how the document is formatted.
+    pub fn document(writer: &mut dyn io::Write, folders_no_match: &FoldersNoMatch, json_args: &JsonArgs) -> Result<()> {
+        let folders_no_match_md: Vec<FolderNoMatchMd> = folders_no_match.into_iter().collect();
+        write_json(writer, &folders_no_match_md, json_args.pretty())
+    }
 
     /// Generate the report showing folders that did not have matches.
     ///
@@ -367,3 +507,56 @@ fn summary(session: &Session) -> Result<Report> {
     report.text(rptrow!(_, format!("Folders with duplicate filenames: {}", commafy(folder_cnt))));
     Ok(report)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fs::domain::get_session;
+    use std::fs as stdfs;
+
+    #[test]
+    fn json_matches_text() {
+        let root = std::env::temp_dir().join(format!("fsview-cli-dups-{}", std::process::id()));
+        let _ = stdfs::remove_dir_all(&root);
+        for (folder, filename, content) in [
+            ("one", "a", "same"),
+            ("one", "b", "size 200"),
+            ("two", "a", "same"),
+            ("two", "b", "size 300 content"),
+            ("three", "c", "unique"),
+        ] {
+            stdfs::create_dir_all(root.join(folder)).unwrap();
+            stdfs::write(root.join(folder).join(filename), content).unwrap();
+        }
+        let root = stdfs::canonicalize(root).unwrap();
+        let session = get_session(root.join("dups.db")).unwrap();
+        session.initialize_db(false).unwrap();
+        session.add_folder(&root).unwrap();
+        session.duplicate_files_reload().unwrap();
+        let mut json = vec![];
+        let json_args = JsonArgs { json: true, pretty: false };
+        matches::document(&mut json, &session.duplicate_folders_files_match(2).unwrap(), &json_args).unwrap();
+        let document: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        let report = matches::report(session.duplicate_folders_files_match(2).unwrap());
+        let text: Vec<String> = report.into_iter().collect();
+        let text: Vec<&str> = text.iter().map(|line| line.trim()).collect();
+        let folder_groups = document.as_array().unwrap();
+        assert_eq!(folder_groups.len(), text.iter().filter(|&&line| line == "Folders filename match").count());
+        assert_eq!(folder_groups.len(), 1);
+        let folder_group = &folder_groups[0];
+        for pathname in folder_group["pathnames"].as_array().unwrap() {
+            let pathname = pathname.as_str().unwrap();
+            assert!(text.iter().any(|line| line.starts_with(pathname)), "{pathname} not in {text:?}");
+        }
+        let common = |key: &str| -> String {
+            let filenames = folder_group[key].as_array().unwrap().iter().map(|filename| filename.as_str().unwrap());
+            filenames.collect::<Vec<_>>().join(", ")
+        };
+        assert_eq!(common("matches"), "a, b");
+        assert_eq!(common("except"), "b");
+        let position = |line: &str| text.iter().position(|&text_line| text_line.starts_with(line)).unwrap();
+        assert_eq!(text[position("Common filenames:") + 1], common("matches"));
+        assert_eq!(text[position("Files that did not match:") + 1], common("except"));
+        stdfs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,65 @@
+//! # The subcommand that reports filenames found in more than one folder.
+//!
+use clap::Args;
+
+use super::{
+    rptcols, rptrow,
+    text::{get_writer, write_strings, Report},
+    Result, Session, StopWatch,
+};
+
+/// The filename folders command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {
+    /// Reload the duplicate files metadata before reporting.
+    #[clap(long)]
+    init: bool,
+}
+
+/// The filename folders command definition.
+pub struct Command {
+    /// The commands arguments.
+    args: CommandArgs,
+}
+
+impl Command {
+
+    /// Creates an instance of the command.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that will find filenames in multiple folders.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `session` - the `domain` session that will be searched.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        if self.args.init {
+            session.duplicate_files_reload()?;
+        }
+        let filename_folders = session.filename_folders()?;
+        let mut report = Report::from(rptcols!(<, <));
+        report.header(rptrow!(^ "Filename", ^ "Folders")).separator("-");
+        if filename_folders.is_empty() {
+            report.text(rptrow!("None"));
+        }
+        for filename_md in &filename_folders {
+            for (index, folder) in filename_md.folders.iter().enumerate() {
+                match index {
+                    0 => report.text(rptrow!(filename_md.filename.as_str(), folder.as_str())),
+                    _ => report.text(rptrow!(_, folder.as_str())),
+                };
+            }
+        }
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, report.into_iter())?;
+        log::info!("filename folders took {elapsed}");
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+//! # The subcommand that reports the most deeply nested folder.
+//!
+use clap::Args;
+
+use super::{
+    rptcols, rptrow,
+    text::{get_writer, write_strings, Report},
+    Result, Session, StopWatch,
+};
+
+/// The folder depth command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {}
+
+/// The folder depth command definition.
+pub struct Command {
+    /// The commands arguments.
+    #[allow(unused)]
+    args: CommandArgs,
+}
+
+impl Command {
+
+    /// Creates an instance of the command.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that will find the deepest folder.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `session` - the `domain` session that will be searched.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        let mut report = Report::from(rptcols!(<, <));
+        match session.deepest_folder()? {
+            Some(folder_depth) => {
+                report.text(rptrow!("Depth:", folder_depth.depth));
+                report.text(rptrow!("Folder:", folder_depth.pathname));
+            }
+            None => {
+                report.text(rptrow!("Depth:", "None"));
+            }
+        }
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, report.into_iter())?;
+        log::info!("folder depth took {elapsed}");
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! # The subcommand that reports a fingerprint of a folders content.
+//!
+use clap::Args;
+
+use super::{
+    text::{get_writer, write_strings},
+    Result, Session, StopWatch,
+};
+
+/// The folder fingerprint command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {
+    /// The pathname of the folder that will be fingerprinted.
+    #[clap(value_name = "PATHNAME", forbid_empty_values = true)]
+    pathname: String,
+}
+
+/// The folder fingerprint command definition.
+pub struct Command {
+    /// The commands arguments.
+    args: CommandArgs,
+}
+
+impl Command {
+    /// Creates an instance of the command.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that fingerprints the folder content.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - the `domain` session that contains the folder.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        let fingerprint = session.folder_fingerprint(&self.args.pathname)?;
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, std::iter::once(format!("{fingerprint}  {}", self.args.pathname)))?;
+        log::info!("folder fingerprint took {elapsed}");
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! # The subcommand that exports a folder hierarchy as `JSON`.
+//!
+use clap::Args;
+
+use super::{
+    text::{get_writer, write_strings},
+    Result, Session, StopWatch,
+};
+
+/// The folder tree command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {
+    /// The pathname of the top level folder that will be exported.
+    #[clap(value_name = "PATHNAME", forbid_empty_values = true)]
+    pathname: String,
+}
+
+/// The folder tree command definition.
+pub struct Command {
+    /// The commands arguments.
+    args: CommandArgs,
+}
+
+impl Command {
+    /// Creates an instance of the command.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that exports the folder hierarchy.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - the `domain` session that contains the folder hierarchy.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        let tree_json = session.tree_json(&self.args.pathname)?;
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, std::iter::once(tree_json))?;
+        log::info!("folder tree took {elapsed}");
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+//! # The subcommand that reports the folders containing the most files.
+//!
+use clap::Args;
+
+use super::{
+    rptcols, rptrow,
+    text::{get_writer, write_strings, Report},
+    Result, Session, StopWatch,
+};
+
+/// The largest folders command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {
+    /// The number of folders that will be reported.
+    #[clap(short, long = "count", value_name = "N", default_value_t = 10)]
+    n: usize,
+}
+
+/// The largest folders command definition.
+pub struct Command {
+    /// The commands arguments.
+    args: CommandArgs,
+}
+
+impl Command {
+    /// Creates an instance of the command.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that will find the folders with the most files.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - the `domain` session that will be searched.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        let folder_counts = session.largest_folders_by_count(self.args.n)?;
+        let mut report = Report::from(rptcols!(>, >, <));
+        report.header(rptrow!(^ "Id", ^ "Files", ^ "Pathname")).separator("-");
+        if folder_counts.is_empty() {
+            report.text(rptrow!(_, _, "None"));
+        }
+        for folder_count in &folder_counts {
+            report.text(rptrow!(folder_count.id, folder_count.count, folder_count.pathname.as_str()));
+        }
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, report.into_iter())?;
+        log::info!("largest folders took {elapsed}");
+        Ok(())
+    }
+}
@@ -6,17 +6,23 @@
 //! * list folders that have a specified pathname.
 //! * list the root folders that have been added.
 //! * provide a summary of what metadata has been collected.
+//! * provide a histogram of file sizes.
+//!
+//! The summary and problems reports can also be generated as `JSON` or `CSV`.
 //!
 #[cfg(windows)]
 use std::path::{Component, Prefix, StripPrefixError};
 use std::{fs, path::PathBuf};
 
 use super::{
-    commafy, mbufmt, rptcols, rptrow,
+    commafy,
+    lib::domain::{DbInformation, ProblemMd},
+    mbufmt, rptcols, rptrow,
     text::{get_writer, write_strings, Report},
-    Error, Metadata, Result, Session, StopWatch,
+    write_csv, write_json, Error, Metadata, ReportArgs, Result, Session, StopWatch,
 };
 use clap::Args;
+use serde::Serialize;
 
 #[cfg(windows)]
 /// Convert an error dealing with path prefixes into a CLI error.
@@ -44,20 +50,23 @@ pub struct CommandArgs {
     /// Show a list of files that had an error when loading.
     #[clap(long = "prob", group = "cmd")]
     problems: bool,
+    /// Show the count and size of files by size range.
+    #[clap(long = "sizes", group = "cmd")]
+    sizes: bool,
     /// Show the details of files, folders, and disk space used.
-    #[clap(short = 'D', long = "details", group = "opts", conflicts_with_all = &["info", "problems"])]
+    #[clap(short = 'D', long = "details", group = "opts", conflicts_with_all = &["info", "problems", "sizes"])]
     details: bool,
     /// Show a summary of the files, folders, and size of each folder.
-    #[clap(short = 'S', long = "sum", group = "opts", conflicts_with_all = &["info", "problems"])]
+    #[clap(short = 'S', long = "sum", group = "opts", conflicts_with_all = &["info", "problems", "sizes"])]
     summary: bool,
     /// Recursively follow a folder structure.
-    #[clap(short = 'R', long = "recurse", conflicts_with_all = &["info", "problems", "root"])]
+    #[clap(short = 'R', long = "recurse", conflicts_with_all = &["info", "problems", "sizes", "root"])]
     recurse: bool,
     /// The folder path or folder name to list
     #[clap(forbid_empty_values = true, value_name = "FOLDER", requires = "folder_cmd")]
     folder: Option<String>,
     #[clap(
-        short = 'r', long = "rpt", value_name="FILE", forbid_empty_values = true,
+        short = 'r', long = "rpt", visible_alias = "file", value_name="FILE", forbid_empty_values = true,
         parse(try_from_str = super::parse_filename), group = "out"
     )]
     /// The report file pathname.
@@ -65,6 +74,9 @@ pub struct CommandArgs {
     /// Append to the report file, otherwise overwrite
     #[clap(short, long = "append", requires("out"))]
     pub append: bool,
+    /// The report format.
+    #[clap(flatten)]
+    report_args: ReportArgs,
 }
 
 /// The list folders command.
@@ -114,6 +126,9 @@ impl Command {
     ///
     /// * `session` is the `domain` session used to get folder metadata.
     pub fn execute(self, session: &Session) -> Result<()> {
+        if !self.args.report_args.text() {
+            return self.execute_document(session);
+        }
         let elapsed = StopWatch::start_new();
         let mut create_report = StopWatch::start_new();
         let report = if self.args.name {
@@ -125,6 +140,8 @@ impl Command {
             report_root(session, self.report_type())?
         } else if self.args.problems {
             report_problems(session)?
+        } else if self.args.sizes {
+            report_sizes(session)?
         } else {
             report_summary(session)?
         };
@@ -136,6 +153,38 @@ impl Command {
         log::info!("report create: {}, overall: {}", create_report.time_str(), elapsed.time_str());
         Ok(())
     }
+    /// Creates a `JSON` or `CSV` report.
+    ///
+    /// Only the database summary and problems reports are available as documents.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` is the `domain` session used to get the report metadata.
+    fn execute_document(&self, session: &Session) -> Result<()> {
+        if self.args.name || self.args.pathname || self.args.root || self.args.sizes {
+            return Err(Error::from("JSON and CSV reports are only available for the summary and problems."));
+        }
+        let report_args = &self.args.report_args;
+        let mut writer = get_writer(&self.args.report_path, self.args.append)?;
+        if self.args.problems {
+            let metadatas = session.get_problems()?;
+            let problems = problems_md(&metadatas);
+            if report_args.csv() {
+                write_csv(&mut *writer, problems)
+            } else {
+                write_json(&mut *writer, &problems, report_args.pretty())
+            }
+        } else {
+            let db_information = session.get_db_information()?;
+            let database = session.db();
+            if report_args.csv() {
+                write_csv(&mut *writer, [DbInformationRecord::new(&database, &db_information)])
+            } else {
+                let document = DbInformationDoc { database: &database, information: &db_information };
+                write_json(&mut *writer, &document, report_args.pretty())
+            }
+        }
+    }
     /// The folder name that will be reported.
     ///
     /// An empty string will be returned if the folder name has not been provided.
@@ -218,6 +267,85 @@ fn report_summary(session: &Session) -> Result<Report> {
     Ok(report)
 }
 
+/// The database information `JSON` document.
+#[derive(Debug, Serialize)]
+struct DbInformationDoc<'a> {
+    /// The database pathname.
+    database: &'a str,
+    /// The database information.
+    #[serde(flatten)]
+    information: &'a DbInformation,
+}
+
+/// The database information `CSV` record.
+#[derive(Debug, Serialize)]
+struct DbInformationRecord<'a> {
+    /// The database pathname.
+    database: &'a str,
+    /// The database allocation size.
+    database_size: u64,
+    /// The total count of folders.
+    folder_count: u64,
+    /// The total count of files.
+    file_count: u64,
+    /// The total count of problems.
+    problem_count: u64,
+    /// The top-level folder pathnames separated by a semi-colon.
+    root_folders: String,
+}
+impl<'a> DbInformationRecord<'a> {
+    /// Create the `CSV` record from the database information.
+    ///
+    /// # Arguments
+    ///
+    /// * `database` is the database pathname.
+    /// * `db_information` is the database information.
+    fn new(database: &'a str, db_information: &DbInformation) -> Self {
+        Self {
+            database,
+            database_size: db_information.database_size,
+            folder_count: db_information.folder_count,
+            file_count: db_information.file_count,
+            problem_count: db_information.problem_count,
+            root_folders: db_information.root_folders.join(";"),
+        }
+    }
+}
+
+/// Get the problems from the folders returned by the problems query.
+///
+/// # Arguments
+///
+/// * `metadatas` are the folders containing the problems.
+fn problems_md(metadatas: &Vec<Metadata>) -> Vec<&ProblemMd> {
+    let mut problems = vec![];
+    for metadata in metadatas {
+        if let Metadata::Folder(folder_md) = metadata {
+            for child in folder_md.children.values() {
+                if let Metadata::Problem(problem) = child {
+                    problems.push(problem);
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// Generate a report of file counts and sizes by file size range.
+///
+/// # Arguments
+///
+/// * `session` will be used to get the file size histogram.
+fn report_sizes(session: &Session) -> Result<Report> {
+    let size_histogram = session.size_histogram()?;
+    let mut report = Report::from(rptcols!(<, >, >));
+    report.header(rptrow!(^ "Size", ^ "Files", ^ "Total")).separator("-");
+    for bucket in size_histogram.buckets {
+        report.text(rptrow!(bucket.label, commafy(bucket.count), mbufmt!(bucket.size)));
+    }
+    Ok(report)
+}
+
 /// Generate a report of any problems that may have happened loading filesystem metadata.
 ///
 /// # Arguments
@@ -11,6 +11,9 @@ pub struct CommandArgs {
     /// A filesystem directory that will be traversed and loaded into the database.
     #[clap(forbid_empty_values = true, parse(try_from_str = parse_dir_name))]
     folder_path: PathBuf,
+    /// Skip folders and files matching the GLOB name or pathname (may be repeated).
+    #[clap(long, value_name = "GLOB", multiple_occurrences = true)]
+    exclude: Vec<String>,
 }
 
 /// Used by the `clap` API to convert the CLI argument into a `PathBuf`.
@@ -52,7 +55,7 @@ impl Command {
     /// * `session` - the `domain` session that will be used to add the metadata.
     pub fn execute(&self, session: &Session) -> Result<()> {
         let elapsed = StopWatch::start_new();
-        session.add_folder(&self.args.folder_path)?;
+        session.add_folder_excluding(&self.args.folder_path, &self.args.exclude)?;
         log::info!("overall={elapsed}");
         Ok(())
     }
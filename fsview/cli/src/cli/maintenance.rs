@@ -0,0 +1,51 @@
+//! # The subcommand that reclaims unused database space.
+//!
+use clap::Args;
+
+use super::{
+    mbufmt, rptcols, rptrow,
+    text::{get_writer, write_strings, Report},
+    Result, Session, StopWatch,
+};
+
+/// The database maintenance command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {}
+
+/// The database maintenance command definition.
+pub struct Command {
+    /// The commands arguments.
+    #[allow(unused)]
+    args: CommandArgs,
+}
+
+impl Command {
+
+    /// Creates an instance of the command.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that will vacuum the database.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `session` - the `domain` session that will be called to maintain the database.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        let maintenance_report = session.maintenance()?;
+        let mut report = Report::from(rptcols!(<, >));
+        report.text(rptrow!(= "Database:", = session.db()));
+        report.text(rptrow!("Before:", mbufmt!(maintenance_report.before)));
+        report.text(rptrow!("After:", mbufmt!(maintenance_report.after)));
+        report.text(rptrow!("Reclaimed:", mbufmt!(maintenance_report.reclaimed())));
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, report.into_iter())?;
+        log::info!("maintenance took {elapsed}");
+        Ok(())
+    }
+}
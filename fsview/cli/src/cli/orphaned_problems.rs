@@ -0,0 +1,55 @@
+//! # The subcommand that reports problems whose folder is no longer in the database.
+//!
+use clap::Args;
+
+use super::{
+    rptcols, rptrow,
+    text::{get_writer, write_strings, Report},
+    Result, Session, StopWatch,
+};
+
+/// The orphaned problems command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {}
+
+/// The orphaned problems command definition.
+pub struct Command {
+    /// The commands arguments.
+    #[allow(unused)]
+    args: CommandArgs,
+}
+
+impl Command {
+
+    /// Creates an instance of the command.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that will find orphaned problems.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `session` - the `domain` session that will be checked for orphaned problems.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        let orphaned_problems = session.orphaned_problems()?;
+        let mut report = Report::from(rptcols!(>, >, <, <));
+        report.header(rptrow!(^ "Id", ^ "Folder Id", ^ "Pathname", ^ "Description")).separator("-");
+        if orphaned_problems.is_empty() {
+            report.text(rptrow!(_, _, "None"));
+        }
+        for problem_md in &orphaned_problems {
+            let (pathname, description) = (problem_md.pathname.as_str(), problem_md.description.as_str());
+            report.text(rptrow!(problem_md.id, problem_md.parent_id, pathname, description));
+        }
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, report.into_iter())?;
+        log::info!("orphaned problems took {elapsed}");
+        Ok(())
+    }
+}
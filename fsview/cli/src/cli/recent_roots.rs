@@ -0,0 +1,58 @@
+//! # The subcommand that reports the root folders most recently added to the database.
+//!
+use clap::Args;
+use toolslib::date_time::get_local_ts;
+
+use super::{
+    rptcols, rptrow,
+    text::{get_writer, write_strings, Report},
+    Result, Session, StopWatch,
+};
+
+/// The recent roots command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {
+    /// The number of root folders that will be reported.
+    #[clap(short, long = "count", value_name = "N", default_value_t = 10)]
+    n: usize,
+}
+
+/// The recent roots command definition.
+pub struct Command {
+    /// The commands arguments.
+    args: CommandArgs,
+}
+
+impl Command {
+    /// Creates an instance of the command.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that gets the most recently added root folders.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - the `domain` session whose root folders will be reported.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let elapsed = StopWatch::start_new();
+        let recent_roots = session.recent_roots(self.args.n)?;
+        let mut report = Report::from(rptcols!(<, <));
+        report.header(rptrow!(^ "Scanned", ^ "Pathname")).separator("-");
+        if recent_roots.is_empty() {
+            report.text(rptrow!(_, "None"));
+        }
+        for scan_md in &recent_roots {
+            let scanned = get_local_ts(scan_md.scanned as i64).format("%Y-%m-%d %H:%M:%S").to_string();
+            report.text(rptrow!(scanned, scan_md.pathname.as_str()));
+        }
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, report.into_iter())?;
+        log::info!("recent roots took {elapsed}");
+        Ok(())
+    }
+}
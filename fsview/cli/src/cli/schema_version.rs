@@ -0,0 +1,50 @@
+//! # The subcommand that reports the database schema version.
+//!
+use clap::Args;
+
+use super::{
+    rptcols, rptrow,
+    text::{get_writer, write_strings, Report},
+    Result, Session,
+};
+
+/// The schema version command arguments.
+#[derive(Args, Debug)]
+pub struct CommandArgs {}
+
+/// The schema version command definition.
+pub struct Command {
+    /// The commands arguments.
+    #[allow(unused)]
+    args: CommandArgs,
+}
+
+impl Command {
+    /// Creates an instance of the command.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` - the command arguments that will be used.
+    pub fn new(args: CommandArgs) -> Command {
+        Command { args }
+    }
+
+    /// Uses a [Session] from `fsviewlib` to call the API that gets the schema version.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - the `domain` session whose schema version will be reported.
+    pub fn execute(&self, session: &Session) -> Result<()> {
+        let schema_version = match session.schema_version()? {
+            Some(version) => version.to_string(),
+            None => "not initialized".to_string(),
+        };
+        let mut report = Report::from(rptcols!(>, <));
+        report.text(rptrow!("Database:", session.db()));
+        report.text(rptrow!("Schema version:", schema_version));
+        report.text(rptrow!("Supported version:", session.supported_schema_version()));
+        let mut writer = get_writer(&None, false)?;
+        write_strings(&mut writer, report.into_iter())?;
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ mod cli;
 /// Its role is to parse the command line arguments and execute the appropriate CLI command.
 fn main() -> Result<(), cli::Error> {
     let cmd: cli::Cli = cli::Cli::parse();
-    cli::initialize(&cmd)?;
+    // hold the log guard so logging is flushed when the program exits
+    let _log_guard = cli::initialize(&cmd)?;
     cli::execute(cmd)
 }
@@ -12,32 +12,48 @@ mod schema;
 use super::{domain, filesys, StopWatch};
 
 use rusqlite as sql;
-use std::{fmt, path::PathBuf, result};
+use std::{fmt, path::{Path, PathBuf}, result};
 
 #[rustfmt::skip]
 pub(crate) use {
     load::{
         load_fs_metadata,
+        load_scanned_fs_metadata,
+        reload_fs_metadata,
         file_duplicates_reload,
+        file_duplicates_reload_incremental,
     },
     query::{
         database_metrics_query,
+        deepest_folder_query,
         duplicate_ids,
         // duplicate_filename_metadata_query,
         duplicate_files_metadata_query,
         duplicate_file_metrics,
+        empty_files_query,
+        excludes_query,
+        files_metadata_query,
+        folder_content_by_filedups_query,
         folder_content_by_name_query,
         folder_content_by_pathname_query,
         folder_tree_by_name_query,
         folder_tree_by_pathname_query,
         get_table_counts_query,
+        largest_folders_by_count_query,
+        orphaned_problems_query,
         problems_query,
+        recent_roots_query,
         root_folder_content_query,
         root_folders_pathname_query,
+        scans_query,
     },
     schema::{
         drop as schema_drop,
         init as schema_init,
+        maintenance as schema_maintenance,
+        table_exists,
+        version as schema_version,
+        SCHEMA_VERSION,
     },
 };
 
@@ -84,6 +100,15 @@ pub(crate) fn database_connection(db_path_option: Option<&PathBuf>) -> Result<sq
     Ok(conn)
 }
 
+/// Create a connection to an existing database that cannot modify it.
+/// 
+/// # Arguments
+/// 
+/// * `db_path` - the path to the database.
+pub(crate) fn read_only_connection(db_path: &Path) -> Result<sql::Connection> {
+    Ok(sql::Connection::open_with_flags(db_path, sql::OpenFlags::SQLITE_OPEN_READ_ONLY)?)
+}
+
 /// The filename for a dummy file used when a folder does not have any files.
 pub const EMPTY_FOLDER_FILENAME: &str = r"<?>";
 
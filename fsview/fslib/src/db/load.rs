@@ -1,10 +1,14 @@
 //! The API that loads a database with filesystem metadata.
 use super::{
+    domain::ScanMd,
     filesys::{FileMetadata, FolderMetadata, FsMetadata, ProblemMetadata},
-    sql, Error, Result, StopWatch,
+    sql, table_exists, Error, Result, StopWatch,
 };
 
-use std::{fmt, ops};
+use std::{
+    fmt, ops,
+    path::{Path, MAIN_SEPARATOR},
+};
 
 /// The main function API called from the `domain` to load filesystem metadata.
 ///
@@ -12,75 +16,221 @@ use std::{fmt, ops};
 ///
 /// * `conn` - the database connection.
 /// * `fs_metadata` - the filesystem metadata that will be added to the database.
-pub(crate) fn load_fs_metadata(conn: &mut sql::Connection, fs_metadata: &FsMetadata) -> Result<()> {
+pub(crate) fn load_fs_metadata(conn: &mut sql::Connection, fs_metadata: &FsMetadata) -> Result<InsertCount> {
+    load(conn, fs_metadata, None)
+}
+
+/// The function API called from the `domain` to load filesystem metadata and record the scan that collected it.
+///
+/// The scan and the patterns used to exclude folders and files are recorded in the same transaction as the
+/// filesystem metadata. Databases created before scans were recorded do not have the `scans` or `excludes`
+/// tables. The scan will not be recorded and exclude patterns cannot be used until the database schema has
+/// been initialized again.
+///
+/// # Arguments
+///
+/// * `conn` - the database connection.
+/// * `fs_metadata` - the filesystem metadata that will be added to the database.
+/// * `exclude` - the glob patterns of folders and files that were not collected.
+/// * `scanned` - when the scan started as the number of seconds since the Unix epoch.
+/// * `stopwatch` - the stopwatch started when the scan began.
+pub(crate) fn load_scanned_fs_metadata(
+    conn: &mut sql::Connection,
+    fs_metadata: &FsMetadata,
+    exclude: &[String],
+    scanned: u64,
+    stopwatch: &StopWatch,
+) -> Result<InsertCount> {
+    load(conn, fs_metadata, Some((exclude, scanned, stopwatch)))
+}
+
+/// Load filesystem metadata into the database optionally recording the scan that collected it.
+///
+/// # Arguments
+///
+/// * `conn` - the database connection.
+/// * `fs_metadata` - the filesystem metadata that will be added to the database.
+/// * `scan` - the exclude patterns, when the scan started, and the stopwatch started when the scan began.
+fn load(
+    conn: &mut sql::Connection,
+    fs_metadata: &FsMetadata,
+    scan: Option<(&[String], u64, &StopWatch)>,
+) -> Result<InsertCount> {
     let transaction = conn.transaction()?;
     let mut timer = StopWatch::start_new();
-    let insert_count = insert_fs_metadata(&transaction, fs_metadata, super::ROOT_FOLDER_PARENT_ID)?;
+    let insert_count = {
+        let mut inserts = InsertStatements::new(&transaction)?;
+        inserts.insert_fs_metadata(fs_metadata, super::ROOT_FOLDER_PARENT_ID)?
+    };
     log::debug!("insert={timer}");
+    if let Some((exclude, scanned, stopwatch)) = scan {
+        let pathname = fs_metadata.path().display().to_string();
+        match table_exists(&transaction, "excludes")? {
+            true => record_excludes(&transaction, &pathname, exclude)?,
+            false if exclude.is_empty() => (),
+            false => {
+                let reason = "initialize the database before excluding folders and files...";
+                return Err(Error::from(reason.to_string()));
+            }
+        }
+        match table_exists(&transaction, "scans")? {
+            true => {
+                let scan_md = ScanMd {
+                    id: 0,
+                    scanned,
+                    pathname,
+                    folders: insert_count.folders as u64,
+                    files: insert_count.files as u64,
+                    problems: insert_count.problems as u64,
+                    elapsed: stopwatch.millis() as u64,
+                };
+                record_scan(&transaction, &scan_md)?;
+            }
+            false => log::warn!("The scan was not recorded, initialize the database to add the scan history."),
+        }
+    }
     timer.start();
     transaction.commit()?;
     log::debug!("commit={timer}");
     log::info!("{insert_count}");
+    Ok(insert_count)
+}
+
+/// The SQL used to record a folder being added to the database.
+const SCAN_INSERT: &str = r#"
+    INSERT INTO scans (scanned, pathname, folders, files, problems, elapsed) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+"#;
+
+/// Record a folder being added to the database.
+///
+/// The scan identifier is ignored, the database will assign it.
+///
+/// # Arguments
+///
+/// * `conn` - the database connection.
+/// * `scan_md` - the metadata describing the folder that was added.
+fn record_scan(conn: &sql::Connection, scan_md: &ScanMd) -> Result<()> {
+    let params =
+        (scan_md.scanned, scan_md.pathname.as_str(), scan_md.folders, scan_md.files, scan_md.problems, scan_md.elapsed);
+    conn.execute(SCAN_INSERT, params)?;
     Ok(())
 }
 
-/// The primary internal API to insert filesystem metadata.
+/// Record the patterns used to exclude folders and files from a root folder.
 ///
-/// This function will be called recursively to process the filesystem metadata.
+/// Patterns previously recorded for the root folder are replaced.
 ///
 /// # Arguments
 ///
-/// * `tx` - the database transaction used to insert metadata.
-/// * `fs_metadata` - the filesystem metadata to insert.
-/// * `parent_id` - the parent id for data being inserted.
-fn insert_fs_metadata(tx: &sql::Transaction, fs_metadata: &FsMetadata, parent_id: i64) -> Result<InsertCount> {
-    match fs_metadata {
-        FsMetadata::File(file_md) => insert_files(tx, file_md, parent_id),
-        FsMetadata::Folder(folder_md) => insert_folders(tx, folder_md, parent_id),
-        FsMetadata::Problem(problem_md) => insert_problems(tx, problem_md, parent_id),
+/// * `conn` - the database connection.
+/// * `pathname` - the root folder pathname.
+/// * `exclude` - the glob patterns of folders and files that were not collected.
+fn record_excludes(conn: &sql::Connection, pathname: &str, exclude: &[String]) -> Result<()> {
+    conn.execute("DELETE FROM excludes WHERE pathname = ?1", [pathname])?;
+    let mut stmt = conn.prepare("INSERT OR IGNORE INTO excludes (pathname, pattern) VALUES (?1, ?2)")?;
+    for pattern in exclude {
+        stmt.execute((pathname, pattern.as_str()))?;
     }
+    Ok(())
 }
 
-/// The SQL used to insert folder metadata.
-pub const FOLDERS_INSERT: &str = r#"
-    INSERT INTO folders
-    (parent_id, pathname, name, size, created, modified)
-    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
-"#;
-
-/// The internal API that inserts folder metadata.
+/// Replace a folder hierarchy in the database with current filesystem metadata.
+///
+/// The existing folder hierarchy is removed before the filesystem metadata is added. If the folder
+/// no longer exists on the filesystem it is only removed.
 ///
 /// # Arguments
 ///
-/// * `tx` - the database tranasction used to insert folder metadata.
-/// * `folder_md` - the folder metadata.
-/// * `parent_id` - the folder parent identifier.
-fn insert_folders(tx: &sql::Transaction, folder_md: &FolderMetadata, parent_id: i64) -> Result<InsertCount> {
-    let params =
-        (parent_id, folder_md.pathname(), folder_md.filename(), folder_md.size, folder_md.created, folder_md.modified);
-    match tx.execute(FOLDERS_INSERT, params) {
-        Err(error) => Err(Error::from(format!("directory='{}' {error}.", folder_md.pathname()))),
-        _ => {
-            log::trace!("FOLDER: {}", folder_md.pathname());
-            let mut insert_count = InsertCount::default() + ADD_FOLDER;
-            let parent_id = tx.last_insert_rowid();
-            let mut has_file = false;
-            for child in &folder_md.children {
-                if child.is_file() {
-                    has_file = true;
-                }
-                insert_count += insert_fs_metadata(tx, child, parent_id)?;
-            }
-            if !has_file {
-                let fileless_folder = empty_folder_file(&folder_md.pathname());
-                insert_fs_metadata(tx, &FsMetadata::File(fileless_folder), parent_id)?;
-                insert_count.empty_files += 1;
-            }
-            Ok(insert_count)
+/// * `conn` - the database connection.
+/// * `folder_pathname` - the folder that will be replaced.
+/// * `fs_metadata` - the current filesystem metadata for the folder.
+pub(crate) fn reload_fs_metadata(
+    conn: &mut sql::Connection,
+    folder_pathname: &str,
+    fs_metadata: Option<&FsMetadata>,
+) -> Result<()> {
+    let transaction = conn.transaction()?;
+    let mut timer = StopWatch::start_new();
+    let pathname_prefix = format!("{folder_pathname}{MAIN_SEPARATOR}");
+    let params = (folder_pathname, pathname_prefix.as_str());
+    // keep the existing parent otherwise use the containing folder if it has been loaded
+    let parent_pathname = Path::new(folder_pathname).parent().map(|parent| parent.display().to_string());
+    let parent_id = match query_id(&transaction, FOLDER_PARENT_ID_QUERY, folder_pathname)? {
+        Some(parent_id) => parent_id,
+        None => match parent_pathname {
+            Some(parent_pathname) => query_id(&transaction, FOLDER_ID_QUERY, &parent_pathname)?,
+            None => None,
         }
+        .unwrap_or(super::ROOT_FOLDER_PARENT_ID),
+    };
+    let mut delete_count = 0;
+    for delete_sql in [FILEDUPS_HIERARCHY_DELETE, FILES_HIERARCHY_DELETE, PROBLEMS_HIERARCHY_DELETE, FOLDERS_HIERARCHY_DELETE] {
+        delete_count += transaction.execute(delete_sql, params)?;
+    }
+    log::debug!("delete={timer} rows={delete_count}");
+    if let Some(fs_metadata) = fs_metadata {
+        timer.start();
+        let insert_count = {
+            let mut inserts = InsertStatements::new(&transaction)?;
+            inserts.insert_fs_metadata(fs_metadata, parent_id)?
+        };
+        log::debug!("insert={timer}");
+        log::info!("{insert_count}");
     }
+    transaction.commit()?;
+    Ok(())
 }
 
+/// Query an identifier using a folder pathname.
+///
+/// # Arguments
+///
+/// * `tx` - the database transaction.
+/// * `query` - the SQL that selects the identifier.
+/// * `pathname` - the folder pathname.
+fn query_id(tx: &sql::Transaction, query: &str, pathname: &str) -> Result<Option<i64>> {
+    match tx.query_row(query, [pathname], |row| row.get(0)) {
+        Ok(id) => Ok(Some(id)),
+        Err(sql::Error::QueryReturnedNoRows) => Ok(None),
+        Err(error) => Err(Error::from(error)),
+    }
+}
+
+/// The SQL used to find the parent id of a folder.
+const FOLDER_PARENT_ID_QUERY: &str = "SELECT parent_id FROM folders WHERE pathname = ?1";
+
+/// The SQL used to find the id of a folder.
+const FOLDER_ID_QUERY: &str = "SELECT id FROM folders WHERE pathname = ?1";
+
+/// The SQL used to remove the duplicate files metadata of a folder hierarchy.
+const FILEDUPS_HIERARCHY_DELETE: &str = r#"
+    DELETE FROM filedups WHERE parent_id IN
+        (SELECT id FROM folders WHERE pathname = ?1 OR substr(pathname, 1, length(?2)) = ?2)
+"#;
+
+/// The SQL used to remove the files metadata of a folder hierarchy.
+const FILES_HIERARCHY_DELETE: &str = r#"
+    DELETE FROM files WHERE parent_id IN
+        (SELECT id FROM folders WHERE pathname = ?1 OR substr(pathname, 1, length(?2)) = ?2)
+"#;
+
+/// The SQL used to remove the problems metadata of a folder hierarchy.
+const PROBLEMS_HIERARCHY_DELETE: &str = r#"
+    DELETE FROM problems WHERE pathname = ?1 OR substr(pathname, 1, length(?2)) = ?2
+"#;
+
+/// The SQL used to remove the folders metadata of a folder hierarchy.
+const FOLDERS_HIERARCHY_DELETE: &str = r#"
+    DELETE FROM folders WHERE pathname = ?1 OR substr(pathname, 1, length(?2)) = ?2
+"#;
+
+/// The SQL used to insert folder metadata.
+pub const FOLDERS_INSERT: &str = r#"
+    INSERT INTO folders
+    (parent_id, pathname, name, size, created, modified)
+    VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+"#;
+
 /// The SQL used to insert file metadata.
 pub const FILES_INSERT: &str = r#"
     INSERT INTO files
@@ -88,40 +238,6 @@ pub const FILES_INSERT: &str = r#"
     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
 "#;
 
-/// The internal API that inserts file metadata.
-///
-/// # Arguments
-///
-/// * `tx` - the database tranasction used to insert folder metadata.
-/// * `file_md` - the file metadata.
-/// * `parent_id` - the file parent identifier.
-fn insert_files(tx: &sql::Transaction, file_md: &FileMetadata, parent_id: i64) -> Result<InsertCount> {
-    log::trace!(
-        "{}: {}",
-        if file_md.is_symlink {
-            "SYMLINK"
-        } else if file_md.filename() == super::EMPTY_FOLDER_FILENAME {
-            "EFF"
-        } else {
-            "FILE"
-        },
-        file_md.pathname(),
-    );
-    let params = (
-        parent_id,
-        file_md.pathname(),
-        file_md.filename(),
-        file_md.is_symlink,
-        file_md.size,
-        file_md.created,
-        file_md.modified,
-    );
-    match tx.execute(FILES_INSERT, params) {
-        Err(error) => Err(Error::from(format!("file='{}' {error}.", file_md.pathname()))),
-        _ => Ok(ADD_FILE),
-    }
-}
-
 /// The SQL used to insert problem metadata.
 pub const PROBLEMS_INSERT: &str = r#"
     INSERT INTO problems
@@ -129,19 +245,127 @@ pub const PROBLEMS_INSERT: &str = r#"
     VALUES (?1, ?2, ?3)
 "#;
 
-/// The internal API that inserts problem metadata.
-///
-/// # Arguments
+/// The prepared statements used to insert filesystem metadata.
 ///
-/// * `tx` - the database tranasction used to insert folder metadata.
-/// * `problem_md` - the problem metadata.
-/// * `parent_id` - the problem parent identifier.
-fn insert_problems(tx: &sql::Transaction, problem_md: &ProblemMetadata, parent_id: i64) -> Result<InsertCount> {
-    log::trace!("PROBLEM: {}", problem_md.pathname());
-    let params = (parent_id, problem_md.pathname(), problem_md.description.clone());
-    match tx.execute(PROBLEMS_INSERT, params) {
-        Err(error) => Err(Error::from(format!("db: problem='{}' {error}.", problem_md.pathname()))),
-        _ => Ok(ADD_PROBLEM),
+/// The statements are prepared once for the transaction and re-used for each row inserted.
+struct InsertStatements<'tx> {
+    /// The database transaction used to insert metadata.
+    tx: &'tx sql::Transaction<'tx>,
+    /// The prepared folder insert statement.
+    folders: sql::Statement<'tx>,
+    /// The prepared file insert statement.
+    files: sql::Statement<'tx>,
+    /// The prepared problem insert statement.
+    problems: sql::Statement<'tx>,
+}
+impl<'tx> InsertStatements<'tx> {
+    /// Prepare the insert statements.
+    ///
+    /// # Arguments
+    ///
+    /// * `tx` - the database transaction used to insert metadata.
+    fn new(tx: &'tx sql::Transaction<'tx>) -> Result<Self> {
+        Ok(Self {
+            tx,
+            folders: tx.prepare(FOLDERS_INSERT)?,
+            files: tx.prepare(FILES_INSERT)?,
+            problems: tx.prepare(PROBLEMS_INSERT)?,
+        })
+    }
+    /// The primary internal API to insert filesystem metadata.
+    ///
+    /// This function will be called recursively to process the filesystem metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `fs_metadata` - the filesystem metadata to insert.
+    /// * `parent_id` - the parent id for data being inserted.
+    fn insert_fs_metadata(&mut self, fs_metadata: &FsMetadata, parent_id: i64) -> Result<InsertCount> {
+        match fs_metadata {
+            FsMetadata::File(file_md) => self.insert_files(file_md, parent_id),
+            FsMetadata::Folder(folder_md) => self.insert_folders(folder_md, parent_id),
+            FsMetadata::Problem(problem_md) => self.insert_problems(problem_md, parent_id),
+        }
+    }
+    /// The internal API that inserts folder metadata.
+    ///
+    /// The folder is inserted before its children so the children will have the folder identifier
+    /// as their parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `folder_md` - the folder metadata.
+    /// * `parent_id` - the folder parent identifier.
+    fn insert_folders(&mut self, folder_md: &FolderMetadata, parent_id: i64) -> Result<InsertCount> {
+        let params =
+            (parent_id, folder_md.pathname(), folder_md.filename(), folder_md.size, folder_md.created, folder_md.modified);
+        match self.folders.execute(params) {
+            Err(error) => Err(Error::from(format!("directory='{}' {error}.", folder_md.pathname()))),
+            _ => {
+                log::trace!("FOLDER: {}", folder_md.pathname());
+                let mut insert_count = InsertCount::default() + ADD_FOLDER;
+                let parent_id = self.tx.last_insert_rowid();
+                let mut has_file = false;
+                for child in &folder_md.children {
+                    if child.is_file() {
+                        has_file = true;
+                    }
+                    insert_count += self.insert_fs_metadata(child, parent_id)?;
+                }
+                if !has_file {
+                    let fileless_folder = empty_folder_file(&folder_md.pathname());
+                    self.insert_fs_metadata(&FsMetadata::File(fileless_folder), parent_id)?;
+                    insert_count.empty_files += 1;
+                }
+                Ok(insert_count)
+            }
+        }
+    }
+    /// The internal API that inserts file metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_md` - the file metadata.
+    /// * `parent_id` - the file parent identifier.
+    fn insert_files(&mut self, file_md: &FileMetadata, parent_id: i64) -> Result<InsertCount> {
+        log::trace!(
+            "{}: {}",
+            if file_md.is_symlink {
+                "SYMLINK"
+            } else if file_md.filename() == super::EMPTY_FOLDER_FILENAME {
+                "EFF"
+            } else {
+                "FILE"
+            },
+            file_md.pathname(),
+        );
+        let params = (
+            parent_id,
+            file_md.pathname(),
+            file_md.filename(),
+            file_md.is_symlink,
+            file_md.size,
+            file_md.created,
+            file_md.modified,
+        );
+        match self.files.execute(params) {
+            Err(error) => Err(Error::from(format!("file='{}' {error}.", file_md.pathname()))),
+            _ => Ok(ADD_FILE),
+        }
+    }
+    /// The internal API that inserts problem metadata.
+    ///
+    /// # Arguments
+    ///
+    /// * `problem_md` - the problem metadata.
+    /// * `parent_id` - the problem parent identifier.
+    fn insert_problems(&mut self, problem_md: &ProblemMetadata, parent_id: i64) -> Result<InsertCount> {
+        log::trace!("PROBLEM: {}", problem_md.pathname());
+        let params = (parent_id, problem_md.pathname(), problem_md.description.clone());
+        match self.problems.execute(params) {
+            Err(error) => Err(Error::from(format!("db: problem='{}' {error}.", problem_md.pathname()))),
+            _ => Ok(ADD_PROBLEM),
+        }
     }
 }
 
@@ -153,15 +377,15 @@ fn empty_folder_file(folder_pathname: &str) -> FileMetadata {
     FileMetadata { path: file_path, is_symlink: false, size: 0, created: 0, modified: 0 }
 }
 
-/// An internal structure that tracks insert counts into the database.
+/// The structure that tracks insert counts into the database.
 #[derive(Debug, Default)]
-struct InsertCount {
+pub(crate) struct InsertCount {
     /// The count of folders inserted.
-    folders: usize,
+    pub(crate) folders: usize,
     /// The count of files inserted.
-    files: usize,
+    pub(crate) files: usize,
     /// The count of problems inserted.
-    problems: usize,
+    pub(crate) problems: usize,
     /// The count of empty folder files inserted.
     empty_files: usize,
 }
@@ -230,3 +454,144 @@ pub(crate) fn file_duplicates_reload(conn: &sql::Connection) -> Result<u64> {
     let row_count = conn.query_row(DUPLICATE_FILES_ROW_COUNT_SQL, (), |row| row.get(0))?;
     Ok(row_count)
 }
+
+/// The SQL that prepares the table of folders whose duplicate files will be updated.
+const CHANGED_FOLDERS_INIT_SQL: &str = r#"
+    CREATE TEMP TABLE IF NOT EXISTS changed_folders (id INTEGER PRIMARY KEY);
+    DELETE FROM changed_folders;
+"#;
+
+/// The SQL that adds a folder to the changed folders table.
+const CHANGED_FOLDERS_INSERT: &str = "INSERT OR IGNORE INTO changed_folders (id) VALUES (?1)";
+
+/// The SQL to update the filedups table for changed folders (see `sql/update_duplicate_files.sql`).
+const DUPLICATE_FILES_UPDATE_SQL: &str = include_str!("sql/update_duplicate_files.sql");
+
+/// Updates the duplicate filenames table for folders whose files have changed.
+///
+/// Only filenames found in the changed folders, either now or when the table was last loaded, are
+/// updated. The table will be the same as a full reload as long as every folder whose files were
+/// added or removed since the last load is included. Folders that were removed do not need to be
+/// included.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection that will be used.
+/// * `folder_ids` are the identifiers of the folders whose files have changed.
+pub(crate) fn file_duplicates_reload_incremental(conn: &sql::Connection, folder_ids: &[i64]) -> Result<u64> {
+    log::debug!("update filedups table");
+    conn.execute_batch(CHANGED_FOLDERS_INIT_SQL)?;
+    {
+        let mut stmt = conn.prepare_cached(CHANGED_FOLDERS_INSERT)?;
+        for folder_id in folder_ids {
+            stmt.execute([folder_id])?;
+        }
+    }
+    conn.execute_batch(DUPLICATE_FILES_UPDATE_SQL)?;
+    let row_count = conn.query_row(DUPLICATE_FILES_ROW_COUNT_SQL, (), |row| row.get(0))?;
+    Ok(row_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{database_connection, get_table_counts_query, schema_init, PathBuf};
+    use super::*;
+    use crate::filesys::testlib::folder_of_files as folder;
+
+    #[test]
+    fn load() {
+        let mut root = FolderMetadata { path: PathBuf::from("/root"), size: 0, created: 0, modified: 0, children: vec![] };
+        for folder_idx in 0..20 {
+            let folder_path = root.path.join(format!("folder{folder_idx}"));
+            let mut folder =
+                FolderMetadata { path: folder_path.clone(), size: 0, created: 0, modified: 0, children: vec![] };
+            for file_idx in 0..15 {
+                folder.children.push(FsMetadata::File(FileMetadata {
+                    path: folder_path.join(format!("file{file_idx}")),
+                    is_symlink: false,
+                    size: file_idx,
+                    created: 0,
+                    modified: 0,
+                }));
+            }
+            root.children.push(FsMetadata::Folder(folder));
+        }
+        let mut conn = database_connection(None).unwrap();
+        schema_init(&conn).unwrap();
+        let elapsed = StopWatch::start_new();
+        load_fs_metadata(&mut conn, &FsMetadata::Folder(root)).unwrap();
+        assert!(elapsed.millis() < 5000);
+        // the root folder has no files so it gets an empty folder file
+        assert_eq!(get_table_counts_query(&conn).unwrap(), (21, 301, 0));
+        let orphans: u64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM files WHERE parent_id NOT IN (SELECT id FROM folders)",
+                (),
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(orphans, 0);
+    }
+
+    #[test]
+    fn reload() {
+        let mut root = folder("/root", &["file"]);
+        root.children.push(FsMetadata::Folder(folder("/root/folder", &["file1", "file2"])));
+        root.children.push(FsMetadata::Folder(folder("/root/folder2", &["file"])));
+        let mut conn = database_connection(None).unwrap();
+        schema_init(&conn).unwrap();
+        load_fs_metadata(&mut conn, &FsMetadata::Folder(root)).unwrap();
+        assert_eq!(get_table_counts_query(&conn).unwrap(), (3, 4, 0));
+        let root_id: i64 = conn.query_row(FOLDER_ID_QUERY, ["/root"], |row| row.get(0)).unwrap();
+        // a sibling that shares the folder name prefix is not touched
+        let reloaded = FsMetadata::Folder(folder("/root/folder", &["file1", "file2", "file3"]));
+        reload_fs_metadata(&mut conn, "/root/folder", Some(&reloaded)).unwrap();
+        assert_eq!(get_table_counts_query(&conn).unwrap(), (3, 5, 0));
+        let parent_id: i64 = conn.query_row(FOLDER_PARENT_ID_QUERY, ["/root/folder"], |row| row.get(0)).unwrap();
+        assert_eq!(parent_id, root_id);
+        // a folder that no longer exists is removed
+        reload_fs_metadata(&mut conn, "/root/folder2", None).unwrap();
+        assert_eq!(get_table_counts_query(&conn).unwrap(), (2, 4, 0));
+    }
+
+    #[test]
+    fn incremental_duplicates() {
+        let duplicates = |conn: &sql::Connection| {
+            let mut stmt = conn
+                .prepare("SELECT pathname FROM filedups JOIN files ON file_id = files.id ORDER BY pathname")
+                .unwrap();
+            let rows = stmt.query_map((), |row| row.get::<usize, String>(0)).unwrap();
+            rows.map(|row| row.unwrap()).collect::<Vec<String>>()
+        };
+        let mut root = folder("/root", &["a", "b", "c"]);
+        root.children.push(FsMetadata::Folder(folder("/root/one", &["a", "b", "x"])));
+        root.children.push(FsMetadata::Folder(folder("/root/two", &["b", "y"])));
+        let mut conn = database_connection(None).unwrap();
+        schema_init(&conn).unwrap();
+        load_fs_metadata(&mut conn, &FsMetadata::Folder(root)).unwrap();
+        assert_eq!(file_duplicates_reload(&conn).unwrap(), 5);
+        // "a" is no longer a duplicate, "c" and "y" become duplicates
+        let reloaded = FsMetadata::Folder(folder("/root/one", &["b", "c", "x", "y"]));
+        reload_fs_metadata(&mut conn, "/root/one", Some(&reloaded)).unwrap();
+        let folder_id: i64 = conn.query_row(FOLDER_ID_QUERY, ["/root/one"], |row| row.get(0)).unwrap();
+        let row_count = file_duplicates_reload_incremental(&conn, &[folder_id]).unwrap();
+        let incremental = duplicates(&conn);
+        assert_eq!(row_count, incremental.len() as u64);
+        assert_eq!(file_duplicates_reload(&conn).unwrap(), row_count);
+        assert_eq!(incremental, duplicates(&conn));
+        assert_eq!(
+            incremental,
+            vec!["/root/b", "/root/c", "/root/one/b", "/root/one/c", "/root/one/y", "/root/two/b", "/root/two/y"]
+        );
+        // nothing changes when there are no changed folders
+        assert_eq!(file_duplicates_reload_incremental(&conn, &[]).unwrap(), row_count);
+        assert_eq!(incremental, duplicates(&conn));
+        // a folder that is removed takes its duplicates with it
+        reload_fs_metadata(&mut conn, "/root/two", None).unwrap();
+        file_duplicates_reload_incremental(&conn, &[]).unwrap();
+        let incremental = duplicates(&conn);
+        file_duplicates_reload(&conn).unwrap();
+        assert_eq!(incremental, duplicates(&conn));
+        assert_eq!(incremental, vec!["/root/b", "/root/c", "/root/one/b", "/root/one/c"]);
+    }
+}
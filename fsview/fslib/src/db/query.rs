@@ -7,8 +7,8 @@
 use std::collections::BTreeMap;
 
 use super::{
-    domain::{DuplicateIds, FileMd, FolderMd, Metadata, ProblemMd},
-    Error, PathBuf, Result, EMPTY_FOLDER_FILENAME, ROOT_FOLDER_PARENT_ID,
+    domain::{DuplicateIds, FileMd, FolderCount, FolderDepth, FolderMd, Metadata, ProblemMd, ScanMd},
+    table_exists, Error, PathBuf, Result, EMPTY_FOLDER_FILENAME, ROOT_FOLDER_PARENT_ID,
 };
 use rusqlite as sql;
 
@@ -217,6 +217,49 @@ pub(crate) fn database_metrics_query(conn: &sql::Connection) -> Result<u64> {
     Ok(db_size)
 }
 
+/// The SQL to query for the most deeply nested folder (see `sql/query_deepest_folder.sql`).
+const DEEPEST_FOLDER_QUERY: &str = include_str!("sql/query_deepest_folder.sql");
+
+/// Query the most deeply nested folder.
+///
+/// It uses the [DEEPEST_FOLDER_QUERY] query to follow the folder parent identifiers from the top-level
+/// folders. If there are several folders at the deepest level the first by pathname is returned.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection that will be used for the query.
+pub(crate) fn deepest_folder_query(conn: &sql::Connection) -> Result<Option<FolderDepth>> {
+    let mut stmt = conn.prepare_cached(DEEPEST_FOLDER_QUERY)?;
+    let mut rows = stmt.query(())?;
+    match rows.next()? {
+        Some(row) => Ok(Some(FolderDepth { depth: row.get("depth")?, pathname: row.get("pathname")? })),
+        None => Ok(None),
+    }
+}
+
+/// The SQL to query for the folders with the most files (see `sql/query_largest_folders_by_count.sql`).
+const LARGEST_FOLDERS_BY_COUNT_QUERY: &str = include_str!("sql/query_largest_folders_by_count.sql");
+
+/// Query the folders that directly contain the most files.
+///
+/// It uses the [LARGEST_FOLDERS_BY_COUNT_QUERY] query to count files grouped by their parent folder. The
+/// placeholder file of an empty folder is not counted. Folders with the same count are ordered by pathname.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection that will be used for the query.
+/// * `limit` is the maximum number of folders returned.
+pub(crate) fn largest_folders_by_count_query(conn: &sql::Connection, limit: usize) -> Result<Vec<FolderCount>> {
+    let mut stmt = conn.prepare_cached(LARGEST_FOLDERS_BY_COUNT_QUERY)?;
+    let mut rows = stmt.query(sql::named_params! {":empty_folder_filename": EMPTY_FOLDER_FILENAME, ":limit": limit})?;
+    let mut folder_counts = vec![];
+    while let Some(row) = rows.next()? {
+        let (id, pathname, count) = (row.get("id")?, row.get("pathname")?, row.get("count")?);
+        folder_counts.push(FolderCount { id, pathname, count });
+    }
+    Ok(folder_counts)
+}
+
 /// The SQL to query for problems (see `sql/query_problems.sql`).
 const PROBLEMS_QUERY: &str = include_str!("sql/query_problems.sql");
 
@@ -234,6 +277,120 @@ pub(crate) fn problems_query(conn: &sql::Connection) -> Result<Vec<Metadata>> {
     Ok(mapper.to_problems(&mut rows)?)
 }
 
+/// The SQL to query for problems that do not have a folder (see `sql/query_orphaned_problems.sql`).
+const ORPHANED_PROBLEMS_QUERY: &str = include_str!("sql/query_orphaned_problems.sql");
+
+/// Query the problems whose parent folder is not in the database.
+///
+/// Problems are removed along with their folder so anything found indicates a data integrity issue. The
+/// foreign key on the problem parent is enforced by the connections this library creates however `SQLite`
+/// tools such as the `sqlite3` shell do not enforce foreign keys by default. Folders deleted by one of them
+/// will leave the problem rows behind.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection that will be used for the query.
+pub(crate) fn orphaned_problems_query(conn: &sql::Connection) -> Result<Vec<ProblemMd>> {
+    let mut stmt = conn.prepare_cached(ORPHANED_PROBLEMS_QUERY)?;
+    let mut rows = stmt.query(())?;
+    let mut problems = vec![];
+    while let Some(row) = rows.next()? {
+        let pathname: String = row.get("problem_pathname")?;
+        let name = PathBuf::from(&pathname).file_name().map_or(String::default(), |name| name.to_string_lossy().into());
+        problems.push(ProblemMd {
+            id: row.get("problem_id")?,
+            parent_id: row.get("problem_parent_id")?,
+            pathname,
+            name,
+            description: row.get("problem_description")?,
+        });
+    }
+    Ok(problems)
+}
+
+/// The SQL to query the history of folders added to the database (see `sql/query_scans.sql`).
+const SCANS_QUERY: &str = include_str!("sql/query_scans.sql");
+
+/// Query the history of folders added to the database.
+///
+/// An empty history is returned for databases created before scans were recorded.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection that will be used for the query.
+pub(crate) fn scans_query(conn: &sql::Connection) -> Result<Vec<ScanMd>> {
+    if !table_exists(conn, "scans")? {
+        return Ok(vec![]);
+    }
+    let mut stmt = conn.prepare_cached(SCANS_QUERY)?;
+    let mut rows = stmt.query(())?;
+    let mut scans = vec![];
+    while let Some(row) = rows.next()? {
+        scans.push(ScanMd {
+            id: row.get("scan_id")?,
+            scanned: row.get("scan_scanned")?,
+            pathname: row.get("scan_pathname")?,
+            folders: row.get("scan_folders")?,
+            files: row.get("scan_files")?,
+            problems: row.get("scan_problems")?,
+            elapsed: row.get("scan_elapsed")?,
+        });
+    }
+    Ok(scans)
+}
+
+/// Query the patterns used to exclude folders and files from each root folder.
+///
+/// An empty collection is returned for databases created before exclude patterns were recorded.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection that will be used for the query.
+pub(crate) fn excludes_query(conn: &sql::Connection) -> Result<BTreeMap<String, Vec<String>>> {
+    let mut excludes: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if table_exists(conn, "excludes")? {
+        let mut stmt = conn.prepare_cached("SELECT pathname, pattern FROM excludes ORDER BY pathname, pattern")?;
+        let mut rows = stmt.query(())?;
+        while let Some(row) = rows.next()? {
+            excludes.entry(row.get("pathname")?).or_default().push(row.get("pattern")?);
+        }
+    }
+    Ok(excludes)
+}
+
+/// The SQL to query the most recent scan of each root folder (see `sql/query_recent_roots.sql`).
+const RECENT_ROOTS_QUERY: &str = include_str!("sql/query_recent_roots.sql");
+
+/// Query the root folders that were most recently added to the database.
+///
+/// It uses the [RECENT_ROOTS_QUERY] query to get the latest scan of each root folder, most recent first.
+/// Root folders that do not have a scan recorded are not included.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection that will be used for the query.
+/// * `limit` is the maximum number of root folders returned.
+pub(crate) fn recent_roots_query(conn: &sql::Connection, limit: usize) -> Result<Vec<ScanMd>> {
+    if !table_exists(conn, "scans")? {
+        return Ok(vec![]);
+    }
+    let mut stmt = conn.prepare_cached(RECENT_ROOTS_QUERY)?;
+    let mut rows = stmt.query(sql::named_params! {":parent_id": ROOT_FOLDER_PARENT_ID, ":limit": limit})?;
+    let mut scans = vec![];
+    while let Some(row) = rows.next()? {
+        scans.push(ScanMd {
+            id: row.get("scan_id")?,
+            scanned: row.get("scan_scanned")?,
+            pathname: row.get("scan_pathname")?,
+            folders: row.get("scan_folders")?,
+            files: row.get("scan_files")?,
+            problems: row.get("scan_problems")?,
+            elapsed: row.get("scan_elapsed")?,
+        });
+    }
+    Ok(scans)
+}
+
 /// The data mapper for results returned from the problems query result set.
 ///
 /// The attributes of the structure hold the column index in the result set for the metadata attributes.
@@ -506,6 +663,82 @@ impl FolderFileRowMap {
     }
 }
 
+/// The SQL to query the metadata of all files excluding the empty folder placeholders.
+const FILES_METADATA_QUERY: &str = r#"
+    SELECT id, parent_id, pathname, name, is_symlink, size, created, modified
+    FROM files
+    WHERE name != :empty_folder_filename
+    ORDER BY name, size
+"#;
+
+/// Query the metadata of all files in the database.
+///
+/// It uses the [FILES_METADATA_QUERY] query to get the metadata. Files that are placeholders
+/// for empty folders will not be included.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection that will be used for the query.
+/// * `file_callback` is the function that will be called with file metadata. If `false` is returned
+/// from the function iteration over the result set will stop.
+pub(crate) fn files_metadata_query<F>(conn: &sql::Connection, mut file_callback: F) -> Result<()>
+where
+    F: FnMut(FileMd) -> Result<bool>,
+{
+    let mut stmt = conn.prepare_cached(FILES_METADATA_QUERY)?;
+    let mut rows = stmt.query(&[(":empty_folder_filename", EMPTY_FOLDER_FILENAME)])?;
+    while let Some(row) = rows.next()? {
+        if !file_callback(file_metadata(row)?)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// The SQL query to get the metadata of files that are 0 bytes in size.
+const EMPTY_FILES_QUERY: &str = r#"
+    SELECT id, parent_id, pathname, name, is_symlink, size, created, modified
+    FROM files
+    WHERE size = 0 AND name != :empty_folder_filename
+    ORDER BY pathname
+"#;
+
+/// Query the metadata of files that are 0 bytes in size.
+///
+/// It uses the [EMPTY_FILES_QUERY] query to get the metadata. Files that are placeholders
+/// for empty folders will not be included.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection that will be used for the query.
+pub(crate) fn empty_files_query(conn: &sql::Connection) -> Result<Vec<FileMd>> {
+    let mut stmt = conn.prepare_cached(EMPTY_FILES_QUERY)?;
+    let mut rows = stmt.query(&[(":empty_folder_filename", EMPTY_FOLDER_FILENAME)])?;
+    let mut files = vec![];
+    while let Some(row) = rows.next()? {
+        files.push(file_metadata(row)?);
+    }
+    Ok(files)
+}
+
+/// Create file metadata from a row of the files table.
+///
+/// # Arguments
+///
+/// * `row` is a single result from the query results.
+fn file_metadata(row: &sql::Row) -> Result<FileMd> {
+    Ok(FileMd {
+        id: row.get("id")?,
+        parent_id: row.get("parent_id")?,
+        pathname: row.get("pathname")?,
+        name: row.get("name")?,
+        is_symlink: row.get("is_symlink")?,
+        size: row.get("size")?,
+        created: row.get("created")?,
+        modified: row.get("modified")?,
+    })
+}
+
 /// The SQL query to count the number of duplicate filenames
 const COUNT_DUPLICATE_FILENAMES: &str =
     "SELECT COUNT(DISTINCT files.name) FROM filedups JOIN files ON file_id = files.id";
@@ -572,6 +805,31 @@ where
     Ok(())
 }
 
+/// The SQL to query the content of folders with duplicate filenames (see `sql/query_folder_content_by_filedups.sql`).
+const FOLDER_CONTENT_BY_FILEDUPS_SQL: &str = include_str!("sql/query_folder_content_by_filedups.sql");
+
+/// Query the content of all folders that have duplicate filenames.
+///
+/// It uses the [FOLDER_CONTENT_BY_FILEDUPS_SQL] query to get the metadata. Unlike the duplicate files
+/// metadata query the folders will include all of their files.
+///
+/// # Arguments
+///
+/// * `conn` the database connection that will be used for the query.
+/// * `folder_callback` is the function that will be called with folder metadata properties. The function
+/// will be called once for each resulting folder found. If `false` is returned from the function
+/// iteration over the result set will stop.
+pub(crate) fn folder_content_by_filedups_query<F>(conn: &sql::Connection, folder_callback: F) -> Result<()>
+where
+    F: FnMut(FolderMd) -> Result<bool>,
+{
+    let mut stmt = conn.prepare_cached(FOLDER_CONTENT_BY_FILEDUPS_SQL)?;
+    let mapper = FolderFileRowMap::new(&stmt)?;
+    let mut rows = stmt.query(())?;
+    mapper.to_folders(&mut rows, folder_callback)?;
+    Ok(())
+}
+
 /// The mapper that consummes the duplicate files identifiers query.
 struct DuplicateIdMapper {
     /// The index of the filename.
@@ -637,7 +895,7 @@ impl DuplicateIdMapper {
 mod windows_tests {
     use super::super::{
         database_connection,
-        filesys::{collect_metadata, FsMetadata},
+        filesys::{collect_metadata_excluding, FsMetadata},
         load_fs_metadata, schema_init,
     };
     use super::*;
@@ -720,7 +978,7 @@ mod windows_tests {
     fn collect_fs_metadata(folder: PathBuf, output_file: PathBuf) -> super::Result<()> {
         use std::fs::File;
         use std::io::Write;
-        let fs_metadata = collect_metadata(&folder).unwrap();
+        let fs_metadata = collect_metadata_excluding(&folder, &[]).unwrap();
         let yaml = serde_yaml::to_string(&fs_metadata).unwrap();
         let mut file = File::create(output_file).unwrap();
         file.write_all(yaml.as_bytes()).unwrap();
@@ -781,6 +1039,22 @@ mod tests {
         ProblemsMap::new(&stmt).unwrap();
     }
 
+    #[test]
+    fn orphaned_problems_query() {
+        let conn = test_db_connection();
+        conn.execute("INSERT INTO folders VALUES (1, 0, '/root', 'root', 0, 0, 0)", ()).unwrap();
+        conn.execute("INSERT INTO problems VALUES (1, 1, '/root/problem', 'folder problem')", ()).unwrap();
+        assert!(super::orphaned_problems_query(&conn).unwrap().is_empty());
+        // foreign keys have to be disabled to create the orphan
+        conn.pragma_update(None, "foreign_keys", false).unwrap();
+        conn.execute("INSERT INTO problems VALUES (2, 2, '/other/problem', 'orphaned problem')", ()).unwrap();
+        let testcase = super::orphaned_problems_query(&conn).unwrap();
+        assert_eq!(testcase.len(), 1);
+        assert_eq!(testcase[0].id, 2);
+        assert_eq!(testcase[0].name, "problem");
+        assert_eq!(testcase[0].description, "orphaned problem");
+    }
+
     #[test]
     fn duplicate_ids_sql() {
         let conn = test_db_connection();
@@ -788,6 +1062,19 @@ mod tests {
         DuplicateIdMapper::new(&stmt).unwrap();
     }
 
+    #[test]
+    fn folder_content_by_filedups_sql() {
+        let conn = test_db_connection();
+        let stmt = conn.prepare_cached(FOLDER_CONTENT_BY_FILEDUPS_SQL).unwrap();
+        FolderFileRowMap::new(&stmt).unwrap();
+    }
+
+    #[test]
+    fn files_metadata_query_sql() {
+        let conn = test_db_connection();
+        files_metadata_query(&conn, |_| Ok(true)).unwrap();
+    }
+
     #[test]
     fn duplicate_files_metadata_query_sql() {
         let conn = test_db_connection();
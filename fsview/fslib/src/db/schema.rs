@@ -1,6 +1,12 @@
 //! # The module that initializes and optionally drops a database.
 use super::*;
 
+/// The version of the database schema, it is stamped into the `meta` table when the schema is initialized.
+pub const SCHEMA_VERSION: i64 = 1;
+
+/// The `meta` table key of the schema version.
+const SCHEMA_VERSION_KEY: &str = "schema_version";
+
 /// The API used by the `domain` to drop a database.
 /// 
 /// The SQL to drop the database schema is stored in `sql/schema_drop.sql` within the source directory.
@@ -10,6 +16,7 @@ use super::*;
 /// * `conn` - a connection to the database.
 /// * `reclaim_space` - when `true` space used by an existing database will be reclaimed.
 pub fn drop(conn: &sql::Connection, reclaim_space: bool) -> Result<()> {
+    check_version(conn)?;
     let drop_sql = include_str!("sql/schema_drop.sql");
     log::debug!("drop schema");
     conn.execute_batch(drop_sql)?;
@@ -22,14 +29,88 @@ pub fn drop(conn: &sql::Connection, reclaim_space: bool) -> Result<()> {
 /// The API used by the `domain` to initialize a database.
 /// 
 /// The SQL to drop the database schema is stored in `sql/schema_init.sql` within the source directory.
+/// The [SCHEMA_VERSION] is stamped into the database after the schema has been initialized.
 /// 
 /// # Arguments
 /// 
 /// * `conn` - a connection to the database.
 pub fn init(conn: &sql::Connection) -> Result<()> {
+    check_version(conn)?;
     let schema_sql = include_str!("sql/schema_init.sql");
     log::debug!("init schema");
     conn.execute_batch(schema_sql)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO meta (key, value) VALUES (?1, ?2)",
+        (SCHEMA_VERSION_KEY, SCHEMA_VERSION.to_string()),
+    )?;
+    Ok(())
+}
+
+/// The API used by the `domain` to get the database schema version.
+/// 
+/// `None` is returned if the schema version has not been stamped into the database.
+/// 
+/// # Arguments
+/// 
+/// * `conn` - a connection to the database.
+pub fn version(conn: &sql::Connection) -> Result<Option<i64>> {
+    if !table_exists(conn, "meta")? {
+        Ok(None)
+    } else {
+        let mut stmt = conn.prepare("SELECT value FROM meta WHERE key = ?1")?;
+        let mut rows = stmt.query([SCHEMA_VERSION_KEY])?;
+        match rows.next()? {
+            None => Ok(None),
+            Some(row) => {
+                let value: String = row.get(0)?;
+                match value.parse() {
+                    Ok(version) => Ok(Some(version)),
+                    Err(_) => Err(Error::from(format!("the schema version '{value}' is not valid..."))),
+                }
+            }
+        }
+    }
+}
+
+/// The API used by the `domain` to check if a table is in the database.
+/// 
+/// Databases created by earlier versions of the schema will not have tables that were added later.
+/// 
+/// # Arguments
+/// 
+/// * `conn` - a connection to the database.
+/// * `name` - the name of the table.
+pub fn table_exists(conn: &sql::Connection, name: &str) -> Result<bool> {
+    let sql = "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1";
+    let count: i64 = conn.query_row(sql, [name], |row| row.get(0))?;
+    Ok(count > 0)
+}
+
+/// An internal function that guards against changing a database created by a newer schema.
+/// 
+/// # Arguments
+/// 
+/// * `conn` - a connection to the database.
+fn check_version(conn: &sql::Connection) -> Result<()> {
+    match version(conn)? {
+        Some(version) if version > SCHEMA_VERSION => Err(Error::from(format!(
+            "the database schema version {version} is newer than the supported version {SCHEMA_VERSION}..."
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// The API used by the `domain` to reclaim unused database space.
+/// 
+/// The database is vacuumed and the write-ahead log is truncated.
+/// 
+/// # Arguments
+/// 
+/// * `conn` - a connection to the database.
+pub fn maintenance(conn: &sql::Connection) -> Result<()> {
+    log::debug!("maintenance");
+    conn.execute("VACUUM", ())?;
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", (), |_| Ok(()))?;
     Ok(())
 }
 
@@ -43,4 +124,30 @@ mod tests {
         super::drop(&conn, true).unwrap();
         super::init(&conn).unwrap();
     }
+    #[test]
+    fn version() {
+        let conn = super::database_connection(None).unwrap();
+        assert_eq!(super::version(&conn).unwrap(), None);
+        super::init(&conn).unwrap();
+        assert_eq!(super::version(&conn).unwrap(), Some(super::SCHEMA_VERSION));
+        // initializing again keeps the version
+        super::init(&conn).unwrap();
+        assert_eq!(super::version(&conn).unwrap(), Some(super::SCHEMA_VERSION));
+        // a database from a newer schema cannot be initialized or dropped
+        let future_version = (super::SCHEMA_VERSION + 1).to_string();
+        conn.execute("UPDATE meta SET value = ?1 WHERE key = 'schema_version'", [&future_version]).unwrap();
+        assert!(super::init(&conn).is_err());
+        assert!(super::drop(&conn, false).is_err());
+        assert_eq!(super::version(&conn).unwrap(), Some(super::SCHEMA_VERSION + 1));
+        // dropping the schema removes the version
+        conn.execute("DELETE FROM meta", ()).unwrap();
+        super::drop(&conn, false).unwrap();
+        assert_eq!(super::version(&conn).unwrap(), None);
+    }
+    #[test]
+    fn maintenance() {
+        let conn = super::database_connection(None).unwrap();
+        super::init(&conn).unwrap();
+        super::maintenance(&conn).unwrap();
+    }
 }
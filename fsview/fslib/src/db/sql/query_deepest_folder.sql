@@ -0,0 +1,23 @@
+WITH RECURSIVE folder_depths(id, pathname, depth) AS (
+    SELECT
+        id, pathname, 0
+    FROM
+        folders
+    WHERE
+        parent_id = 0
+    UNION ALL
+    SELECT
+        folders.id, folders.pathname, folder_depths.depth + 1
+    FROM
+        folders
+        INNER JOIN
+            folder_depths on folders.parent_id = folder_depths.id
+)
+SELECT
+    depth,
+    pathname
+FROM
+    folder_depths
+ORDER BY
+    depth DESC, pathname
+LIMIT 1;
@@ -0,0 +1,16 @@
+SELECT
+    folders.id,
+    folders.pathname,
+    COUNT(*) AS count
+FROM
+    files
+    INNER JOIN
+        folders ON files.parent_id = folders.id
+WHERE
+    files.name <> :empty_folder_filename
+GROUP BY
+    files.parent_id
+ORDER BY
+    count DESC, folders.pathname
+LIMIT
+    :limit;
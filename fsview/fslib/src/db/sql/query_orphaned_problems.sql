@@ -0,0 +1,13 @@
+SELECT
+    problems.id AS problem_id,
+    problems.parent_id AS problem_parent_id,
+    problems.pathname AS problem_pathname,
+    problems.description AS problem_description
+FROM
+    problems
+    LEFT JOIN
+        folders on folders.id = problems.parent_id
+WHERE
+    folders.id IS NULL
+ORDER BY
+    problem_pathname;
@@ -0,0 +1,19 @@
+SELECT
+    scans.id AS scan_id,
+    scans.scanned AS scan_scanned,
+    scans.pathname AS scan_pathname,
+    scans.folders AS scan_folders,
+    scans.files AS scan_files,
+    scans.problems AS scan_problems,
+    scans.elapsed AS scan_elapsed
+FROM
+    scans
+    INNER JOIN
+        folders ON scans.pathname = folders.pathname
+WHERE
+    folders.parent_id = :parent_id
+    AND scans.id = (SELECT MAX(latest.id) FROM scans AS latest WHERE latest.pathname = scans.pathname)
+ORDER BY
+    scan_scanned DESC, scan_id DESC
+LIMIT
+    :limit;
@@ -0,0 +1,12 @@
+SELECT
+    scans.id AS scan_id,
+    scans.scanned AS scan_scanned,
+    scans.pathname AS scan_pathname,
+    scans.folders AS scan_folders,
+    scans.files AS scan_files,
+    scans.problems AS scan_problems,
+    scans.elapsed AS scan_elapsed
+FROM
+    scans
+ORDER BY
+    scan_id;
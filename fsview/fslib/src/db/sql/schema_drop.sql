@@ -1,3 +1,7 @@
+-- remove tables that do not have a foreign key
+DROP TABLE IF EXISTS meta;
+DROP TABLE IF EXISTS scans;
+DROP TABLE IF EXISTS excludes;
 -- remove tables that have a foreign key with files and folders
 DROP TABLE IF EXISTS dupfiles;
 -- remove tables that have a foreign key with folders
@@ -63,4 +63,31 @@ CREATE TABLE IF NOT EXISTS filedups
 -- cover the associated folder with an index
 CREATE INDEX IF NOT EXISTS idx_dupfiles_parent ON filedups(parent_id);
 
+-- the history of folders added to the database
+CREATE TABLE IF NOT EXISTS scans
+(
+    id INTEGER PRIMARY KEY,
+    scanned INTEGER NOT NULL,
+    pathname TEXT NOT NULL,
+    folders INTEGER NOT NULL,
+    files INTEGER NOT NULL,
+    problems INTEGER NOT NULL,
+    elapsed INTEGER NOT NULL
+);
+
+-- the patterns of folders and files excluded when a root folder was added
+CREATE TABLE IF NOT EXISTS excludes
+(
+    pathname TEXT NOT NULL,
+    pattern TEXT NOT NULL,
+    PRIMARY KEY (pathname, pattern)
+) WITHOUT ROWID;
+
+-- information about the database such as the schema version
+CREATE TABLE IF NOT EXISTS meta
+(
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
 COMMIT;
\ No newline at end of file
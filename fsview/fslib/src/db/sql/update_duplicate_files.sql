@@ -0,0 +1,82 @@
+-- the changed_folders table must be populated with the folders that changed
+-- use a transaction to mask the table changes
+BEGIN;
+
+-- the filenames that may have changed duplicate status
+CREATE TEMP TABLE IF NOT EXISTS changed_filenames
+(
+    name TEXT PRIMARY KEY
+) WITHOUT ROWID;
+DELETE FROM changed_filenames;
+
+-- the filenames currently in the changed folders
+INSERT OR IGNORE INTO changed_filenames (name)
+SELECT
+    name
+FROM
+    files
+WHERE
+    parent_id IN (SELECT id FROM changed_folders)
+    AND name <> '<?>';
+
+-- the filenames that were duplicates in the changed folders
+INSERT OR IGNORE INTO changed_filenames (name)
+SELECT
+    files.name
+FROM
+    filedups
+JOIN
+    files ON filedups.file_id = files.id
+WHERE
+    filedups.parent_id IN (SELECT id FROM changed_folders);
+
+-- remove duplicates whose file no longer exists
+DELETE FROM filedups WHERE file_id NOT IN (SELECT id FROM files);
+
+-- remove the duplicates of the changed filenames
+DELETE FROM filedups WHERE file_id IN
+(
+    SELECT id FROM files WHERE name IN (SELECT name FROM changed_filenames)
+);
+
+-- add the changed filenames that are duplicates
+INSERT INTO filedups (file_id, parent_id)
+SELECT
+    id AS file_id,
+    parent_id
+FROM
+    files
+WHERE
+    name IN
+    (
+        SELECT
+            files.name
+        FROM
+            files
+        JOIN
+            changed_filenames ON files.name = changed_filenames.name
+        GROUP BY
+            files.name
+        HAVING
+            COUNT(*) > 1
+    )
+ORDER BY
+    file_id;
+
+-- a filename whose other duplicates were removed is no longer a duplicate
+DELETE FROM filedups WHERE file_id IN
+(
+    SELECT
+        MIN(file_id)
+    FROM
+        filedups
+    JOIN
+        files ON filedups.file_id = files.id
+    GROUP BY
+        files.name
+    HAVING
+        COUNT(*) = 1
+);
+
+-- make the table changes visible
+COMMIT;
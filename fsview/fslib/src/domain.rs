@@ -1,21 +1,30 @@
 //! # Domain objects used by cli and files modules
 //!
 use rusqlite as sql;
-use std::{fmt, path::PathBuf, result};
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    result,
+};
 
 use super::{db, filesys, StopWatch};
 
 mod api;
 mod filedups;
 mod objects;
+#[cfg(feature = "watch")]
+mod watch;
 
 pub(crate) use filedups::DuplicateFoldersBuilder;
 pub use filedups::{
     DuplicateFolders, DuplicateFoldersMatch, FolderAnalysisMd, FolderGroupId, FolderGroupMd, FolderNoMatchMd,
-    FoldersMatchMd, FoldersNoMatch,
+    FoldersMatchMd, FoldersNoMatch, SimilarFolders, SimilarFoldersMd,
 };
 pub(crate) use objects::DuplicateIds;
-pub use objects::{DbInformation, FileMd, FolderMd, Metadata, ProblemMd};
+pub use objects::{
+    DatabaseCompare, DbInformation, FileMd, FilenameFolders, FolderCount, FolderDepth, FolderMd, MaintenanceReport,
+    Metadata, ProblemMd, ScanMd, SizeBucket, SizeHistogram,
+};
 
 /// The type of result returned from the domain.
 pub type Result<T> = result::Result<T, Error>;
@@ -25,18 +34,32 @@ pub type Result<T> = result::Result<T, Error>;
 /// I like the pattern of a module consolidating errors. In this case
 /// collecting the `fmt::Error`, `db::Error`, `filesys::Error`, etc. and
 /// mapping it to the module error hides details concerning the implementation.
+/// The variants let callers tell what kind of problem occurred.
 #[derive(Debug)]
-pub struct Error(String);
+pub enum Error {
+    /// A database error.
+    Db(String),
+    /// A filesystem error.
+    Filesys(String),
+    /// Something the domain needs does not exist.
+    NotFound(String),
+    /// The domain was asked to do something that is not valid.
+    Invalid(String),
+}
 /// Include the [`ToString`] trait for the domain [`Error`].
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        match self {
+            Error::Db(error) | Error::Filesys(error) | Error::NotFound(error) | Error::Invalid(error) => {
+                write!(f, "{error}")
+            }
+        }
     }
 }
 /// This is the domain error handler.
 impl From<&str> for Error {
     fn from(error: &str) -> Self {
-        Error(format!("domain: {error}"))
+        Error::Invalid(format!("domain: {error}"))
     }
 }
 /// Create a domain error from a string.
@@ -49,14 +72,32 @@ impl From<String> for Error {
 impl From<db::Error> for Error {
     fn from(error: db::Error) -> Self {
         // simply remap the error
-        Error(error.to_string())
+        Error::Db(error.to_string())
+    }
+}
+/// Convert a `csv::Error` to a domain error.
+impl From<csv::Error> for Error {
+    fn from(error: csv::Error) -> Self {
+        Error::Filesys(format!("domain: csv: {error}"))
+    }
+}
+/// Convert a `serde_json::Error` to a domain error.
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Error::Invalid(format!("domain: json: {error}"))
+    }
+}
+/// Convert an `io::Error` to a domain error.
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Filesys(format!("domain: io: {error}"))
     }
 }
 /// Convert a `filesys::Error` to a domain error.
 impl From<filesys::Error> for Error {
     fn from(error: filesys::Error) -> Self {
         // simply remap the error
-        Error(error.to_string())
+        Error::Filesys(error.to_string())
     }
 }
 
@@ -88,12 +129,55 @@ impl Session {
     /// # Arguments
     /// * `folder_pathname` - a filesystem folder whose hierarchy will be added to the database.
     pub fn add_folder(&self, folder_pathname: &PathBuf) -> Result<()> {
+        self.add_folder_excluding(folder_pathname, &[])
+    }
+    /// Add a folder hierarchy to the database skipping folders and files that match an exclude pattern.
+    ///
+    /// The exclude patterns are saved with the folder and used when it is rescanned.
+    ///
+    /// # Arguments
+    /// * `folder_pathname` - a filesystem folder whose hierarchy will be added to the database.
+    /// * `exclude` - glob patterns of folder and file names or pathnames that will not be added.
+    pub fn add_folder_excluding(&self, folder_pathname: &PathBuf, exclude: &[String]) -> Result<()> {
         if folder_pathname.is_dir() {
             // don't require a mutable session in order to pass in a mutable connection to the api
             let load_conn = db::database_connection(Some(&self.db_path))?;
-            api::add_filesystem_folder(load_conn, folder_pathname)
+            api::add_filesystem_folder(load_conn, folder_pathname, exclude)
         } else {
-            Err(Error(format!("{} must be a filesystem folder!!!", folder_pathname.as_path().display())))
+            Err(Error::Invalid(format!("{} must be a filesystem folder!!!", folder_pathname.as_path().display())))
+        }
+    }
+    /// Load previously exported filesystem metadata into an empty database.
+    ///
+    /// The live filesystem is not used, the `JSON` document must contain the folder hierarchy.
+    ///
+    /// # Arguments
+    /// * `reader` - where the `JSON` filesystem metadata will be read from.
+    pub fn import_metadata<R: io::Read>(&self, reader: R) -> Result<()> {
+        let mut import_conn = db::database_connection(Some(&self.db_path))?;
+        api::import_metadata(&mut import_conn, reader)
+    }
+    /// Replace a folder hierarchy in the database with its current filesystem metadata.
+    ///
+    /// # Arguments
+    /// * `folder_path` - the folder that will be rescanned, it is removed from the database if it no longer exists.
+    pub fn rescan_folder(&self, folder_path: &Path) -> Result<()> {
+        let mut rescan_conn = db::database_connection(Some(&self.db_path))?;
+        api::rescan_folder(&mut rescan_conn, folder_path)
+    }
+    /// Watch a folder hierarchy and rescan folders as they change on the filesystem.
+    ///
+    /// The function blocks until the filesystem watcher stops.
+    ///
+    /// # Arguments
+    /// * `root` - the filesystem folder that will be watched.
+    #[cfg(feature = "watch")]
+    pub fn watch(&self, root: &Path) -> Result<()> {
+        if root.is_dir() {
+            let watch_conn = db::database_connection(Some(&self.db_path))?;
+            watch::watch(watch_conn, root, watch::DEBOUNCE)
+        } else {
+            Err(Error::Invalid(format!("{} must be a filesystem folder!!!", root.display())))
         }
     }
     /// Initialize the database schema.
@@ -104,6 +188,24 @@ impl Session {
     pub fn initialize_db(&self, drop_database: bool) -> Result<()> {
         api::initialize_db(&self.conn, drop_database)
     }
+    /// Reclaim unused database space.
+    pub fn maintenance(&self) -> Result<MaintenanceReport> {
+        api::maintenance(&self.conn)
+    }
+    /// Compare the files in the session database with the files in another database.
+    ///
+    /// The other database is opened read-only so it is never changed by the comparison.
+    ///
+    /// # Arguments
+    /// * `other_db` - the database the session database will be compared to.
+    pub fn compare_databases(&self, other_db: &Path) -> Result<DatabaseCompare> {
+        if other_db.is_file() {
+            let other_conn = db::read_only_connection(other_db)?;
+            api::compare_databases(&self.conn, &other_conn)
+        } else {
+            Err(Error::NotFound(format!("{} must be an existing database!!!", other_db.display())))
+        }
+    }
     /// Get database metrics and properties.
     pub fn get_db_information(&self) -> Result<DbInformation> {
         api::get_db_information(&self.conn)
@@ -126,10 +228,68 @@ impl Session {
     pub fn get_folder_by_pathname(&self, folder_pathname: &str, recursive: bool) -> Result<Vec<Metadata>> {
         api::get_folder_by_pathname(&self.conn, folder_pathname, recursive)
     }
+    /// Get a folder hierarchy as nested `JSON` objects with `children` arrays.
+    ///
+    /// # Arguments
+    /// * `root_pathname` - the pathname of the top level folder in the hierarchy.
+    pub fn tree_json(&self, root_pathname: &str) -> Result<String> {
+        api::tree_json(&self.conn, root_pathname)
+    }
+    /// Get a fingerprint of the content of a folder.
+    ///
+    /// Folders containing files and folders with the same names and sizes have the same fingerprint.
+    ///
+    /// # Arguments
+    /// * `folder_pathname` - the pathname of the folder.
+    pub fn folder_fingerprint(&self, folder_pathname: &str) -> Result<String> {
+        api::folder_fingerprint(&self.conn, folder_pathname)
+    }
+    /// Get the database schema version, `None` if the database has not been initialized.
+    pub fn schema_version(&self) -> Result<Option<i64>> {
+        api::schema_version(&self.conn)
+    }
+    /// Get the database schema version supported by the library.
+    pub fn supported_schema_version(&self) -> i64 {
+        db::SCHEMA_VERSION
+    }
+    /// Get the most deeply nested folder, `None` if there are no folders.
+    pub fn deepest_folder(&self) -> Result<Option<FolderDepth>> {
+        api::deepest_folder(&self.conn)
+    }
+    /// Get the folders that directly contain the most files, ordered by the file count.
+    ///
+    /// # Arguments
+    /// * `n` - the maximum number of folders that will be returned.
+    pub fn largest_folders_by_count(&self, n: usize) -> Result<Vec<FolderCount>> {
+        api::largest_folders_by_count(&self.conn, n)
+    }
+    /// Get the distribution of file sizes in the database.
+    pub fn size_histogram(&self) -> Result<SizeHistogram> {
+        api::size_histogram(&self.conn)
+    }
     /// Get the problems that were encountered adding folders to the database.
     pub fn get_problems(&self) -> Result<Vec<Metadata>> {
         api::get_problems(&self.conn)
     }
+    /// Get the problems whose folder is no longer in the database.
+    pub fn orphaned_problems(&self) -> Result<Vec<ProblemMd>> {
+        api::orphaned_problems(&self.conn)
+    }
+    /// Get the files that are 0 bytes in size, empty folder placeholders are not included.
+    pub fn empty_files(&self) -> Result<Vec<FileMd>> {
+        api::empty_files(&self.conn)
+    }
+    /// Get the history of folders that have been added to the database, oldest first.
+    pub fn scan_history(&self) -> Result<Vec<ScanMd>> {
+        api::scan_history(&self.conn)
+    }
+    /// Get the root folders most recently added to the database, most recent first.
+    ///
+    /// # Arguments
+    /// * `n` - the maximum number of root folders returned.
+    pub fn recent_roots(&self, n: usize) -> Result<Vec<ScanMd>> {
+        api::recent_roots(&self.conn, n)
+    }
     /// Get the contents of the top level folder added to the database.
     pub fn get_root_content(&self) -> Result<Vec<Metadata>> {
         api::get_root_content(&self.conn)
@@ -138,20 +298,162 @@ impl Session {
     pub fn duplicate_files_reload(&self) -> Result<u64> {
         api::file_duplicates_reload(&self.conn)
     }
+    /// Updates the duplicate files table for folders whose files have changed.
+    ///
+    /// The result is the same as a full reload provided every folder whose files were added or removed
+    /// since the table was last loaded is included.
+    ///
+    /// # Arguments
+    /// * `changed_folder_ids` - the folders whose files have changed.
+    pub fn duplicate_files_reload_incremental(&self, changed_folder_ids: &[i64]) -> Result<u64> {
+        api::file_duplicates_reload_incremental(&self.conn, changed_folder_ids)
+    }
+    /// Get the filenames that are in more than one folder along with the folders that contain them.
+    pub fn filename_folders(&self) -> Result<Vec<FilenameFolders>> {
+        api::filename_folders(&self.conn)
+    }
     /// Loads the duplicate files table.
     pub fn duplicate_files_summary(&self) -> Result<(u64, u64)> {
         api::file_duplicates_summary(&self.conn)
     }
     /// Get the metadata concerning all duplicate folders and files.
-    pub fn duplicate_folders_files(&self) -> Result<DuplicateFolders> {
-        api::duplicate_folders_metadata(&self.conn)
+    ///
+    /// # Arguments
+    /// * `min_folders` - folder groups with fewer folders than this are not included.
+    pub fn duplicate_folders_files(&self, min_folders: usize) -> Result<DuplicateFolders> {
+        api::duplicate_folders_metadata(&self.conn, min_folders)
+    }
+    /// Export the duplicate files metadata as `CSV` returning the number of rows written.
+    ///
+    /// # Arguments
+    /// * `writer` - where the `CSV` document will be written.
+    /// * `min_folders` - folder groups with fewer folders than this are not exported.
+    pub fn duplicate_files_csv<W: io::Write>(&self, writer: W, min_folders: usize) -> Result<usize> {
+        api::duplicate_files_csv(&self.conn, writer, min_folders)
     }
     /// Get the metadata for folders that have duplicate file contents.
-    pub fn duplicate_folders_files_match(&self) -> Result<DuplicateFoldersMatch> {
-        api::folders_match_metadata(&self.conn)
+    ///
+    /// # Arguments
+    /// * `min_folders` - folder groups with fewer folders than this are not included.
+    pub fn duplicate_folders_files_match(&self, min_folders: usize) -> Result<DuplicateFoldersMatch> {
+        api::folders_match_metadata(&self.conn, min_folders)
+    }
+    /// Get the metadata for folders that are exact duplicates of each other.
+    ///
+    /// # Arguments
+    /// * `min_folders` - folder groups with fewer folders than this are not included.
+    pub fn duplicate_folders_exact_match(&self, min_folders: usize) -> Result<DuplicateFoldersMatch> {
+        api::folders_exact_match_metadata(&self.conn, min_folders)
+    }
+    /// Get the folders with duplicate filenames whose filenames are similar.
+    ///
+    /// # Arguments
+    /// * `threshold` - the minimum Jaccard similarity (`0.0` to `1.0`) of folder filenames.
+    pub fn duplicate_folders_similar(&self, threshold: f64) -> Result<SimilarFolders> {
+        api::similar_folders_metadata(&self.conn, threshold)
     }
     /// Get the metadata for folders file content that did not match other folders file content.
-    pub fn duplicate_folders_no_match(&self) -> Result<FoldersNoMatch> {
-        api::folders_no_match_metadata(&self.conn)
+    ///
+    /// # Arguments
+    /// * `min_folders` - folder groups with fewer folders than this are not included.
+    pub fn duplicate_folders_no_match(&self, min_folders: usize) -> Result<FoldersNoMatch> {
+        api::folders_no_match_metadata(&self.conn, min_folders)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filesys::testlib::TempFolder;
+
+    /// Create an initialized session whose database lives in the scratch folder.
+    fn temp_session(temp_folder: &TempFolder, db_name: &str) -> Session {
+        let session = get_session(temp_folder.path().join(db_name)).unwrap();
+        session.initialize_db(false).unwrap();
+        session
+    }
+
+    #[test]
+    fn error_variants() {
+        let session = Session { db_path: PathBuf::from("memory"), conn: db::database_connection(None).unwrap() };
+        // the schema has not been initialized so the query fails
+        match session.get_problems() {
+            Err(Error::Db(error)) => assert!(error.starts_with("sql: ")),
+            result => unreachable!("expected a db error, got {:?}", result),
+        }
+        let not_a_folder = PathBuf::from("/no/such/folder");
+        match session.add_folder(&not_a_folder) {
+            Err(error @ Error::Invalid(_)) => {
+                assert_eq!(error.to_string(), "/no/such/folder must be a filesystem folder!!!")
+            }
+            result => unreachable!("expected an invalid error, got {:?}", result),
+        }
+        match session.compare_databases(&not_a_folder) {
+            Err(Error::NotFound(_)) => (),
+            result => unreachable!("expected a not found error, got {:?}", result),
+        }
+        assert!(matches!(Error::from("testcase"), Error::Invalid(error) if error == "domain: testcase"));
+    }
+
+    #[test]
+    fn compare_databases_read_only() {
+        let temp_folder = TempFolder::new("compare");
+        let other_db = temp_session(&temp_folder, "other.db").db_path;
+        let session = temp_session(&temp_folder, "session.db");
+        let testcase = session.compare_databases(&other_db);
+        let other_conn = db::read_only_connection(&other_db).unwrap();
+        let insert = other_conn.execute("INSERT INTO folders VALUES (1, 0, '/root', 'root', 0, 0, 0)", ());
+        drop(other_conn);
+        assert!(testcase.unwrap().db_only.is_empty());
+        assert!(insert.is_err());
+    }
+
+    #[test]
+    fn scan_history() {
+        let temp_folder = TempFolder::new("scans");
+        temp_folder.mkdir("third").mkdir("fourth");
+        temp_folder.write("first/file.txt", "first").write("first/folder/file.txt", "folder");
+        temp_folder.write("second/file.txt", "second");
+        // the scanned pathnames are canonical
+        let root = temp_folder.path().to_path_buf();
+        let session = temp_session(&temp_folder, "scans.db");
+        assert!(session.scan_history().unwrap().is_empty());
+        session.add_folder(&root.join("first")).unwrap();
+        session.add_folder(&root.join("second")).unwrap();
+        let testcase = session.scan_history().unwrap();
+        // databases created before scans were recorded still load folders
+        session.conn.execute("DROP TABLE scans", ()).unwrap();
+        assert!(session.scan_history().unwrap().is_empty());
+        session.add_folder(&root.join("third")).unwrap();
+        assert!(session.scan_history().unwrap().is_empty());
+        session.initialize_db(false).unwrap();
+        session.add_folder(&root.join("fourth")).unwrap();
+        assert_eq!(session.scan_history().unwrap().len(), 1);
+        assert_eq!(testcase.len(), 2);
+        assert_eq!(testcase[0].pathname, root.join("first").display().to_string());
+        assert_eq!((testcase[0].folders, testcase[0].files, testcase[0].problems), (2, 2, 0));
+        assert_eq!(testcase[1].pathname, root.join("second").display().to_string());
+        assert_eq!((testcase[1].folders, testcase[1].files, testcase[1].problems), (1, 1, 0));
+        assert!(testcase[0].id < testcase[1].id);
+        assert!(testcase[0].scanned > 0 && testcase[0].scanned <= testcase[1].scanned);
+    }
+
+    #[test]
+    fn recent_roots() {
+        let temp_folder = TempFolder::new("recent");
+        temp_folder.write("first/file.txt", "first").write("second/file.txt", "second");
+        let root = temp_folder.path().to_path_buf();
+        let session = temp_session(&temp_folder, "recent.db");
+        assert!(session.recent_roots(10).unwrap().is_empty());
+        session.add_folder(&root.join("first")).unwrap();
+        session.add_folder(&root.join("second")).unwrap();
+        let testcase = session.recent_roots(10).unwrap();
+        let limited = session.recent_roots(1).unwrap();
+        assert_eq!(testcase.len(), 2);
+        assert_eq!(testcase[0].pathname, root.join("second").display().to_string());
+        assert_eq!(testcase[1].pathname, root.join("first").display().to_string());
+        assert!(testcase[0].scanned >= testcase[1].scanned);
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].pathname, root.join("second").display().to_string());
     }
 }
@@ -1,11 +1,17 @@
 //! The internal functions used to implement the domain session.
-use std::path::PathBuf;
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
 
 use super::StopWatch;
 
 use super::{
-    db, filesys, sql, DbInformation, DuplicateFolders, DuplicateFoldersBuilder, FolderMd, DuplicateFoldersMatch, FoldersNoMatch,
-    Metadata, Result,
+    db, filesys, sql, DatabaseCompare, DbInformation, FileMd, DuplicateFolders, DuplicateFoldersBuilder, FolderMd, DuplicateFoldersMatch, FoldersNoMatch,
+    Error, FilenameFolders, FolderCount, FolderDepth, MaintenanceReport, Metadata, ProblemMd, Result, ScanMd,
+    SimilarFolders, SizeHistogram,
 };
 
 /// Get metadata for a folder by its filename.
@@ -61,6 +67,114 @@ pub(crate) fn get_folder_by_pathname(
     Ok(hierarchy_builder.get())
 }
 
+/// Get a folder hierarchy as nested `JSON` objects.
+///
+/// Each folder object has a `children` array that contains its files and folders.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `root_pathname` is the pathname of the top level folder in the hierarchy.
+pub(crate) fn tree_json(conn: &sql::Connection, root_pathname: &str) -> Result<String> {
+    let mut folders = get_folder_by_pathname(conn, root_pathname, true)?;
+    match folders.pop() {
+        Some(metadata) if folders.is_empty() => Ok(serde_json::to_string(&metadata_json(&metadata))?),
+        _ => Err(Error::NotFound(format!("{root_pathname} is not a folder in the database!!!"))),
+    }
+}
+
+/// An internal function that converts metadata and its children to a `JSON` object.
+///
+/// # Arguments
+///
+/// * `metadata` is what will be converted.
+fn metadata_json(metadata: &Metadata) -> serde_json::Value {
+    match metadata {
+        Metadata::Root(folder_md) | Metadata::Folder(folder_md) => serde_json::json!({
+            "type": "folder",
+            "id": folder_md.id,
+            "name": folder_md.name,
+            "pathname": folder_md.pathname,
+            "size": folder_md.size,
+            "created": folder_md.created,
+            "modified": folder_md.modified,
+            "children": folder_md.children.values().map(metadata_json).collect::<Vec<_>>(),
+        }),
+        Metadata::File(file_md) => serde_json::json!({
+            "type": if file_md.is_symlink { "symlink" } else { "file" },
+            "id": file_md.id,
+            "name": file_md.name,
+            "pathname": file_md.pathname,
+            "size": file_md.size,
+            "created": file_md.created,
+            "modified": file_md.modified,
+        }),
+        Metadata::Problem(problem_md) => serde_json::json!({
+            "type": "problem",
+            "id": problem_md.id,
+            "name": problem_md.name,
+            "pathname": problem_md.pathname,
+            "description": problem_md.description,
+        }),
+    }
+}
+
+/// The FNV-1a offset basis used to start a fingerprint.
+const FINGERPRINT_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// The FNV-1a prime used to mix bytes into a fingerprint.
+const FINGERPRINT_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Get a fingerprint of the content of a folder.
+///
+/// The fingerprint combines the names, sizes, and types of the files and folders within the hierarchy.
+/// The name of the folder itself is not included so folders with the same content have the same
+/// fingerprint wherever they are found.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `folder_pathname` is the pathname of the folder.
+pub(crate) fn folder_fingerprint(conn: &sql::Connection, folder_pathname: &str) -> Result<String> {
+    let mut folders = get_folder_by_pathname(conn, folder_pathname, true)?;
+    match folders.pop() {
+        Some(metadata) if folders.is_empty() => Ok(format!("{:016x}", content_fingerprint(&metadata))),
+        _ => Err(Error::NotFound(format!("{folder_pathname} is not a folder in the database!!!"))),
+    }
+}
+
+/// An internal function that calculates the fingerprint of a folders children.
+///
+/// FNV-1a is used because the fingerprint needs to be the same between runs and releases.
+///
+/// # Arguments
+///
+/// * `metadata` is the folder whose children will be fingerprinted.
+fn content_fingerprint(metadata: &Metadata) -> u64 {
+    fn mix(fingerprint: u64, bytes: &[u8]) -> u64 {
+        bytes.iter().fold(fingerprint, |fingerprint, byte| (fingerprint ^ *byte as u64).wrapping_mul(FINGERPRINT_PRIME))
+    }
+    let mut fingerprint = FINGERPRINT_OFFSET_BASIS;
+    if let Metadata::Root(folder_md) | Metadata::Folder(folder_md) = metadata {
+        for child in folder_md.children.values() {
+            let child_fingerprint = match child {
+                Metadata::Root(child_md) | Metadata::Folder(child_md) => {
+                    let child_fingerprint = mix(mix(FINGERPRINT_OFFSET_BASIS, b"d"), child_md.name.as_bytes());
+                    mix(child_fingerprint, &content_fingerprint(child).to_le_bytes())
+                }
+                Metadata::File(file_md) => {
+                    let kind: &[u8] = if file_md.is_symlink { b"l" } else { b"f" };
+                    let child_fingerprint = mix(mix(FINGERPRINT_OFFSET_BASIS, kind), file_md.name.as_bytes());
+                    mix(child_fingerprint, &file_md.size.to_le_bytes())
+                }
+                Metadata::Problem(_) => continue,
+            };
+            fingerprint = mix(fingerprint, &child_fingerprint.to_le_bytes());
+        }
+    }
+    fingerprint
+}
+
 /// Get metadata for the root folders.
 ///
 /// The top level directory for each filesystem directory added is considered the root
@@ -92,6 +206,72 @@ pub(crate) fn get_db_information(conn: &sql::Connection) -> Result<DbInformation
     Ok(DbInformation { root_folders, file_count, folder_count, problem_count, database_size })
 }
 
+/// Vacuum the database and truncate the write-ahead log.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+pub(crate) fn maintenance(conn: &sql::Connection) -> Result<MaintenanceReport> {
+    let before = db::database_metrics_query(conn)?;
+    db::schema_maintenance(conn)?;
+    let after = db::database_metrics_query(conn)?;
+    Ok(MaintenanceReport { before, after })
+}
+
+/// Find the files that are unique to each database.
+///
+/// Files are matched by filename and size. A filename and size found in both databases
+/// is considered to be the same file regardless of the folder it is in.
+///
+/// # Arguments
+///
+/// * `conn` is the session database connection.
+/// * `other_conn` is the database connection the session database will be compared to.
+pub(crate) fn compare_databases(conn: &sql::Connection, other_conn: &sql::Connection) -> Result<DatabaseCompare> {
+    let stopwatch = StopWatch::start_new();
+    let mut db_files = files_by_name_size(conn)?;
+    let mut other_db_files = files_by_name_size(other_conn)?;
+    let common: Vec<(String, u64)> = db_files.keys().filter(|key| other_db_files.contains_key(*key)).cloned().collect();
+    for key in common {
+        db_files.remove(&key);
+        other_db_files.remove(&key);
+    }
+    let db_only = db_files.into_values().flatten().collect();
+    let other_db_only = other_db_files.into_values().flatten().collect();
+    log::info!("compare databases: {stopwatch}");
+    Ok(DatabaseCompare { db_only, other_db_only })
+}
+
+/// Collect the database file metadata by filename and size.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+fn files_by_name_size(conn: &sql::Connection) -> Result<BTreeMap<(String, u64), Vec<FileMd>>> {
+    let mut files: BTreeMap<(String, u64), Vec<FileMd>> = BTreeMap::new();
+    db::files_metadata_query(conn, |file_md| {
+        files.entry((file_md.name.clone(), file_md.size)).or_default().push(file_md);
+        Ok(true)
+    })?;
+    Ok(files)
+}
+
+/// Get the distribution of file sizes in the database.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+pub(crate) fn size_histogram(conn: &sql::Connection) -> Result<SizeHistogram> {
+    let stopwatch = StopWatch::start_new();
+    let mut size_histogram = SizeHistogram::default();
+    db::files_metadata_query(conn, |file_md| {
+        size_histogram.add(file_md.size);
+        Ok(true)
+    })?;
+    log::info!("size histogram: {stopwatch}");
+    Ok(size_histogram)
+}
+
 /// Get the metadata describing problems that might have occurred loading filesystem directories
 /// and files.
 ///
@@ -102,6 +282,43 @@ pub(crate) fn get_problems(conn: &sql::Connection) -> Result<Vec<Metadata>> {
     Ok(db::problems_query(conn)?)
 }
 
+/// Get the most deeply nested folder.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+pub(crate) fn deepest_folder(conn: &sql::Connection) -> Result<Option<FolderDepth>> {
+    Ok(db::deepest_folder_query(conn)?)
+}
+
+/// Get the folders that directly contain the most files.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `n` is the maximum number of folders that will be returned.
+pub(crate) fn largest_folders_by_count(conn: &sql::Connection, n: usize) -> Result<Vec<FolderCount>> {
+    Ok(db::largest_folders_by_count_query(conn, n)?)
+}
+
+/// Get the problems whose folder is no longer in the database.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+pub(crate) fn orphaned_problems(conn: &sql::Connection) -> Result<Vec<ProblemMd>> {
+    Ok(db::orphaned_problems_query(conn)?)
+}
+
+/// Get the files that are 0 bytes in size.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+pub(crate) fn empty_files(conn: &sql::Connection) -> Result<Vec<FileMd>> {
+    Ok(db::empty_files_query(conn)?)
+}
+
 /// Initialize the database.
 ///
 /// # Arguments
@@ -116,18 +333,106 @@ pub(crate) fn initialize_db(conn: &sql::Connection, drop_database: bool) -> Resu
     Ok(())
 }
 
+/// Get the database schema version, `None` if the database has not been initialized.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+pub(crate) fn schema_version(conn: &sql::Connection) -> Result<Option<i64>> {
+    Ok(db::schema_version(conn)?)
+}
+
 /// Add a filesystem folder structure.
 ///
 /// # Arguments
 ///
 /// * `conn` is the database connection.
 /// * `folder_pathname` is the name of the filesystem folder that will be loaded.
-pub(crate) fn add_filesystem_folder(mut conn: sql::Connection, folder_pathname: &PathBuf) -> Result<()> {
-    let folder = filesys::collect_metadata(&folder_pathname)?;
+/// * `exclude` are the glob patterns of folders and files that will not be loaded.
+pub(crate) fn add_filesystem_folder(
+    mut conn: sql::Connection,
+    folder_pathname: &PathBuf,
+    exclude: &[String],
+) -> Result<()> {
+    let stopwatch = StopWatch::start_new();
+    let scanned = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map_or(0, |since| since.as_secs());
+    let folder = filesys::collect_metadata_excluding(&folder_pathname, exclude)?;
     if log::log_enabled!(log::Level::Trace) {
         log::trace!("{} entries found...", filesys::count_metadata(&folder));
     }
-    db::load_fs_metadata(&mut conn, &folder)?;
+    db::load_scanned_fs_metadata(&mut conn, &folder, exclude, scanned, &stopwatch)?;
+    Ok(())
+}
+
+/// Get the history of folders added to the database.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+pub(crate) fn scan_history(conn: &sql::Connection) -> Result<Vec<ScanMd>> {
+    Ok(db::scans_query(conn)?)
+}
+
+/// Get the root folders most recently added to the database along with their latest scan.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `n` is the maximum number of root folders returned.
+pub(crate) fn recent_roots(conn: &sql::Connection, n: usize) -> Result<Vec<ScanMd>> {
+    Ok(db::recent_roots_query(conn, n)?)
+}
+
+/// Load exported filesystem metadata into an empty database.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `reader` is where the `JSON` filesystem metadata will be read from.
+pub(crate) fn import_metadata<R: io::Read>(conn: &mut sql::Connection, reader: R) -> Result<()> {
+    let fs_metadata: filesys::FsMetadata = serde_json::from_reader(reader)?;
+    if let Err(error) = fs_metadata.validate() {
+        return Err(Error::Invalid(format!("domain: import: {error}")));
+    }
+    let (folder_count, file_count, problem_count) = db::get_table_counts_query(conn)?;
+    if folder_count + file_count + problem_count > 0 {
+        return Err(Error::Invalid("domain: import: the database must be empty...".to_string()));
+    }
+    db::load_fs_metadata(conn, &fs_metadata)?;
+    Ok(())
+}
+
+/// Replace a folder hierarchy in the database with its current filesystem metadata.
+///
+/// If the folder no longer exists it will be removed from the database. The exclude patterns used when the
+/// root folder was added are applied and a folder that was excluded will not be added.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `folder_path` is the folder that will be rescanned.
+pub(crate) fn rescan_folder(conn: &mut sql::Connection, folder_path: &Path) -> Result<()> {
+    let folder_path = folder_path.canonicalize().unwrap_or(folder_path.to_path_buf());
+    // use the exclude patterns of the deepest root folder containing the folder
+    let excludes = db::excludes_query(conn)?;
+    let exclude = match excludes.iter().rfind(|(root, _)| folder_path.starts_with(root)) {
+        None => &[][..],
+        Some((root, exclude)) => {
+            if filesys::is_excluded_within(Path::new(root), &folder_path, exclude) {
+                log::debug!("{} is excluded", folder_path.display());
+                return Ok(());
+            }
+            exclude.as_slice()
+        }
+    };
+    match folder_path.is_dir() {
+        true => {
+            let folder = filesys::collect_metadata_excluding(&folder_path, exclude)?;
+            let folder_pathname = folder.path().display().to_string();
+            db::reload_fs_metadata(conn, &folder_pathname, Some(&folder))?;
+        }
+        false => db::reload_fs_metadata(conn, &folder_path.display().to_string(), None)?,
+    }
     Ok(())
 }
 
@@ -140,6 +445,16 @@ pub(crate) fn file_duplicates_reload(conn: &sql::Connection) -> Result<u64> {
     Ok(db::file_duplicates_reload(conn)?)
 }
 
+/// Update the data that supports identifying duplicate files for folders that have changed.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `changed_folder_ids` are the folders whose files were added or removed.
+pub(crate) fn file_duplicates_reload_incremental(conn: &sql::Connection, changed_folder_ids: &[i64]) -> Result<u64> {
+    Ok(db::file_duplicates_reload_incremental(conn, changed_folder_ids)?)
+}
+
 /// Get the metadata describing the duplicate files that were found.
 ///
 /// # Arguments
@@ -149,12 +464,59 @@ pub(crate) fn file_duplicates_summary(conn: &sql::Connection) -> Result<(u64, u6
     Ok(db::duplicate_file_metrics(conn)?)
 }
 
+/// Write the duplicate files metadata as a `CSV` document.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `writer` is where the `CSV` document will be written.
+/// * `min_folders` is the minimum number of folders in a folder group.
+pub(crate) fn duplicate_files_csv<W: std::io::Write>(
+    conn: &sql::Connection,
+    writer: W,
+    min_folders: usize,
+) -> Result<usize> {
+    let duplicate_folders = duplicate_folders_metadata(conn, min_folders)?;
+    let elapsed = StopWatch::start_new();
+    let rows = duplicate_folders.write_csv(writer)?;
+    log::info!("duplicate files csv: {}", elapsed);
+    Ok(rows)
+}
+
+/// Get the filenames found in more than one folder along with the folders that contain them.
+///
+/// The duplicate files table must be loaded for filenames to be found.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+pub(crate) fn filename_folders(conn: &sql::Connection) -> Result<Vec<FilenameFolders>> {
+    let mut folder_pathnames = BTreeMap::new();
+    db::duplicate_files_metadata_query(conn, |folder_md| {
+        folder_pathnames.insert(folder_md.id, folder_md.pathname);
+        Ok(true)
+    })?;
+    let mut filename_folders = vec![];
+    db::duplicate_ids(conn, |duplicate_ids| {
+        let mut folders: Vec<String> = duplicate_ids
+            .ids
+            .iter()
+            .filter_map(|(parent_id, _)| folder_pathnames.get(parent_id).cloned())
+            .collect();
+        folders.sort();
+        filename_folders.push(FilenameFolders { filename: duplicate_ids.filename, folders });
+        Ok(true)
+    })?;
+    Ok(filename_folders)
+}
+
 /// Get the metadata describing details about duplicate files that were found.
 ///
 /// # Arguments
 ///
 /// * `conn` is the database connection.
-pub(crate) fn duplicate_folders_metadata(conn: &sql::Connection) -> Result<DuplicateFolders> {
+/// * `min_folders` is the minimum number of folders in a folder group.
+pub(crate) fn duplicate_folders_metadata(conn: &sql::Connection, min_folders: usize) -> Result<DuplicateFolders> {
     let mut builder = DuplicateFoldersBuilder::new();
     let mut stopwatch = StopWatch::start_new();
     db::duplicate_files_metadata_query(conn, |md| {
@@ -169,7 +531,7 @@ pub(crate) fn duplicate_folders_metadata(conn: &sql::Connection) -> Result<Dupli
     })?;
     log::info!("dupldate folder filenames load: {stopwatch}");
     stopwatch.reset().start();
-    let duplicate_folders = builder.build()?;
+    let duplicate_folders = builder.build()?.with_min_folders(min_folders);
     log::info!("duplicate folders build: {stopwatch}");
     Ok(duplicate_folders)
 }
@@ -179,27 +541,72 @@ pub(crate) fn duplicate_folders_metadata(conn: &sql::Connection) -> Result<Dupli
 /// # Arguments
 ///
 /// * `conn` is the database connection.
-pub(crate) fn folders_match_metadata(conn: &sql::Connection) -> Result<DuplicateFoldersMatch> {
-    let duplicate_folders = duplicate_folders_metadata(conn)?;
+/// * `min_folders` is the minimum number of folders in a folder group.
+pub(crate) fn folders_match_metadata(conn: &sql::Connection, min_folders: usize) -> Result<DuplicateFoldersMatch> {
+    let duplicate_folders = duplicate_folders_metadata(conn, min_folders)?;
     let elapsed = StopWatch::start_new();
     let folders_match = DuplicateFoldersMatch::from(duplicate_folders);
     log::info!("folders file match: {}", elapsed);
     Ok(folders_match)
 }
 
+/// Get the metadata describing folders that are exact duplicates of each other.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `min_folders` is the minimum number of folders in a folder group.
+pub(crate) fn folders_exact_match_metadata(
+    conn: &sql::Connection,
+    min_folders: usize,
+) -> Result<DuplicateFoldersMatch> {
+    let folders_match = folders_match_metadata(conn, min_folders)?;
+    // the duplicate folders metadata only has the duplicate filenames so get all the folders filenames
+    let mut folders_filenames: HashMap<i64, Vec<String>> = HashMap::new();
+    db::folder_content_by_filedups_query(conn, |folder_md| {
+        folders_filenames.insert(folder_md.id, folder_md.children.into_keys().collect());
+        Ok(true)
+    })?;
+    let elapsed = StopWatch::start_new();
+    let exact_match = folders_match.exact_duplicates(&folders_filenames);
+    log::info!("folders exact match: {}", elapsed);
+    Ok(exact_match)
+}
+
 /// Get metadata describing folders that were not part of a folder group match.
 ///
 /// # Arguments
 ///
 /// * `conn` is the database connection.
-pub(crate) fn folders_no_match_metadata(conn: &sql::Connection) -> Result<FoldersNoMatch> {
-    let duplicate_folders = duplicate_folders_metadata(conn)?;
+/// * `min_folders` is the minimum number of folders in a folder group.
+pub(crate) fn folders_no_match_metadata(conn: &sql::Connection, min_folders: usize) -> Result<FoldersNoMatch> {
+    let duplicate_folders = duplicate_folders_metadata(conn, min_folders)?;
     let elapsed = StopWatch::start_new();
     let folders_no_match = FoldersNoMatch::from(duplicate_folders);
     log::info!("folders file match: {}", elapsed);
     Ok(folders_no_match)
 }
 
+/// Get metadata describing folders with duplicate filenames that have similar filenames.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `threshold` is the minimum Jaccard similarity of the folders filenames.
+pub(crate) fn similar_folders_metadata(conn: &sql::Connection, threshold: f64) -> Result<SimilarFolders> {
+    let mut folders: Vec<FolderMd> = vec![];
+    let mut stopwatch = StopWatch::start_new();
+    db::folder_content_by_filedups_query(conn, |folder_md| {
+        folders.push(folder_md);
+        Ok(true)
+    })?;
+    log::info!("similar folders metadata load: {stopwatch}");
+    stopwatch.reset().start();
+    let similar_folders = SimilarFolders::new(folders, threshold);
+    log::info!("similar folders build: {stopwatch}");
+    Ok(similar_folders)
+}
+
 mod hierarchy {
     //! Contains the builder that creates a folders structure.
     use super::*;
@@ -297,3 +704,343 @@ mod hierarchy {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filesys::testlib::{file, folder, TempFolder};
+
+    #[test]
+    fn maintenance() {
+        let conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        let maintenance_report = super::maintenance(&conn).unwrap();
+        assert!(maintenance_report.before > 0);
+        assert!(maintenance_report.after <= maintenance_report.before);
+        assert_eq!(maintenance_report.reclaimed(), maintenance_report.before - maintenance_report.after);
+    }
+
+    #[test]
+    fn import_metadata() {
+        let temp_folder = TempFolder::new("import");
+        temp_folder.mkdir("folder/empty").write("file.txt", "root file").write("folder/file.txt", "folder file");
+        // scan the folder and export it
+        let fs_metadata = filesys::collect_metadata_excluding(&temp_folder.path().to_path_buf(), &[]).unwrap();
+        drop(temp_folder);
+        let mut conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        db::load_fs_metadata(&mut conn, &fs_metadata).unwrap();
+        let export = serde_json::to_vec(&fs_metadata).unwrap();
+        // import the scan into a new database
+        let mut import_conn = db::database_connection(None).unwrap();
+        db::schema_init(&import_conn).unwrap();
+        super::import_metadata(&mut import_conn, export.as_slice()).unwrap();
+        let db_information = get_db_information(&conn).unwrap();
+        let testcase = get_db_information(&import_conn).unwrap();
+        assert_eq!(testcase.root_folders, db_information.root_folders);
+        assert_eq!(testcase.folder_count, db_information.folder_count);
+        assert_eq!(testcase.file_count, db_information.file_count);
+        assert_eq!(testcase.problem_count, db_information.problem_count);
+        // the database must be empty
+        match super::import_metadata(&mut import_conn, export.as_slice()) {
+            Err(Error::Invalid(error)) => assert!(error.ends_with("the database must be empty...")),
+            result => unreachable!("expected an invalid error, got {:?}", result),
+        }
+        // children must be contained by their folder
+        let invalid = r#"{"Folder":{"path":"/root","size":0,"created":0,"modified":0,"children":[
+            {"File":{"path":"/other/file.txt","is_symlink":false,"size":0,"created":0,"modified":0}}]}}"#;
+        let mut invalid_conn = db::database_connection(None).unwrap();
+        db::schema_init(&invalid_conn).unwrap();
+        match super::import_metadata(&mut invalid_conn, invalid.as_bytes()) {
+            Err(Error::Invalid(error)) => assert!(error.contains("is not contained by /root")),
+            result => unreachable!("expected an invalid error, got {:?}", result),
+        }
+        assert_eq!(db::get_table_counts_query(&invalid_conn).unwrap(), (0, 0, 0));
+    }
+
+    #[test]
+    fn exclude_folders() {
+        let temp_folder = TempFolder::new("exclude");
+        temp_folder.mkdir(".git/objects").write(".git/config", "config");
+        temp_folder.write("src/main.rs", "main").write("src/main.bak", "backup");
+        temp_folder.write("web/node_modules/module.js", "module");
+        let root = temp_folder.path().to_path_buf();
+        // nothing is excluded by default
+        let all_count = filesys::count_metadata(&filesys::collect_metadata_excluding(&root, &[]).unwrap());
+        let db_path = root.join("exclude.db");
+        let db_conn = || db::database_connection(Some(&db_path)).unwrap();
+        db::schema_init(&db_conn()).unwrap();
+        let exclude = [".git", "*.bak", "*/web/node_modules", "exclude.db*"].map(String::from);
+        let added = add_filesystem_folder(db_conn(), &root, &exclude);
+        let mut conn = db_conn();
+        let filenames = |conn: &sql::Connection| {
+            let mut stmt = conn.prepare("SELECT name FROM files ORDER BY pathname").unwrap();
+            let names = stmt.query_map((), |row| row.get::<_, String>(0)).unwrap();
+            names.map(|name| name.unwrap()).collect::<Vec<String>>()
+        };
+        let counts = db::get_table_counts_query(&conn).unwrap();
+        let added_filenames = filenames(&conn);
+        // rescans use the exclude patterns saved with the root folder
+        temp_folder.write("src/lib.rs", "lib").write("src/lib.bak", "backup").write(".git/objects/object", "object");
+        let rescan_src = rescan_folder(&mut conn, &root.join("src"));
+        let rescan_git = rescan_folder(&mut conn, &root.join(".git").join("objects"));
+        let rescan_filenames = filenames(&conn);
+        let sql = "SELECT COUNT(*) FROM folders WHERE pathname LIKE '%.git%' OR pathname LIKE '%node_modules%'";
+        let excluded_count: u64 = conn.query_row(sql, (), |row| row.get(0)).unwrap();
+        assert_eq!(all_count, 10);
+        added.unwrap();
+        // root, src, and web folders, the root and emptied web folders have an empty folder placeholder
+        assert_eq!(counts, (3, 3, 0));
+        assert_eq!(added_filenames, vec!["<?>", "main.rs", "<?>"]);
+        rescan_src.unwrap();
+        rescan_git.unwrap();
+        assert_eq!(rescan_filenames, vec!["<?>", "lib.rs", "main.rs", "<?>"]);
+        assert_eq!(excluded_count, 0);
+    }
+
+    #[test]
+    fn deepest_folder() {
+        let mut conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        assert!(super::deepest_folder(&conn).unwrap().is_none());
+        let root = folder(
+            "/root",
+            vec![
+                folder("/root/a", vec![folder("/root/a/b", vec![folder("/root/a/b/c", vec![])])]),
+                folder("/root/x", vec![folder("/root/x/y", vec![])]),
+            ],
+        );
+        db::load_fs_metadata(&mut conn, &root).unwrap();
+        let testcase = super::deepest_folder(&conn).unwrap().unwrap();
+        assert_eq!(testcase.depth, 3);
+        assert_eq!(testcase.pathname, "/root/a/b/c");
+    }
+
+    #[test]
+    fn largest_folders_by_count() {
+        let files = |folder: &str, count: usize| {
+            (0..count)
+                .map(|index| file(&format!("{folder}/file{index}.txt"), 0))
+                .collect::<Vec<_>>()
+        };
+        let mut conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        assert!(super::largest_folders_by_count(&conn, 3).unwrap().is_empty());
+        let mut root_children = files("/root", 2);
+        root_children.push(folder("/root/a", files("/root/a", 5)));
+        root_children.push(folder("/root/b", files("/root/b", 1)));
+        root_children.push(folder("/root/c", files("/root/c", 3)));
+        // subfolders are not counted as files
+        let mut d_children = files("/root/d", 3);
+        d_children.push(folder("/root/d/e", vec![]));
+        root_children.push(folder("/root/d", d_children));
+        db::load_fs_metadata(&mut conn, &folder("/root", root_children)).unwrap();
+        let counts = |testcase: Vec<FolderCount>| {
+            testcase.into_iter().map(|folder_count| (folder_count.pathname, folder_count.count)).collect::<Vec<_>>()
+        };
+        let testcase = super::largest_folders_by_count(&conn, 3).unwrap();
+        assert_eq!(
+            counts(testcase),
+            vec![("/root/a".to_string(), 5), ("/root/c".to_string(), 3), ("/root/d".to_string(), 3)]
+        );
+        let testcase = super::largest_folders_by_count(&conn, 1).unwrap();
+        assert_eq!(counts(testcase), vec![("/root/a".to_string(), 5)]);
+        // folders without files are not reported
+        let testcase = super::largest_folders_by_count(&conn, 10).unwrap();
+        assert_eq!(testcase.len(), 5);
+        assert_eq!(testcase[3].pathname, "/root");
+        assert_eq!(testcase[4].pathname, "/root/b");
+        assert!(super::largest_folders_by_count(&conn, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn filename_folders() {
+        let mut conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        let root = folder(
+            "/root",
+            vec![
+                file("/root/readme.txt", 0),
+                folder("/root/a", vec![file("/root/a/readme.txt", 0), file("/root/a/unique.txt", 0)]),
+                folder("/root/b", vec![folder("/root/b/c", vec![file("/root/b/c/readme.txt", 0)])]),
+            ],
+        );
+        db::load_fs_metadata(&mut conn, &root).unwrap();
+        db::file_duplicates_reload(&conn).unwrap();
+        let testcase = super::filename_folders(&conn).unwrap();
+        assert_eq!(testcase.len(), 1);
+        assert_eq!(testcase[0].filename, "readme.txt");
+        assert_eq!(testcase[0].folders, vec!["/root", "/root/a", "/root/b/c"]);
+    }
+
+    #[test]
+    fn tree_json() {
+        let mut conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        let root = folder(
+            "/root",
+            vec![
+                file("/root/readme.txt", 0),
+                folder("/root/a", vec![file("/root/a/one.txt", 0), file("/root/a/two.txt", 0)]),
+                folder(
+                    "/root/b",
+                    vec![folder("/root/b/c", vec![file("/root/b/c/three.txt", 0)]), folder("/root/b/d", vec![])],
+                ),
+            ],
+        );
+        db::load_fs_metadata(&mut conn, &root).unwrap();
+        let testcase: serde_json::Value = serde_json::from_str(&super::tree_json(&conn, "/root").unwrap()).unwrap();
+        fn depth(json: &serde_json::Value) -> usize {
+            let children = json["children"].as_array().unwrap();
+            1 + children.iter().filter(|child| child["type"] == "folder").map(depth).max().unwrap_or(0)
+        }
+        assert_eq!(depth(&testcase), 3);
+        assert_eq!(testcase["pathname"], "/root");
+        let children = testcase["children"].as_array().unwrap();
+        let names: Vec<&str> = children.iter().map(|child| child["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["a", "b", "readme.txt"]);
+        assert_eq!(children[0]["children"].as_array().unwrap().len(), 2);
+        assert_eq!(children[1]["children"].as_array().unwrap().len(), 2);
+        assert_eq!(children[1]["children"][0]["children"].as_array().unwrap().len(), 1);
+        assert_eq!(children[1]["children"][0]["children"][0]["type"], "file");
+        assert!(children[1]["children"][1]["children"].as_array().unwrap().is_empty());
+        assert!(children[2].get("children").is_none());
+        // the root folder must be in the database
+        assert!(matches!(super::tree_json(&conn, "/other"), Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn folder_fingerprint() {
+        let content = |pathname: &str, size: u64| {
+            folder(
+                pathname,
+                vec![
+                    file(&format!("{pathname}/one.txt"), 10),
+                    folder(&format!("{pathname}/sub"), vec![file(&format!("{pathname}/sub/two.txt"), size)]),
+                    folder(&format!("{pathname}/empty"), vec![]),
+                ],
+            )
+        };
+        let mut conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        let root = folder(
+            "/root",
+            vec![
+                content("/root/a", 20),
+                content("/root/b", 20),
+                content("/root/c", 21),
+                folder("/root/d", vec![file("/root/d/one.txt", 10)]),
+            ],
+        );
+        db::load_fs_metadata(&mut conn, &root).unwrap();
+        let fingerprint = |pathname: &str| super::folder_fingerprint(&conn, pathname).unwrap();
+        let a = fingerprint("/root/a");
+        assert_eq!(a.len(), 16);
+        // identical content has the same fingerprint
+        assert_eq!(a, fingerprint("/root/b"));
+        assert_eq!(a, fingerprint("/root/a"));
+        assert_eq!(fingerprint("/root/a/sub"), fingerprint("/root/b/sub"));
+        // a file with a different size or missing content changes the fingerprint
+        assert_ne!(a, fingerprint("/root/c"));
+        assert_ne!(a, fingerprint("/root/d"));
+        assert_ne!(fingerprint("/root/a/sub"), fingerprint("/root/c/sub"));
+        assert!(matches!(super::folder_fingerprint(&conn, "/other"), Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn orphaned_problems() {
+        let mut conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        conn.execute("INSERT INTO folders VALUES (1, 0, '/root', 'root', 0, 0, 0)", ()).unwrap();
+        conn.execute("INSERT INTO folders VALUES (2, 1, '/root/folder', 'folder', 0, 0, 0)", ()).unwrap();
+        conn.execute("INSERT INTO problems VALUES (1, 2, '/root/folder/problem', 'access denied')", ()).unwrap();
+        assert!(super::orphaned_problems(&conn).unwrap().is_empty());
+        // the foreign key prevents the folder from being deleted
+        assert!(conn.execute("DELETE FROM folders WHERE id = 2", ()).is_err());
+        // deleting the folder row without foreign keys, like the sqlite3 shell, leaves the problem behind
+        conn.pragma_update(None, "foreign_keys", false).unwrap();
+        conn.execute("DELETE FROM folders WHERE id = 2", ()).unwrap();
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+        let testcase = super::orphaned_problems(&conn).unwrap();
+        assert_eq!(testcase.len(), 1);
+        assert_eq!(testcase[0].pathname, "/root/folder/problem");
+        assert_eq!(testcase[0].parent_id, 2);
+        // removing the folder cleans up its problems
+        db::reload_fs_metadata(&mut conn, "/root/folder", None).unwrap();
+        assert!(super::orphaned_problems(&conn).unwrap().is_empty());
+        assert_eq!(db::get_table_counts_query(&conn).unwrap(), (1, 0, 0));
+    }
+
+    #[test]
+    fn empty_files() {
+        let conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        conn.execute("INSERT INTO folders VALUES (1, 0, '/root', 'root', 0, 0, 0)", ()).unwrap();
+        let files: [(&str, u64); 4] = [("empty", 0), ("tiny", 1), ("kib", 1024), (db::EMPTY_FOLDER_FILENAME, 0)];
+        for (name, size) in files {
+            conn.execute(
+                "INSERT INTO files (parent_id, pathname, name, is_symlink, size, created, modified)
+                 VALUES (1, ?1, ?2, 0, ?3, 0, 0)",
+                (format!("/root/{name}"), name, size),
+            )
+            .unwrap();
+        }
+        let testcase = super::empty_files(&conn).unwrap();
+        assert_eq!(testcase.len(), 1);
+        assert_eq!(testcase[0].pathname, "/root/empty");
+        assert_eq!(testcase[0].size, 0);
+    }
+
+    #[test]
+    fn size_histogram() {
+        let conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        conn.execute("INSERT INTO folders VALUES (1, 0, '/root', 'root', 0, 0, 0)", ()).unwrap();
+        let files: [(&str, u64); 7] = [
+            ("empty", 0),
+            ("tiny", 1023),
+            ("kib", 1024),
+            ("small", 500 * 1024),
+            ("mib", 1024 * 1024),
+            ("gib", 1024 * 1024 * 1024),
+            (db::EMPTY_FOLDER_FILENAME, 0),
+        ];
+        for (name, size) in files {
+            conn.execute(
+                "INSERT INTO files (parent_id, pathname, name, is_symlink, size, created, modified)
+                 VALUES (1, ?1, ?2, 0, ?3, 0, 0)",
+                (format!("/root/{name}"), name, size),
+            )
+            .unwrap();
+        }
+        let testcase = super::size_histogram(&conn).unwrap();
+        let counts: Vec<(u64, u64)> = testcase.buckets.iter().map(|bucket| (bucket.count, bucket.size)).collect();
+        assert_eq!(counts, vec![(2, 1023), (2, 1024 + 500 * 1024), (1, 1024 * 1024), (1, 1024 * 1024 * 1024)]);
+    }
+
+    #[test]
+    fn compare_databases() {
+        fn test_db(files: &[(&str, u64)]) -> sql::Connection {
+            let conn = db::database_connection(None).unwrap();
+            db::schema_init(&conn).unwrap();
+            conn.execute("INSERT INTO folders VALUES (1, 0, '/root', 'root', 0, 0, 0)", ()).unwrap();
+            for (name, size) in files {
+                conn.execute(
+                    "INSERT INTO files (parent_id, pathname, name, is_symlink, size, created, modified)
+                     VALUES (1, ?1, ?2, 0, ?3, 0, 0)",
+                    (format!("/root/{name}"), name, size),
+                )
+                .unwrap();
+            }
+            conn
+        }
+        let conn = test_db(&[("both", 10), ("size", 20), ("left", 30)]);
+        let other_conn = test_db(&[("both", 10), ("size", 21), ("right", 40), (db::EMPTY_FOLDER_FILENAME, 0)]);
+        let testcase = super::compare_databases(&conn, &other_conn).unwrap();
+        let db_only: Vec<&str> = testcase.db_only.iter().map(|md| md.name.as_str()).collect();
+        assert_eq!(db_only, vec!["left", "size"]);
+        let other_db_only: Vec<&str> = testcase.other_db_only.iter().map(|md| md.name.as_str()).collect();
+        assert_eq!(other_db_only, vec!["right", "size"]);
+        assert_eq!(testcase.other_db_only[1].size, 21);
+    }
+}
@@ -12,15 +12,17 @@
 pub(crate) use ver4::DuplicateFoldersBuilder;
 pub use ver4::{
     DuplicateFolders, DuplicateFoldersMatch, FolderAnalysisMd, FolderGroupId, FolderGroupMd, FolderNoMatchMd,
-    FoldersMatchMd, FoldersNoMatch,
+    FoldersMatchMd, FoldersNoMatch, SimilarFolders, SimilarFoldersMd,
 };
 
 // #[allow(unused)]
 pub mod ver4 {
     use super::super::{DuplicateIds, Error, FileMd, FolderMd, Metadata, Result};
+    use serde::{ser::SerializeStruct, Serialize, Serializer};
     use std::{
         cmp::{Ord, Ordering, PartialEq},
-        collections::HashMap,
+        collections::{HashMap, HashSet},
+        io::Write,
         ops::Index,
         path::PathBuf,
     };
@@ -288,6 +290,15 @@ pub mod ver4 {
             vsort_by(&mut folder_groups, |lhs, rhs| lhs.fgid.cmp(&rhs.fgid));
             Self { folders_md, folder_groups }
         }
+        /// Only keep the folder groups that have at least a minimum number of folders.
+        ///
+        /// # Arguments
+        ///
+        /// * `min_folders` is the minimum number of folders a folder group must have.
+        pub fn with_min_folders(mut self, min_folders: usize) -> Self {
+            self.folder_groups.retain(|folder_group| folder_group.fgid.0.len() >= min_folders);
+            self
+        }
         /// Retrieves folder group metadata by index for the internal folder groups.
         ///
         /// # Arguments
@@ -325,6 +336,38 @@ pub mod ver4 {
             }
         }
     }
+    impl DuplicateFolders {
+        /// Writes the duplicate files as `CSV` returning the number of data rows written.
+        ///
+        /// There will be a row for each file in a folder group. The group id is the ordinal
+        /// position of the folder group, starting at 1.
+        ///
+        /// # Arguments
+        ///
+        /// * `writer` is where the `CSV` document will be written.
+        pub fn write_csv<W: Write>(&self, writer: W) -> Result<usize> {
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            csv_writer.write_record(["group_id", "folder_pathname", "filename", "size"])?;
+            let mut rows = 0;
+            for (index, folder_group_md) in self.into_iter().enumerate() {
+                let group_id = (index + 1).to_string();
+                for folder_md in &folder_group_md.folders_md {
+                    for &filename in &folder_group_md.filenames {
+                        let size = folder_md.children.get(filename).map_or(0, |md| md.size());
+                        csv_writer.write_record([
+                            group_id.as_str(),
+                            folder_md.pathname.as_str(),
+                            filename,
+                            size.to_string().as_str(),
+                        ])?;
+                        rows += 1;
+                    }
+                }
+            }
+            csv_writer.flush()?;
+            Ok(rows)
+        }
+    }
     impl<'df> IntoIterator for &'df DuplicateFolders {
         type Item = FolderGroupMd<'df>;
         type IntoIter = DuplicateFolderIterator<'df>;
@@ -579,6 +622,111 @@ pub mod ver4 {
         (group_matches, not_matched)
     }
 
+    /// The container of folders whose filenames are similar but not necessarily the same.
+    #[derive(Debug)]
+    pub struct SimilarFolders {
+        /// A collection to lookup folder metadata based on its identifier.
+        folders_md: FoldersMd,
+        /// The pairs of folder identifiers and their similarity.
+        similarities: Vec<(i64, i64, f64)>,
+    }
+    impl SimilarFolders {
+        /// Creates the similar folders metadata.
+        ///
+        /// Only folders that have at least one filename in common are compared. The pairs of folders
+        /// will be ordered by descending similarity then by pathname.
+        ///
+        /// # Arguments
+        ///
+        /// * `folders` is the metadata of the folders including all of their files.
+        /// * `threshold` is the minimum Jaccard similarity of folders that will be kept.
+        pub(crate) fn new(folders: Vec<FolderMd>, threshold: f64) -> Self {
+            let mut folders_md = FoldersMd::new();
+            folders.into_iter().for_each(|folder_md| {
+                folders_md.add(folder_md);
+            });
+            // find the folders that share a filename
+            let mut filename_folders: HashMap<&str, Vec<i64>> = HashMap::new();
+            for folder_md in folders_md.0.values() {
+                for filename in folder_md.children.keys() {
+                    filename_folders.entry(filename.as_str()).or_default().push(folder_md.id);
+                }
+            }
+            let mut candidates: HashSet<(i64, i64)> = HashSet::new();
+            for folder_ids in filename_folders.values_mut().filter(|ids| ids.len() > 1) {
+                vsort(folder_ids);
+                for (index, lhs) in folder_ids.iter().enumerate() {
+                    folder_ids[index + 1..].iter().for_each(|rhs| {
+                        candidates.insert((*lhs, *rhs));
+                    });
+                }
+            }
+            let mut similarities: Vec<(i64, i64, f64)> = candidates
+                .into_iter()
+                .filter_map(|(lhs, rhs)| {
+                    let similarity = jaccard_similarity(&folders_md[&lhs], &folders_md[&rhs]);
+                    match similarity >= threshold {
+                        true => Some((lhs, rhs, similarity)),
+                        false => None,
+                    }
+                })
+                .collect();
+            vsort_by(&mut similarities, |(lhs_l, lhs_r, lhs), (rhs_l, rhs_r, rhs)| {
+                rhs.total_cmp(lhs)
+                    .then_with(|| paths_cmp(&folders_md[lhs_l].pathname, &folders_md[rhs_l].pathname))
+                    .then_with(|| paths_cmp(&folders_md[lhs_r].pathname, &folders_md[rhs_r].pathname))
+            });
+            Self { folders_md, similarities }
+        }
+        /// Get the metadata for the similar folders.
+        pub fn get_md(&self) -> Vec<SimilarFoldersMd> {
+            self.similarities
+                .iter()
+                .map(|(lhs, rhs, similarity)| {
+                    let lhs = &self.folders_md[lhs];
+                    let rhs = &self.folders_md[rhs];
+                    let common = lhs
+                        .children
+                        .keys()
+                        .filter(|filename| rhs.children.contains_key(*filename))
+                        .map(|filename| filename.as_str())
+                        .collect();
+                    SimilarFoldersMd { lhs, rhs, similarity: *similarity, common }
+                })
+                .collect()
+        }
+    }
+
+    /// The metadata for a pair of folders with similar filenames.
+    #[derive(Debug)]
+    pub struct SimilarFoldersMd<'s> {
+        /// The folder metadata with the lesser identifier.
+        pub lhs: &'s FolderMd,
+        /// The folder metadata with the greater identifier.
+        pub rhs: &'s FolderMd,
+        /// The Jaccard similarity of the folders filenames.
+        pub similarity: f64,
+        /// The filenames the folders have in common.
+        pub common: Vec<&'s str>,
+    }
+
+    /// Calculates the Jaccard similarity of the filenames in two folders.
+    ///
+    /// The similarity is the count of filenames in common divided by the count of all filenames.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` is one of the folders being compared.
+    /// * `rhs` is the other folder being compared.
+    fn jaccard_similarity(lhs: &FolderMd, rhs: &FolderMd) -> f64 {
+        let common = lhs.children.keys().filter(|filename| rhs.children.contains_key(*filename)).count();
+        let all = lhs.children.len() + rhs.children.len() - common;
+        match all {
+            0 => 0.0,
+            _ => common as f64 / all as f64,
+        }
+    }
+
     /// The container of folders with common filenames that match.
     #[derive(Debug)]
     pub struct DuplicateFoldersMatch {
@@ -615,6 +763,48 @@ pub mod ver4 {
                 None => None,
             }
         }
+        /// Consumes the folders match metadata keeping only folder groups that are exact duplicates.
+        ///
+        /// A folder group is an exact duplicate when all of its files matched and each folder in the
+        /// group has the same set of filenames.
+        ///
+        /// # Arguments
+        ///
+        /// * `folders_filenames` has all the filenames of each folder, not just the duplicate filenames.
+        pub fn exact_duplicates(self, folders_filenames: &HashMap<i64, Vec<String>>) -> Self {
+            let folders_md = self.folders_md;
+            let folder_matches = self
+                .folder_matches
+                .into_iter()
+                .filter(|folders_match| is_exact_duplicate(folders_filenames, folders_match))
+                .collect();
+            Self { folders_md, folder_matches }
+        }
+    }
+
+    /// Determines if a group of matching folders are exact duplicates of each other.
+    ///
+    /// The folder metadata only contains the duplicate filenames so the complete filenames of each folder
+    /// are used to make sure a folder does not have files the others do not.
+    ///
+    /// # Arguments
+    ///
+    /// * `folders_filenames` has all the filenames of each folder.
+    /// * `folders_match` is the folder group that will be examined.
+    fn is_exact_duplicate(folders_filenames: &HashMap<i64, Vec<String>>, folders_match: &FoldersMatch) -> bool {
+        if !folders_match.except.is_empty() {
+            return false;
+        }
+        let mut filenames = folders_match.matches.clone();
+        vsort(&mut filenames);
+        folders_match.fgid.0.iter().all(|folder_id| match folders_filenames.get(folder_id) {
+            Some(folder_filenames) => {
+                let mut folder_filenames = folder_filenames.clone();
+                vsort(&mut folder_filenames);
+                folder_filenames == filenames
+            }
+            None => false,
+        })
     }
 
     /// The internal metadata for a group of folders that have common file names with matching files.
@@ -677,6 +867,34 @@ pub mod ver4 {
         /// Other folder group matches folders in this group might have.
         pub other_matches: Vec<(&'m FolderMd, Vec<Vec<&'m FolderMd>>)>,
     }
+    impl<'m> Serialize for FoldersMatchMd<'m> {
+        /// Folders are serialized by pathname so the document does not include the folder contents.
+        fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+            /// The other folder groups a folder is part of.
+            #[derive(Serialize)]
+            struct OtherMatches<'o> {
+                pathname: &'o str,
+                folder_groups: Vec<Vec<&'o str>>,
+            }
+            let pathnames = |folders_md: &Vec<&'m FolderMd>| -> Vec<&'m str> {
+                folders_md.iter().map(|folder_md| folder_md.pathname.as_str()).collect()
+            };
+            let other_matches: Vec<OtherMatches> = self
+                .other_matches
+                .iter()
+                .map(|(folder_md, folder_groups)| OtherMatches {
+                    pathname: &folder_md.pathname,
+                    folder_groups: folder_groups.iter().map(pathnames).collect(),
+                })
+                .collect();
+            let mut state = serializer.serialize_struct("FoldersMatch", 4)?;
+            state.serialize_field("pathnames", &pathnames(&self.folders_md))?;
+            state.serialize_field("matches", &self.matches)?;
+            state.serialize_field("except", &self.except)?;
+            state.serialize_field("other_matches", &other_matches)?;
+            state.end()
+        }
+    }
 
     /// The iterator structure allowing the folder match metadata to be traversed.
     pub struct FoldersMatchIterator<'m> {
@@ -739,6 +957,17 @@ pub mod ver4 {
         /// The count of files the folder did match.
         pub other_matches: usize,
     }
+    impl<'m> Serialize for FolderNoMatchMd<'m> {
+        /// The folder is serialized by pathname and file count so the document does not include the folder contents.
+        fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("FolderNoMatch", 4)?;
+            state.serialize_field("pathname", &self.folder_md.pathname)?;
+            state.serialize_field("files", &self.folder_md.children.len())?;
+            state.serialize_field("filenames", &self.filenames)?;
+            state.serialize_field("other_matches", &self.other_matches)?;
+            state.end()
+        }
+    }
 
     /// The internal metadata for a folder that did not match common file names.
     #[derive(Debug)]
@@ -1223,6 +1452,137 @@ pub mod ver4 {
             }
         }
         #[test]
+        fn duplicate_folders_csv() {
+            let mut builder = duplicate_folders_builder(vec![
+                folder_md(1, "/folder/one", vec![filemd!(11, "a", 100), filemd!(12, "b", 200)]),
+                folder_md(2, "/folder/two", vec![filemd!(21, "a", 100), filemd!(22, "b", 10)]),
+                folder_md(3, "/folder/three", vec![filemd!(31, "a", 5)]),
+            ]);
+            builder.add_duplicate_ids(duplicate_ids("a", vec![(1, 11), (2, 21), (3, 31)]));
+            builder.add_duplicate_ids(duplicate_ids("b", vec![(1, 12), (2, 22)]));
+            let duplicate_folders = builder.build().unwrap();
+            let mut testcase: Vec<u8> = vec![];
+            assert_eq!(duplicate_folders.write_csv(&mut testcase).unwrap(), 5);
+            let testcase = String::from_utf8(testcase).unwrap();
+            let mut lines = testcase.lines();
+            assert_eq!(lines.next().unwrap(), "group_id,folder_pathname,filename,size");
+            assert_eq!(lines.next().unwrap(), "1,/folder/one,b,200");
+            assert_eq!(lines.count(), 4);
+        }
+        #[test]
+        fn min_folders() {
+            let builder = || {
+                let mut builder = duplicate_folders_builder(vec![
+                    folder_md(1, "/folder/one", vec![filemd!(11, "README.md"), filemd!(12, "a")]),
+                    folder_md(2, "/folder/two", vec![filemd!(21, "README.md"), filemd!(22, "a")]),
+                    folder_md(3, "/folder/three", vec![filemd!(31, "README.md")]),
+                ]);
+                builder.add_duplicate_ids(duplicate_ids("README.md", vec![(1, 11), (2, 21), (3, 31)]));
+                builder.add_duplicate_ids(duplicate_ids("a", vec![(1, 12), (2, 22)]));
+                builder
+            };
+            let fgids = |duplicate_folders: &DuplicateFolders| -> Vec<FolderGroupId> {
+                duplicate_folders.into_iter().map(|folder_group_md| folder_group_md.fgid).collect()
+            };
+            let testcase = builder().build().unwrap().with_min_folders(2);
+            assert_eq!(fgids(&testcase), vec![FolderGroupId::new(vec![1, 2]), FolderGroupId::new(vec![1, 2, 3])]);
+            let testcase = builder().build().unwrap().with_min_folders(3);
+            assert_eq!(fgids(&testcase), vec![FolderGroupId::new(vec![1, 2, 3])]);
+            let testcase = builder().build().unwrap().with_min_folders(4);
+            assert!(fgids(&testcase).is_empty());
+        }
+        #[test]
+        fn similar_folders() {
+            let filenames = |names: &[&str]| names.iter().map(|name| filemd!(0, name)).collect::<Vec<FileMd>>();
+            let folders = || {
+                vec![
+                    folder_md(1, "/folder/one", filenames(&["a", "b", "c", "d", "e"])),
+                    folder_md(2, "/folder/two", filenames(&["a", "b", "c", "d", "f"])),
+                    folder_md(3, "/folder/three", filenames(&["a", "x", "y", "z"])),
+                    folder_md(4, "/folder/four", filenames(&["q"])),
+                ]
+            };
+            assert_eq!(jaccard_similarity(&folder_md(1, "/one", vec![]), &folder_md(2, "/two", vec![])), 0.0);
+            let testcase = SimilarFolders::new(folders(), 0.5);
+            let similar_md = testcase.get_md();
+            assert_eq!(similar_md.len(), 1);
+            assert_eq!(similar_md[0].lhs.id, 1);
+            assert_eq!(similar_md[0].rhs.id, 2);
+            assert_eq!(similar_md[0].similarity, 4.0 / 6.0);
+            assert_eq!(similar_md[0].common, vec!["a", "b", "c", "d"]);
+            let testcase = SimilarFolders::new(folders(), 0.7);
+            assert!(testcase.get_md().is_empty());
+            let testcase = SimilarFolders::new(folders(), 0.0);
+            let similar_md = testcase.get_md();
+            assert_eq!(similar_md.len(), 3);
+            assert_eq!(similar_md[1].similarity, 1.0 / 8.0);
+            assert!(similar_md.iter().all(|md| md.lhs.id != 4 && md.rhs.id != 4));
+        }
+        #[test]
+        fn exact_duplicates() {
+            let folders_match = || {
+                let mut builder = duplicate_folders_builder(vec![
+                    folder_md(1, "/folder/one", vec![filemd!(11, "a", 100), filemd!(12, "b", 200)]),
+                    folder_md(2, "/folder/two", vec![filemd!(21, "a", 100), filemd!(22, "b", 200)]),
+                    folder_md(3, "/folder/three", vec![filemd!(31, "c", 10), filemd!(32, "d", 20)]),
+                    folder_md(4, "/folder/four", vec![filemd!(41, "c", 10), filemd!(42, "d", 30)]),
+                    folder_md(5, "/folder/five", vec![filemd!(51, "e", 5), filemd!(52, "f", 5)]),
+                    folder_md(6, "/folder/six", vec![filemd!(61, "e", 5)]),
+                ]);
+                builder.add_duplicate_ids(duplicate_ids("a", vec![(1, 11), (2, 21)]));
+                builder.add_duplicate_ids(duplicate_ids("b", vec![(1, 12), (2, 22)]));
+                builder.add_duplicate_ids(duplicate_ids("c", vec![(3, 31), (4, 41)]));
+                builder.add_duplicate_ids(duplicate_ids("d", vec![(3, 32), (4, 42)]));
+                builder.add_duplicate_ids(duplicate_ids("e", vec![(5, 51), (6, 61)]));
+                DuplicateFoldersMatch::from(builder.build().unwrap())
+            };
+            assert_eq!(folders_match().into_iter().count(), 3);
+            let filenames = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<String>>();
+            let mut folders_filenames: HashMap<i64, Vec<String>> = HashMap::from([
+                (1, filenames(&["a", "b"])),
+                (2, filenames(&["b", "a"])),
+                (3, filenames(&["c", "d"])),
+                (4, filenames(&["c", "d"])),
+                (5, filenames(&["e", "f"])),
+                (6, filenames(&["e"])),
+            ]);
+            let exact_match = folders_match().exact_duplicates(&folders_filenames);
+            let testcase: Vec<FoldersMatchMd> = exact_match.into_iter().collect();
+            assert_eq!(testcase.len(), 1);
+            assert_eq!(testcase[0].fgid, FolderGroupId::new(vec![1, 2]));
+            assert_eq!(testcase[0].matches, vec!["a", "b"]);
+            assert!(testcase[0].except.is_empty());
+            // a file unique to one of the folders is not part of the duplicate metadata
+            folders_filenames.get_mut(&2).unwrap().push("unique".to_string());
+            let exact_match = folders_match().exact_duplicates(&folders_filenames);
+            assert_eq!(exact_match.into_iter().count(), 0);
+        }
+        #[test]
+        fn serialize_matches() {
+            let mut builder = duplicate_folders_builder(vec![
+                folder_md(1, "/folder/one", vec![filemd!(11, "a", 100), filemd!(12, "b", 200)]),
+                folder_md(2, "/folder/two", vec![filemd!(21, "a", 100), filemd!(22, "b", 300)]),
+                folder_md(3, "/folder/three", vec![filemd!(31, "c", 10)]),
+            ]);
+            builder.add_duplicate_ids(duplicate_ids("a", vec![(1, 11), (2, 21)]));
+            builder.add_duplicate_ids(duplicate_ids("b", vec![(1, 12), (2, 22)]));
+            let duplicate_folders = builder.build().unwrap();
+            let folders_match = DuplicateFoldersMatch::from(duplicate_folders);
+            let folders_match_md: Vec<FoldersMatchMd> = folders_match.into_iter().collect();
+            let testcase = serde_json::to_value(&folders_match_md).unwrap();
+            assert_eq!(testcase.as_array().unwrap().len(), folders_match_md.len());
+            for (json, md) in testcase.as_array().unwrap().iter().zip(&folders_match_md) {
+                let pathnames: Vec<&str> = md.folders_md.iter().map(|folder_md| folder_md.pathname.as_str()).collect();
+                assert_eq!(json["pathnames"], serde_json::json!(pathnames));
+                assert_eq!(json["matches"], serde_json::json!(md.matches));
+                assert_eq!(json["except"], serde_json::json!(md.except));
+                assert_eq!(json["other_matches"].as_array().unwrap().len(), md.other_matches.len());
+            }
+            // matches are the filenames the folders have in common, except are the ones whose files differ
+            assert_eq!(testcase[0]["matches"], serde_json::json!(["a", "b"]));
+            assert_eq!(testcase[0]["except"], serde_json::json!(["b"]));
+        }
+        #[test]
         fn validate_duplicate_ids() {
             let filename = "a_file";
             let mut builder = duplicate_folders_builder(vec![
@@ -1,4 +1,5 @@
 //! The domain objects.
+use serde::Serialize;
 use std::{
     collections::BTreeMap,
     fmt::{Display, Write as FmtWrite},
@@ -203,7 +204,7 @@ pub struct FileMd {
     pub modified: u64,
 }
 /// The metadata associated with a problem.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct ProblemMd {
     /// The unique identifier of the problem.
     pub id: i64,
@@ -217,7 +218,7 @@ pub struct ProblemMd {
     pub description: String,
 }
 /// The database information metadata.
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct DbInformation {
     /// The top-level folder pathnames.
     pub root_folders: Vec<String>,
@@ -231,6 +232,143 @@ pub struct DbInformation {
     pub database_size: u64,
 }
 
+/// The count and size of files whose size falls within a range.
+#[derive(Debug)]
+pub struct SizeBucket {
+    /// A description of the size range.
+    pub label: &'static str,
+    /// The smallest file size in the range.
+    pub min: u64,
+    /// The file size the range is less than or `None` if there is no upper bound.
+    pub max: Option<u64>,
+    /// The count of files in the range.
+    pub count: u64,
+    /// The total size of files in the range.
+    pub size: u64,
+}
+impl SizeBucket {
+    /// Returns `true` if the file size is within the bucket range.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` is the file size.
+    pub fn contains(&self, size: u64) -> bool {
+        size >= self.min && self.max.is_none_or(|max| size < max)
+    }
+}
+
+/// The distribution of file sizes.
+#[derive(Debug)]
+pub struct SizeHistogram {
+    /// The file size ranges ordered from smallest to largest.
+    pub buckets: Vec<SizeBucket>,
+}
+impl Default for SizeHistogram {
+    /// Creates the histogram with empty buckets for files less than 1KiB, 1KiB to 1MiB, 1MiB to 1GiB,
+    /// and 1GiB or more.
+    fn default() -> Self {
+        const KIB: u64 = 1024;
+        const MIB: u64 = KIB * 1024;
+        const GIB: u64 = MIB * 1024;
+        let bucket = |label, min, max| SizeBucket { label, min, max, count: 0, size: 0 };
+        Self {
+            buckets: vec![
+                bucket("< 1KiB", 0, Some(KIB)),
+                bucket("1KiB - 1MiB", KIB, Some(MIB)),
+                bucket("1MiB - 1GiB", MIB, Some(GIB)),
+                bucket(">= 1GiB", GIB, None),
+            ],
+        }
+    }
+}
+impl SizeHistogram {
+    /// Add a file size to the bucket whose range contains it.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` is the file size.
+    pub fn add(&mut self, size: u64) {
+        if let Some(bucket) = self.buckets.iter_mut().find(|bucket| bucket.contains(size)) {
+            bucket.count += 1;
+            bucket.size += size;
+        }
+    }
+}
+
+/// The database maintenance metrics.
+#[derive(Debug)]
+pub struct MaintenanceReport {
+    /// The database allocation size before maintenance.
+    pub before: u64,
+    /// The database allocation size after maintenance.
+    pub after: u64,
+}
+impl MaintenanceReport {
+    /// Returns the database space that was reclaimed.
+    pub fn reclaimed(&self) -> u64 {
+        self.before.saturating_sub(self.after)
+    }
+}
+
+/// The files that are unique to each database when two databases are compared.
+///
+/// Files are matched between the databases by their filename and size.
+#[derive(Debug)]
+pub struct DatabaseCompare {
+    /// The files only found in the session database.
+    pub db_only: Vec<FileMd>,
+    /// The files only found in the other database.
+    pub other_db_only: Vec<FileMd>,
+}
+
+/// The most deeply nested folder in the database.
+#[derive(Debug, Serialize)]
+pub struct FolderDepth {
+    /// How many folders the folder is below its top-level folder.
+    pub depth: u64,
+    /// The folder pathname.
+    pub pathname: String,
+}
+
+/// A folder and how many files it directly contains.
+#[derive(Debug, Serialize)]
+pub struct FolderCount {
+    /// The folder identifier.
+    pub id: i64,
+    /// The folder pathname.
+    pub pathname: String,
+    /// The number of files directly within the folder.
+    pub count: u64,
+}
+
+/// A filename that is in more than one folder.
+#[derive(Debug, Serialize)]
+pub struct FilenameFolders {
+    /// The filename found in multiple folders.
+    pub filename: String,
+    /// The pathnames of the folders that contain the filename.
+    pub folders: Vec<String>,
+}
+
+/// The metadata recorded each time a folder is added to the database.
+#[derive(Debug, Serialize)]
+pub struct ScanMd {
+    /// The unique identifier of the scan.
+    pub id: i64,
+    /// When the scan was run as the number of seconds since the Unix epoch.
+    pub scanned: u64,
+    /// The pathname of the folder that was added.
+    pub pathname: String,
+    /// The count of folders added.
+    pub folders: u64,
+    /// The count of files added.
+    pub files: u64,
+    /// The count of problems added.
+    pub problems: u64,
+    /// How long the scan took in milliseconds.
+    pub elapsed: u64,
+}
+
 /// The metadata that associates the parent folders with a duplicate filename.
 #[derive(Debug)]
 pub(crate) struct DuplicateIds {
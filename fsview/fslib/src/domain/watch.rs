@@ -0,0 +1,167 @@
+//! Watch a folder hierarchy and rescan the folders that change.
+use super::{api, sql, Error, Result};
+use notify::{RecursiveMode, Watcher};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+/// Convert a `notify::Error` to a domain error.
+impl From<notify::Error> for Error {
+    fn from(error: notify::Error) -> Self {
+        Error::Filesys(format!("domain: watch: {error}"))
+    }
+}
+
+/// How long a folder must be free of filesystem events before it is rescanned.
+pub const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Watch a folder hierarchy rescanning folders into the database as they change.
+///
+/// A folder that cannot be rescanned is logged and watching continues with the next folder that changes.
+///
+/// # Arguments
+///
+/// * `conn` is the database connection.
+/// * `root` is the folder hierarchy that will be watched.
+/// * `debounce` is how long a folder must be quiet before it is rescanned.
+pub(crate) fn watch(mut conn: sql::Connection, root: &Path, debounce: Duration) -> Result<()> {
+    let root = root.canonicalize()?;
+    let (sender, receiver) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(&root, RecursiveMode::Recursive)?;
+    log::info!("watching {}", root.display());
+    let mut changes = PendingChanges::new(&root, debounce);
+    loop {
+        match receiver.recv_timeout(debounce) {
+            Ok(Ok(event)) => changes.add(&event.paths, Instant::now()),
+            Ok(Err(error)) => log::error!("watch: {error}"),
+            Err(mpsc::RecvTimeoutError::Timeout) => (),
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+        for folder in changes.ready(Instant::now()) {
+            log::debug!("rescan {}", folder.display());
+            // a folder can change again before it is rescanned so keep watching
+            if let Err(error) = api::rescan_folder(&mut conn, &folder) {
+                log::warn!("rescan {} ({error})", folder.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The folders that have changed and are waiting to be rescanned.
+#[derive(Debug)]
+struct PendingChanges {
+    /// The folder hierarchy being watched.
+    root: PathBuf,
+    /// How long a folder must be quiet before it is ready to be rescanned.
+    debounce: Duration,
+    /// The changed folders and when they last changed.
+    folders: BTreeMap<PathBuf, Instant>,
+}
+impl PendingChanges {
+    /// Create a new instance of the pending changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` is the folder hierarchy being watched.
+    /// * `debounce` is how long a folder must be quiet before it is ready to be rescanned.
+    fn new(root: &Path, debounce: Duration) -> Self {
+        Self { root: root.to_path_buf(), debounce, folders: BTreeMap::new() }
+    }
+    /// Record the paths from a filesystem event.
+    ///
+    /// A path that is not a folder records the folder that contains it.
+    ///
+    /// # Arguments
+    ///
+    /// * `paths` are the filesystem paths that changed.
+    /// * `when` is the time the change was seen.
+    fn add(&mut self, paths: &[PathBuf], when: Instant) {
+        for path in paths {
+            let folder = match path.is_dir() {
+                true => path.as_path(),
+                false => path.parent().unwrap_or(&self.root),
+            };
+            let folder = match folder.starts_with(&self.root) {
+                true => folder.to_path_buf(),
+                false => self.root.clone(),
+            };
+            self.folders.insert(folder, when);
+        }
+    }
+    /// Remove the folders that are ready to be rescanned.
+    ///
+    /// Folders contained by another folder that is ready are dropped since the rescan will include them.
+    ///
+    /// # Arguments
+    ///
+    /// * `now` is the current time.
+    fn ready(&mut self, now: Instant) -> Vec<PathBuf> {
+        let ready: Vec<PathBuf> = self
+            .folders
+            .iter()
+            .filter(|(_, when)| now.saturating_duration_since(**when) >= self.debounce)
+            .map(|(folder, _)| folder.clone())
+            .collect();
+        let mut folders: Vec<PathBuf> = vec![];
+        // the map is ordered so a containing folder will always be seen first
+        for folder in ready {
+            self.folders.remove(&folder);
+            if !folders.iter().any(|ancestor| folder.starts_with(ancestor)) {
+                folders.push(folder);
+            }
+        }
+        folders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{db, filesys};
+    use super::*;
+    use filesys::testlib::TempFolder;
+
+    #[test]
+    fn pending_changes() {
+        let root = PathBuf::from("/does/not/exist");
+        let mut testcase = PendingChanges::new(&root, DEBOUNCE);
+        let start = Instant::now();
+        testcase.add(&[root.join("folder/file1"), root.join("file")], start);
+        testcase.add(&[root.join("folder/file2"), PathBuf::from("/outside/file")], start + DEBOUNCE / 2);
+        assert!(testcase.ready(start).is_empty());
+        // the folders are not ready until all of their events are quiet
+        assert!(testcase.ready(start + DEBOUNCE).is_empty());
+        assert_eq!(testcase.ready(start + DEBOUNCE * 2), vec![root.clone()]);
+        assert!(testcase.folders.is_empty());
+    }
+
+    #[test]
+    fn rescan_created_file() {
+        let temp_folder = TempFolder::new("watch");
+        let root = temp_folder.mkdir("folder").path().to_path_buf();
+        let mut conn = db::database_connection(None).unwrap();
+        db::schema_init(&conn).unwrap();
+        db::load_fs_metadata(&mut conn, &filesys::collect_metadata_excluding(&root, &[]).unwrap()).unwrap();
+        let file_count = |conn: &sql::Connection| -> u64 {
+            conn.query_row("SELECT COUNT(*) FROM files WHERE name = 'created.txt'", (), |row| row.get(0)).unwrap()
+        };
+        assert_eq!(file_count(&conn), 0);
+        // simulate the event that would be sent by the watcher
+        let created = root.join("folder").join("created.txt");
+        temp_folder.write("folder/created.txt", "watched");
+        let mut changes = PendingChanges::new(&root, DEBOUNCE);
+        let start = Instant::now();
+        changes.add(&[created], start);
+        let folders = changes.ready(start + DEBOUNCE);
+        assert_eq!(folders, vec![root.join("folder")]);
+        for folder in folders {
+            api::rescan_folder(&mut conn, &folder).unwrap();
+        }
+        assert_eq!(file_count(&conn), 1);
+        assert_eq!(db::get_table_counts_query(&conn).unwrap().0, 2);
+    }
+}
@@ -7,8 +7,10 @@
 //! * [ProblemMetadata]
 //! * [FsMetadata]
 //!
-//! The domain calls [collect_metadata] to collect metadata for that folder.
+//! The domain calls [collect_metadata_excluding] to collect metadata for that folder skipping the folders and
+//! files that should not be collected.
 use std::{
+    collections::BTreeSet,
     ffi::OsString,
     fmt,
     fs::{self, DirEntry, Metadata},
@@ -224,6 +226,41 @@ impl FsMetadata {
             _ => false,
         }
     }
+    /// Verify the metadata is a consistent folder hierarchy.
+    ///
+    /// The metadata must be an absolute folder pathname and each child must be contained by its
+    /// parent folder. Problems reading a folder are recorded against the folder itself so they
+    /// are allowed to have the folder pathname.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            FsMetadata::Folder(folder_md) if folder_md.path.is_absolute() => validate_folder(folder_md),
+            FsMetadata::Folder(folder_md) => Err(Error::from(format!("{} is not absolute...", folder_md.pathname()))),
+            _ => Err(Error::from(format!("{self} is not a folder..."))),
+        }
+    }
+}
+
+/// Verify the children of a folder are contained by it.
+///
+/// # Arguments
+/// * `folder_md` - the folder whose hierarchy will be verified.
+fn validate_folder(folder_md: &FolderMetadata) -> Result<()> {
+    let mut pathnames: BTreeSet<&Path> = BTreeSet::new();
+    for child in &folder_md.children {
+        let child_path = child.path();
+        match child {
+            FsMetadata::Problem(_) if child_path == folder_md.path => continue,
+            _ if child_path.parent() != Some(folder_md.path.as_path()) => {
+                return Err(Error::from(format!("{child} is not contained by {}...", folder_md.pathname())));
+            }
+            _ if !pathnames.insert(child_path) => {
+                return Err(Error::from(format!("{child} is a duplicate...")));
+            }
+            FsMetadata::Folder(child_md) => validate_folder(child_md)?,
+            _ => (),
+        }
+    }
+    Ok(())
 }
 
 impl fmt::Display for FsMetadata {
@@ -236,14 +273,19 @@ impl fmt::Display for FsMetadata {
     }
 }
 
-/// Collect the metadata for a folder hierarchy.
+/// Collect the metadata for a folder hierarchy skipping folders and files that match an exclude pattern.
+///
+/// An exclude pattern is a glob where `*` matches any sequence of characters and `?` matches a single
+/// character. A pattern containing a path separator is matched against the full pathname, otherwise it is
+/// matched against the folder or file name. Excluded folders are not traversed.
 ///
 /// # Arguments
 /// * `folder_path` - the path to some folder.
+/// * `exclude` - the glob patterns of folders and files that will be skipped.
 ///
 /// # Note
 /// An error will be returned if the `folder_path` does not exist or if it is not a folder.
-pub fn collect_metadata(folder_path: &PathBuf) -> Result<FsMetadata> {
+pub fn collect_metadata_excluding(folder_path: &PathBuf, exclude: &[String]) -> Result<FsMetadata> {
     if folder_path.exists() {
         let folder_path = if cfg!(windows) {
             // the windows version of canonicalize comes back as a Win32 file I/O namesapce (\\?\drive:\directory_path)
@@ -255,7 +297,7 @@ pub fn collect_metadata(folder_path: &PathBuf) -> Result<FsMetadata> {
             std::fs::canonicalize(folder_path.clone())?
         };
         let collect_time = StopWatch::start_new();
-        let folder = visit_folder(&folder_path)?;
+        let folder = visit_folder(&folder_path, exclude)?;
         log::debug!("collect_metadata={collect_time}");
         if log::log_enabled!(log::Level::Trace) {
             dump_metadata(&folder);
@@ -288,7 +330,11 @@ fn dump_metadata(metadata: &FsMetadata) {
 ///
 /// This function will call itself recursively for each child folder. It guarantees the
 /// child metadata is ordered by its name.
-fn visit_folder(folder_path: &PathBuf) -> Result<FsMetadata> {
+///
+/// # Arguments
+/// * `folder_path` - the folder whose metadata will be collected.
+/// * `exclude` - the glob patterns of folders and files that will be skipped.
+fn visit_folder(folder_path: &PathBuf, exclude: &[String]) -> Result<FsMetadata> {
     if !folder_path.is_dir() {
         Err(Error::from(format!("files: {} is not a folder!!!", folder_path.display())))
     } else {
@@ -309,8 +355,12 @@ fn visit_folder(folder_path: &PathBuf) -> Result<FsMetadata> {
                         }
                         Ok(dir_entry) => {
                             let entry_path = dir_entry.path();
+                            if is_excluded(&entry_path, exclude) {
+                                log::debug!("excluded {}", entry_path.display());
+                                continue;
+                            }
                             let fs_node = if entry_path.is_dir() {
-                                visit_folder(&entry_path)?
+                                visit_folder(&entry_path, exclude)?
                             } else {
                                 FsMetadata::File(FileMetadata::new(&dir_entry)?)
                             };
@@ -326,6 +376,68 @@ fn visit_folder(folder_path: &PathBuf) -> Result<FsMetadata> {
     }
 }
 
+/// Identifies if a folder or file in a folder hierarchy is excluded or is contained by an excluded folder.
+///
+/// # Arguments
+/// * `root` - the folder hierarchy the exclude patterns were applied to.
+/// * `path` - the folder or file path within the folder hierarchy.
+/// * `exclude` - the glob patterns of folders and files that will be skipped.
+pub fn is_excluded_within(root: &Path, path: &Path, exclude: &[String]) -> bool {
+    match path.strip_prefix(root) {
+        Err(_) => false,
+        Ok(relative) => {
+            let mut path = root.to_path_buf();
+            relative.components().any(|component| {
+                path.push(component);
+                is_excluded(&path, exclude)
+            })
+        }
+    }
+}
+
+/// Identifies if a folder or file matches one of the exclude patterns.
+///
+/// # Arguments
+/// * `path` - the folder or file path.
+/// * `exclude` - the glob patterns of folders and files that will be skipped.
+fn is_excluded(path: &Path, exclude: &[String]) -> bool {
+    exclude.iter().any(|pattern| match pattern.contains(std::path::MAIN_SEPARATOR) || pattern.contains('/') {
+        true => glob_match(pattern, &as_pathname(path)),
+        false => glob_match(pattern, &as_filename(path)),
+    })
+}
+
+/// Matches text against a glob pattern where `*` matches any sequence of characters and `?` matches any
+/// single character.
+///
+/// # Arguments
+/// * `pattern` - the glob pattern.
+/// * `text` - the text that will be matched.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // the pattern and text positions following the last '*' seen, used to backtrack
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && pattern[p] == '*' {
+            p += 1;
+            backtrack = Some((p, t));
+        } else if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            // let the '*' consume one more character
+            p = star_p;
+            t = star_t + 1;
+            backtrack = Some((star_p, t));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|ch| *ch == '*')
+}
+
 /// Converts a filesystem timesamp into the number of seconds since the [SystemTime::UNIX_EPOCH].
 fn file_timestamp(system_time: SystemTime) -> u64 {
     match system_time.duration_since(SystemTime::UNIX_EPOCH) {
@@ -356,3 +468,85 @@ pub fn count_metadata(fs_metadata: &FsMetadata) -> i64 {
     traverse(fs_metadata, &mut counter);
     count
 }
+
+/// Helpers shared by tests that need filesystem metadata or a scratch folder.
+#[cfg(test)]
+pub(crate) mod testlib {
+    use super::*;
+
+    /// Create folder metadata.
+    ///
+    /// # Arguments
+    /// * `pathname` - The folder pathname.
+    /// * `children` - The contents of the folder.
+    pub fn folder(pathname: &str, children: Vec<FsMetadata>) -> FsMetadata {
+        FsMetadata::Folder(FolderMetadata { path: PathBuf::from(pathname), size: 0, created: 0, modified: 0, children })
+    }
+
+    /// Create file metadata.
+    ///
+    /// # Arguments
+    /// * `pathname` - The file pathname.
+    /// * `size` - The file size.
+    pub fn file(pathname: &str, size: u64) -> FsMetadata {
+        let path = PathBuf::from(pathname);
+        FsMetadata::File(FileMetadata { path, is_symlink: false, size, created: 0, modified: 0 })
+    }
+
+    /// Create folder metadata that contains empty files.
+    ///
+    /// # Arguments
+    /// * `pathname` - The folder pathname.
+    /// * `filenames` - The names of the files in the folder.
+    pub fn folder_of_files(pathname: &str, filenames: &[&str]) -> FolderMetadata {
+        let path = PathBuf::from(pathname);
+        let children = filenames.iter().map(|name| file(&path.join(name).display().to_string(), 0)).collect();
+        FolderMetadata { path, size: 0, created: 0, modified: 0, children }
+    }
+
+    /// A scratch folder in the system temporary directory that is removed when dropped.
+    pub struct TempFolder(
+        /// The canonical folder path.
+        PathBuf,
+    );
+    impl TempFolder {
+        /// Create an empty scratch folder, anything left behind by a previous run is removed.
+        ///
+        /// # Arguments
+        /// * `name` - Identifies the test using the folder.
+        pub fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("fsview-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            Self(fs::canonicalize(path).unwrap())
+        }
+        /// Get the path of the scratch folder.
+        pub fn path(&self) -> &Path {
+            &self.0
+        }
+        /// Create a folder and any missing parent folders.
+        ///
+        /// # Arguments
+        /// * `relative_path` - The folder path relative to the scratch folder.
+        pub fn mkdir(&self, relative_path: &str) -> &Self {
+            fs::create_dir_all(self.0.join(relative_path)).unwrap();
+            self
+        }
+        /// Write a file creating any missing parent folders.
+        ///
+        /// # Arguments
+        /// * `relative_path` - The file path relative to the scratch folder.
+        /// * `contents` - What will be written to the file.
+        pub fn write(&self, relative_path: &str, contents: &str) -> &Self {
+            let path = self.0.join(relative_path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+            self
+        }
+    }
+    impl Drop for TempFolder {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+}
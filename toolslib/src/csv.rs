@@ -0,0 +1,132 @@
+//! # A simple CSV reader shared by the import features.
+//!
+//! Records are returned as a collection of field values with leading and trailing whitespace removed.
+//! Quoted fields can contain embedded commas, quotes, and line breaks.
+use crate::{Error, Result};
+use std::io::Read;
+
+/// Read CSV records where the first row contains the column headers.
+///
+/// The header row is not returned by the iterator, use [Records::headers] to get it.
+///
+/// # Arguments
+///
+/// * `reader` is where the CSV content will be read from.
+pub fn read_records<R: Read>(reader: R) -> Records<R> {
+    Records::new(reader, true)
+}
+
+/// Read CSV records that do not have a header row.
+///
+/// # Arguments
+///
+/// * `reader` is where the CSV content will be read from.
+pub fn read_headerless_records<R: Read>(reader: R) -> Records<R> {
+    Records::new(reader, false)
+}
+
+/// An iterator over the records of CSV content.
+///
+/// A record that cannot be parsed is returned as an error and reading continues with the next record
+/// unless the error came from the underlying reader.
+pub struct Records<R> {
+    /// The CSV parser.
+    reader: ::csv::Reader<R>,
+    /// Indicates no more records will be returned.
+    done: bool,
+}
+impl<R: Read> Records<R> {
+    /// Create the CSV records iterator.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` is where the CSV content will be read from.
+    /// * `has_headers` indicates the first row contains column headers.
+    fn new(reader: R, has_headers: bool) -> Self {
+        let reader = ::csv::ReaderBuilder::new().has_headers(has_headers).trim(::csv::Trim::All).from_reader(reader);
+        Self { reader, done: false }
+    }
+    /// Get the column headers, an empty collection is returned if the content does not have a header row.
+    pub fn headers(&mut self) -> Result<Vec<String>> {
+        match self.reader.has_headers() {
+            true => Ok(self.reader.headers().map_err(csv_error)?.iter().map(String::from).collect()),
+            false => Ok(vec![]),
+        }
+    }
+}
+impl<R: Read> Iterator for Records<R> {
+    type Item = Result<Vec<String>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut record = ::csv::StringRecord::new();
+        match self.reader.read_record(&mut record) {
+            Ok(true) => Some(Ok(record.iter().map(String::from).collect())),
+            Ok(false) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = err.is_io_error();
+                Some(Err(csv_error(err)))
+            }
+        }
+    }
+}
+
+/// Convert a CSV parsing error into a tools library error.
+///
+/// # Arguments
+///
+/// * `err` is the CSV parsing error.
+fn csv_error(err: ::csv::Error) -> Error {
+    Error::from(format!("csv: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CSV: &str = r#"name, state, population
+"Seattle, WA", Washington, 737015
+Tucson,Arizona,"542,629"
+"The ""Big"" Apple",New York,8804190
+"#;
+
+    #[test]
+    fn records() {
+        let mut testcase = read_records(CSV.as_bytes());
+        assert_eq!(testcase.headers().unwrap(), vec!["name", "state", "population"]);
+        let records = testcase.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0], vec!["Seattle, WA", "Washington", "737015"]);
+        assert_eq!(records[1], vec!["Tucson", "Arizona", "542,629"]);
+        assert_eq!(records[2], vec!["The \"Big\" Apple", "New York", "8804190"]);
+        // headers can be read after the records
+        let mut testcase = read_records(CSV.as_bytes());
+        assert_eq!(testcase.by_ref().count(), 3);
+        assert_eq!(testcase.headers().unwrap().len(), 3);
+        assert!(read_records("".as_bytes()).next().is_none());
+    }
+
+    #[test]
+    fn headerless() {
+        let mut testcase = read_headerless_records(CSV.as_bytes());
+        assert!(testcase.headers().unwrap().is_empty());
+        let records = testcase.collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0], vec!["name", "state", "population"]);
+    }
+
+    #[test]
+    fn errors() {
+        let mut testcase = read_records("a,b\n1,2\n3\n4,5\n".as_bytes());
+        assert_eq!(testcase.next().unwrap().unwrap(), vec!["1", "2"]);
+        let error = testcase.next().unwrap().unwrap_err();
+        assert!(error.to_string().starts_with("toolslib: csv: "));
+        // reading continues after a record that cannot be parsed
+        assert_eq!(testcase.next().unwrap().unwrap(), vec!["4", "5"]);
+        assert!(testcase.next().is_none());
+    }
+}
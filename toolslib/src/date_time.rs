@@ -96,6 +96,41 @@ pub fn get_date(y: i32, m: u32, d: u32) -> NaiveDate {
     }
 }
 
+/// Create an iterator that returns each date from the starting date through the ending date.
+///
+/// Both dates are included so a range where the dates are the same will return that one date.
+/// Nothing is returned if the ending date is before the starting date.
+///
+/// # Arguments
+///
+/// * `from` is the starting date.
+/// * `thru` is the inclusive ending date.
+pub fn date_range_iter(from: NaiveDate, thru: NaiveDate) -> DateRangeIter {
+    DateRangeIter { next: Some(from), thru }
+}
+
+/// The iterator over an inclusive range of dates.
+#[derive(Clone, Debug)]
+pub struct DateRangeIter {
+    /// The next date that will be returned.
+    next: Option<NaiveDate>,
+    /// The inclusive ending date.
+    thru: NaiveDate,
+}
+impl Iterator for DateRangeIter {
+    type Item = NaiveDate;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next {
+            Some(date) if date <= self.thru => {
+                // the last date chrono supports ends the iteration
+                self.next = date.succ_opt();
+                Some(date)
+            }
+            _ => None,
+        }
+    }
+}
+
 /// A boiler plate helper that creates a `NaiveTime` from hours, minutes, and seconds.
 ///
 /// This really exists just to hide some of the changes that has happened in the 0.8.24
@@ -208,6 +243,18 @@ mod test {
         assert_eq!(isodate(&date), "2022-10-05")
     }
     #[test]
+    fn date_range() {
+        let dates: Vec<NaiveDate> = date_range_iter(get_date(2024, 2, 29), get_date(2024, 2, 29)).collect();
+        assert_eq!(dates, vec![get_date(2024, 2, 29)]);
+        let dates: Vec<NaiveDate> = date_range_iter(get_date(2023, 12, 30), get_date(2024, 1, 2)).collect();
+        assert_eq!(
+            dates,
+            vec![get_date(2023, 12, 30), get_date(2023, 12, 31), get_date(2024, 1, 1), get_date(2024, 1, 2)]
+        );
+        assert_eq!(date_range_iter(get_date(2024, 1, 2), get_date(2024, 1, 1)).count(), 0);
+        assert_eq!(date_range_iter(NaiveDate::MAX, NaiveDate::MAX).count(), 1);
+    }
+    #[test]
     fn parse_dates() {
         assert_eq!(parse_date("2022-7-15").unwrap(), get_date(2022, 7, 15));
         assert_eq!(parse_date("7-1-2022").unwrap(), get_date(2022, 7, 1));
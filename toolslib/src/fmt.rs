@@ -34,6 +34,35 @@ pub fn fmt_float(value: &Option<f64>, precision: usize) -> String {
     }
 }
 
+/// Creates a percentage string from a ratio.
+///
+/// The percentage is rounded half away from zero to the number of decimal places.
+///
+/// # Arguments
+///
+/// * `value` is the ratio where `1.0` is 100 percent.
+/// * `decimals` is the number of decimal places in the percentage.
+///
+pub fn format_percent(value: f64, decimals: usize) -> String {
+    format_percent_width(value, decimals, 0)
+}
+
+/// Creates a percentage string from a ratio with the number right aligned to a minimum width.
+///
+/// The width does not include the trailing `%`.
+///
+/// # Arguments
+///
+/// * `value` is the ratio where `1.0` is 100 percent.
+/// * `decimals` is the number of decimal places in the percentage.
+/// * `width` is the minimum width of the percentage number.
+///
+pub fn format_percent_width(value: f64, decimals: usize, width: usize) -> String {
+    let scale = 10f64.powi(decimals as i32);
+    let percent = (value * 100.0 * scale).round() / scale;
+    format!("{:>width$.decimals$}%", percent, width = width, decimals = decimals)
+}
+
 /// The default precision used by the multiple-byte formatters.
 pub const DEFAULT_MULTIBYTE_PRECISION: usize = 3;
 
@@ -458,4 +487,15 @@ mod tests {
         assert_eq!(fmt_float(&Some(testcase), 1), "123.4");
         assert_eq!(fmt_float(&Some(testcase), 0), "123");
     }
+
+    #[test]
+    fn percent() {
+        assert_eq!(format_percent_width(0.0, 0, 3), "  0%");
+        assert_eq!(format_percent_width(0.1049, 0, 3), " 10%");
+        assert_eq!(format_percent_width(0.995, 0, 3), "100%");
+        assert_eq!(format_percent(0.1049, 0), "10%");
+        assert_eq!(format_percent(0.123456, 2), "12.35%");
+        assert_eq!(format_percent(0.5, 2), "50.00%");
+        assert_eq!(format_percent_width(0.05, 2, 6), "  5.00%");
+    }
 }
@@ -5,12 +5,17 @@
 // #![feature(log_syntax)]
 // #![feature(trace_macros)]
 use std::{result, fmt::{Display, Formatter, Result as FmtResult}};
+pub mod csv;
 pub mod date_time;
 pub mod fmt;
 pub mod logs;
 pub mod stopwatch;
 pub mod text;
+pub mod units;
 pub mod report;
+pub mod retry;
+
+pub use retry::{retry, retry_cancelable};
 
 /// The tools library result.
 type Result<T> = result::Result<T, Error>;
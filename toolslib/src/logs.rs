@@ -8,7 +8,7 @@ use log4rs::append::console::{ConsoleAppender, Target};
 use log4rs::append::file::FileAppender;
 use log4rs::config::runtime::{ConfigErrors, Logger};
 use log4rs::config::{Appender, Config, Root};
-use log4rs::encode::pattern::PatternEncoder;
+use log4rs::encode::{json::JsonEncoder, pattern::PatternEncoder, Encode};
 use log4rs::filter::threshold::ThresholdFilter;
 use log4rs::Handle;
 
@@ -58,10 +58,36 @@ const DEFAULT_FILE_PATTERN: &str = "{d(%H:%M:%S%.3f)}|{l:<5}|{M} {m}{n}";
 #[cfg(debug_assertions)]
 const DEFAULT_FILE_PATTERN: &str = "{d(%H:%M:%S%.3f)}|{l:<5}|{f}:{L} {m}{n}";
 
+/// The format of the logging output.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum LogFormat {
+    /// Log output follows the console and file logging patterns.
+    #[default]
+    Pattern,
+    /// Log output is a `JSON` object per line containing the timestamp, level, target, and message.
+    Json,
+}
+
+/// Create the encoder used by an appender.
+///
+/// # Arguments
+///
+/// * `format` determines what type of encoder will be created.
+/// * `pattern` is the logging pattern used by the pattern encoder.
+fn encoder(format: LogFormat, pattern: &str) -> Box<dyn Encode> {
+    match format {
+        LogFormat::Pattern => Box::new(PatternEncoder::new(pattern)),
+        LogFormat::Json => Box::new(JsonEncoder::new()),
+    }
+}
+
 /// The structure used to initialize `log4rs`.
+#[derive(Clone)]
 pub struct LogProperties {
     /// The default log level that will be used.
     pub level: log::LevelFilter,
+    /// The format of log output, the logging patterns are ignored when using `JSON`.
+    pub format: LogFormat,
     /// The console logging pattern that will be used, if `None` the `DEFAULT_CONSOLE_PATTERN` will be used.
     pub console_pattern: Option<String>,
     /// The file logging pattern that will be used, if `None` the `DEFAULT_FILE_PATTERN` will be used.
@@ -79,6 +105,7 @@ pub struct LogProperties {
 /// The following defaults will be set.
 ///
 /// * The logging level will be set to `Info`.
+/// * The logging format will be set to `Pattern`.
 /// * The default console logging pattern will be used.
 /// * The default file logging pattern will be used.
 /// * The logfile path will be `None`.
@@ -88,6 +115,7 @@ impl Default for LogProperties {
     fn default() -> Self {
         Self {
             level: log::LevelFilter::Info,
+            format: LogFormat::Pattern,
             console_pattern: None,
             logfile_pattern: None,
             logfile_path: None,
@@ -124,23 +152,74 @@ impl LogProperties {
     }
 }
 
+/// The guard returned when `log4rs` has been initialized.
+///
+/// Pending log output is flushed when the guard is dropped so it should be held for the
+/// lifetime of the program.
+pub struct LogGuard(
+    /// The handle that allows the `log4rs` configuration to be changed.
+    Handle,
+    /// The properties used to build the current `log4rs` configuration.
+    LogProperties,
+);
+impl LogGuard {
+    /// Get the handle that allows the logging configuration to be changed at runtime.
+    pub fn handle(&self) -> &Handle {
+        &self.0
+    }
+    /// Get the current log level.
+    pub fn level(&self) -> log::LevelFilter {
+        self.1.level
+    }
+    /// Change the log level at runtime.
+    ///
+    /// The `log4rs` configuration is rebuilt using the new level. If there is a log file it will
+    /// be appended to rather than being overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` is the log level that will be used.
+    pub fn set_level(&mut self, level: log::LevelFilter) -> Result<(), LogError> {
+        let log_properties = LogProperties { level, logfile_append: true, ..self.1.clone() };
+        self.0.set_config(configuration(&log_properties)?);
+        self.1.level = level;
+        Ok(())
+    }
+}
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        log::logger().flush();
+    }
+}
+
 /// Initializes `log4rs` with a console logger (`stderr`) and an optional file logger.
 ///
-/// On success it returns a handle to an object that allows the logging configuration to be
-/// changed at runtime.
+/// On success it returns a guard that flushes the logging output when it is dropped. The guard
+/// can also be used to change the log level at runtime.
 /// 
 /// # Arguments
 /// 
 /// * `log_properties` the properties that help define the behaviour of `log4rs`.
-pub fn initialize(log_properties: LogProperties) -> Result<Handle, LogError> {
+pub fn initialize(log_properties: LogProperties) -> Result<LogGuard, LogError> {
+    let config = configuration(&log_properties)?;
+    let handle = log4rs::init_config(config)?;
+    Ok(LogGuard(handle, log_properties))
+}
+
+/// Create the `log4rs` configuration.
+///
+/// # Arguments
+///
+/// * `log_properties` the properties that help define the behaviour of `log4rs`.
+fn configuration(log_properties: &LogProperties) -> Result<Config, LogError> {
     // the console appender goes to stderr
-    let console_pattern = if let Some(pattern) = log_properties.console_pattern {
-        pattern
+    let console_pattern = if let Some(pattern) = &log_properties.console_pattern {
+        pattern.clone()
     } else {
         String::from(DEFAULT_CONSOLE_PATTERN)
     };
-    let console_encoder = PatternEncoder::new(&console_pattern);
-    let console_appender = ConsoleAppender::builder().target(Target::Stderr).encoder(Box::new(console_encoder)).build();
+    let console_encoder = encoder(log_properties.format, &console_pattern);
+    let console_appender = ConsoleAppender::builder().target(Target::Stderr).encoder(console_encoder).build();
     let console_log_level = if log_properties.logfile_path.is_none() {
         log_properties.level
     } else {
@@ -161,17 +240,17 @@ pub fn initialize(log_properties: LogProperties) -> Result<Handle, LogError> {
     let mut root_builder = Root::builder().appender("console");
 
     // the log pathname is the trigger to use a log file
-    if let Some(log_pathname) = log_properties.logfile_path {
+    if let Some(log_pathname) = &log_properties.logfile_path {
         // create the file appender
-        let file_pattern = if let Some(pattern) = log_properties.logfile_pattern {
-            pattern
+        let file_pattern = if let Some(pattern) = &log_properties.logfile_pattern {
+            pattern.clone()
         } else {
             String::from(DEFAULT_FILE_PATTERN)
         };
-        let file_encoder = PatternEncoder::new(&file_pattern);
+        let file_encoder = encoder(log_properties.format, &file_pattern);
         let file_appender = FileAppender::builder()
             .append(log_properties.logfile_append)
-            .encoder(Box::new(file_encoder))
+            .encoder(file_encoder)
             .build(log_pathname)?;
 
         // add the file appender to both configuration and root logger
@@ -194,8 +273,7 @@ pub fn initialize(log_properties: LogProperties) -> Result<Handle, LogError> {
     }
     let config = config_builder.build(root_builder.build(log_properties.level))?;
     // eprintln!("{:?}", config);
-    let handle = log4rs::init_config(config)?;
-    Ok(handle)
+    Ok(config)
 }
 
 #[cfg(test)]
@@ -205,11 +283,13 @@ mod tests {
     #[test]
     fn initialize() {
         // super::initialize(3, Some(PathBuf::from("test.log")), false).unwrap();
-        super::initialize(LogProperties {
+        let logfile = std::env::temp_dir().join(format!("toolslib-logs-{}.log", std::process::id()));
+        let mut log_guard = super::initialize(LogProperties {
             level: log::LevelFilter::Info,
+            format: LogFormat::Pattern,
             console_pattern: None,
             logfile_pattern: None,
-            logfile_path: Some(PathBuf::from("test.log")),
+            logfile_path: Some(logfile.clone()),
             logfile_append: false,
             file_loggers: vec![String::from("toolslib::logs::tests")],
         })
@@ -219,5 +299,40 @@ mod tests {
         info!("info message");
         debug!("debug message");
         trace!("trace message");
+        // log4rs can only be initialized once so changing the level at runtime is checked here
+        assert_eq!(log_guard.level(), log::LevelFilter::Info);
+        log_guard.set_level(log::LevelFilter::Debug).unwrap();
+        assert_eq!(log_guard.level(), log::LevelFilter::Debug);
+        debug!("debug message after set level");
+        trace!("trace message after set level");
+        // dropping the guard flushes the log file
+        drop(log_guard);
+        let content = std::fs::read_to_string(&logfile);
+        std::fs::remove_file(&logfile).unwrap();
+        let content = content.unwrap();
+        assert!(content.contains("error message"));
+        assert!(content.contains("info message"));
+        assert_eq!(content.matches("debug message").count(), 1);
+        // the log file was appended to when the level changed
+        assert!(content.contains("debug message after set level"));
+        assert!(!content.contains("trace message after set level"));
+    }
+    #[test]
+    fn json_format() {
+        use log4rs::encode::writer::simple::SimpleWriter;
+        let record = log::Record::builder()
+            .level(log::Level::Warn)
+            .target("toolslib::logs::tests")
+            .args(format_args!("json log message"))
+            .build();
+        let mut writer = SimpleWriter(vec![]);
+        encoder(LogFormat::Json, DEFAULT_CONSOLE_PATTERN).encode(&mut writer, &record).unwrap();
+        let output = String::from_utf8(writer.0).unwrap();
+        assert_eq!(output.lines().count(), 1);
+        let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert!(json["time"].is_string());
+        assert_eq!(json["level"], "WARN");
+        assert_eq!(json["target"], "toolslib::logs::tests");
+        assert_eq!(json["message"], "json log message");
     }
 }
@@ -131,6 +131,8 @@ pub struct SheetCell {
     text: String,
     // The hook to override a reports cell definition
     layout: Option<CellLayout>,
+    // The number of report columns the cell occupies.
+    spans: usize,
 }
 impl SheetCell {
     /// Create a sheet cell with a cell type of [Header](CellType::Header).
@@ -140,7 +142,7 @@ impl SheetCell {
     /// - `text` is the cell text data.
     ///
     pub fn header(text: impl ToString) -> Self {
-        Self { cell_type: CellType::Header, text: text.to_string(), layout: None }
+        Self { cell_type: CellType::Header, text: text.to_string(), layout: None, spans: 1 }
     }
     /// Create a sheet cell with a cell type of [Separator](CellType::Separator).
     ///
@@ -149,7 +151,7 @@ impl SheetCell {
     /// - `text` is the cell separator text.
     ///
     pub fn separator(text: impl ToString) -> Self {
-        Self { cell_type: CellType::Separator, text: text.to_string(), layout: None }
+        Self { cell_type: CellType::Separator, text: text.to_string(), layout: None, spans: 1 }
     }
     /// Create a sheet cell with a cell type of [Text](CellType::Text).
     ///
@@ -158,7 +160,7 @@ impl SheetCell {
     /// - `text` is the cell text data.
     ///
     pub fn text(text: impl ToString) -> Self {
-        Self { cell_type: CellType::Text, text: text.to_string(), layout: None }
+        Self { cell_type: CellType::Text, text: text.to_string(), layout: None, spans: 1 }
     }
     /// Create a sheet cell with a cell type of [Plain](CellType::Plain).
     ///
@@ -167,7 +169,28 @@ impl SheetCell {
     /// - `text` is the cell text data.
     ///
     pub fn plain(text: impl ToString) -> Self {
-        Self { cell_type: CellType::Plain, text: text.to_string(), layout: Some(CellLayout::default()) }
+        Self { cell_type: CellType::Plain, text: text.to_string(), layout: Some(CellLayout::default()), spans: 1 }
+    }
+    /// Create a sheet cell with a cell type of [Header](CellType::Header) that is centered across
+    /// multiple report columns.
+    ///
+    /// # Arguments
+    ///
+    /// - `text` is the cell text data.
+    /// - `spans` is the number of report columns the header occupies.
+    ///
+    pub fn span(text: impl ToString, spans: usize) -> Self {
+        Self::header(text).with_layout(CellLayout::new(CellAlignment::Center)).with_spans(spans)
+    }
+    /// A builder method that sets the number of report columns the cell occupies.
+    ///
+    /// # Arguments
+    ///
+    /// - `spans` is the number of report columns, a value of `0` will be treated as `1`.
+    ///
+    pub fn with_spans(mut self, spans: usize) -> Self {
+        self.spans = cmp::max(spans, 1);
+        self
     }
     /// A builder method that adds a cell layout to the cell.
     ///
@@ -191,6 +214,10 @@ struct SheetLayout {
     layouts: Vec<CellLayout>,
     /// The default cell layout.
     default_layout: CellLayout,
+    /// The first column, number of columns, and text width of cells that span columns.
+    spanned: Vec<(usize, usize, usize)>,
+    /// The cell layouts for each column widened by the cells that span columns.
+    widened: Vec<CellLayout>,
 }
 impl SheetLayout {
     /// Get the layout of a specific column.
@@ -201,7 +228,37 @@ impl SheetLayout {
     /// layout will be used if the column index is out of bounds.
     ///
     pub fn get(&self, index: usize) -> &CellLayout {
-        self.layouts.get(index).unwrap_or_else(|| &self.default_layout)
+        self.widened.get(index).unwrap_or(&self.default_layout)
+    }
+    /// Update the widened column layouts after the column widths or spanned cells have changed.
+    ///
+    fn widen(&mut self) {
+        for index in 0..self.layouts.len() {
+            self.widened[index].width = self.layouts[index].width + self.spanned_width(index);
+        }
+    }
+    /// Get the additional width a column requires for the cells spanning into it.
+    ///
+    /// Spanned cells that are wider than the columns they cover widen the last column spanned.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` is the column whose additional width will be returned.
+    ///
+    fn spanned_width(&self, index: usize) -> usize {
+        match self.layouts.get(index) {
+            Some(layout) if !(layout.alignment.is_none() || layout.fixed) => self
+                .spanned
+                .iter()
+                .filter(|(first, spans, _)| first + spans - 1 == index)
+                .map(|(first, spans, width)| {
+                    let columns_width: usize = self.layouts[*first..=index].iter().map(|layout| layout.width).sum();
+                    width.saturating_sub(columns_width + spans - 1)
+                })
+                .max()
+                .unwrap_or(0),
+            _ => 0,
+        }
     }
     /// Get a mutable cell layout for a specific column.
     ///
@@ -212,6 +269,22 @@ impl SheetLayout {
     fn get_mut(&mut self, index: usize) -> Option<&mut CellLayout> {
         self.layouts.get_mut(index)
     }
+    /// Get the layout of columns that are spanned by a cell.
+    ///
+    /// The width of the layout includes the space between each of the columns.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` is the first column spanned.
+    /// - `spans` is the number of columns spanned.
+    ///
+    fn get_spanned(&self, index: usize, spans: usize) -> CellLayout {
+        let mut layout = self.get(index).clone();
+        if spans > 1 {
+            layout.width = (index..index + spans).map(|column| self.get(column).width).sum::<usize>() + spans - 1;
+        }
+        layout
+    }
 }
 
 /// The column descriptions and content that comprise a report.
@@ -231,7 +304,9 @@ impl ReportSheet {
     /// - `layouts` describe the report column formats.
     ///
     pub fn new(layouts: Vec<CellLayout>) -> Self {
-        Self { layout: SheetLayout { layouts, default_layout: CellLayout::default() }, rows: vec![] }
+        let widened = layouts.clone();
+        let layout = SheetLayout { layouts, default_layout: CellLayout::default(), spanned: vec![], widened };
+        Self { layout, rows: vec![] }
     }
     /// Add a row to the report.
     ///
@@ -240,11 +315,18 @@ impl ReportSheet {
     /// - `row` is the textual content.
     ///
     pub fn add_row(&mut self, row: Vec<SheetCell>) {
-        for (index, cell) in row.iter().enumerate() {
-            if let Some(layout) = self.layout.get_mut(index) {
+        let mut index = 0;
+        for cell in row.iter() {
+            if cell.spans > 1 {
+                if index + cell.spans <= self.layout.layouts.len() {
+                    self.layout.spanned.push((index, cell.spans, cell.text.chars().count()));
+                }
+            } else if let Some(layout) = self.layout.get_mut(index) {
                 adjust_width(layout, cell);
             }
+            index += cell.spans;
         }
+        self.layout.widen();
         self.rows.push(row);
     }
     /// Get the number of report columns.
@@ -255,7 +337,7 @@ impl ReportSheet {
     /// Get a collection of the cell layouts.
     ///
     pub fn layouts(&self) -> Vec<&CellLayout> {
-        self.layout.layouts.iter().collect()
+        self.layout.widened.iter().collect()
     }
 }
 impl<'report> IntoIterator for &'report ReportSheet {
@@ -319,17 +401,19 @@ impl SheetRow<'_> {
         match self.cells.get(index) {
             None => None,
             Some(cell) => {
+                // spanned cells shift the report column of the cells that follow
+                let column = self.cells[..index].iter().map(|cell| cell.spans).sum();
+                let sheet_layout = self.layout.get_spanned(column, cell.spans);
                 let layout = match &cell.layout {
                     // if the cell does not have a layout, use the sheets
-                    None => self.layout.get(index).clone(),
+                    None => sheet_layout,
                     Some(cell_layout) => {
-                        let sheet_layout = self.layout.get(index);
                         if sheet_layout.alignment.is_none() {
                             // use the cell layout regardless
                             cell_layout.clone()
                         } else {
                             // right now only pay attention to the cells alignment
-                            let mut merged_layout = sheet_layout.clone();
+                            let mut merged_layout = sheet_layout;
                             merged_layout.alignment = cell_layout.alignment;
                             merged_layout.fill = cell_layout.fill;
                             merged_layout
@@ -574,25 +658,46 @@ mod tests {
     #[test]
     fn cell() {
         let mut header = SheetCell::header("header");
-        assert_eq!(header, SheetCell { cell_type: CellType::Header, text: "header".to_string(), layout: None });
+        assert_eq!(
+            header,
+            SheetCell { cell_type: CellType::Header, text: "header".to_string(), layout: None, spans: 1 }
+        );
         header = header.with_layout(CellLayout::default());
         assert_eq!(
             header,
-            SheetCell { cell_type: CellType::Header, text: "header".to_string(), layout: Some(CellLayout::default()) }
+            SheetCell {
+                cell_type: CellType::Header,
+                text: "header".to_string(),
+                layout: Some(CellLayout::default()),
+                spans: 1
+            }
         );
         let mut separator = SheetCell::separator("-");
-        assert_eq!(separator, SheetCell { cell_type: CellType::Separator, text: "-".to_string(), layout: None });
+        assert_eq!(
+            separator,
+            SheetCell { cell_type: CellType::Separator, text: "-".to_string(), layout: None, spans: 1 }
+        );
         separator = separator.with_layout(CellLayout::default());
         assert_eq!(
             separator,
-            SheetCell { cell_type: CellType::Separator, text: "-".to_string(), layout: Some(CellLayout::default()) }
+            SheetCell {
+                cell_type: CellType::Separator,
+                text: "-".to_string(),
+                layout: Some(CellLayout::default()),
+                spans: 1
+            }
         );
         let mut text = SheetCell::text("text");
-        assert_eq!(text, SheetCell { cell_type: CellType::Text, text: "text".to_string(), layout: None });
+        assert_eq!(text, SheetCell { cell_type: CellType::Text, text: "text".to_string(), layout: None, spans: 1 });
         text = text.with_layout(CellLayout::default());
         assert_eq!(
             text,
-            SheetCell { cell_type: CellType::Text, text: "text".to_string(), layout: Some(CellLayout::default()) }
+            SheetCell {
+                cell_type: CellType::Text,
+                text: "text".to_string(),
+                layout: Some(CellLayout::default()),
+                spans: 1
+            }
         );
         let mut plain = SheetCell::plain("plain");
         assert_eq!(
@@ -600,13 +705,19 @@ mod tests {
             SheetCell {
                 cell_type: CellType::Plain,
                 text: "plain".to_string(),
-                layout: Some(CellLayout::new(CellAlignment::None))
+                layout: Some(CellLayout::new(CellAlignment::None)),
+                spans: 1,
             }
         );
         plain = plain.with_layout(CellLayout::new(CellAlignment::Left));
         assert_eq!(
             plain,
-            SheetCell { cell_type: CellType::Plain, text: "plain".to_string(), layout: Some(CellLayout::default()) }
+            SheetCell {
+                cell_type: CellType::Plain,
+                text: "plain".to_string(),
+                layout: Some(CellLayout::default()),
+                spans: 1
+            }
         );
     }
 
@@ -702,9 +813,12 @@ mod tests {
 
     #[test]
     fn row() {
+        let layouts = vec![layout!(< [1]), layout!(^ [5]), layout!(^ [8]), layout!(> [6])];
         let layout = SheetLayout {
-            layouts: vec![layout!(< [1]), layout!(^ [5]), layout!(^ [8]), layout!(> [6])],
+            layouts: layouts.clone(),
             default_layout: Default::default(),
+            spanned: vec![],
+            widened: layouts,
         };
         let cells = vec![
             plain!("override"),
@@ -746,4 +860,36 @@ mod tests {
         assert!(iter.next().is_some());
         assert!(iter.next().is_none())
     }
+
+    #[test]
+    fn span() {
+        let mut report = ReportSheet::new(vec![layout!(<), layout!(>), layout!(>), layout!(>)]);
+        report.add_row(vec![header!(""), SheetCell::span("Temperature", 3)]);
+        report.add_row(vec![header!("Date"), header!(> "High"), header!(> "Low"), header!(> "Mean")]);
+        report.add_row(vec![separator!(*"-"), separator!(*"-").with_spans(3)]);
+        report.add_row(vec![text!("2024-01-01"), text!("52.5"), text!("31.0"), text!("41.8")]);
+        // the header fits within the spanned columns
+        assert_eq!(report.layout.get_spanned(1, 3).width, 14);
+        let rows: Vec<String> = report.into_iter().map(|row| row.to_string()).collect();
+        assert_eq!(rows[0], format!("{:10} {:^14}", "", "Temperature").trim_end());
+        assert_eq!(rows[1], "Date       High  Low Mean");
+        assert_eq!(rows[2], "---------- --------------");
+        assert_eq!(rows[3], "2024-01-01 52.5 31.0 41.8");
+        // a wide header adjusts the last spanned column
+        let mut report = ReportSheet::new(vec![layout!(>), layout!(>), layout!(>)]);
+        report.add_row(vec![SheetCell::span("Precipitation", 3)]);
+        report.add_row(vec![text!("1"), text!("2"), text!("3")]);
+        let widths: Vec<usize> = report.layouts().iter().map(|layout| layout.width()).collect();
+        assert_eq!(widths, vec![1, 1, 9]);
+        let rows: Vec<String> = report.into_iter().map(|row| row.to_string()).collect();
+        assert_eq!(rows[0], "Precipitation");
+        assert_eq!(rows[1], "1 2         3");
+        assert_eq!(rows[0].len(), rows[1].len());
+        // the spanned width is the number of characters not bytes
+        let mut report = ReportSheet::new(vec![layout!(>), layout!(>)]);
+        report.add_row(vec![SheetCell::span("Température", 2)]);
+        report.add_row(vec![text!("1"), text!("2")]);
+        let widths: Vec<usize> = report.layouts().iter().map(|layout| layout.width()).collect();
+        assert_eq!(widths, vec![1, 9]);
+    }
 }
@@ -0,0 +1,168 @@
+//! # Retry an operation that can fail with exponential backoff.
+//!
+//! The delay between attempts doubles after each failure. A small random jitter is added to
+//! the delay so clients that failed together don't all retry at the same time.
+use std::{
+    fmt::Debug,
+    sync::atomic::{AtomicBool, Ordering},
+    thread::sleep,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// How often a pending retry checks if it has been canceled.
+const CANCEL_CHECK: Duration = Duration::from_millis(10);
+
+/// Call a function until it succeeds or the number of attempts have been exhausted.
+///
+/// The result of the last attempt is returned. The function is always called at least once.
+///
+/// # Arguments
+///
+/// * `attempts` is the maximum number of times the function will be called.
+/// * `base_delay` is how long to wait before the first retry.
+/// * `f` is called with the attempt number, starting at 1.
+pub fn retry<T, E, F>(attempts: usize, base_delay: Duration, f: F) -> Result<T, E>
+where
+    E: Debug,
+    F: FnMut(usize) -> Result<T, E>,
+{
+    retry_cancelable(attempts, base_delay, &AtomicBool::new(false), f)
+}
+
+/// Call a function until it succeeds, the number of attempts have been exhausted, or it is canceled.
+///
+/// The result of the last attempt is returned. The function is always called at least once and
+/// will not be called again once `cancel` has been set.
+///
+/// # Arguments
+///
+/// * `attempts` is the maximum number of times the function will be called.
+/// * `base_delay` is how long to wait before the first retry.
+/// * `cancel` stops further retries when it is set to `true`.
+/// * `f` is called with the attempt number, starting at 1.
+pub fn retry_cancelable<T, E, F>(attempts: usize, base_delay: Duration, cancel: &AtomicBool, mut f: F) -> Result<T, E>
+where
+    E: Debug,
+    F: FnMut(usize) -> Result<T, E>,
+{
+    let attempts = attempts.max(1);
+    let mut attempt = 1;
+    loop {
+        match f(attempt) {
+            Ok(value) => break Ok(value),
+            Err(err) if attempt == attempts => {
+                log::warn!("retry: giving up after {} attempts ({:?}).", attempts, err);
+                break Err(err);
+            }
+            Err(err) => {
+                let delay = backoff(base_delay, attempt);
+                log::info!("retry: attempt {} of {} failed, retrying in {:?} ({:?}).", attempt, attempts, delay, err);
+                if !wait(delay, cancel) {
+                    log::info!("retry: canceled after {} attempts.", attempt);
+                    break Err(err);
+                }
+            }
+        }
+        attempt += 1;
+    }
+}
+
+/// Calculate the delay before the next attempt.
+///
+/// The base delay is doubled for each failed attempt then up to half of that is added as jitter.
+///
+/// # Arguments
+///
+/// * `base_delay` is the delay before the first retry.
+/// * `attempt` is the attempt that failed, starting at 1.
+fn backoff(base_delay: Duration, attempt: usize) -> Duration {
+    let shift = (attempt - 1).min(16) as u32;
+    let delay = base_delay.saturating_mul(1 << shift);
+    let jitter_range = delay.as_nanos() / 2;
+    if jitter_range == 0 {
+        delay
+    } else {
+        // the system clock nanos are random enough to spread out retries
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.subsec_nanos()) as u128;
+        delay + Duration::from_nanos((nanos % jitter_range) as u64)
+    }
+}
+
+/// Wait for the delay to pass returning `false` if the wait was canceled.
+///
+/// # Arguments
+///
+/// * `delay` is how long to wait.
+/// * `cancel` stops the wait when it is set to `true`.
+fn wait(delay: Duration, cancel: &AtomicBool) -> bool {
+    let started = Instant::now();
+    loop {
+        if cancel.load(Ordering::Relaxed) {
+            break false;
+        }
+        let elapsed = started.elapsed();
+        if elapsed >= delay {
+            break true;
+        }
+        sleep((delay - elapsed).min(CANCEL_CHECK));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn succeeds() {
+        let mut calls = vec![];
+        let testcase = retry(5, Duration::from_millis(1), |attempt| {
+            calls.push(attempt);
+            match attempt {
+                1 | 2 => Err(format!("attempt {} failed", attempt)),
+                _ => Ok(attempt),
+            }
+        });
+        assert_eq!(testcase, Ok(3));
+        assert_eq!(calls, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn exhausted() {
+        let mut calls = 0;
+        let testcase: Result<(), String> = retry(3, Duration::from_millis(1), |attempt| {
+            calls += 1;
+            Err(format!("attempt {} failed", attempt))
+        });
+        assert_eq!(testcase, Err("attempt 3 failed".to_string()));
+        assert_eq!(calls, 3);
+        // the function is always called
+        let testcase: Result<(), usize> = retry(0, Duration::ZERO, Err);
+        assert_eq!(testcase, Err(1));
+    }
+
+    #[test]
+    fn canceled() {
+        let cancel = AtomicBool::new(false);
+        let mut calls = 0;
+        let testcase: Result<(), usize> = retry_cancelable(5, Duration::from_millis(1), &cancel, |attempt| {
+            calls += 1;
+            if attempt == 2 {
+                cancel.store(true, Ordering::Relaxed);
+            }
+            Err(attempt)
+        });
+        assert_eq!(testcase, Err(2));
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn backoff_delay() {
+        let base_delay = Duration::from_millis(100);
+        for (attempt, delay) in [(1, 100), (2, 200), (3, 400)] {
+            let testcase = backoff(base_delay, attempt);
+            assert!(testcase >= Duration::from_millis(delay));
+            assert!(testcase < Duration::from_millis(delay + delay / 2));
+        }
+        assert_eq!(backoff(Duration::ZERO, 3), Duration::ZERO);
+    }
+}
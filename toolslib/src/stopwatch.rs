@@ -6,6 +6,21 @@ use std::fmt;
 use std::fmt::Formatter;
 use std::time::{Duration, Instant};
 
+/// The elapsed time where [DisplayFormat::Auto] switches from milliseconds to `H:MM:SS.mmm`.
+pub const AUTO_HMS_THRESHOLD: Duration = Duration::from_secs(60);
+
+/// How the stopwatch elapsed time is displayed.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum DisplayFormat {
+    /// The elapsed time is displayed in milliseconds.
+    #[default]
+    Millis,
+    /// The elapsed time is displayed as `H:MM:SS.mmm`.
+    Hms,
+    /// The elapsed time is displayed as `H:MM:SS.mmm` once it reaches the [AUTO_HMS_THRESHOLD].
+    Auto,
+}
+
 /// The stopwatch data.
 #[derive(Debug)]
 pub struct StopWatch {
@@ -13,6 +28,10 @@ pub struct StopWatch {
     start: Option<Instant>,
     /// How long the stopwatch was run or `None`
     duration: Option<Duration>,
+    /// The recorded elapsed times of repeated measurements.
+    samples: Vec<Duration>,
+    /// How the elapsed time is displayed.
+    display_format: DisplayFormat,
 }
 
 /// How the stopwatch should be displayed.
@@ -20,16 +39,40 @@ impl fmt::Display for StopWatch {
     /// The default is to display the stop watch in milliseconds.
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         use thousands::Separable;
-        write!(f, "{}ms", self.millis().separate_with_commas())
+        let hms = match self.display_format {
+            DisplayFormat::Millis => false,
+            DisplayFormat::Hms => true,
+            DisplayFormat::Auto => self.elapsed() >= AUTO_HMS_THRESHOLD,
+        };
+        match hms {
+            true => write!(f, "{}", self.as_hms_string()),
+            false => write!(f, "{}ms", self.millis().separate_with_commas()),
+        }
     }
 }
 
+/// Formats a duration as `H:MM:SS.mmm`.
+///
+/// # Arguments
+///
+/// * `duration` is what will be formatted.
+pub fn fmt_hms(duration: Duration) -> String {
+    let overall_millis = duration.as_millis();
+    let millis = overall_millis % 1000;
+    let seconds = (overall_millis / 1000) % 60;
+    let minutes = (overall_millis / 1000 / 60) % 60;
+    let hours = overall_millis / 1000 / 60 / 60;
+    format!("{}:{:0>2}:{:0>2}.{:0>3}", hours, minutes, seconds, millis)
+}
+
 impl StopWatch {
     /// Returns a new instance of the stopwatch.
     pub fn new() -> StopWatch {
         StopWatch {
             start: None,
             duration: None,
+            samples: vec![],
+            display_format: DisplayFormat::default(),
         }
     }
     /// Returns a new instance of the stopwatch that has been started.
@@ -37,8 +80,19 @@ impl StopWatch {
         StopWatch {
             start: Some(Instant::now()),
             duration: None,
+            samples: vec![],
+            display_format: DisplayFormat::default(),
         }
     }
+    /// Sets how the elapsed time will be displayed.
+    ///
+    /// # Arguments
+    ///
+    /// * `display_format` controls the elapsed time display.
+    pub fn display_format(&mut self, display_format: DisplayFormat) -> &mut Self {
+        self.display_format = display_format;
+        self
+    }
     /// Starts or re-starts the stopwatch.
     pub fn start(&mut self) {
         self.start = Some(Instant::now());
@@ -60,15 +114,45 @@ impl StopWatch {
     pub fn reset(&mut self) -> &mut Self {
         self.start = None;
         self.duration = None;
+        self.samples.clear();
+        self
+    }
+    /// Adds the current elapsed time as a sample then resets the elapsed time.
+    ///
+    /// If the stopwatch is running it will be re-started for the next measurement.
+    pub fn record(&mut self) -> &mut Self {
+        self.samples.push(self.elapsed());
+        match self.start {
+            Some(_) => self.start = Some(Instant::now()),
+            None => self.duration = None,
+        }
         self
     }
+    /// Returns the recorded samples.
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+    /// Returns the average of the recorded samples or `None` if nothing has been recorded.
+    pub fn mean(&self) -> Option<Duration> {
+        match self.samples.len() {
+            0 => None,
+            count => Some(self.samples.iter().sum::<Duration>() / count as u32),
+        }
+    }
+    /// Returns the shortest recorded sample or `None` if nothing has been recorded.
+    pub fn min(&self) -> Option<Duration> {
+        self.samples.iter().min().copied()
+    }
+    /// Returns the longest recorded sample or `None` if nothing has been recorded.
+    pub fn max(&self) -> Option<Duration> {
+        self.samples.iter().max().copied()
+    }
     pub fn time_str(&self) -> String {
-        let overall_millis = self.millis();
-        let millis = overall_millis % 1000;
-        let seconds = (overall_millis / 1000) % 60;
-        let minutes = (overall_millis / 1000 / 60) % 60;
-        let hours = overall_millis / 1000 / 60 / 60;
-        format!("{}:{:0>2}:{:0>2}.{:0>3}", hours, minutes, seconds, millis)
+        self.as_hms_string()
+    }
+    /// Returns the elapsed time as `H:MM:SS.mmm`.
+    pub fn as_hms_string(&self) -> String {
+        fmt_hms(self.elapsed())
     }
     /// Returns the duration recorded in the stopwatch.
     pub fn elapsed(&self) -> Duration {
@@ -80,6 +164,12 @@ impl StopWatch {
             Duration::from_secs(0)
         }
     }
+    /// Returns the elapsed time at this point without stopping the stopwatch or recording a sample.
+    ///
+    /// If the stopwatch has been stopped the split will be the stopped duration.
+    pub fn split(&self) -> Duration {
+        self.elapsed()
+    }
     /// Returns true if the stopwatch has been started.
     pub fn is_running(&self) -> bool {
         return self.start.is_some();
@@ -88,4 +178,165 @@ impl StopWatch {
     pub fn millis(&self) -> i64 {
         return self.elapsed().as_millis() as i64;
     }
+    /// Captures the current elapsed time as a [Measurement].
+    ///
+    /// # Arguments
+    ///
+    /// * `label` identifies what was timed.
+    pub fn measure(&self, label: &str) -> Measurement {
+        Measurement::new(label, self.elapsed())
+    }
+}
+
+/// A finalized elapsed time.
+///
+/// The elapsed time of a running stopwatch keeps changing so measurements are used to compare
+/// and sort timed results. Measurements are ordered by their elapsed time then by their label.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Measurement {
+    /// How long the operation took.
+    elapsed: Duration,
+    /// What was timed.
+    label: String,
+}
+
+/// The default is to display the label and elapsed time in milliseconds.
+impl fmt::Display for Measurement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        use thousands::Separable;
+        write!(f, "{} {}ms", self.label, (self.elapsed.as_millis() as i64).separate_with_commas())
+    }
+}
+
+impl Measurement {
+    /// Returns a new instance of the measurement.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` identifies what was timed.
+    /// * `elapsed` is how long the operation took.
+    pub fn new(label: &str, elapsed: Duration) -> Measurement {
+        Measurement {
+            elapsed,
+            label: label.to_string(),
+        }
+    }
+    /// Returns what was timed.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+    /// Returns how long the operation took.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::sleep;
+
+    #[test]
+    fn record() {
+        let mut testcase = StopWatch::new();
+        assert!(testcase.mean().is_none());
+        assert!(testcase.min().is_none());
+        assert!(testcase.max().is_none());
+        testcase.start();
+        for millis in [5, 15, 10] {
+            sleep(Duration::from_millis(millis));
+            testcase.record();
+        }
+        assert!(testcase.is_running());
+        assert_eq!(testcase.samples().len(), 3);
+        let (mean, min, max) = (testcase.mean().unwrap(), testcase.min().unwrap(), testcase.max().unwrap());
+        assert!(min >= Duration::from_millis(5));
+        assert!(max >= Duration::from_millis(15));
+        assert!(min <= mean && mean <= max);
+        testcase.reset();
+        assert!(testcase.samples().is_empty());
+    }
+
+    #[test]
+    fn record_stopped() {
+        let mut testcase = StopWatch::start_new();
+        sleep(Duration::from_millis(5));
+        testcase.stop();
+        testcase.record();
+        assert!(!testcase.is_running());
+        assert_eq!(testcase.elapsed(), Duration::from_secs(0));
+        assert_eq!(testcase.samples().len(), 1);
+        assert_eq!(testcase.mean(), testcase.min());
+        assert_eq!(testcase.mean(), testcase.max());
+    }
+
+    #[test]
+    fn split() {
+        let mut testcase = StopWatch::start_new();
+        let first = testcase.split();
+        sleep(Duration::from_millis(5));
+        let second = testcase.split();
+        assert!(testcase.is_running());
+        assert!(second > first);
+        assert!(second - first >= Duration::from_millis(5));
+        assert!(testcase.samples().is_empty());
+        testcase.stop();
+        let stopped = testcase.split();
+        assert!(stopped >= second);
+        sleep(Duration::from_millis(1));
+        assert_eq!(testcase.split(), stopped);
+        assert_eq!(StopWatch::new().split(), Duration::from_secs(0));
+    }
+
+    #[test]
+    fn hms() {
+        let stopped = |millis: u64| StopWatch {
+            start: None,
+            duration: Some(Duration::from_millis(millis)),
+            samples: vec![],
+            display_format: DisplayFormat::default(),
+        };
+        assert_eq!(fmt_hms(Duration::from_secs_f64(3661.5)), "1:01:01.500");
+        assert_eq!(fmt_hms(Duration::from_secs(0)), "0:00:00.000");
+        assert_eq!(fmt_hms(Duration::from_secs(36_000)), "10:00:00.000");
+        let mut testcase = stopped(3_661_500);
+        assert_eq!(testcase.as_hms_string(), "1:01:01.500");
+        assert_eq!(testcase.time_str(), "1:01:01.500");
+        assert_eq!(testcase.to_string(), "3,661,500ms");
+        testcase.display_format(DisplayFormat::Hms);
+        assert_eq!(testcase.to_string(), "1:01:01.500");
+        testcase.display_format(DisplayFormat::Auto);
+        assert_eq!(testcase.to_string(), "1:01:01.500");
+        // short durations are still displayed in milliseconds
+        let mut testcase = stopped(59_999);
+        testcase.display_format(DisplayFormat::Auto);
+        assert_eq!(testcase.to_string(), "59,999ms");
+        let mut testcase = stopped(60_000);
+        testcase.display_format(DisplayFormat::Auto);
+        assert_eq!(testcase.to_string(), "0:01:00.000");
+    }
+
+    #[test]
+    fn measurements() {
+        let mut testcase = [
+            Measurement::new("medium", Duration::from_millis(20)),
+            Measurement::new("slow", Duration::from_millis(1_500)),
+            Measurement::new("fast", Duration::from_millis(5)),
+            Measurement::new("another", Duration::from_millis(20)),
+        ];
+        testcase.sort();
+        let labels: Vec<&str> = testcase.iter().map(|measurement| measurement.label()).collect();
+        assert_eq!(labels, vec!["fast", "another", "medium", "slow"]);
+        assert_eq!(testcase.iter().max().unwrap().label(), "slow");
+        assert_eq!(testcase[3].to_string(), "slow 1,500ms");
+        // measurements do not change once they are taken
+        let mut stopwatch = StopWatch::start_new();
+        let first = stopwatch.measure("first");
+        sleep(Duration::from_millis(5));
+        stopwatch.stop();
+        let second = stopwatch.measure("second");
+        assert!(first < second);
+        assert!(second.elapsed() >= Duration::from_millis(5));
+        assert_eq!(second.elapsed(), stopwatch.measure("again").elapsed());
+    }
 }
@@ -9,7 +9,15 @@
 //! the output will be in order.
 
 // use std::{fmt::{self, Alignment}, fs, io, iter::Iterator, path::PathBuf, result};
-use std::{fmt, fs, io, iter::Iterator, path::PathBuf, result, string::ToString};
+use std::{
+    fmt, fs,
+    io::{self, IsTerminal},
+    iter::Iterator,
+    path::PathBuf,
+    result,
+    string::ToString,
+};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// The text module result.
 type Result<T> = result::Result<T, Error>;
@@ -73,6 +81,27 @@ pub fn get_writer(file_option: &Option<PathBuf>, append: bool) -> Result<Box<dyn
     }
 }
 
+/// Identifies if text styling can be used for report output.
+///
+/// Styling is only used when the output is `stdout` and it is a terminal.
+///
+/// # Arguments
+///
+/// * `file_option` - if `None` then output is going to `stdout` otherwise it is going to a file.
+/// * `is_terminal` - indicates `stdout` is a terminal.
+fn use_styling(file_option: &Option<PathBuf>, is_terminal: bool) -> bool {
+    file_option.is_none() && is_terminal
+}
+
+/// The `ANSI` escape sequence that dims text.
+const DIM_STYLE: &str = "\x1b[2m";
+
+/// The `ANSI` escape sequence that resets text styling.
+const RESET_STYLE: &str = "\x1b[0m";
+
+/// The number of spaces between report columns unless the report changes it.
+const DEFAULT_GUTTER: usize = 1;
+
 /// Writes a collection of strings.
 ///
 /// # Arguments
@@ -109,6 +138,8 @@ pub struct ReportColumn {
     width: usize,
     /// When `true` the width of the column is fixed in length.
     fixed_width: bool,
+    /// When provided, text wider than this is truncated with a trailing ellipsis.
+    max_width: Option<usize>,
 }
 impl ReportColumn {
     /// Creates a new instance of the report column.
@@ -119,7 +150,17 @@ impl ReportColumn {
     /// * `width` is the initial width of the report column.
     /// * `fixed_width` indicates whether or not the report column is fixed width.
     pub fn new(alignment: Alignment, width: usize, fixed_width: bool) -> Self {
-        Self { alignment, width, fixed_width }
+        Self { alignment, width, fixed_width, max_width: None }
+    }
+    /// Limit the width of an auto-sizing column, text that is wider will be truncated with a trailing `…`.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_width` is the maximum display width of the report column.
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        self.width = std::cmp::min(self.width, max_width);
+        self.max_width = Some(max_width);
+        self
     }
     /// Identifies if column text should be added to the report as is.
     pub fn ignore_alignment(&self) -> bool {
@@ -161,51 +202,111 @@ impl ReportData {
     ///
     /// * `report_column` is the associated report column definition.
     pub fn fmt(&self, report_column: &ReportColumn) -> String {
+        let data_width = self.data.width();
         let width = if self.as_is {
-            // irregardless the column format, use the data length
-            self.data.len()
+            // irregardless the column format, use the data width
+            data_width
         } else if report_column.ignore_alignment() {
-            // use the data length if the column is configured as is
-            self.data.len()
+            // use the data width if the column is configured as is
+            data_width
         } else {
             report_column.width
         };
         let alignment = self.alignment.as_ref().unwrap_or_else(|| &report_column.alignment);
-        let data_len = self.data.len();
-        let data = if data_len <= width {
-            &self.data[..]
+        if !self.as_is && report_column.max_width.is_some() && data_width > width {
+            return align_ellipsis(&self.data, width, alignment);
+        }
+        let data = if data_width <= width {
+            self.data.clone()
         } else {
             match alignment {
                 Alignment::Left => {
                     // truncate the rhs
-                    &self.data[0..width]
+                    take_width(self.data.chars(), width)
                 }
                 Alignment::Center | &Alignment::Span => {
                     // truncate the lhs and rhs
-                    let offset = (data_len - width) / 2;
-                    &self.data[offset..offset + width]
+                    let offset = (data_width - width) / 2;
+                    let mut skipped = 0;
+                    let chars = self.data.chars().skip_while(|ch| {
+                        skipped += ch.width().unwrap_or(0);
+                        skipped <= offset
+                    });
+                    take_width(chars, width)
                 }
                 Alignment::Right => {
                     // truncate the lhs
-                    let offset = data_len - width;
-                    &self.data[offset..]
+                    take_width(self.data.chars().rev(), width).chars().rev().collect()
                 }
             }
         };
         match alignment {
-            Alignment::Left => format!("{data:<width$}"),
-            Alignment::Center => format!("{data:^width$}"),
-            Alignment::Right => format!("{data:>width$}"),
             Alignment::Span => {
-                if data_len == width {
-                    data.to_string()
+                let data_width = data.width();
+                if data_width == width || data_width == 0 {
+                    data
                 } else {
-                    let repeat = (width / data_len) + 1;
-                    data.repeat(repeat)[0..width].to_string()
+                    take_width(data.chars().cycle(), width)
                 }
             }
+            alignment => align_width(&data, width, alignment),
+        }
+    }
+}
+
+/// Get the leading characters that fit within a display width.
+///
+/// # Arguments
+///
+/// * `chars` are the characters that will be taken.
+/// * `width` is the maximum display width of the text.
+fn take_width(chars: impl Iterator<Item = char>, width: usize) -> String {
+    let mut text = String::new();
+    let mut text_width = 0;
+    for ch in chars {
+        let ch_width = ch.width().unwrap_or(0);
+        if text_width + ch_width > width {
+            break;
         }
+        text.push(ch);
+        text_width += ch_width;
+    }
+    text
+}
+
+/// Pad text to a display width using the alignment.
+///
+/// # Arguments
+///
+/// * `text` is what will be padded, it must not be wider than `width`.
+/// * `width` is the display width of the column.
+/// * `alignment` is the alignment of the text within the column.
+fn align_width(text: &str, width: usize, alignment: &Alignment) -> String {
+    let padding = width.saturating_sub(text.width());
+    match alignment {
+        Alignment::Left | Alignment::Span => format!("{text}{}", " ".repeat(padding)),
+        Alignment::Center => format!("{}{text}{}", " ".repeat(padding / 2), " ".repeat(padding - padding / 2)),
+        Alignment::Right => format!("{}{text}", " ".repeat(padding)),
+    }
+}
+
+/// Truncate text to a display width with a trailing `…` and align it within the width.
+///
+/// The display width of text is used so wide characters will not overflow the column.
+///
+/// # Arguments
+///
+/// * `text` is what will be truncated.
+/// * `width` is the display width of the column.
+/// * `alignment` is the alignment of the truncated text within the column.
+fn align_ellipsis(text: &str, width: usize, alignment: &Alignment) -> String {
+    const ELLIPSIS: char = '…';
+    let mut truncated = String::new();
+    if width > 0 {
+        truncated = take_width(text.chars(), width - 1);
+        truncated.push(ELLIPSIS);
     }
+    align_width(&truncated, width, alignment)
 }
 
 /// The type of row that has been added to a [`Report`].
@@ -216,6 +317,8 @@ pub enum ReportRow {
     Header(Vec<ReportData>),
     /// This variant is a separator and holds the data used to generate the rows text.
     Separator(String),
+    /// This variant is a separator that holds the character used to fill each column.
+    ColumnSeparators(Vec<char>),
     /// This variant is content and holds the collection of [`ReportData`] used to generate the rows text.
     Text(Vec<ReportData>),
 }
@@ -226,16 +329,20 @@ impl ReportRow {
     ///
     /// * [`Header`](ReportRow::Header) delegates row creation to the [`format_header`] function.
     /// * [`Separator`](ReportRow::Separator) delegates row creation to the [`format_separator`] function.
+    /// * [`ColumnSeparators`](ReportRow::ColumnSeparators) delegates row creation to the
+    ///   [`format_column_separators`] function.
     /// * [`Text`](ReportRow::Text) delegates row creation to the [`format_text`] function.
     ///
     /// # Arguments
     ///
     /// * `report_columns` contains the report column descriptions.
-    fn generate(&self, report_columns: &Vec<ReportColumn>) -> String {
+    /// * `gutter` is the number of spaces between columns.
+    fn generate(&self, report_columns: &Vec<ReportColumn>, gutter: usize) -> String {
         match self {
-            ReportRow::Header(headers) => format_header(report_columns, headers),
-            ReportRow::Separator(separator) => format_separator(report_columns, separator),
-            ReportRow::Text(columns) => format_text(report_columns, columns),
+            ReportRow::Header(headers) => format_header(report_columns, headers, gutter),
+            ReportRow::Separator(separator) => format_separator(report_columns, separator, gutter),
+            ReportRow::ColumnSeparators(separators) => format_column_separators(report_columns, separators, gutter),
+            ReportRow::Text(columns) => format_text(report_columns, columns, gutter),
         }
     }
 }
@@ -247,13 +354,58 @@ pub struct Report {
     report_columns: Vec<ReportColumn>,
     /// The collection of report rows.
     report_rows: Vec<ReportRow>,
+    /// When `true` every other text row will be dimmed.
+    shaded: bool,
+    /// The number of spaces between columns.
+    gutter: usize,
 }
 impl From<Vec<ReportColumn>> for Report {
     fn from(rc: Vec<ReportColumn>) -> Self {
-        Self { report_columns: rc, report_rows: vec![] }
+        Self { report_columns: rc, report_rows: vec![], shaded: false, gutter: DEFAULT_GUTTER }
     }
 }
 impl Report {
+    /// Creates a report from rows of text, the first record is the header row.
+    ///
+    /// The column count is inferred from the widest record and columns are left aligned. Use
+    /// the [`ReportColumn`] and [`ReportData`] builders when more control over the report is needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `records` contains the header row followed by the rows content.
+    pub fn from_records(records: Vec<Vec<String>>) -> Self {
+        let columns = records.iter().map(|record| record.len()).max().unwrap_or(0);
+        let report_columns = (0..columns).map(|_| ReportColumn::new(Alignment::Left, 0, false)).collect::<Vec<_>>();
+        let mut report = Report::from(report_columns);
+        let mut records = records.into_iter();
+        if let Some(header) = records.next() {
+            report.header(header.into_iter().map(|data| ReportData::new(data, None)).collect());
+        }
+        for record in records {
+            report.text(record.into_iter().map(|data| ReportData::new(data, None)).collect());
+        }
+        report
+    }
+    /// Dims every other text row to make wide reports easier to read.
+    ///
+    /// Shading is only used when the report is written to a terminal, it is ignored for file output.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_option` - the report output file, if `None` the report is being written to `stdout`.
+    pub fn shade_rows(&mut self, file_option: &Option<PathBuf>) -> &mut Self {
+        self.shaded = use_styling(file_option, io::stdout().is_terminal());
+        self
+    }
+    /// Sets the number of spaces between columns, by default columns are separated by a single space.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` is the number of spaces that will separate header, separator, and text columns.
+    pub fn gutter(&mut self, width: usize) -> &mut Self {
+        self.gutter = width;
+        self
+    }
     /// Adds a header row to the report.
     ///
     /// # Arguments
@@ -273,6 +425,16 @@ impl Report {
         self.report_rows.push(ReportRow::Separator(separator.to_string()));
         self
     }
+    /// Adds a separator row to the report that uses a different character for each column.
+    ///
+    /// # Arguments
+    ///
+    /// * `separators` are the characters used to fill each column, the last character is used for any
+    ///   remaining columns.
+    pub fn column_separators(&mut self, separators: &[char]) -> &mut Self {
+        self.report_rows.push(ReportRow::ColumnSeparators(separators.to_vec()));
+        self
+    }
     /// Adds a text row to the report.
     ///
     /// # Arguments
@@ -283,21 +445,43 @@ impl Report {
         self.report_rows.push(ReportRow::Text(row));
         self
     }
+    /// Get the width of the report rows, the sum of the column widths plus the gutters between them.
+    ///
+    /// Column widths are adjusted as rows are added so the width reflects the rows added so far. Row
+    /// content beyond the report columns is added as is and not included in the width.
+    pub fn rendered_width(&self) -> usize {
+        let columns_width: usize = self.report_columns.iter().map(|report_column| report_column.width).sum();
+        let gutters = self.report_columns.len().saturating_sub(1);
+        columns_width + gutters * self.gutter
+    }
     /// An internal function that adjusts the width of each report column description.
     ///
-    /// A columns width will not be adjusted if:
+    /// # Arguments
     ///
-    /// * the report data has been set [as is](ReportData::as_is).
-    /// * the column description has been set to [fixed width](ReportColumn::fixed_width).
+    /// * `report_data` is the row content the report columns will be sized to.
     fn adjust_column_widths(&mut self, report_data: &Vec<ReportData>) {
-        for i in 0..std::cmp::min(self.report_columns.len(), report_data.len()) {
-            let data = &report_data[i];
-            if !data.as_is {
-                let column_format = self.report_columns.get_mut(i).unwrap();
-                if !column_format.fixed_width {
-                    column_format.width = std::cmp::max(column_format.width, data.data.len());
-                }
-            }
+        adjust_column_widths(&mut self.report_columns, report_data);
+    }
+}
+
+/// Adjusts the width of each report column description to fit the row content.
+///
+/// A columns width will not be adjusted if:
+///
+/// * the report data has been set [as is](ReportData::as_is).
+/// * the column description has been set to [fixed width](ReportColumn::fixed_width).
+///
+/// # Arguments
+///
+/// * `report_columns` are the report column descriptions that will be adjusted.
+/// * `report_data` is the row content the report columns will be sized to.
+fn adjust_column_widths(report_columns: &mut [ReportColumn], report_data: &[ReportData]) {
+    for (column_format, data) in report_columns.iter_mut().zip(report_data.iter()) {
+        if !data.as_is && !column_format.fixed_width {
+            column_format.width = match column_format.max_width {
+                Some(max_width) => std::cmp::min(max_width, std::cmp::max(column_format.width, data.data.width())),
+                None => std::cmp::max(column_format.width, data.data.width()),
+            };
         }
     }
 }
@@ -310,7 +494,7 @@ impl<'r> IntoIterator for &'r Report {
     type Item = String;
     /// Creates the report builder iterator.
     fn into_iter(self) -> Self::IntoIter {
-        ReportIterator { report: self, row_index: 0 }
+        ReportIterator { report: self, row_index: 0, text_rows: 0 }
     }
 }
 
@@ -320,6 +504,8 @@ pub struct ReportIterator<'r> {
     report: &'r Report,
     /// The report row returned when `next` is called.
     row_index: usize,
+    /// The count of text rows that have been returned.
+    text_rows: usize,
 }
 
 /// The report row iterator used to return the rows of a report.
@@ -330,13 +516,116 @@ impl<'r> Iterator for ReportIterator<'r> {
         match self.report.report_rows.get(self.row_index) {
             Some(row) => {
                 self.row_index += 1;
-                Some(row.generate(&self.report.report_columns).trim_end().to_string())
+                let row_text = row.generate(&self.report.report_columns, self.report.gutter).trim_end().to_string();
+                match row {
+                    ReportRow::Text(_) => {
+                        self.text_rows += 1;
+                        match self.report.shaded && self.text_rows.is_multiple_of(2) {
+                            true => Some(format!("{DIM_STYLE}{row_text}{RESET_STYLE}")),
+                            false => Some(row_text),
+                        }
+                    }
+                    _ => Some(row_text),
+                }
             }
             None => None,
         }
     }
 }
 
+/// A report that writes rows as they are added instead of holding them until the report is complete.
+///
+/// A [`Report`] sizes columns to the widest content so every row has to be kept in memory. The
+/// streaming report cannot look ahead so column widths come from the report column descriptions
+/// or, for columns that are not fixed width, the first header row added to the report. Once a row
+/// has been written the widths no longer change and text wider than a column is truncated.
+///
+/// Rows are formatted the same as a [`Report`] so the output will match a buffered report when the
+/// declared widths match its content.
+pub struct StreamingReport<W: io::Write> {
+    /// The collection of report row column descriptions.
+    report_columns: Vec<ReportColumn>,
+    /// Where the report rows are written.
+    writer: W,
+    /// Becomes `true` when the first row has been written and column widths can no longer change.
+    widths_final: bool,
+    /// The number of spaces between columns.
+    gutter: usize,
+}
+impl<W: io::Write> StreamingReport<W> {
+    /// Creates a report that writes its rows to the writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `report_columns` are the report column descriptions.
+    /// * `writer` is where the report rows will be written.
+    pub fn new(report_columns: Vec<ReportColumn>, writer: W) -> Self {
+        Self { report_columns, writer, widths_final: false, gutter: DEFAULT_GUTTER }
+    }
+    /// Set the number of spaces between columns. The gutter should be set before the first row is written.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` is the number of spaces that will separate columns.
+    pub fn gutter(&mut self, width: usize) -> &mut Self {
+        self.gutter = width;
+        self
+    }
+    /// Writes a header row to the report.
+    ///
+    /// If this is the first row of the report, columns that are not fixed width will be sized to fit the header.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` is the collection report data that comprise the header row.
+    pub fn header(&mut self, row: Vec<ReportData>) -> Result<&mut Self> {
+        if !self.widths_final {
+            adjust_column_widths(&mut self.report_columns, &row);
+        }
+        self.write_row(ReportRow::Header(row))
+    }
+    /// Writes a separator row to the report.
+    ///
+    /// # Arguments
+    ///
+    /// * `separator` is the string used to create the separator row.
+    pub fn separator(&mut self, separator: &str) -> Result<&mut Self> {
+        self.write_row(ReportRow::Separator(separator.to_string()))
+    }
+    /// Writes a separator row to the report that uses a different character for each column.
+    ///
+    /// # Arguments
+    ///
+    /// * `separators` are the characters used to fill each column, the last character is used for any
+    ///   remaining columns.
+    pub fn column_separators(&mut self, separators: &[char]) -> Result<&mut Self> {
+        self.write_row(ReportRow::ColumnSeparators(separators.to_vec()))
+    }
+    /// Writes a text row to the report.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` is the collection report data that comprise the rows content.
+    pub fn text(&mut self, row: Vec<ReportData>) -> Result<&mut Self> {
+        self.write_row(ReportRow::Text(row))
+    }
+    /// Flushes the report and returns the writer.
+    pub fn into_inner(mut self) -> Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+    /// An internal function that formats a report row and writes it.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` is the report row that will be written.
+    fn write_row(&mut self, row: ReportRow) -> Result<&mut Self> {
+        self.widths_final = true;
+        writeln!(self.writer, "{}", row.generate(&self.report_columns, self.gutter).trim_end())?;
+        Ok(self)
+    }
+}
+
 /// Creates a line of header text using the collection of [`ReportColumn`] and collection of [`ReportData`].
 ///
 /// See [`format_text`] for details about how the header text will be formatted.
@@ -345,8 +634,9 @@ impl<'r> Iterator for ReportIterator<'r> {
 ///
 /// * `cols` is the collection of column definitions describing the report header row.
 /// * `headers` is the collection of header text data used to populate the report row.
-fn format_header(cols: &Vec<ReportColumn>, headers: &Vec<ReportData>) -> String {
-    format_text(cols, headers)
+/// * `gutter` is the number of spaces between columns.
+fn format_header(cols: &Vec<ReportColumn>, headers: &Vec<ReportData>, gutter: usize) -> String {
+    format_text(cols, headers, gutter)
 }
 
 /// Create a line of text with each report column containing the separator.
@@ -359,12 +649,14 @@ fn format_header(cols: &Vec<ReportColumn>, headers: &Vec<ReportData>) -> String
 ///
 /// * `cols` is the collection of report column definitions.
 /// * `separator` is the separator string that will fill each of the report columns.
-fn format_separator(cols: &Vec<ReportColumn>, separator: &str) -> String {
+/// * `gutter` is the number of spaces between columns.
+fn format_separator(cols: &Vec<ReportColumn>, separator: &str, gutter: usize) -> String {
     let mut row_text = String::from("");
     let separator_len = separator.len();
+    let gutter = " ".repeat(gutter);
     cols.iter().for_each(|report_column| {
         if !row_text.is_empty() {
-            row_text.push(' ');
+            row_text.push_str(&gutter);
         }
         if report_column.width == 0 {
             ();
@@ -383,6 +675,28 @@ fn format_separator(cols: &Vec<ReportColumn>, separator: &str) -> String {
     row_text
 }
 
+/// Create a line of text with each report column filled by its own separator character.
+///
+/// Columns without a separator character use the last character. If there are no separator
+/// characters or the column width is 0, the column will be empty.
+///
+/// # Arguments
+///
+/// * `cols` is the collection of report column definitions.
+/// * `separators` are the characters that will fill each of the report columns.
+/// * `gutter` is the number of spaces between columns.
+fn format_column_separators(cols: &[ReportColumn], separators: &[char], gutter: usize) -> String {
+    let gutter = " ".repeat(gutter);
+    cols.iter()
+        .enumerate()
+        .map(|(index, report_column)| match separators.get(index).or(separators.last()) {
+            Some(separator) => separator.to_string().repeat(report_column.width),
+            None => String::default(),
+        })
+        .collect::<Vec<String>>()
+        .join(&gutter)
+}
+
 /// Creates a line of text using the collection of [`ReportColumn`] and collection of [`ReportData`].
 ///
 /// The collection of report column definitions can be larger than the collection of report data.
@@ -393,20 +707,23 @@ fn format_separator(cols: &Vec<ReportColumn>, separator: &str) -> String {
 ///
 /// * `cols` is the collection of column definitions describing the report row.
 /// * `row` is the collection of text data used to populate the report row.
-fn format_text(cols: &Vec<ReportColumn>, row: &Vec<ReportData>) -> String {
+/// * `gutter` is the number of spaces between columns.
+fn format_text(cols: &Vec<ReportColumn>, row: &Vec<ReportData>, gutter: usize) -> String {
     let col_formats_len = cols.len();
     let text_columns_len = row.len();
     let mut row_text = String::new();
+    let gutter = " ".repeat(gutter);
     for i in 0..std::cmp::min(col_formats_len, text_columns_len) {
         if !row_text.is_empty() {
-            row_text.push(' ');
+            row_text.push_str(&gutter);
         }
         row_text.push_str(&row[i].fmt(&cols[i]));
     }
     if col_formats_len < text_columns_len {
-        const AS_IS: ReportColumn = ReportColumn { alignment: Alignment::Left, width: 0, fixed_width: true };
+        const AS_IS: ReportColumn =
+            ReportColumn { alignment: Alignment::Left, width: 0, fixed_width: true, max_width: None };
         for i in col_formats_len..text_columns_len {
-            row_text.push(' ');
+            row_text.push_str(&gutter);
             row_text.push_str(&row[i].fmt(&AS_IS));
         }
     }
@@ -483,34 +800,107 @@ mod tests {
     #[test]
     fn format_text_fn() {
         let column_formats = rptcols!(<+(10), ^+(5), >+(10));
-        let testcase = format_text(&column_formats, &rptrow!("hello", "-", "there"));
+        let testcase = format_text(&column_formats, &rptrow!("hello", "-", "there"), 1);
         assert_eq!(testcase, format!("{} {} {}", "hello     ", "  -  ", "     there"));
-        let testcase = format_text(&column_formats, &rptrow!("another", "try"));
+        let testcase = format_text(&column_formats, &rptrow!("another", "try"), 1);
         assert_eq!(testcase, format!("{} {}", "another   ", " try "));
-        let testcase = format_text(&column_formats, &rptrow!("too", "many", "text", "columns"));
+        let testcase = format_text(&column_formats, &rptrow!("too", "many", "text", "columns"), 1);
         assert_eq!(testcase, format!("{} {} {} {}", "too       ", "many ", "      text", "columns"));
     }
     #[test]
     fn format_text_fixed_width() {
         let column_formats = rptcols!(=, ^+(5), =);
-        let testcase = format_text(&column_formats, &rptrow!("hello", "-", "there"));
+        let testcase = format_text(&column_formats, &rptrow!("hello", "-", "there"), 1);
         assert_eq!(testcase, format!("{} {} {}", "hello", "  -  ", "there"));
-        let testcase = format_text(&column_formats, &rptrow!("lets", "tryit", "one more time"));
+        let testcase = format_text(&column_formats, &rptrow!("lets", "tryit", "one more time"), 1);
         assert_eq!(testcase, format!("{} {} {}", "lets", "tryit", "one more time"));
     }
     #[test]
+    fn format_text_ellipsis() {
+        let pathname = "/home/user/documents/notes.txt";
+        assert_eq!(pathname.len(), 30);
+        let column_formats = vec![ReportColumn::new(Alignment::Left, 10, true).with_max_width(10)];
+        let testcase = format_text(&column_formats, &rptrow!(pathname), 1);
+        assert_eq!(testcase, "/home/use…");
+        assert_eq!(testcase.chars().count(), 10);
+        // text that fits is not truncated
+        let testcase = format_text(&column_formats, &rptrow!("/home"), 1);
+        assert_eq!(testcase, "/home     ");
+        let column_formats = vec![ReportColumn::new(Alignment::Right, 10, true).with_max_width(10)];
+        assert_eq!(format_text(&column_formats, &rptrow!(pathname), 1), "/home/use…");
+        // wide characters use 2 columns
+        let testcase = format_text(&column_formats, &rptrow!("日本語のファイル名"), 1);
+        assert_eq!(testcase, " 日本語の…");
+        assert_eq!(testcase.width(), 10);
+    }
+    #[test]
+    fn report_ellipsis() {
+        let mut report = Report::from(rptcols!(<~(10), >));
+        report.header(rptrow!("Pathname", "Size"));
+        report.text(rptrow!("/home/user/documents/notes.txt", 1024));
+        report.text(rptrow!("/tmp", 1));
+        let mut testcase = report.into_iter();
+        assert_eq!(testcase.next().unwrap(), "Pathname   Size");
+        assert_eq!(testcase.next().unwrap(), "/home/use… 1024");
+        assert_eq!(testcase.next().unwrap(), "/tmp          1");
+        assert_eq!(testcase.next(), None);
+    }
+    #[test]
+    fn format_text_multibyte() {
+        // the display width fits the column but the byte length does not
+        for alignment in [Alignment::Left, Alignment::Center, Alignment::Right] {
+            let column_formats = vec![ReportColumn::new(alignment, 10, false).with_max_width(10)];
+            assert_eq!(format_text(&column_formats, &rptrow!("abcdefghié"), 1), "abcdefghié");
+        }
+        let mut report = Report::from(rptcols!(<~(10), >));
+        report.text(rptrow!("abcdefghié", 1));
+        assert_eq!(report.into_iter().next().unwrap(), "abcdefghié 1");
+        // fixed width columns truncate and pad by display width
+        let left = vec![ReportColumn::new(Alignment::Left, 4, true)];
+        assert_eq!(format_text(&left, &rptrow!("éé"), 1), "éé  ");
+        assert_eq!(format_text(&left, &rptrow!("ééééé"), 1), "éééé");
+        let center = vec![ReportColumn::new(Alignment::Center, 4, true)];
+        assert_eq!(format_text(&center, &rptrow!("éé"), 1), " éé ");
+        assert_eq!(format_text(&center, &rptrow!("aéébcd"), 1), "éébc");
+        let right = vec![ReportColumn::new(Alignment::Right, 4, true)];
+        assert_eq!(format_text(&right, &rptrow!("éé"), 1), "  éé");
+        assert_eq!(format_text(&right, &rptrow!("aéééé"), 1), "éééé");
+        // wide characters are not split
+        assert_eq!(format_text(&right, &rptrow!("日本語"), 1), "本語");
+        assert_eq!(format_text(&left, &rptrow!("日本語"), 1), "日本");
+    }
+    #[test]
     fn format_separator_fn() {
         let column_formats = rptcols!(<+(1), <+(2), <+(5));
-        let testcase = format_separator(&column_formats, "");
+        let testcase = format_separator(&column_formats, "", 1);
         assert_eq!(testcase, String::default());
-        let testcase = format_separator(&column_formats, "-");
+        let testcase = format_separator(&column_formats, "-", 1);
         assert_eq!(testcase, String::from("- -- -----"));
-        let testcase = format_separator(&column_formats, "+-");
+        let testcase = format_separator(&column_formats, "+-", 1);
         assert_eq!(testcase, String::from("+ +- +-+-+"));
-        let testcase = format_separator(&column_formats, "+-=");
+        let testcase = format_separator(&column_formats, "+-=", 1);
         assert_eq!(testcase, String::from("+ +- +-=+-"));
     }
     #[test]
+    fn column_separators() {
+        let column_formats = rptcols!(<+(1), <+(2), <+(5));
+        let testcase = format_column_separators(&column_formats, &[], 1);
+        assert_eq!(testcase, "  ");
+        let testcase = format_column_separators(&column_formats, &['=', '-', '+'], 1);
+        assert_eq!(testcase, "= -- +++++");
+        let testcase = format_column_separators(&column_formats, &['═', '─'], 1);
+        assert_eq!(testcase, "═ ── ─────");
+        let mut report = Report::from(rptcols!(<, >, >));
+        report.header(rptrow!("Name", "Count", "Total")).column_separators(&['=', '-', '-']);
+        report.text(rptrow!("alpha", 1, 10));
+        let testcase = report.into_iter().collect::<Vec<String>>();
+        assert_eq!(testcase[1], "===== ----- -----");
+        let mut streaming_report = StreamingReport::new(rptcols!(<, >), vec![]);
+        streaming_report.header(rptrow!("Name", "Count")).unwrap().column_separators(&['─', '=']).unwrap();
+        let testcase = String::from_utf8(streaming_report.into_inner().unwrap()).unwrap();
+        assert_eq!(testcase.lines().nth(1), Some("──── ====="));
+    }
+    #[test]
     fn report() {
         let mut report = Report::from(rptcols!(<, ^, >));
         report.header(rptrow!(^ "#", "TestCase", ^ "Value"));
@@ -525,6 +915,99 @@ mod tests {
         assert_eq!(testcase.next(), None);
     }
     #[test]
+    fn report_gutter() {
+        let report = |gutter: usize| {
+            let mut report = Report::from(rptcols!(<, >, <));
+            report.gutter(gutter);
+            report.header(rptrow!("Name", "Count", "Status")).separator("-");
+            report.text(rptrow!("alpha", 1, "ok"));
+            report.into_iter().collect::<Vec<String>>()
+        };
+        let testcase = report(1);
+        assert_eq!(testcase[0], "Name  Count Status");
+        assert_eq!(testcase[1], "----- ----- ------");
+        assert_eq!(testcase[2], "alpha     1 ok");
+        let testcase = report(3);
+        assert_eq!(testcase[0], "Name    Count   Status");
+        assert_eq!(testcase[1], "-----   -----   ------");
+        assert_eq!(testcase[2], "alpha       1   ok");
+        // each column after the first shifts by the extra gutter width
+        assert_eq!(report(1)[0].find("Count"), Some(6));
+        assert_eq!(report(3)[0].find("Count"), Some(8));
+        assert_eq!(report(1)[0].find("Status"), Some(12));
+        assert_eq!(report(3)[0].find("Status"), Some(16));
+        assert_eq!(report(0)[2], "alpha    1ok");
+        // the streaming report uses the same gutter
+        let mut streaming_report = StreamingReport::new(rptcols!(<, >, <), vec![]);
+        streaming_report.gutter(3).header(rptrow!("Name", "Count", "Status")).unwrap().separator("-").unwrap();
+        let testcase = String::from_utf8(streaming_report.into_inner().unwrap()).unwrap();
+        assert_eq!(testcase.lines().collect::<Vec<&str>>(), vec!["Name   Count   Status", "----   -----   ------"]);
+    }
+    #[test]
+    fn rendered_width() {
+        let mut report = Report::from(rptcols!(<, >, <));
+        assert_eq!(report.rendered_width(), 2);
+        report.header(rptrow!("Name", "Count", "Status")).separator("-");
+        assert_eq!(report.rendered_width(), 4 + 5 + 6 + 2);
+        report.text(rptrow!("alphabet", 1, "ok"));
+        assert_eq!(report.rendered_width(), 8 + 5 + 6 + 2);
+        report.gutter(3);
+        assert_eq!(report.rendered_width(), 8 + 5 + 6 + 6);
+        let widest = report.into_iter().map(|row| row.len()).max().unwrap();
+        assert_eq!(report.rendered_width(), widest);
+        // fixed and maximum widths are honored
+        let mut report = Report::from(vec![
+            ReportColumn::new(Alignment::Left, 3, true),
+            ReportColumn::new(Alignment::Left, 0, false).with_max_width(4),
+        ]);
+        report.text(rptrow!("abcdef", "abcdef"));
+        assert_eq!(report.rendered_width(), 3 + 4 + 1);
+        assert_eq!(Report::from(Vec::<ReportColumn>::new()).rendered_width(), 0);
+    }
+    #[test]
+    fn report_shading() {
+        assert!(use_styling(&None, true));
+        assert!(!use_styling(&None, false));
+        assert!(!use_styling(&Some(PathBuf::from("report.txt")), true));
+        let report = || {
+            let mut report = Report::from(rptcols!(<, >));
+            report.header(rptrow!("Name", "Value")).separator("-");
+            report.text(rptrow!("one", 1)).text(rptrow!("two", 2)).text(rptrow!("three", 3));
+            report
+        };
+        // mock terminal output
+        let mut shaded = report();
+        shaded.shaded = true;
+        let testcase: Vec<String> = shaded.into_iter().collect();
+        assert_eq!(testcase[2], "one       1");
+        assert_eq!(testcase[3], format!("{DIM_STYLE}two       2{RESET_STYLE}"));
+        assert_eq!(testcase[4], "three     3");
+        // file output is never styled
+        let filename = std::env::temp_dir().join(format!("toolslib-shading-{}.txt", std::process::id()));
+        let file_option = Some(filename.clone());
+        let mut report = report();
+        report.shade_rows(&file_option);
+        let mut writer = get_writer(&file_option, false).unwrap();
+        write_strings(&mut writer, report.into_iter()).unwrap();
+        drop(writer);
+        let content = fs::read_to_string(&filename);
+        fs::remove_file(&filename).unwrap();
+        assert!(!content.unwrap().contains('\x1b'));
+    }
+    #[test]
+    fn report_from_records() {
+        let records = vec![
+            vec!["Name".to_string(), "Count".to_string(), "Status".to_string()],
+            vec!["alpha".to_string(), "1".to_string(), "ok".to_string()],
+        ];
+        let report = Report::from_records(records);
+        let mut testcase = report.into_iter();
+        assert_eq!(testcase.next().unwrap(), "Name  Count Status");
+        assert_eq!(testcase.next().unwrap(), "alpha 1     ok");
+        assert_eq!(testcase.next(), None);
+        assert_eq!(Report::from_records(vec![]).into_iter().next(), None);
+    }
+    #[test]
     fn report_fixed_width() {
         let mut report = Report::from(rptcols!(<=(2), <, >));
         report.text(rptrow!(= "Header1"));
@@ -552,6 +1035,38 @@ mod tests {
         assert_eq!(testcase.next().unwrap(), "Second line");
         assert_eq!(testcase.next(), None);
     }
+    #[test]
+    fn streaming_report() {
+        let rows = || vec![rptrow!(1, "TC1", 45.6), rptrow!("Two", "TC2", (4 + 5)), rptrow!(3, "TC3", "-")];
+        let mut report = Report::from(rptcols!(<, ^, >));
+        report.header(rptrow!(^ "#", "TestCase", ^ "Value")).separator("-");
+        for row in rows() {
+            report.text(row);
+        }
+        let mut buffered = String::new();
+        report.into_iter().for_each(|row| buffered.push_str(&format!("{}\n", row)));
+        // declared widths
+        let mut streaming_report = StreamingReport::new(rptcols!(<+(3), ^+(8), >+(5)), vec![]);
+        streaming_report.header(rptrow!(^ "#", "TestCase", ^ "Value")).unwrap().separator("-").unwrap();
+        for row in rows() {
+            streaming_report.text(row).unwrap();
+        }
+        let testcase = String::from_utf8(streaming_report.into_inner().unwrap()).unwrap();
+        assert_eq!(testcase, buffered);
+        // the first header defines the widths
+        let mut streaming_report = StreamingReport::new(rptcols!(<, ^, >), vec![]);
+        streaming_report.header(rptrow!(^ "###", "TestCase", ^ "Value")).unwrap().separator("-").unwrap();
+        for row in rows() {
+            streaming_report.text(row).unwrap();
+        }
+        let testcase = String::from_utf8(streaming_report.into_inner().unwrap()).unwrap();
+        assert_eq!(testcase, buffered.replacen(" # ", "###", 1));
+        // widths do not change after the first row
+        let mut streaming_report = StreamingReport::new(rptcols!(<+(3), >+(3)), vec![]);
+        streaming_report.text(rptrow!("abc", 123)).unwrap().header(rptrow!("abcdef", 123456)).unwrap();
+        let testcase = String::from_utf8(streaming_report.into_inner().unwrap()).unwrap();
+        assert_eq!(testcase, "abc 123\nabc 456\n");
+    }
 }
 
 mod macros {
@@ -744,11 +1259,12 @@ mod macros {
     /// ```
     /// # use toolslib::text::{Alignment, ReportColumn};
     /// use toolslib::rptcols;
-    /// assert_eq!(rptcols!(<, <+(1), <=(2), ^, ^+(3), ^=(4), >, >+(5), >=(6), =),
+    /// assert_eq!(rptcols!(<, <+(1), <=(2), <~(7), ^, ^+(3), ^=(4), >, >+(5), >=(6), =),
     ///     vec![
     ///         ReportColumn::new(Alignment::Left, 0, false),
     ///         ReportColumn::new(Alignment::Left, 1, false),
     ///         ReportColumn::new(Alignment::Left, 2, true),
+    ///         ReportColumn::new(Alignment::Left, 0, false).with_max_width(7),
     ///         ReportColumn::new(Alignment::Center, 0, false),
     ///         ReportColumn::new(Alignment::Center, 3, false),
     ///         ReportColumn::new(Alignment::Center, 4, true),
@@ -813,6 +1329,22 @@ mod macros {
                     ,
                 ])
             };
+            // from comma delimited markup creates a left justified, auto-sizing column truncated with an ellipsis
+            (@rc (<~( $width:expr ), $($cols_markup:tt)*) -> [ $($col_descrs:tt)* ]) => {
+                rptcols!(@rc ($($cols_markup)*) -> [
+                    $($col_descrs)*
+                    $crate::text::ReportColumn::new($crate::text::Alignment::Left, 0, false).with_max_width($width)
+                    ,
+                ])
+            };
+            // creates a left justified, auto-sizing column truncated with an ellipsis and ends markup parsing
+            (@rc (<~( $width:expr )) -> [ $($col_descrs:tt)* ]) => {
+                rptcols!(@rc () -> [
+                    $($col_descrs)*
+                    $crate::text::ReportColumn::new($crate::text::Alignment::Left, 0, false).with_max_width($width)
+                    ,
+                ])
+            };
             // from comma delimited markup creates a center justified, auto-sizing column
             (@rc (^, $($cols_markup:tt)*) -> [ $($col_descrs:tt)* ]) => {
                 rptcols!(@rc ($($cols_markup)*) -> [
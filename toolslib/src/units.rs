@@ -0,0 +1,126 @@
+//! A collection of functions that convert temperature and wind speed units.
+//!
+//! The conversions are pure functions so front-ends can share one implementation.
+//!
+
+/// The number of meters in a mile.
+const METERS_PER_MILE: f64 = 1609.344;
+
+/// The number of seconds in an hour.
+const SECONDS_PER_HOUR: f64 = 3600.0;
+
+/// Convert a Fahrenheit temperature to Celsius.
+///
+/// # Arguments
+///
+/// * `fahrenheit` is the temperature that will be converted.
+pub fn fahrenheit_to_celsius(fahrenheit: f64) -> f64 {
+    (fahrenheit - 32.0) * 5.0 / 9.0
+}
+
+/// Convert a Celsius temperature to Fahrenheit.
+///
+/// # Arguments
+///
+/// * `celsius` is the temperature that will be converted.
+pub fn celsius_to_fahrenheit(celsius: f64) -> f64 {
+    celsius * 9.0 / 5.0 + 32.0
+}
+
+/// Convert a speed in miles per hour to kilometers per hour.
+///
+/// # Arguments
+///
+/// * `mph` is the speed that will be converted.
+pub fn mph_to_kph(mph: f64) -> f64 {
+    mph * METERS_PER_MILE / 1000.0
+}
+
+/// Convert a speed in kilometers per hour to miles per hour.
+///
+/// # Arguments
+///
+/// * `kph` is the speed that will be converted.
+pub fn kph_to_mph(kph: f64) -> f64 {
+    kph * 1000.0 / METERS_PER_MILE
+}
+
+/// Convert a speed in miles per hour to meters per second.
+///
+/// # Arguments
+///
+/// * `mph` is the speed that will be converted.
+pub fn mph_to_ms(mph: f64) -> f64 {
+    mph * METERS_PER_MILE / SECONDS_PER_HOUR
+}
+
+/// Convert a speed in meters per second to miles per hour.
+///
+/// # Arguments
+///
+/// * `ms` is the speed that will be converted.
+pub fn ms_to_mph(ms: f64) -> f64 {
+    ms * SECONDS_PER_HOUR / METERS_PER_MILE
+}
+
+/// Convert a speed in kilometers per hour to meters per second.
+///
+/// # Arguments
+///
+/// * `kph` is the speed that will be converted.
+pub fn kph_to_ms(kph: f64) -> f64 {
+    kph * 1000.0 / SECONDS_PER_HOUR
+}
+
+/// Convert a speed in meters per second to kilometers per hour.
+///
+/// # Arguments
+///
+/// * `ms` is the speed that will be converted.
+pub fn ms_to_kph(ms: f64) -> f64 {
+    ms * SECONDS_PER_HOUR / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOLERANCE: f64 = 1e-9;
+
+    macro_rules! assert_near {
+        ($lhs:expr, $rhs:expr) => {
+            assert!(($lhs - $rhs).abs() < TOLERANCE, "{} is not {}", $lhs, $rhs)
+        };
+    }
+
+    #[test]
+    fn temperatures() {
+        assert_near!(fahrenheit_to_celsius(32.0), 0.0);
+        assert_near!(fahrenheit_to_celsius(212.0), 100.0);
+        assert_near!(fahrenheit_to_celsius(-40.0), -40.0);
+        assert_near!(fahrenheit_to_celsius(98.6), 37.0);
+        assert_near!(celsius_to_fahrenheit(0.0), 32.0);
+        assert_near!(celsius_to_fahrenheit(100.0), 212.0);
+        assert_near!(celsius_to_fahrenheit(-40.0), -40.0);
+        for temperature in [-60.5, -1.0, 0.0, 21.3, 72.4, 120.0] {
+            assert_near!(celsius_to_fahrenheit(fahrenheit_to_celsius(temperature)), temperature);
+            assert_near!(fahrenheit_to_celsius(celsius_to_fahrenheit(temperature)), temperature);
+        }
+    }
+
+    #[test]
+    fn wind_speeds() {
+        assert_near!(mph_to_kph(1.0), 1.609344);
+        assert_near!(kph_to_mph(1.609344), 1.0);
+        assert_near!(mph_to_ms(1.0), 0.44704);
+        assert_near!(ms_to_mph(0.44704), 1.0);
+        assert_near!(kph_to_ms(36.0), 10.0);
+        assert_near!(ms_to_kph(10.0), 36.0);
+        for speed in [0.0, 0.5, 10.0, 33.3, 120.0] {
+            assert_near!(kph_to_mph(mph_to_kph(speed)), speed);
+            assert_near!(ms_to_mph(mph_to_ms(speed)), speed);
+            assert_near!(ms_to_kph(kph_to_ms(speed)), speed);
+            assert_near!(kph_to_ms(mph_to_kph(speed)), mph_to_ms(speed));
+        }
+    }
+}
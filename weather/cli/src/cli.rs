@@ -11,80 +11,152 @@
 //! mining data for the implementation.
 
 use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command};
-use std::{io, path::PathBuf};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 
 mod admin;
+#[cfg(feature = "clipboard")]
+mod clipboard;
 mod reports;
 mod tui;
 mod user;
+mod validate_config;
 
 /// The command line interface result.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The category of a CLI error.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ErrorKind {
+    /// The error has not been categorized.
+    #[default]
+    Other,
+    /// Something being used does not exist.
+    NotFound,
+    /// The input provided is not valid.
+    Invalid,
+    /// There was a problem reading or writing something.
+    Io,
+    /// The stored weather data is corrupt or cannot be decoded.
+    Data,
+    /// The request conflicts with the weather data, such as something that already exists or read-only data.
+    Conflict,
+}
+impl ErrorKind {
+    /// Get the process exit code for the error category.
+    ///
+    /// Exit code `2` is not used since it is the exit code for command line usage errors.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            ErrorKind::Other => 1,
+            ErrorKind::NotFound => 3,
+            ErrorKind::Invalid => 4,
+            ErrorKind::Io => 5,
+            ErrorKind::Data => 6,
+            ErrorKind::Conflict => 7,
+        }
+    }
+}
+impl From<weather_lib::ErrorKind> for ErrorKind {
+    fn from(kind: weather_lib::ErrorKind) -> Self {
+        use weather_lib::ErrorKind as LibErrorKind;
+        match kind {
+            LibErrorKind::NotFound => ErrorKind::NotFound,
+            LibErrorKind::AlreadyExists | LibErrorKind::ReadOnly => ErrorKind::Conflict,
+            LibErrorKind::Corrupt | LibErrorKind::Decode => ErrorKind::Data,
+            LibErrorKind::Io => ErrorKind::Io,
+            LibErrorKind::Other => ErrorKind::Other,
+        }
+    }
+}
+
 /// The CLI error definition.
 #[derive(Debug)]
-pub struct Error(String);
-impl std::fmt::Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
+pub struct Error {
+    /// The category of error.
+    kind: ErrorKind,
+    /// A description of what happened.
+    message: String,
 }
-impl From<String> for Error {
-    fn from(error: String) -> Self {
-        Error::from(error.as_str())
+impl Error {
+    /// Create an error of a specific kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` is the category of error.
+    /// * `message` describes what happened.
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self { kind, message: message.into() }
+    }
+    /// Get the category of error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
     }
 }
-impl From<&str> for Error {
-    fn from(error: &str) -> Self {
-        Error(format!("cli: {error}"))
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
     }
 }
 impl From<weather_lib::Error> for Error {
     fn from(error: weather_lib::Error) -> Self {
-        Error(error.to_string())
+        Error::new(ErrorKind::from(error.kind()), error.to_string())
     }
 }
 impl From<io::Error> for Error {
     fn from(error: io::Error) -> Self {
-        Error(format!("io: {error}"))
+        Error::new(ErrorKind::Io, format!("io: {error}"))
     }
 }
 impl From<csv::Error> for Error {
     fn from(error: csv::Error) -> Self {
-        Error(format!("csv: {error}"))
+        let kind = match error.is_io_error() {
+            true => ErrorKind::Io,
+            false => ErrorKind::Invalid,
+        };
+        Error::new(kind, format!("csv: {error}"))
     }
 }
 impl From<serde_json::Error> for Error {
     fn from(error: serde_json::Error) -> Self {
-        Error(format!("json: {error}"))
+        let kind = match error.classify() {
+            serde_json::error::Category::Io => ErrorKind::Io,
+            _ => ErrorKind::Invalid,
+        };
+        Error::new(kind, format!("json: {error}"))
     }
 }
 impl From<toolslib::Error> for Error {
+    /// The tools library errors come from parsing dates and CSV content.
     fn from(error: toolslib::Error) -> Self {
-        Error(error.to_string())
+        Error::new(ErrorKind::Invalid, error.to_string())
     }
 }
 impl From<toolslib::text::Error> for Error {
+    /// The text errors come from creating and writing report output.
     fn from(error: toolslib::text::Error) -> Self {
-        Error(error.to_string())
+        Error::new(ErrorKind::Io, error.to_string())
     }
 }
 impl From<termui_lib::Error> for Error {
+    /// The terminal UI errors come from the console.
     fn from(error: termui_lib::Error) -> Self {
-        Error(error.to_string())
+        Error::new(ErrorKind::Io, error.to_string())
     }
 }
 
 // pub use current::{get, initialize, initialize_and_run, run, CommandLineArgs};
 pub use current::{get, initialize_and_run};
-use current::{get_writer, CriteriaArgs, ReportArgs};
+use current::{get_location_writer, get_writer, parse_dirname, CriteriaArgs, ReportArgs};
 mod current {
     //! The current command line implementation.
     use toolslib::logs;
 
     use admin::Admin;
     use user::User;
-    use weather_lib::{admin_prelude::weather_admin, prelude::WeatherData, create_weather_data};
+    use weather_lib::{admin_prelude::weather_admin, check_config, create_weather_data, prelude::WeatherData};
 
     use super::*;
 
@@ -105,6 +177,7 @@ mod current {
             // the subcommands
             .subcommands(User::get_commands())
             .subcommand(TerminalUI::get())
+            .subcommand(validate_config::command())
             .subcommand(Admin::get())
     }
 
@@ -114,7 +187,8 @@ mod current {
     ///
     /// * `args` holds the arguments from the parsed command line.
     pub fn initialize_and_run(args: ArgMatches) -> Result<()> {
-        initialize(&args);
+        // hold the log guard so logging is flushed when the command finishes
+        let _log_guard = initialize(&args);
         log::trace!("initialize_and_run Enter");
         run(args)
     }
@@ -124,7 +198,7 @@ mod current {
     /// # Arguments
     ///
     /// * `args` holds the arguments from the parsed command line.
-    pub fn initialize(args: &ArgMatches) {
+    pub fn initialize(args: &ArgMatches) -> Option<logs::LogGuard> {
         let cmd_args = CommandLineArgs::from(args);
         let (logfile, append) = match args.subcommand_name().unwrap_or("") == TerminalUI::NAME {
             true => match cmd_args.logfile() {
@@ -140,6 +214,7 @@ mod current {
                 2 => log::LevelFilter::Debug,
                 _ => log::LevelFilter::Trace,
             },
+            format: logs::LogFormat::Pattern,
             console_pattern: None,
             logfile_pattern: None,
             logfile_path: logfile,
@@ -152,9 +227,12 @@ mod current {
                 "termui_lib".to_string(),
             ],
         }) {
-            Ok(_) => (),
-            Err(log_error) => eprintln!("Error initializing logging!!! {:?}", log_error),
-        };
+            Ok(log_guard) => Some(log_guard),
+            Err(log_error) => {
+                eprintln!("Error initializing logging!!! {:?}", log_error);
+                None
+            }
+        }
     }
 
     /// Run the appropriate subcommand.
@@ -168,21 +246,34 @@ mod current {
         let command_args = CommandLineArgs::from(&args);
         match name.as_str() {
             Admin::NAME => run_admin(command_args, subcommand_args),
+            validate_config::COMMAND_NAME => run_validate_config(command_args, subcommand_args),
             _ => run_user(&name, command_args, subcommand_args),
         }
     }
 
+    fn run_validate_config(command_args: CommandLineArgs, args: ArgMatches) -> Result<()> {
+        let config_file = command_args.config_file();
+        let weather_dir = command_args.weather_dir();
+        let no_db = command_args.backend() == Backend::Archive;
+        let config_check = check_config(config_file, weather_dir, no_db, command_args.read_only())?;
+        validate_config::execute(config_check, args)
+    }
+
     fn run_admin(command_args: CommandLineArgs, args: ArgMatches) -> Result<()> {
         let weather_dir = command_args.weather_dir();
-        let weather_admin = weather_admin(weather_dir)?;
+        let weather_admin = weather_admin(weather_dir)?.with_read_only(command_args.read_only());
         Admin::run(&weather_admin, args)
     }
 
     fn run_user(name: &str, command_args: CommandLineArgs, args: ArgMatches) -> Result<()> {
         let config_file = command_args.config_file();
         let weather_dir = command_args.weather_dir();
-        let no_db = command_args.no_db();
-        let weather_data = create_weather_data(config_file, weather_dir, no_db)?;
+        let no_db = match command_args.backend() {
+            Backend::Db => false,
+            Backend::Archive => true,
+        };
+        let read_only = command_args.read_only();
+        let weather_data = create_weather_data(config_file, weather_dir, no_db, read_only)?;
         match name {
             // TerminalUI::NAME => TerminalUI::run_tui(&weather_data, args),
             TerminalUI::NAME => TerminalUI::run_tui(weather_data, args),
@@ -226,6 +317,43 @@ mod current {
         }
     }
 
+    /// Used by the command parser to validate an existing directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `dirname` is the directory name that will be validated.
+    pub fn parse_dirname(dirname: &str) -> std::result::Result<PathBuf, String> {
+        if dirname.is_empty() {
+            Err("The directory name cannot be empty.".to_string())
+        } else {
+            let dirpath = PathBuf::from(dirname);
+            match dirpath.is_dir() {
+                true => Ok(dirpath),
+                false => Err(format!("{} is not a directory...", dirname)),
+            }
+        }
+    }
+
+    /// Used by the command parser to validate a `chrono` date format.
+    ///
+    /// The format is checked by formatting a date so time specifiers are not valid.
+    ///
+    /// # Arguments
+    ///
+    /// * `date_format` is the date format that will be validated.
+    pub fn parse_date_format(date_format: &str) -> std::result::Result<String, String> {
+        use std::fmt::Write;
+        let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+        let mut formatted_epoch = String::new();
+        if date_format.is_empty() {
+            Err("The date format cannot be empty.".to_string())
+        } else if write!(formatted_epoch, "{}", epoch.format(date_format)).is_err() {
+            Err(format!("'{}' is not a valid date format...", date_format))
+        } else {
+            Ok(date_format.to_string())
+        }
+    }
+
     /// Creates a `Write` instance where reports will be written.
     ///
     /// If the report writer contains a file pathname, an error can occur due to permission
@@ -235,10 +363,30 @@ mod current {
     ///
     /// * `report_args` has the command line arguments surrounding report generation.
     pub fn get_writer(report_args: &ReportArgs) -> Result<Box<dyn io::Write>> {
+        #[cfg(feature = "clipboard")]
+        if report_args.clipboard() {
+            let sink = clipboard::SystemClipboard::new()?;
+            return Ok(Box::new(clipboard::ClipboardWriter::new(sink)));
+        }
         let writer = toolslib::text::get_writer(&report_args.report_file(), report_args.append())?;
         Ok(writer)
     }
 
+    /// Creates a `Write` instance where a locations report will be written.
+    ///
+    /// The report file will be named using the location alias and report type (`<alias>.<ext>`).
+    ///
+    /// # Arguments
+    ///
+    /// * `report_args` has the command line arguments surrounding report generation.
+    /// * `output_dir` is the directory where the report file will be created.
+    /// * `alias` is the location alias.
+    pub fn get_location_writer(report_args: &ReportArgs, output_dir: &Path, alias: &str) -> Result<Box<dyn io::Write>> {
+        let report_file = output_dir.join(format!("{}.{}", alias, report_args.extension()));
+        let writer = toolslib::text::get_writer(&Some(report_file), report_args.append())?;
+        Ok(writer)
+    }
+
     #[derive(Debug)]
     struct TerminalUI;
     impl TerminalUI {
@@ -261,6 +409,16 @@ mod current {
         }
     }
 
+    /// The weather data backends that can be selected.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, clap::ValueEnum)]
+    pub enum Backend {
+        /// Use the weather history DB if one is available.
+        #[default]
+        Db,
+        /// Use only the weather history archives.
+        Archive,
+    }
+
     /// The common command line arguments.
     pub struct CommandLineArgs<'a>(
         /// The subcommand command line arguments.
@@ -277,8 +435,12 @@ mod current {
         const APPEND: &'static str = "APPEND_LOGFILE";
         /// The logging verbosity level argument id.
         const VERBOSITY: &'static str = "LOG_VERBOSITY";
-        /// Use the filesystem implementation of weather data.
+        /// The weather data backend argument id.
+        const BACKEND: &'static str = "BACKEND";
+        /// Use the filesystem implementation of weather data (deprecated).
         const FS: &'static str = "FS";
+        /// Open the weather data without write access.
+        const READ_ONLY: &'static str = "READ_ONLY";
         /// Get the common command line arguments.
         fn get() -> Vec<Arg> {
             vec![
@@ -298,10 +460,22 @@ mod current {
                     // .require_equals(true)
                     .value_parser(Self::parse_weather_dir)
                     .help("The weather data directory pathname."),
+                Arg::new(Self::BACKEND)
+                    .long("backend")
+                    .action(ArgAction::Set)
+                    .value_name("BACKEND")
+                    .value_parser(clap::value_parser!(Backend))
+                    .help("The weather data backend (DEFAULT db)."),
                 Arg::new(Self::FS)
                     .long("fs")
                     .action(ArgAction::SetTrue)
-                    .help("Do not use a weather history DB if one is available."),
+                    .conflicts_with(Self::BACKEND)
+                    .hide(true)
+                    .help("Deprecated, use --backend archive."),
+                Arg::new(Self::READ_ONLY)
+                    .long("read-only")
+                    .action(ArgAction::SetTrue)
+                    .help("Do not allow weather data to be modified."),
                 Arg::new(Self::LOGFILE)
                     .short('l')
                     .long("logfile")
@@ -354,9 +528,19 @@ mod current {
         pub fn append(&self) -> bool {
             self.0.get_flag(Self::APPEND)
         }
-        /// Get the use a database configuration flag.
-        pub fn no_db(&self) -> bool {
-            self.0.get_flag(Self::FS)
+        /// Get the weather data backend that should be used.
+        pub fn backend(&self) -> Backend {
+            match self.0.get_flag(Self::FS) {
+                true => {
+                    log::warn!("--fs is deprecated, use --backend archive.");
+                    Backend::Archive
+                }
+                false => self.0.get_one::<Backend>(Self::BACKEND).copied().unwrap_or_default(),
+            }
+        }
+        /// Get the open weather data without write access flag.
+        pub fn read_only(&self) -> bool {
+            self.0.get_flag(Self::READ_ONLY)
         }
         /// Get the logging verbosity flag.
         pub fn verbosity(&self) -> u8 {
@@ -378,15 +562,24 @@ mod current {
         /// Generate a textual based report.
         const TEXT: &'static str = "REPORT_TEXT";
         /// Generate a CSV based report.
-        const CSV: &'static str = "REPORT_CSV";
+        pub const CSV: &'static str = "REPORT_CSV";
         /// Generate a JSON based report.
-        const JSON: &'static str = "REPORT_JSON";
+        pub const JSON: &'static str = "REPORT_JSON";
         /// For JSON reports output the data in a pretty format.
         const PRETTY: &'static str = "REPORT_JSON_PRETTY";
+        /// For CSV reports pad the fields so columns line up.
+        const CSV_ALIGNED: &'static str = "REPORT_CSV_ALIGNED";
         /// The name of the report file.
-        const REPORT_FILE: &'static str = "REPORT_FILE";
+        pub const REPORT_FILE: &'static str = "REPORT_FILE";
         /// Append data to the report file.
         const APPEND: &'static str = "REPORT_APPEND";
+        /// Send the report to the clipboard.
+        #[cfg(feature = "clipboard")]
+        const CLIPBOARD: &'static str = "REPORT_CLIPBOARD";
+        /// The format of dates in text reports.
+        const DATE_FORMAT: &'static str = "REPORT_DATE_FORMAT";
+        /// Fail the command when the report does not have any data.
+        const FAIL_ON_EMPTY: &'static str = "REPORT_FAIL_ON_EMPTY";
         pub fn new(args: &'a ArgMatches) -> Self {
             Self(args)
         }
@@ -407,6 +600,11 @@ mod current {
                     // only when JSON is true.
                     .requires(Self::JSON)
                     .help("For JSON reports output will be pretty printed."),
+                Arg::new(Self::CSV_ALIGNED)
+                    .long("csv-aligned")
+                    .action(ArgAction::SetTrue)
+                    .requires(Self::CSV)
+                    .help("For CSV reports fields will be padded so columns line up."),
                 Arg::new(Self::REPORT_FILE)
                     .short('r')
                     .long("report")
@@ -420,8 +618,29 @@ mod current {
                     .requires(Self::REPORT_FILE)
                     .action(ArgAction::SetTrue)
                     .help("Append to the report file, otherwise overwrite."),
+                Arg::new(Self::FAIL_ON_EMPTY)
+                    .long("fail-on-empty")
+                    .action(ArgAction::SetTrue)
+                    .help("Exit with an error if the report does not have any data."),
+                #[cfg(feature = "clipboard")]
+                Arg::new(Self::CLIPBOARD)
+                    .long("clipboard")
+                    .action(ArgAction::SetTrue)
+                    .conflicts_with(Self::REPORT_FILE)
+                    .help("Send the report to the clipboard instead of stdout."),
             ]
         }
+        /// Get the text report date format argument.
+        ///
+        /// The argument is not part of the common arguments because not every report has dates.
+        pub fn date_format_arg() -> Arg {
+            Arg::new(Self::DATE_FORMAT)
+                .long("date-format")
+                .value_name("FORMAT")
+                .action(ArgAction::Set)
+                .value_parser(parse_date_format)
+                .help("The strftime format of dates in text reports (CSV and JSON dates are always ISO).")
+        }
         /// Get the command argument group for selecting either text, CSV, or JSON reports,
         pub fn arg_group() -> ArgGroup {
             ArgGroup::new("REPORT_TYPES").args([Self::TEXT, Self::CSV, Self::JSON]).required(false)
@@ -443,14 +662,64 @@ mod current {
         pub fn pretty(&self) -> bool {
             self.0.get_flag(ReportArgs::PRETTY)
         }
+        /// Get the `CSV` aligned columns report flag.
+        pub fn csv_aligned(&self) -> bool {
+            self.0.get_flag(ReportArgs::CSV_ALIGNED)
+        }
+        /// Apply the `CSV` report options to the report content.
+        ///
+        /// # Arguments
+        ///
+        /// * `report` is the `CSV` report content.
+        pub fn csv_report(&self, report: String) -> String {
+            match self.csv_aligned() {
+                true => reports::align_csv(report),
+                false => report,
+            }
+        }
         /// Get the append to report flag.
         pub fn append(&self) -> bool {
             self.0.get_flag(ReportArgs::APPEND)
         }
+        /// Get the fail when the report does not have any data flag.
+        pub fn fail_on_empty(&self) -> bool {
+            self.0.get_flag(ReportArgs::FAIL_ON_EMPTY)
+        }
+        /// Check if the command should fail because the report does not have any data.
+        ///
+        /// # Arguments
+        ///
+        /// * `empty` indicates the report data is empty.
+        /// * `what` describes the report data.
+        pub fn check_empty(&self, empty: bool, what: &str) -> Result<()> {
+            match empty && self.fail_on_empty() {
+                true => Err(Error::new(ErrorKind::NotFound, format!("There are no {} to report.", what))),
+                false => Ok(()),
+            }
+        }
+        /// Get the send report to clipboard flag.
+        #[cfg(feature = "clipboard")]
+        pub fn clipboard(&self) -> bool {
+            self.0.get_flag(ReportArgs::CLIPBOARD)
+        }
+        /// Get the report filename extension.
+        pub fn extension(&self) -> &'static str {
+            if self.csv() {
+                "csv"
+            } else if self.json() {
+                "json"
+            } else {
+                "txt"
+            }
+        }
         /// Get the report filename argument.
         pub fn report_file(&self) -> Option<PathBuf> {
             self.0.get_one::<PathBuf>(ReportArgs::REPORT_FILE).map_or(None, |p| Some(p.clone()))
         }
+        /// Get the text report date format argument.
+        pub fn date_format(&self) -> Option<&String> {
+            self.0.get_one::<String>(ReportArgs::DATE_FORMAT)
+        }
     }
 
     /// The common command locations_win criteria.
@@ -460,7 +729,7 @@ mod current {
     );
     impl<'a> CriteriaArgs<'a> {
         /// The locations_win filter.
-        const LOCATIONS: &'static str = "LOCATIONS_FILTER";
+        pub const LOCATIONS: &'static str = "LOCATIONS_FILTER";
         pub fn new(args: &'a ArgMatches) -> Self {
             Self(args)
         }
@@ -492,6 +761,42 @@ mod current {
             };
         }
 
+        #[test]
+        fn exit_codes() {
+            assert_eq!(ErrorKind::Other.exit_code(), 1);
+            assert_eq!(ErrorKind::NotFound.exit_code(), 3);
+            assert_eq!(ErrorKind::Invalid.exit_code(), 4);
+            assert_eq!(ErrorKind::Io.exit_code(), 5);
+            assert_eq!(ErrorKind::Data.exit_code(), 6);
+            assert_eq!(ErrorKind::Conflict.exit_code(), 7);
+            // the error categories can be told apart from command line usage errors
+            let usage_error = Command::new("testcase").try_get_matches_from(["testcase", "--bad"]).unwrap_err();
+            use ErrorKind::*;
+            for kind in [Other, NotFound, Invalid, Io, Data, Conflict] {
+                assert_ne!(kind.exit_code() as i32, usage_error.exit_code(), "{:?}", kind);
+            }
+            macro_rules! lib_kind {
+                ($kind:ident) => {
+                    Error::from(weather_lib::Error::new(weather_lib::ErrorKind::$kind, "testcase")).kind()
+                };
+            }
+            assert_eq!(lib_kind!(Other), ErrorKind::Other);
+            assert_eq!(lib_kind!(NotFound), ErrorKind::NotFound);
+            assert_eq!(lib_kind!(AlreadyExists), ErrorKind::Conflict);
+            assert_eq!(lib_kind!(Corrupt), ErrorKind::Data);
+            assert_eq!(lib_kind!(Decode), ErrorKind::Data);
+            assert_eq!(lib_kind!(ReadOnly), ErrorKind::Conflict);
+            assert_eq!(lib_kind!(Io), ErrorKind::Io);
+            assert_eq!(Error::from(io::Error::other("testcase")).kind(), ErrorKind::Io);
+            let csv_error = csv::Reader::from_reader("a,b\n1\n".as_bytes()).records().next().unwrap().unwrap_err();
+            assert_eq!(Error::from(csv_error).kind(), ErrorKind::Invalid);
+            let json_error = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+            assert_eq!(Error::from(json_error).kind(), ErrorKind::Invalid);
+            assert_eq!(Error::from(toolslib::Error::from("testcase")).kind(), ErrorKind::Invalid);
+            assert_eq!(Error::from(toolslib::text::Error::from("testcase")).kind(), ErrorKind::Io);
+            assert_eq!(Error::from(termui_lib::Error::new("testcase")).kind(), ErrorKind::Io);
+        }
+
         #[test]
         fn criteria() {
             fn testcase(args: &[&str]) -> ArgMatches {
@@ -553,11 +858,33 @@ mod current {
             assert!(!report_args.csv());
             assert!(report_args.json());
             assert!(report_args.pretty());
+            let args = testcase(&mut cmd, &["testcase", "--csv", "--csv-aligned"]);
+            let report_args = ReportArgs(&args);
+            assert!(report_args.csv());
+            assert!(report_args.csv_aligned());
+            assert_eq!(report_args.csv_report("a,bb\nccc,d\n".to_string()), "a  ,bb\nccc,d\n");
+            assert!(cmd.try_get_matches_from_mut(["testcase", "--csv-aligned"]).is_err());
             assert!(cmd.try_get_matches_from_mut(["testcase", "--text", "--csv"]).is_err());
             assert!(cmd.try_get_matches_from_mut(["testcase", "--text", "--json"]).is_err());
             assert!(cmd.try_get_matches_from_mut(["testcase", "--csv", "--json"]).is_err());
         }
 
+        #[test]
+        fn fail_on_empty() {
+            let mut cmd = Command::new("test").no_binary_name(true).args(ReportArgs::get());
+            let args = cmd.try_get_matches_from_mut(["--csv"]).unwrap();
+            let report_args = ReportArgs(&args);
+            assert!(!report_args.fail_on_empty());
+            assert!(report_args.check_empty(true, "locations").is_ok());
+            let args = cmd.try_get_matches_from_mut(["--csv", "--fail-on-empty"]).unwrap();
+            let report_args = ReportArgs(&args);
+            assert!(report_args.fail_on_empty());
+            assert!(report_args.check_empty(false, "locations").is_ok());
+            let error = report_args.check_empty(true, "locations").unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::NotFound);
+            assert_eq!(error.to_string(), "There are no locations to report.");
+        }
+
         #[test]
         fn command_args() {
             let mut cmd = Command::new("test")
@@ -575,7 +902,7 @@ mod current {
             assert!(command_args.weather_dir().is_none());
             assert!(command_args.logfile().is_none());
             assert!(!command_args.append());
-            assert!(!command_args.no_db());
+            assert_eq!(command_args.backend(), Backend::Db);
             assert_eq!(command_args.verbosity(), 0);
             let known_dir = env!("CARGO_MANIFEST_DIR");
             let dir = format!("-d={}", known_dir);
@@ -584,8 +911,23 @@ mod current {
             assert_eq!(command_args.weather_dir().unwrap(), PathBuf::from(known_dir));
             assert_eq!(command_args.logfile().unwrap(), PathBuf::from("logfile"));
             assert!(command_args.append());
-            assert!(command_args.no_db());
+            assert_eq!(command_args.backend(), Backend::Archive);
             assert_eq!(command_args.verbosity(), 3)
         }
+
+        #[test]
+        fn backend() {
+            let mut cmd = Command::new("test").no_binary_name(true).args(CommandLineArgs::get());
+            let mut testcase = |args: &[&str]| match cmd.try_get_matches_from_mut(args) {
+                Ok(matches) => Some(CommandLineArgs(&matches).backend()),
+                Err(_) => None,
+            };
+            assert_eq!(testcase(&[]), Some(Backend::Db));
+            assert_eq!(testcase(&["--backend", "db"]), Some(Backend::Db));
+            assert_eq!(testcase(&["--backend", "archive"]), Some(Backend::Archive));
+            assert_eq!(testcase(&["--fs"]), Some(Backend::Archive));
+            assert_eq!(testcase(&["--backend", "sqlite"]), None);
+            assert_eq!(testcase(&["--backend", "db", "--fs"]), None);
+        }
     }
 }
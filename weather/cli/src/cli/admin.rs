@@ -2,9 +2,11 @@
 use super::*;
 use weather_lib::admin_prelude::WeatherAdmin;
 
+mod compact;
 mod drop;
 mod init;
 mod migrate;
+mod normalize;
 mod reload;
 mod show;
 mod us_cities;
@@ -13,9 +15,11 @@ pub(super) use v3::Admin;
 mod v3 {
     //! The latest version of weather data administration.
     use super::*;
+    use compact::CompactCmd;
     use drop::DropCmd;
     use init::InitCmd;
     use migrate::MigrateCmd;
+    use normalize::NormalizeCmd;
     use reload::ReloadCmd;
     use show::ShowCmd;
     use us_cities::UsCitiesCmd;
@@ -42,6 +46,8 @@ mod v3 {
                 .subcommand(MigrateCmd::get())
                 .subcommand(ReloadCmd::get())
                 .subcommand(ShowCmd::get())
+                .subcommand(CompactCmd::get())
+                .subcommand(NormalizeCmd::get())
                 .subcommand(UsCitiesCmd::get())
         }
         /// Executes the command.
@@ -58,6 +64,8 @@ mod v3 {
                 (MigrateCmd::NAME, cmd_args) => MigrateCmd::run(weather_admin, cmd_args),
                 (ShowCmd::NAME, cmd_args) => ShowCmd::run(weather_admin, cmd_args),
                 (ReloadCmd::NAME, cmd_args) => ReloadCmd::run(weather_admin, cmd_args),
+                (CompactCmd::NAME, cmd_args) => CompactCmd::run(weather_admin, cmd_args),
+                (NormalizeCmd::NAME, cmd_args) => NormalizeCmd::run(weather_admin, cmd_args),
                 (UsCitiesCmd::NAME, cmd_args) => UsCitiesCmd::run(weather_admin, cmd_args),
                 _ => unreachable!("Admin command should not be here..."),
             }
@@ -0,0 +1,63 @@
+//! The compact weather history archives command.
+use super::*;
+use toolslib::{fmt::commafy, rptcols, rptrow, text, text::Report};
+use weather_lib::prelude::DataCriteria;
+
+pub(super) use v3::CompactCmd;
+mod v3 {
+    //! The current version of the compact command.
+    use super::*;
+
+    #[derive(Debug)]
+    pub struct CompactCmd(
+        /// The compact command arguments
+        ArgMatches,
+    );
+
+    impl CompactCmd {
+        /// The compact sub-command name.
+        pub const NAME: &'static str = "compact";
+        /// The command argument id for which archives should be compacted.
+        const CRITERIA: &'static str = "CRITERIA";
+        /// Get the compact sub-command definition.
+        pub fn get() -> Command {
+            Command::new(Self::NAME).about("Rewrite weather history archives using maximum compression.").arg(
+                Arg::new(Self::CRITERIA)
+                    .value_name("LOCATION")
+                    .action(ArgAction::Append)
+                    .help("The locations that will be compacted (supports wildcards)."),
+            )
+        }
+        /// Collect the command line arguments and run the compact command.
+        ///
+        /// # Arguments
+        ///
+        /// * `admin_api` is the backend weather administration `API`.
+        /// * `args` is the compact command arguments.
+        pub fn run(admin_api: &WeatherAdmin, args: ArgMatches) -> Result<()> {
+            let cmd_args = Self(args);
+            let criteria = DataCriteria { filters: cmd_args.criteria(), icase: true, sort: true, alias_only: false };
+            let compaction_reports = admin_api.compact(criteria)?;
+            let mut report = Report::from(rptcols!(<, >, >, >));
+            report.header(rptrow!(^ "Location", ^ "Histories", ^ "Before", ^ "After")).separator("-");
+            for compaction_report in compaction_reports {
+                report.text(rptrow!(
+                    compaction_report.alias,
+                    commafy(compaction_report.histories),
+                    commafy(compaction_report.before),
+                    commafy(compaction_report.after)
+                ));
+            }
+            let mut writer = text::get_writer(&None, false)?;
+            text::write_strings(&mut writer, report.into_iter())?;
+            Ok(())
+        }
+        /// Get the location criteria arguments.
+        fn criteria(&self) -> Vec<String> {
+            match self.0.get_many::<String>(Self::CRITERIA) {
+                Some(filters) => filters.cloned().collect(),
+                None => vec![],
+            }
+        }
+    }
+}
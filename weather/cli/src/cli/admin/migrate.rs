@@ -71,7 +71,7 @@ mod v3 {
             let into = cmd_args.into_dir();
             let create = cmd_args.create();
             let retain = cmd_args.retain();
-            let criteria = DataCriteria { filters: cmd_args.criteria(), icase: true, sort: false };
+            let criteria = DataCriteria { filters: cmd_args.criteria(), icase: true, sort: false, alias_only: false };
             let convert_count = admin_api.migrate(into, create, retain, criteria)?;
             log::info!("{} archives converted.", convert_count);
             Ok(())
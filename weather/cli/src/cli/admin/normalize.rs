@@ -0,0 +1,62 @@
+//! The normalize weather history archives command.
+use super::*;
+use toolslib::{fmt::commafy, rptcols, rptrow, text, text::Report};
+use weather_lib::prelude::DataCriteria;
+
+pub(super) use v3::NormalizeCmd;
+mod v3 {
+    //! The current version of the normalize command.
+    use super::*;
+
+    #[derive(Debug)]
+    pub struct NormalizeCmd(
+        /// The normalize command arguments
+        ArgMatches,
+    );
+
+    impl NormalizeCmd {
+        /// The normalize sub-command name.
+        pub const NAME: &'static str = "normalize";
+        /// The command argument id for which archives should be normalized.
+        const CRITERIA: &'static str = "CRITERIA";
+        /// Get the normalize sub-command definition.
+        pub fn get() -> Command {
+            Command::new(Self::NAME).about("Rewrite weather history archives in ascending date order.").arg(
+                Arg::new(Self::CRITERIA)
+                    .value_name("LOCATION")
+                    .action(ArgAction::Append)
+                    .help("The locations that will be normalized (supports wildcards)."),
+            )
+        }
+        /// Collect the command line arguments and run the normalize command.
+        ///
+        /// # Arguments
+        ///
+        /// * `admin_api` is the backend weather administration `API`.
+        /// * `args` is the normalize command arguments.
+        pub fn run(admin_api: &WeatherAdmin, args: ArgMatches) -> Result<()> {
+            let cmd_args = Self(args);
+            let criteria = DataCriteria { filters: cmd_args.criteria(), icase: true, sort: true, alias_only: false };
+            let normalize_reports = admin_api.normalize(criteria)?;
+            let mut report = Report::from(rptcols!(<, >, ^));
+            report.header(rptrow!(^ "Location", ^ "Histories", ^ "Rewritten")).separator("-");
+            for normalize_report in normalize_reports {
+                let rewritten = match normalize_report.rewritten {
+                    true => "yes",
+                    false => "no",
+                };
+                report.text(rptrow!(normalize_report.alias, commafy(normalize_report.histories), rewritten));
+            }
+            let mut writer = text::get_writer(&None, false)?;
+            text::write_strings(&mut writer, report.into_iter())?;
+            Ok(())
+        }
+        /// Get the location criteria arguments.
+        fn criteria(&self) -> Vec<String> {
+            match self.0.get_many::<String>(Self::CRITERIA) {
+                Some(filters) => filters.cloned().collect(),
+                None => vec![],
+            }
+        }
+    }
+}
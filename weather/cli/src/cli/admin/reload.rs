@@ -38,7 +38,7 @@ mod v3 {
         /// * `args` is the migrate command arguments.
         pub fn run(admin_api: &WeatherAdmin, args: ArgMatches) -> Result<()> {
             let cmd_args = Self(args);
-            let criteria = DataCriteria { filters: cmd_args.criteria(), icase: true, sort: false };
+            let criteria = DataCriteria { filters: cmd_args.criteria(), icase: true, sort: false, alias_only: false };
             let sync_count = admin_api.reload(criteria)?;
             log::info!("{} archives converted.", sync_count);
             Ok(())
@@ -125,6 +125,7 @@ mod v3 {
                 report.text(rptrow!(=format!("Total cities: {}", mbufmt!(total_cities))));
                 report.text(rptrow!(=format!("Database size: {}", mbufmt!(uscities_info.db_size))));
             }
+            report.shade_rows(&None);
             let mut writer = text::get_writer(&None, false)?;
             text::write_strings(&mut writer, report.into_iter())?;
             Ok(())
@@ -0,0 +1,117 @@
+//! The report writer that sends report content to the system clipboard.
+//!
+//! The clipboard is only available when the CLI is built with the `clipboard` feature.
+use std::io;
+
+/// Where the clipboard writer sends report content.
+pub trait ClipboardSink {
+    /// Replace the contents of the clipboard.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` is the clipboard content.
+    ///
+    fn set_text(&mut self, text: &str) -> io::Result<()>;
+}
+
+/// The system clipboard.
+pub struct SystemClipboard(
+    /// The platform clipboard.
+    arboard::Clipboard,
+);
+impl SystemClipboard {
+    /// Connect to the platform clipboard.
+    ///
+    /// An error is returned if the platform clipboard is not available.
+    ///
+    pub fn new() -> io::Result<Self> {
+        match arboard::Clipboard::new() {
+            Ok(clipboard) => Ok(Self(clipboard)),
+            Err(err) => Err(io::Error::new(io::ErrorKind::Unsupported, format!("clipboard is not available ({})", err))),
+        }
+    }
+}
+impl ClipboardSink for SystemClipboard {
+    fn set_text(&mut self, text: &str) -> io::Result<()> {
+        match self.0.set_text(text) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(io::Error::other(format!("clipboard update failed ({})", err))),
+        }
+    }
+}
+
+/// A writer that sends everything written to the clipboard when it is flushed or dropped.
+pub struct ClipboardWriter<S: ClipboardSink> {
+    /// Where the report content is sent.
+    sink: S,
+    /// The report content that has been written.
+    content: Vec<u8>,
+    /// Indicates content has been written since the clipboard was last updated.
+    pending: bool,
+}
+impl<S: ClipboardSink> ClipboardWriter<S> {
+    /// Create a new instance of the clipboard writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `sink` is where the report content will be sent.
+    ///
+    pub fn new(sink: S) -> Self {
+        Self { sink, content: vec![], pending: false }
+    }
+}
+impl<S: ClipboardSink> io::Write for ClipboardWriter<S> {
+    /// The content is buffered until the writer is flushed.
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.content.extend_from_slice(buf);
+        self.pending = true;
+        Ok(buf.len())
+    }
+    /// The clipboard is replaced with all of the content written so far.
+    fn flush(&mut self) -> io::Result<()> {
+        if self.pending {
+            self.sink.set_text(&String::from_utf8_lossy(&self.content))?;
+            self.pending = false;
+        }
+        Ok(())
+    }
+}
+impl<S: ClipboardSink> Drop for ClipboardWriter<S> {
+    /// Make sure the clipboard has the content even if the writer was not flushed.
+    fn drop(&mut self) {
+        if let Err(err) = io::Write::flush(self) {
+            log::error!("{}", err);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{cell::RefCell, io::Write, rc::Rc};
+
+    struct MockClipboard(Rc<RefCell<(String, usize)>>);
+    impl ClipboardSink for MockClipboard {
+        fn set_text(&mut self, text: &str) -> io::Result<()> {
+            self.0.replace_with(|(_, updates)| (text.to_string(), *updates + 1));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn writer() {
+        let clipboard = Rc::new(RefCell::new((String::default(), 0)));
+        let mut testcase = ClipboardWriter::new(MockClipboard(clipboard.clone()));
+        let report = "date,temperatureHigh\n2024-01-01,50\n2024-01-02,51\n";
+        testcase.write_all(&report.as_bytes()[..10]).unwrap();
+        testcase.write_all(&report.as_bytes()[10..]).unwrap();
+        assert_eq!(*clipboard.borrow(), (String::default(), 0));
+        testcase.flush().unwrap();
+        testcase.flush().unwrap();
+        assert_eq!(*clipboard.borrow(), (report.to_string(), 1));
+        // dropping the writer sends content that was not flushed
+        testcase.write_all(report.as_bytes()).unwrap();
+        drop(testcase);
+        assert_eq!(*clipboard.borrow(), (format!("{}{}", report, report), 2));
+    }
+}
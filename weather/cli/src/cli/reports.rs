@@ -1,4 +1,5 @@
 //! The Weather Data reports.
+pub mod diff_history;
 pub mod list_history;
 pub mod list_locations;
 pub mod list_summary;
@@ -66,3 +67,92 @@ macro_rules! text_title_separator {
 /// Create separators between header rows and text rows.
 ///
 use text_title_separator;
+
+/// Pad the fields of a `CSV` document so the columns line up.
+///
+/// The comma delimiters are kept so the document can still be parsed, fields simply need to be
+/// trimmed. The last column is not padded to avoid trailing whitespace.
+///
+/// # Arguments
+///
+/// - `csv` is the document that will be aligned.
+///
+pub fn align_csv(csv: String) -> String {
+    let mut reader = csv_lib::ReaderBuilder::new().has_headers(false).flexible(true).from_reader(csv.as_bytes());
+    let mut rows: Vec<Vec<String>> = vec![];
+    for record in reader.records() {
+        match record {
+            Ok(record) => rows.push(record.iter().map(csv_field).collect()),
+            Err(err) => {
+                log::error!("Did not align CSV ({}).", err);
+                return csv;
+            }
+        }
+    }
+    let mut widths: Vec<usize> = vec![];
+    for row in &rows {
+        for (column, field) in row.iter().enumerate() {
+            let width = field.chars().count();
+            match widths.get_mut(column) {
+                Some(max_width) => *max_width = width.max(*max_width),
+                None => widths.push(width),
+            }
+        }
+    }
+    let mut aligned = String::with_capacity(csv.len());
+    for row in rows {
+        let last = row.len().saturating_sub(1);
+        let fields: Vec<String> = row
+            .into_iter()
+            .enumerate()
+            .map(|(column, field)| match column == last {
+                true => field,
+                false => format!("{:<width$}", field, width = widths[column]),
+            })
+            .collect();
+        aligned.push_str(&fields.join(","));
+        aligned.push('\n');
+    }
+    aligned
+}
+
+/// Quote a `CSV` field if it contains a delimiter, quote, or line ending.
+///
+/// # Arguments
+///
+/// - `field` is the unquoted field content.
+///
+fn csv_field(field: &str) -> String {
+    match field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligned_csv() {
+        let csv = "date,high,description\n2024-01-01,5,\"cold, windy\"\n2024-01-02,51.5,sunny\n".to_string();
+        let testcase = align_csv(csv.clone());
+        let mut lines = testcase.lines();
+        assert_eq!(lines.next(), Some("date      ,high,description"));
+        assert_eq!(lines.next(), Some("2024-01-01,5   ,\"cold, windy\""));
+        assert_eq!(lines.next(), Some("2024-01-02,51.5,sunny"));
+        assert_eq!(lines.next(), None);
+        // the aligned document parses to the same fields once they are trimmed
+        fn records(csv: &str) -> Vec<Vec<String>> {
+            csv_lib::ReaderBuilder::new()
+                .has_headers(false)
+                .trim(csv_lib::Trim::All)
+                .from_reader(csv.as_bytes())
+                .records()
+                .map(|record| record.unwrap().iter().map(|field| field.to_string()).collect())
+                .collect()
+        }
+        assert_eq!(records(&testcase), records(&csv));
+        assert_eq!(records(&testcase)[1][2], "cold, windy");
+    }
+}
@@ -0,0 +1,322 @@
+//! The weather history differences report.
+use super::*;
+use chrono::NaiveDate;
+use weather_lib::prelude::DailyHistories;
+
+/// The temperatures of two locations for a common history date.
+#[derive(Debug, PartialEq)]
+pub struct HistoryDelta {
+    /// The history date.
+    pub date: NaiveDate,
+    /// The first location high and low temperatures.
+    pub lhs: (Option<f64>, Option<f64>),
+    /// The second location high and low temperatures.
+    pub rhs: (Option<f64>, Option<f64>),
+}
+impl HistoryDelta {
+    /// The difference in high temperature between the locations.
+    pub fn high(&self) -> Option<f64> {
+        difference(self.lhs.0, self.rhs.0)
+    }
+    /// The difference in low temperature between the locations.
+    pub fn low(&self) -> Option<f64> {
+        difference(self.lhs.1, self.rhs.1)
+    }
+}
+
+/// Get the difference between two temperatures if both are available.
+///
+/// # Arguments
+///
+/// - `lhs` is the first location temperature.
+/// - `rhs` is the second location temperature.
+///
+fn difference(lhs: Option<f64>, rhs: Option<f64>) -> Option<f64> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(lhs - rhs),
+        _ => None,
+    }
+}
+
+/// The weather history differences between two locations.
+#[derive(Debug)]
+pub struct HistoryDeltas {
+    /// The first location name.
+    pub lhs: String,
+    /// The second location name.
+    pub rhs: String,
+    /// The temperature differences for dates both locations have history.
+    pub deltas: Vec<HistoryDelta>,
+}
+impl HistoryDeltas {
+    /// Align the histories of two locations by date.
+    ///
+    /// Only dates where both locations have weather history are included.
+    ///
+    /// # Arguments
+    ///
+    /// - `lhs` is the first location weather history.
+    /// - `rhs` is the second location weather history.
+    ///
+    pub fn new(lhs: DailyHistories, rhs: DailyHistories) -> Self {
+        let deltas = lhs
+            .histories
+            .iter()
+            .filter_map(|lhs_history| {
+                rhs.histories.iter().find(|rhs_history| rhs_history.date == lhs_history.date).map(|rhs_history| {
+                    HistoryDelta {
+                        date: lhs_history.date,
+                        lhs: (lhs_history.temperature_high, lhs_history.temperature_low),
+                        rhs: (rhs_history.temperature_high, rhs_history.temperature_low),
+                    }
+                })
+            })
+            .collect();
+        Self { lhs: lhs.location.name, rhs: rhs.location.name, deltas }
+    }
+}
+
+pub mod text {
+    //! The history differences text based reporting implementation.
+    //!
+    use super::*;
+
+    /// Returns a temperature as a string.
+    ///
+    /// If the option is `None` an empty string will be returned.
+    ///
+    fn fmt_temperature(t: Option<f64>) -> String {
+        match t {
+            Some(temperature) => format!("{:>-5.1}", temperature),
+            None => Default::default(),
+        }
+    }
+
+    /// The text based history differences report.
+    ///
+    #[derive(Debug, Default)]
+    pub struct Report(
+        /// Controls if a separator row will be added between the report headers and report text.
+        bool,
+    );
+    impl Report {
+        /// A builder method that control if a separator row will be added between the report headers and report text.
+        pub fn with_title_separator(mut self) -> Self {
+            self.0 = true;
+            self
+        }
+        /// Generates the history differences text based report.
+        ///
+        /// # Arguments
+        ///
+        /// * `history_deltas` is the weather history differences that will be reported.
+        ///
+        pub fn generate(&self, history_deltas: HistoryDeltas) -> ReportSheet {
+            let mut report = ReportSheet::new(vec![
+                layout!(^),
+                layout!(>),
+                layout!(>),
+                layout!(>),
+                layout!(>),
+                layout!(>),
+                layout!(>),
+            ]);
+            report.add_row(vec![
+                header!(""),
+                header!(^ history_deltas.lhs),
+                header!(""),
+                header!(^ history_deltas.rhs),
+                header!(""),
+                header!(^ "Difference"),
+                header!(""),
+            ]);
+            report.add_row(vec![
+                header!("Date"),
+                header!(^ "High"),
+                header!(^ "Low"),
+                header!(^ "High"),
+                header!(^ "Low"),
+                header!(^ "High"),
+                header!(^ "Low"),
+            ]);
+            if self.0 {
+                report.add_row(text_title_separator!(report.columns()));
+            }
+            for delta in history_deltas.deltas {
+                report.add_row(vec![
+                    text!(delta.date.to_string()),
+                    text!(fmt_temperature(delta.lhs.0)),
+                    text!(fmt_temperature(delta.lhs.1)),
+                    text!(fmt_temperature(delta.rhs.0)),
+                    text!(fmt_temperature(delta.rhs.1)),
+                    text!(fmt_temperature(delta.high())),
+                    text!(fmt_temperature(delta.low())),
+                ]);
+            }
+            report
+        }
+    }
+}
+
+pub mod csv {
+    //! The history differences CSV based reporting implementation.
+    //!
+    use super::*;
+
+    /// Returns a float value as a string value.
+    ///
+    /// If option is `None` an empty string will be returned.
+    ///
+    fn float_value(option: Option<f64>) -> String {
+        option.map_or(Default::default(), |float| float.to_string())
+    }
+
+    /// The `CSV` based history differences report.
+    #[derive(Debug, Default)]
+    pub struct Report;
+    impl Report {
+        /// Generates the history differences CSV based report.
+        ///
+        /// # Arguments
+        ///
+        /// * `history_deltas` is the weather history differences that will be reported.
+        ///
+        pub fn generate(&self, history_deltas: HistoryDeltas) -> String {
+            let mut writer = csv_lib::Writer::from_writer(vec![]);
+            csv_write_record!(
+                writer,
+                &[
+                    "date",
+                    "lhsTemperatureHigh",
+                    "lhsTemperatureLow",
+                    "rhsTemperatureHigh",
+                    "rhsTemperatureLow",
+                    "temperatureHighDelta",
+                    "temperatureLowDelta",
+                ]
+            );
+            for delta in history_deltas.deltas {
+                csv_write_record!(
+                    writer,
+                    &[
+                        delta.date.to_string(),
+                        float_value(delta.lhs.0),
+                        float_value(delta.lhs.1),
+                        float_value(delta.rhs.0),
+                        float_value(delta.rhs.1),
+                        float_value(delta.high()),
+                        float_value(delta.low()),
+                    ]
+                );
+            }
+            csv_to_string(writer)
+        }
+    }
+}
+
+pub mod json {
+    //! The history differences JSON based reporting implementation.
+    //!
+    use super::*;
+
+    /// The `JSON` based history differences report.
+    #[derive(Debug, Default)]
+    pub struct Report(
+        /// Controls if the `JSON` document will be pretty printed or not.
+        bool,
+    );
+    impl Report {
+        /// Create a report instance and configure it to pretty print the `JSON` document.
+        ///
+        pub fn pretty_printed() -> Self {
+            Self(true)
+        }
+        /// Generates the history differences JSON based report.
+        ///
+        /// # Arguments
+        ///
+        /// * `history_deltas` is the weather history differences that will be reported.
+        ///
+        pub fn generate(&self, history_deltas: HistoryDeltas) -> String {
+            let deltas: Vec<Value> = history_deltas
+                .deltas
+                .into_iter()
+                .map(|delta| {
+                    json!({
+                        "date": delta.date.to_string(),
+                        "lhsTemperatureHigh": delta.lhs.0,
+                        "lhsTemperatureLow": delta.lhs.1,
+                        "rhsTemperatureHigh": delta.rhs.0,
+                        "rhsTemperatureLow": delta.rhs.1,
+                        "temperatureHighDelta": delta.high(),
+                        "temperatureLowDelta": delta.low(),
+                    })
+                })
+                .collect();
+            let root = json!({
+                "lhs": history_deltas.lhs,
+                "rhs": history_deltas.rhs,
+                "type": "history_diff",
+                "history": deltas,
+            });
+            json_to_string(root, self.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toolslib::date_time::get_date;
+    use weather_lib::prelude::{History, Location};
+
+    fn daily_histories(name: &str, temperatures: &[(u32, f64, f64)]) -> DailyHistories {
+        let location = Location {
+            name: name.to_string(),
+            alias: name.to_lowercase(),
+            longitude: "0".to_string(),
+            latitude: "0".to_string(),
+            tz: "UTC".to_string(),
+        };
+        let histories = temperatures
+            .iter()
+            .map(|(day, high, low)| History {
+                alias: location.alias.clone(),
+                date: get_date(2024, 1, *day),
+                temperature_high: Some(*high),
+                temperature_low: Some(*low),
+                ..Default::default()
+            })
+            .collect();
+        DailyHistories { location, histories }
+    }
+
+    #[test]
+    fn deltas() {
+        let lhs = daily_histories("North", &[(1, 50.0, 30.0), (2, 52.5, 31.0), (3, 49.0, 29.5)]);
+        let rhs = daily_histories("South", &[(2, 60.0, 40.0), (3, 45.0, 30.0), (4, 70.0, 50.0)]);
+        let testcase = HistoryDeltas::new(lhs, rhs);
+        assert_eq!(testcase.lhs, "North");
+        assert_eq!(testcase.rhs, "South");
+        assert_eq!(testcase.deltas.len(), 2);
+        assert_eq!(testcase.deltas[0].date, get_date(2024, 1, 2));
+        assert_eq!(testcase.deltas[0].high(), Some(-7.5));
+        assert_eq!(testcase.deltas[0].low(), Some(-9.0));
+        assert_eq!(testcase.deltas[1].date, get_date(2024, 1, 3));
+        assert_eq!(testcase.deltas[1].high(), Some(4.0));
+        assert_eq!(testcase.deltas[1].low(), Some(-0.5));
+        let csv = csv::Report.generate(testcase);
+        let mut rows = csv.lines();
+        assert!(rows.next().unwrap().starts_with("date,lhsTemperatureHigh"));
+        assert_eq!(rows.next().unwrap(), "2024-01-02,52.5,31,60,40,-7.5,-9");
+        assert_eq!(rows.next().unwrap(), "2024-01-03,49,29.5,45,30,4,-0.5");
+        assert!(rows.next().is_none());
+    }
+
+    #[test]
+    fn missing_temperature() {
+        let delta = HistoryDelta { date: get_date(2024, 1, 1), lhs: (Some(1.0), None), rhs: (None, Some(2.0)) };
+        assert_eq!(delta.high(), None);
+        assert_eq!(delta.low(), None);
+    }
+}
@@ -16,6 +16,8 @@ pub mod text {
         title_separator: bool,
         /// Controls if the location alias name will be included in the report or not.
         skip_alias: bool,
+        /// Controls if the location coordinates and timezone will be included in the report or not.
+        details: bool,
     }
     impl Report {
         /// Controls if a separator row will separate report headers from the report text.
@@ -28,6 +30,11 @@ pub mod text {
             self.skip_alias = true;
             self
         }
+        /// Controls if the location coordinates and timezone will be displayed or not.
+        pub fn with_details(mut self) -> Self {
+            self.details = true;
+            self
+        }
         /// Generates the list locations_win text based report.
         ///
         /// An error will be returned if there are issues writing the report.
@@ -43,16 +50,20 @@ pub mod text {
             if !self.skip_alias {
                 layouts.push(layout!(<))
             }
-            layouts.push(layout!(^ [ll_width * 2 + 1]));
-            layouts.push(layout!(<));
+            if self.details {
+                layouts.push(layout!(^ [ll_width * 2 + 1]));
+                layouts.push(layout!(<));
+            }
             let mut report = ReportSheet::new(layouts);
             let mut headers = vec![];
             headers.push(header!(^ "Location"));
             if !self.skip_alias {
                 headers.push(header!(^ "Alias"));
             }
-            headers.push(header!(^ " Latitude/Longitude"));
-            headers.push(header!(^ "Timezone"));
+            if self.details {
+                headers.push(header!(^ " Latitude/Longitude"));
+                headers.push(header!(^ "Timezone"));
+            }
             report.add_row(headers);
             if self.title_separator {
                 report.add_row(text_title_separator!(report.columns()));
@@ -63,8 +74,10 @@ pub mod text {
                 if !self.skip_alias {
                     content.push(text!(location.alias.as_str()))
                 }
-                content.push(text!(format!("{:>ll_width$}/{:<ll_width$}", &location.latitude, &location.longitude)));
-                content.push(text!(location.tz.as_str()));
+                if self.details {
+                    content.push(text!(format!("{:>ll_width$}/{:<ll_width$}", &location.latitude, &location.longitude)));
+                    content.push(text!(location.tz.as_str()));
+                }
                 report.add_row(content);
             });
             report
@@ -144,3 +157,55 @@ pub mod json {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locations() -> Vec<Location> {
+        vec![Location {
+            name: "Testcase".to_string(),
+            alias: "testcase".to_string(),
+            longitude: "-111.8315".to_string(),
+            latitude: "33.4152".to_string(),
+            tz: "America/Phoenix".to_string(),
+        }]
+    }
+
+    #[test]
+    fn text() {
+        let report = |report: text::Report| {
+            report.generate(&locations()).into_iter().map(|row| row.to_string()).collect::<Vec<String>>().join("\n")
+        };
+        let testcase = report(text::Report::default());
+        assert!(testcase.contains("testcase"));
+        assert!(!testcase.contains("Timezone"));
+        assert!(!testcase.contains("America/Phoenix"));
+        assert!(!testcase.contains("33.4152"));
+        let testcase = report(text::Report::default().with_details());
+        assert!(testcase.contains("Latitude/Longitude"));
+        assert!(testcase.contains("Timezone"));
+        assert!(testcase.contains("America/Phoenix"));
+        assert!(testcase.contains("33.4152"));
+        assert!(testcase.contains("-111.8315"));
+    }
+
+    #[test]
+    fn csv() {
+        let testcase = csv::Report.generate(locations());
+        assert_eq!(
+            testcase,
+            "name,alias,longitude,latitude,tz\nTestcase,testcase,-111.8315,33.4152,America/Phoenix\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let testcase = serde_json::from_str::<Value>(&json::Report::default().generate(locations())).unwrap();
+        let location = &testcase["locations_win"][0];
+        assert_eq!(location["alias"], "testcase");
+        assert_eq!(location["tz"], "America/Phoenix");
+        assert_eq!(location["latitude"], "33.4152");
+        assert_eq!(location["longitude"], "-111.8315");
+    }
+}
@@ -2,25 +2,68 @@
 use super::*;
 use weather_lib::prelude::HistorySummaries;
 
+/// Indicates if a location has fewer days of history than the threshold.
+///
+/// # Arguments
+///
+/// * `history_summary` is the location history summary.
+/// * `threshold` is the minimum count of history days expected.
+fn below_threshold(history_summary: &HistorySummaries, threshold: usize) -> bool {
+    history_summary.count < threshold
+}
+
+/// The history summary size used to order locations.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SizeOrder {
+    /// Order by the size of the history before it was compressed.
+    Raw,
+    /// Order by the size of the history when it is compressed.
+    Compressed,
+}
+
+/// Order the location history summaries from largest to smallest size.
+///
+/// Locations with the same size are ordered by name.
+///
+/// # Arguments
+///
+/// * `location_histories` are the location history summaries that will be ordered.
+/// * `size_order` selects the size used to order locations.
+pub fn sort_by_size(location_histories: &mut [HistorySummaries], size_order: SizeOrder) {
+    let size = |history_summary: &HistorySummaries| match size_order {
+        SizeOrder::Raw => history_summary.raw_size.unwrap_or(0),
+        SizeOrder::Compressed => history_summary.store_size.unwrap_or(0),
+    };
+    location_histories
+        .sort_by(|lhs, rhs| size(rhs).cmp(&size(lhs)).then_with(|| lhs.location.name.cmp(&rhs.location.name)));
+}
+
 pub mod text {
     /// The list summary text based reporting implementation.
     ///
     /// This module utilizes the `text_reports` module to generate reports.
     ///
     use super::*;
-    use toolslib::{fmt::commafy, kib};
+    use toolslib::{fmt::commafy, mbufmt};
 
     /// The metadata controlling the report appearance.
     ///
     #[derive(Debug, Default)]
-    pub struct Report(
+    pub struct Report {
         /// Controls if a separator row will be added between the report headers and report text.
-        bool
-    );
+        title_separator: bool,
+        /// When present locations with fewer days of history will be marked.
+        threshold: Option<usize>,
+    }
     impl Report {
         /// A builder method that control if a separator row will be added between the report headers and report text.
         pub fn with_title_separator(mut self) -> Self {
-            self.0 = true;
+            self.title_separator = true;
+            self
+        }
+        /// A builder method that marks locations having fewer days of history than the threshold.
+        pub fn with_threshold(mut self, threshold: usize) -> Self {
+            self.threshold.replace(threshold);
             self
         }
         /// Generates the locations_win summary text based report.
@@ -33,16 +76,22 @@ pub mod text {
         ///
         // pub fn generate(location_histories: Vec<HistorySummaries>, writer: &mut impl Write) -> Result<()> {
         pub fn generate(&self, location_histories: Vec<HistorySummaries>) -> ReportSheet {
-            let mut report = ReportSheet::new(vec![layout!(<), layout!(>), layout!(>), layout!(>), layout!(>)]);
-            report.add_row(vec![
+            let mut layouts = vec![layout!(<), layout!(>), layout!(>), layout!(>), layout!(>)];
+            let mut headers = vec![
                 header!(^ "Location"),
                 header!(^ "Overall Size"),
                 header!(^ "History Count"),
                 header!(^ "History Size"),
                 header!(^ "Store Size"),
-            ]);
+            ];
+            if self.threshold.is_some() {
+                layouts.push(layout!(^));
+                headers.push(header!(^ "Backfill"));
+            }
+            let mut report = ReportSheet::new(layouts);
+            report.add_row(headers);
             let columns = report.columns();
-            if self.0 {
+            if self.title_separator {
                 report.add_row(text_title_separator!(report.columns()));
             }
             let mut total_size = 0;
@@ -53,26 +102,39 @@ pub mod text {
                 let overall_size = location_history_summary.overall_size.unwrap_or(0);
                 let raw_size = location_history_summary.raw_size.unwrap_or(0);
                 let compressed_size = location_history_summary.store_size.unwrap_or(0);
-                report.add_row(vec![
+                let below = self.threshold.map(|threshold| below_threshold(&location_history_summary, threshold));
+                let mut row = vec![
                     text!(location_history_summary.location.name),
-                    text!(kib!(overall_size, 0)),
+                    text!(mbufmt!(overall_size, 1, true)),
                     text!(commafy(location_history_summary.count)),
-                    text!(kib!(raw_size, 0)),
-                    text!(kib!(compressed_size, 0)),
-                ]);
+                    text!(mbufmt!(raw_size, 1, true)),
+                    text!(mbufmt!(compressed_size, 1, true)),
+                ];
+                if let Some(below) = below {
+                    let marker = match below {
+                        true => "*",
+                        false => "",
+                    };
+                    row.push(text!(marker));
+                }
+                report.add_row(row);
                 total_size += overall_size;
                 total_history_count += location_history_summary.count;
                 total_raw_size += raw_size;
                 total_compressed_size += compressed_size;
             }
             report.add_row((0..columns).into_iter().map(|_| text!(+ "=")).collect());
-            report.add_row(vec![
+            let mut totals = vec![
                 header!("Total"),
-                text!(kib!(total_size, 0)),
+                text!(mbufmt!(total_size, 1, true)),
                 text!(commafy(total_history_count)),
-                text!(kib!(total_raw_size, 0)),
-                text!(kib!(total_compressed_size, 0)),
-            ]);
+                text!(mbufmt!(total_raw_size, 1, true)),
+                text!(mbufmt!(total_compressed_size, 1, true)),
+            ];
+            if self.threshold.is_some() {
+                totals.push(text!(""));
+            }
+            report.add_row(totals);
             report
         }
     }
@@ -84,8 +146,16 @@ pub mod csv {
     use super::*;
 
     #[derive(Debug, Default)]
-    pub struct Report;
+    pub struct Report(
+        /// When present locations with fewer days of history will be flagged.
+        Option<usize>,
+    );
     impl Report {
+        /// A builder method that flags locations having fewer days of history than the threshold.
+        pub fn with_threshold(mut self, threshold: usize) -> Self {
+            self.0.replace(threshold);
+            self
+        }
         /// Generates the list summary CSV based report.
         ///
         /// An error will be returned if there are issues writing the report.
@@ -96,21 +166,27 @@ pub mod csv {
         ///
         pub fn generate(&self, locations_history_summary: Vec<HistorySummaries>) -> String {
             let mut writer = csv_lib::Writer::from_writer(vec![]);
-            csv_write_record!(writer, &["location", "entries", "entries_size", "compressed_size", "size"]);
+            let mut headers = vec!["location", "entries", "entries_size", "compressed_size", "size"];
+            if self.0.is_some() {
+                headers.push("below_threshold");
+            }
+            csv_write_record!(writer, &headers);
             for location_history_summary in locations_history_summary {
                 let raw_size = location_history_summary.raw_size.map_or(0, |v| v);
                 let compressed_size = location_history_summary.store_size.map_or(0, |v| v);
                 let overall_size = location_history_summary.overall_size.map_or(0, |v| v);
-                csv_write_record!(
-                    writer,
-                    &[
-                        location_history_summary.location.name,
-                        location_history_summary.count.to_string(),
-                        raw_size.to_string(),
-                        compressed_size.to_string(),
-                        overall_size.to_string(),
-                    ]
-                );
+                let below = self.0.map(|threshold| below_threshold(&location_history_summary, threshold));
+                let mut record = vec![
+                    location_history_summary.location.name,
+                    location_history_summary.count.to_string(),
+                    raw_size.to_string(),
+                    compressed_size.to_string(),
+                    overall_size.to_string(),
+                ];
+                if let Some(below) = below {
+                    record.push(below.to_string());
+                }
+                csv_write_record!(writer, &record);
             }
             csv_to_string(writer)
         }
@@ -124,15 +200,22 @@ pub mod json {
 
     /// The list summary JSON report.
     #[derive(Debug, Default)]
-    pub struct Report (
+    pub struct Report {
         /// Controls if the `JSON` document will be pretty printed or not.
-        bool
-    );
+        pretty: bool,
+        /// When present locations with fewer days of history will be flagged.
+        threshold: Option<usize>,
+    }
     impl Report {
         /// Create a report instance and configure it to pretty print the `JSON` document.
         ///
         pub fn pretty_printed() -> Self {
-            Self(true)
+            Self { pretty: true, threshold: None }
+        }
+        /// A builder method that flags locations having fewer days of history than the threshold.
+        pub fn with_threshold(mut self, threshold: usize) -> Self {
+            self.threshold.replace(threshold);
+            self
         }
         /// Generates the list summary JSON based report.
         ///
@@ -146,17 +229,112 @@ pub mod json {
             let location_array: Vec<Value> = location_histories
                 .into_iter()
                 .map(|location_history_summary| {
-                    json!({
+                    let below = self.threshold.map(|threshold| below_threshold(&location_history_summary, threshold));
+                    let mut location = json!({
                         "location": location_history_summary.location.name,
                         "entries": location_history_summary.count,
                         "entries_size": location_history_summary.raw_size.map_or(0, |v| v),
                         "compressed_size": location_history_summary.store_size.map_or(0, |v| v),
                         "size": location_history_summary.overall_size.map_or(0, |v| v),
-                    })
+                    });
+                    if let Some(below) = below {
+                        location["below_threshold"] = json!(below);
+                    }
+                    location
                 })
                 .collect();
             let root = json!({ "locations_win": location_array });
-            json_to_string(root, self.0)
+            json_to_string(root, self.pretty)
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use weather_lib::prelude::Location;
+
+    fn history_summaries() -> Vec<HistorySummaries> {
+        let summary = |name: &str, count: usize| HistorySummaries {
+            location: Location {
+                name: name.to_string(),
+                alias: name.to_lowercase(),
+                longitude: "-111.8315".to_string(),
+                latitude: "33.4152".to_string(),
+                tz: "America/Phoenix".to_string(),
+            },
+            count,
+            overall_size: None,
+            raw_size: None,
+            store_size: None,
+        };
+        vec![summary("Sparse", 9), summary("Complete", 10)]
+    }
+
+    #[test]
+    fn text() {
+        let report = |report: text::Report| {
+            report.generate(history_summaries()).into_iter().map(|row| row.to_string()).collect::<Vec<String>>()
+        };
+        let testcase = report(text::Report::default());
+        assert!(!testcase[0].contains("Backfill"));
+        let testcase = report(text::Report::default().with_threshold(10));
+        assert!(testcase[0].contains("Backfill"));
+        assert!(testcase[1].contains("Sparse") && testcase[1].trim_end().ends_with('*'));
+        assert!(testcase[2].contains("Complete") && !testcase[2].contains('*'));
+    }
+
+    #[test]
+    fn sort_by_size() {
+        let sized_summaries = || {
+            let mut history_summaries = history_summaries();
+            history_summaries[0].raw_size = Some(3 * 1024 * 1024);
+            history_summaries[0].store_size = Some(512 * 1024);
+            history_summaries[1].raw_size = Some(1024 * 1024);
+            history_summaries[1].store_size = Some(1024 * 1024);
+            history_summaries
+        };
+        let names = |history_summaries: &[HistorySummaries]| -> Vec<String> {
+            history_summaries.iter().map(|summary| summary.location.name.clone()).collect()
+        };
+        let mut testcase = sized_summaries();
+        super::sort_by_size(&mut testcase, SizeOrder::Compressed);
+        assert_eq!(names(&testcase), vec!["Complete", "Sparse"]);
+        super::sort_by_size(&mut testcase, SizeOrder::Raw);
+        assert_eq!(names(&testcase), vec!["Sparse", "Complete"]);
+        // the same size is ordered by name
+        let mut testcase = history_summaries();
+        super::sort_by_size(&mut testcase, SizeOrder::Raw);
+        assert_eq!(names(&testcase), vec!["Complete", "Sparse"]);
+        // the sizes are human readable
+        let rows: Vec<String> =
+            text::Report::default().generate(sized_summaries()).into_iter().map(|row| row.to_string()).collect();
+        let totals = rows.last().unwrap();
+        assert!(totals.contains("4.0 MiB"));
+        assert!(totals.contains("1.5 MiB"));
+        assert!(rows[1].contains("512.0 KiB"));
+    }
+
+    #[test]
+    fn csv() {
+        let testcase = csv::Report::default().generate(history_summaries());
+        assert!(!testcase.contains("below_threshold"));
+        let testcase = csv::Report::default().with_threshold(10).generate(history_summaries());
+        assert_eq!(
+            testcase,
+            "location,entries,entries_size,compressed_size,size,below_threshold\n\
+             Sparse,9,0,0,0,true\n\
+             Complete,10,0,0,0,false\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let parse = |report: String| serde_json::from_str::<Value>(&report).unwrap();
+        let testcase = parse(json::Report::default().generate(history_summaries()));
+        assert!(testcase["locations_win"][0].get("below_threshold").is_none());
+        let testcase = parse(json::Report::default().with_threshold(10).generate(history_summaries()));
+        assert_eq!(testcase["locations_win"][0]["below_threshold"], true);
+        assert_eq!(testcase["locations_win"][1]["below_threshold"], false);
+    }
+}
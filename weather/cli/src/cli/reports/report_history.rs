@@ -2,7 +2,8 @@
 use super::*;
 use chrono::prelude::*;
 use chrono_tz::*;
-use weather_lib::prelude::DailyHistories;
+use toolslib::date_time::date_range_iter;
+use weather_lib::prelude::{DailyHistories, DateRange, History};
 
 /// The report content selection categories.
 #[derive(Debug, Default)]
@@ -17,6 +18,321 @@ pub struct ReportSelector {
     pub summary: bool,
 }
 
+/// The naming style of the `CSV` and `JSON` report field names.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum HeaderStyle {
+    /// Field names are camel case (`temperatureHigh`).
+    #[default]
+    Camel,
+    /// Field names are snake case (`temperature_high`).
+    Snake,
+}
+impl HeaderStyle {
+    /// Get the name of the header style used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            HeaderStyle::Camel => "camel",
+            HeaderStyle::Snake => "snake",
+        }
+    }
+    /// Get the field name for the header style.
+    ///
+    /// # Arguments
+    ///
+    /// - `name` is the camel case field name found in [FIELD_NAMES].
+    ///
+    pub fn field_name(&self, name: &'static str) -> &'static str {
+        match self {
+            HeaderStyle::Camel => name,
+            HeaderStyle::Snake => FIELD_NAMES
+                .iter()
+                .find_map(|(camel, snake)| if *camel == name { Some(*snake) } else { None })
+                .unwrap_or(name),
+        }
+    }
+}
+impl TryFrom<&str> for HeaderStyle {
+    type Error = String;
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "camel" => Ok(HeaderStyle::Camel),
+            "snake" => Ok(HeaderStyle::Snake),
+            _ => Err(format!("'{}' is not a header style (camel or snake).", value)),
+        }
+    }
+}
+
+/// The layout of the `JSON` report document.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum JsonShape {
+    /// The daily histories are a `history` array within an object that describes the location.
+    #[default]
+    Object,
+    /// The document is the array of daily histories, each day includes the location name.
+    Array,
+}
+impl JsonShape {
+    /// Get the name of the JSON document layout used on the command line.
+    pub fn name(&self) -> &'static str {
+        match self {
+            JsonShape::Object => "object",
+            JsonShape::Array => "array",
+        }
+    }
+}
+impl TryFrom<&str> for JsonShape {
+    type Error = String;
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        match value {
+            "object" => Ok(JsonShape::Object),
+            "array" => Ok(JsonShape::Array),
+            _ => Err(format!("'{}' is not a JSON shape (array or object).", value)),
+        }
+    }
+}
+
+/// The report field names as camel case and snake case pairs.
+///
+/// Both the `CSV` and `JSON` reports use this table so the field names stay in sync.
+const FIELD_NAMES: [(&str, &str); 18] = [
+    ("temperatureHigh", "temperature_high"),
+    ("temperatureLow", "temperature_low"),
+    ("temperatureMean", "temperature_mean"),
+    ("dewPoint", "dew_point"),
+    ("cloudCover", "cloud_cover"),
+    ("humidity", "humidity"),
+    ("precip", "precip"),
+    ("precipChance", "precip_chance"),
+    ("precipType", "precip_type"),
+    ("windSpeed", "wind_speed"),
+    ("windGust", "wind_gust"),
+    ("windBearing", "wind_bearing"),
+    ("uvIndex", "uv_index"),
+    ("pressure", "pressure"),
+    ("sunrise", "sunrise"),
+    ("sunset", "sunset"),
+    ("moonPhase", "moon_phase"),
+    ("summary", "summary"),
+];
+
+/// The high and low temperature statistics used as the baseline of a temperature anomaly report.
+#[derive(Debug, Default, PartialEq)]
+pub struct TemperatureBaseline {
+    /// The mean high temperature.
+    pub high: Option<f64>,
+    /// The standard deviation of the high temperatures.
+    pub high_deviation: Option<f64>,
+    /// The mean low temperature.
+    pub low: Option<f64>,
+    /// The standard deviation of the low temperatures.
+    pub low_deviation: Option<f64>,
+}
+impl TemperatureBaseline {
+    /// Calculate the temperature baseline of weather histories.
+    ///
+    /// # Arguments
+    ///
+    /// - `histories` are the weather histories used to calculate the baseline.
+    ///
+    pub fn new(histories: &[History]) -> Self {
+        let (high, high_deviation) = mean_deviation(histories.iter().filter_map(|history| history.temperature_high));
+        let (low, low_deviation) = mean_deviation(histories.iter().filter_map(|history| history.temperature_low));
+        Self { high, high_deviation, low, low_deviation }
+    }
+    /// Identify a day whose temperatures are more than one standard deviation from the baseline.
+    ///
+    /// # Arguments
+    ///
+    /// - `history` has the high and low temperature anomalies of a day.
+    ///
+    pub fn classify(&self, history: &History) -> &'static str {
+        let exceeds = |anomaly: Option<f64>, deviation: Option<f64>| match (anomaly, deviation) {
+            (Some(anomaly), Some(deviation)) if deviation > 0.0 => anomaly / deviation,
+            _ => 0.0,
+        };
+        if exceeds(history.temperature_high, self.high_deviation) > 1.0 {
+            "hot"
+        } else if exceeds(history.temperature_low, self.low_deviation) < -1.0 {
+            "cold"
+        } else {
+            ""
+        }
+    }
+}
+
+/// Calculate the mean and population standard deviation of values.
+///
+/// # Arguments
+///
+/// - `values` are what will be used for the calculation.
+///
+fn mean_deviation(values: impl Iterator<Item = f64>) -> (Option<f64>, Option<f64>) {
+    let values: Vec<f64> = values.collect();
+    if values.is_empty() {
+        (None, None)
+    } else {
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+        let variance = values.iter().map(|value| (value - mean).powi(2)).sum::<f64>() / count;
+        (Some(mean), Some(variance.sqrt()))
+    }
+}
+
+/// The summary statistics of the high and low temperatures over a range of days.
+#[derive(Debug, Default, PartialEq)]
+pub struct TemperatureStats {
+    /// The number of days in the range.
+    pub days: usize,
+    /// The mean high temperature.
+    pub high_mean: Option<f64>,
+    /// The lowest high temperature.
+    pub high_min: Option<f64>,
+    /// The highest high temperature.
+    pub high_max: Option<f64>,
+    /// The mean low temperature.
+    pub low_mean: Option<f64>,
+    /// The lowest low temperature.
+    pub low_min: Option<f64>,
+    /// The highest low temperature.
+    pub low_max: Option<f64>,
+}
+impl TemperatureStats {
+    /// Calculate the temperature statistics of weather histories.
+    ///
+    /// # Arguments
+    ///
+    /// - `histories` are the weather histories that will be summarized.
+    ///
+    pub fn new(histories: &[History]) -> Self {
+        let (high_mean, high_min, high_max) =
+            mean_min_max(histories.iter().filter_map(|history| history.temperature_high));
+        let (low_mean, low_min, low_max) = mean_min_max(histories.iter().filter_map(|history| history.temperature_low));
+        Self { days: histories.len(), high_mean, high_min, high_max, low_mean, low_min, low_max }
+    }
+}
+
+/// Calculate the mean, minimum, and maximum of values.
+///
+/// # Arguments
+///
+/// - `values` are what will be used for the calculation.
+///
+fn mean_min_max(values: impl Iterator<Item = f64>) -> (Option<f64>, Option<f64>, Option<f64>) {
+    let values: Vec<f64> = values.collect();
+    if values.is_empty() {
+        (None, None, None)
+    } else {
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+        let min = values.iter().copied().fold(f64::MAX, f64::min);
+        let max = values.iter().copied().fold(f64::MIN, f64::max);
+        (Some(mean), Some(min), Some(max))
+    }
+}
+
+/// Replace the daily high and low temperatures with their deviation from the temperature baseline.
+///
+/// This is a two pass calculation, the baseline is calculated over the histories and then each
+/// days temperature is replaced by the raw temperature minus the baseline mean.
+///
+/// # Arguments
+///
+/// - `daily_histories` are the weather histories whose temperatures will be replaced.
+///
+pub fn temperature_anomalies(mut daily_histories: DailyHistories) -> (TemperatureBaseline, DailyHistories) {
+    let baseline = TemperatureBaseline::new(&daily_histories.histories);
+    for history in daily_histories.histories.iter_mut() {
+        history.temperature_high = history.temperature_high.zip(baseline.high).map(|(raw, mean)| raw - mean);
+        history.temperature_low = history.temperature_low.zip(baseline.low).map(|(raw, mean)| raw - mean);
+    }
+    (baseline, daily_histories)
+}
+
+/// Keep the days whose high temperature falls within a range.
+///
+/// Days without a high temperature are removed when either bound is provided.
+///
+/// # Arguments
+///
+/// - `daily_histories` are the weather histories that will be filtered.
+/// - `min` is the lowest high temperature that will be kept.
+/// - `max` is the highest high temperature that will be kept.
+///
+pub fn filter_high_temperature(
+    mut daily_histories: DailyHistories,
+    min: Option<f64>,
+    max: Option<f64>,
+) -> DailyHistories {
+    if min.is_some() || max.is_some() {
+        daily_histories.histories.retain(|history| match history.temperature_high {
+            Some(high) => min.is_none_or(|min| high >= min) && max.is_none_or(|max| high <= max),
+            None => false,
+        });
+    }
+    daily_histories
+}
+
+/// Keep every Nth day of the histories starting with the first day.
+///
+/// # Arguments
+///
+/// - `daily_histories` are the weather histories that will be sampled.
+/// - `every` is the number of days between samples, `0` and `1` keep every day.
+///
+pub fn sample_histories(mut daily_histories: DailyHistories, every: usize) -> DailyHistories {
+    if every > 1 {
+        daily_histories.histories = daily_histories
+            .histories
+            .into_iter()
+            .enumerate()
+            .filter(|(index, _)| index % every == 0)
+            .map(|(_, history)| history)
+            .collect();
+    }
+    daily_histories
+}
+
+/// Render the history times in UTC instead of the location timezone.
+///
+/// # Arguments
+///
+/// - `daily_histories` are the weather histories whose times will be rendered in UTC.
+///
+pub fn utc_times(mut daily_histories: DailyHistories) -> DailyHistories {
+    daily_histories.location.tz = "UTC".to_string();
+    daily_histories
+}
+
+/// Add a placeholder history for each day in the date range that does not have weather history.
+///
+/// The placeholder histories do not have any values so reports show them as empty. Days that have
+/// weather history but were filtered out of the histories are not filled in.
+///
+/// # Arguments
+///
+/// - `daily_histories` are the weather histories that will be filled in.
+/// - `date_range` covers the days that will be reported.
+/// - `history_dates` are the dates, in ascending order, that have weather history.
+///
+pub fn fill_gaps(
+    daily_histories: DailyHistories,
+    date_range: &DateRange,
+    history_dates: &[NaiveDate],
+) -> DailyHistories {
+    let DailyHistories { location, histories } = daily_histories;
+    let mut histories = histories.into_iter().peekable();
+    let mut filled = vec![];
+    for date in date_range_iter(date_range.from, date_range.to) {
+        // histories are in date order so anything before the date is outside the range
+        while histories.next_if(|history| history.date < date).is_some() {}
+        match histories.next_if(|history| history.date == date) {
+            Some(history) => filled.push(history),
+            None if history_dates.binary_search(&date).is_ok() => (),
+            None => filled.push(History { alias: location.alias.clone(), date, ..Default::default() }),
+        }
+    }
+    DailyHistories { location, histories: filled }
+}
+
 fn sanitize_report_selector(report_selector: &mut ReportSelector) {
     if !(report_selector.precipitation || report_selector.conditions || report_selector.summary) {
         // temperatures is the default
@@ -31,11 +347,14 @@ pub mod text {
     use std::fmt::Write;
     use toolslib::{
         date_time::{fmt_date, get_tz_ts},
-        fmt::fmt_float,
+        fmt::{fmt_float, format_percent_width},
     };
 
     const DEFAULT_DATE_FORMAT: &'static str = "%Y-%m-%d";
 
+    /// The default number of decimal places used for temperatures.
+    pub const DEFAULT_PRECISION: usize = 1;
+
     /// The text based history report.
     ///
     #[derive(Debug)]
@@ -46,6 +365,10 @@ pub mod text {
         title_separator: bool,
         /// Allow the dates to have a custom format
         date_format: Option<String>,
+        /// When available the high and low temperatures are anomalies from this baseline.
+        baseline: Option<TemperatureBaseline>,
+        /// The number of decimal places used for temperatures, wind, and pressure.
+        precision: usize,
     }
     impl Report {
         /// Create a new instance of the text based history report.
@@ -56,7 +379,13 @@ pub mod text {
         ///
         pub fn new(mut report_selector: ReportSelector) -> Self {
             sanitize_report_selector(&mut report_selector);
-            Self { report_selector, title_separator: false, date_format: None }
+            Self {
+                report_selector,
+                title_separator: false,
+                date_format: None,
+                baseline: None,
+                precision: DEFAULT_PRECISION,
+            }
         }
         /// Add a separator between header rows and report text rows.
         ///
@@ -64,6 +393,26 @@ pub mod text {
             self.title_separator = true;
             self
         }
+        /// Report the high and low temperatures as anomalies from a temperature baseline.
+        ///
+        /// # Arguments
+        ///
+        /// - `baseline` is the temperature baseline used to create the anomalies.
+        ///
+        pub fn with_anomaly(mut self, baseline: TemperatureBaseline) -> Self {
+            self.baseline.replace(baseline);
+            self
+        }
+        /// Set the number of decimal places used for temperatures, wind, and pressure.
+        ///
+        /// # Arguments
+        ///
+        /// - `precision` is the number of decimal places.
+        ///
+        pub fn with_precision(mut self, precision: usize) -> Self {
+            self.precision = precision;
+            self
+        }
         /// Use a custom date format for report dates.
         ///
         /// # Arguments
@@ -115,6 +464,11 @@ pub mod text {
                 layouts!(vec![layout!(^), layout!(^), layout!(^), layout!(^)]);
                 header1!(vec![header!(+ "-"), header!("Temperature"), header!(+ "-"), header!("Dew")]);
                 header2!(vec![header!("High"), header!("Low"), header!("Mean"), header!("Point")]);
+                if self.baseline.is_some() {
+                    layouts!(vec![layout!(<)]);
+                    header1!(vec![header!("")]);
+                    header2!(vec![header!("Anomaly")]);
+                }
             }
             if self.report_selector.precipitation {
                 layouts!(vec![layout!(^), layout!(^), layout!(^), layout!(^), layout!(^)]);
@@ -152,14 +506,26 @@ pub mod text {
             }
             let tz: Tz = daily_histories.location.tz.parse().unwrap();
             let date_format = self.date_format.as_ref().map_or(DEFAULT_DATE_FORMAT, |format| format.as_str());
+            let precision = self.precision;
             for history in daily_histories.histories {
                 let mut row = Vec::with_capacity(columns);
                 row.push(text!(fmt_date(&history.date, date_format)));
                 if self.report_selector.temperatures {
-                    row.push(text!(fmt_temperature(&history.temperature_high)));
-                    row.push(text!(fmt_temperature(&history.temperature_low)));
-                    row.push(text!(fmt_temperature(&history.temperature_mean)));
-                    row.push(text!(fmt_temperature(&history.dew_point)));
+                    match &self.baseline {
+                        Some(_) => {
+                            row.push(text!(fmt_anomaly(&history.temperature_high, precision)));
+                            row.push(text!(fmt_anomaly(&history.temperature_low, precision)));
+                        }
+                        None => {
+                            row.push(text!(fmt_temperature(&history.temperature_high, precision)));
+                            row.push(text!(fmt_temperature(&history.temperature_low, precision)));
+                        }
+                    }
+                    row.push(text!(fmt_temperature(&history.temperature_mean, precision)));
+                    row.push(text!(fmt_temperature(&history.dew_point, precision)));
+                    if let Some(baseline) = &self.baseline {
+                        row.push(text!(baseline.classify(&history)));
+                    }
                 }
                 if self.report_selector.precipitation {
                     row.push(text!(fmt_percent(&history.cloud_cover)));
@@ -169,11 +535,11 @@ pub mod text {
                     row.push(text!(history.precipitation_type.as_ref().map_or(Default::default(), |t| t.as_str())));
                 }
                 if self.report_selector.conditions {
-                    row.push(text!(fmt_float(&history.wind_speed, 1)));
-                    row.push(text!(fmt_float(&history.wind_gust, 1)));
+                    row.push(text!(fmt_float(&history.wind_speed, precision)));
+                    row.push(text!(fmt_float(&history.wind_gust, precision)));
                     row.push(text!(fmt_wind_bearing(&history.wind_direction)));
                     row.push(text!(fmt_uv_index(&history.uv_index)));
-                    row.push(text!(fmt_float(&history.pressure, 1)));
+                    row.push(text!(fmt_float(&history.pressure, precision)));
                 }
                 // if self.summary {
                 if self.report_selector.summary {
@@ -186,6 +552,97 @@ pub mod text {
             }
             report
         }
+        /// Generates a sparkline of the daily high temperatures instead of a table.
+        ///
+        /// # Arguments
+        ///
+        /// * `daily_histories` is the location weather history that will be graphed.
+        ///
+        pub fn generate_graph(&self, daily_histories: DailyHistories) -> Vec<String> {
+            let date_format = self.date_format.as_ref().map_or(DEFAULT_DATE_FORMAT, |format| format.as_str());
+            let highs: Vec<Option<f64>> =
+                daily_histories.histories.iter().map(|history| history.temperature_high).collect();
+            let mut lines = vec![];
+            if let (Some(first), Some(last)) = (daily_histories.histories.first(), daily_histories.histories.last()) {
+                lines.push(format!(
+                    "{} {} to {}",
+                    daily_histories.location.name,
+                    fmt_date(&first.date, date_format),
+                    fmt_date(&last.date, date_format)
+                ));
+            }
+            lines.push(sparkline(&highs));
+            lines
+        }
+        /// Generates the high and low temperature statistics instead of a table.
+        ///
+        /// # Arguments
+        ///
+        /// * `daily_histories` is the location weather history that will be summarized.
+        ///
+        pub fn generate_stats(&self, daily_histories: DailyHistories) -> Vec<String> {
+            let date_format = self.date_format.as_ref().map_or(DEFAULT_DATE_FORMAT, |format| format.as_str());
+            let stats = TemperatureStats::new(&daily_histories.histories);
+            let mut lines = vec![];
+            if let (Some(first), Some(last)) = (daily_histories.histories.first(), daily_histories.histories.last()) {
+                lines.push(format!(
+                    "{} {} to {} ({} days)",
+                    daily_histories.location.name,
+                    fmt_date(&first.date, date_format),
+                    fmt_date(&last.date, date_format),
+                    stats.days
+                ));
+            }
+            let mut report = ReportSheet::new(vec![layout!(<), layout!(>), layout!(>), layout!(>)]);
+            report.add_row(vec![header!(""), header!("Mean"), header!("Min"), header!("Max")]);
+            if self.title_separator {
+                report.add_row(text_title_separator!(4));
+            }
+            let precision = self.precision;
+            for (label, mean, min, max) in [
+                ("High", stats.high_mean, stats.high_min, stats.high_max),
+                ("Low", stats.low_mean, stats.low_min, stats.low_max),
+            ] {
+                report.add_row(vec![
+                    text!(label),
+                    text!(fmt_temperature(&mean, precision)),
+                    text!(fmt_temperature(&min, precision)),
+                    text!(fmt_temperature(&max, precision)),
+                ]);
+            }
+            lines.extend(report.into_iter().map(|row| row.to_string().trim_end().to_string()));
+            lines
+        }
+    }
+
+    /// The characters used to graph a sparkline from lowest to highest.
+    const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    /// Returns a sparkline of the values.
+    ///
+    /// The lowest value is graphed as `▁` and the highest as `█` with values in between
+    /// scaled to the remaining characters. Missing values are shown as a space. If all
+    /// the values are the same they are graphed as `▁`.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` are the values that will be graphed.
+    ///
+    pub fn sparkline(values: &[Option<f64>]) -> String {
+        let (min, max) =
+            values.iter().flatten().fold((f64::MAX, f64::MIN), |(min, max), value| (min.min(*value), max.max(*value)));
+        let range = max - min;
+        values
+            .iter()
+            .map(|value| match value {
+                None => ' ',
+                Some(_) if range <= 0.0 => SPARKS[0],
+                Some(value) => {
+                    let index = ((value - min) / range * (SPARKS.len() - 1) as f64).round() as usize;
+                    SPARKS[index.min(SPARKS.len() - 1)]
+                }
+            })
+            .collect()
     }
 
     /// Returns a compass bearing as a human readable direction.
@@ -236,21 +693,35 @@ pub mod text {
     ///
     fn fmt_percent(option: &Option<f64>) -> String {
         match option {
-            Some(value) => format!("{:>3}%", ((value * 100.0) + 0.5) as i64),
+            Some(value) => format_percent_width(*value, 0, 3),
             None => Default::default(),
         }
     }
 
     /// Returns a temperature as a string.
     ///
-    /// The temperature is rounded to the nearest 1/10 degree.
+    /// The temperature is rounded to the number of decimal places.
+    ///
+    /// If the option is `None` an empty string will be returned.
+    ///
+    #[inline]
+    fn fmt_temperature(t: &Option<f64>, precision: usize) -> String {
+        match t {
+            Some(temperature) => format!("{:>-5.1$}", temperature, precision),
+            None => Default::default(),
+        }
+    }
+
+    /// Returns a temperature anomaly as a signed string.
+    ///
+    /// The anomaly is rounded to the number of decimal places.
     ///
     /// If the option is `None` an empty string will be returned.
     ///
     #[inline]
-    fn fmt_temperature(t: &Option<f64>) -> String {
+    fn fmt_anomaly(t: &Option<f64>, precision: usize) -> String {
         match t {
-            Some(temperature) => format!("{:>-5.1}", temperature),
+            Some(anomaly) => format!("{:>+5.1$}", anomaly, precision),
             None => Default::default(),
         }
     }
@@ -422,11 +893,25 @@ pub mod text {
 
         #[test]
         fn temperature() {
-            assert_eq!(fmt_temperature(&None), "");
-            assert_eq!(fmt_temperature(&Some(50.94)), " 50.9");
-            assert_eq!(fmt_temperature(&Some(50.95)), " 51.0");
-            assert_eq!(fmt_temperature(&Some(99.9)), " 99.9");
-            assert_eq!(fmt_temperature(&Some(-29.9)), "-29.9");
+            assert_eq!(fmt_temperature(&None, DEFAULT_PRECISION), "");
+            assert_eq!(fmt_temperature(&Some(50.94), DEFAULT_PRECISION), " 50.9");
+            assert_eq!(fmt_temperature(&Some(50.95), DEFAULT_PRECISION), " 51.0");
+            assert_eq!(fmt_temperature(&Some(99.9), DEFAULT_PRECISION), " 99.9");
+            assert_eq!(fmt_temperature(&Some(-29.9), DEFAULT_PRECISION), "-29.9");
+            assert_eq!(fmt_temperature(&Some(50.94), 0), "   51");
+            assert_eq!(fmt_temperature(&Some(-29.9), 0), "  -30");
+            assert_eq!(fmt_temperature(&Some(50.9), 2), "50.90");
+            assert_eq!(fmt_anomaly(&Some(4.6), 0), "   +5");
+        }
+
+        #[test]
+        fn graph() {
+            let values: Vec<Option<f64>> = (0..8).map(|value| Some(value as f64)).collect();
+            assert_eq!(sparkline(&values), "▁▂▃▄▅▆▇█");
+            assert_eq!(sparkline(&[Some(70.0), None, Some(84.0), Some(77.0)]), "▁ █▅");
+            assert_eq!(sparkline(&[Some(50.0), Some(50.0)]), "▁▁");
+            assert_eq!(sparkline(&[None]), " ");
+            assert_eq!(sparkline(&[]), "");
         }
 
         #[test]
@@ -485,6 +970,10 @@ pub mod json {
         report_selector: ReportSelector,
         /// Controls if the resulting document will be pretty printed of not.
         pretty: bool,
+        /// The naming style of the document field names.
+        header_style: HeaderStyle,
+        /// The layout of the document.
+        json_shape: JsonShape,
     }
     impl Report {
         /// Create a new instance of the `JSON` based weather history report.
@@ -495,7 +984,12 @@ pub mod json {
         ///
         pub fn new(mut report_selector: ReportSelector) -> Self {
             sanitize_report_selector(&mut report_selector);
-            Self { report_selector, pretty: false }
+            Self {
+                report_selector,
+                pretty: false,
+                header_style: HeaderStyle::default(),
+                json_shape: JsonShape::default(),
+            }
         }
         /// Create a new instance of the `JSON` based weather history report that produces pretty printed documents.
         ///
@@ -505,7 +999,32 @@ pub mod json {
         ///
         pub fn pretty_printed(mut report_selector: ReportSelector) -> Self {
             sanitize_report_selector(&mut report_selector);
-            Self { report_selector, pretty: true }
+            Self {
+                report_selector,
+                pretty: true,
+                header_style: HeaderStyle::default(),
+                json_shape: JsonShape::default(),
+            }
+        }
+        /// Use a specific naming style for the document field names.
+        ///
+        /// # Arguments
+        ///
+        /// - `header_style` is the naming style that will be used.
+        ///
+        pub fn with_header_style(mut self, header_style: HeaderStyle) -> Self {
+            self.header_style = header_style;
+            self
+        }
+        /// Use a specific layout for the document.
+        ///
+        /// # Arguments
+        ///
+        /// - `json_shape` is the document layout that will be used.
+        ///
+        pub fn with_json_shape(mut self, json_shape: JsonShape) -> Self {
+            self.json_shape = json_shape;
+            self
         }
         /// Generates the report history JSON based report.
         ///
@@ -520,7 +1039,12 @@ pub mod json {
             let tz: Tz = daily_histories.location.tz.parse().unwrap();
             for history in daily_histories.histories {
                 let mut value = Map::new();
-                let mut add = |key: &str, v: Value| value.insert(key.to_string(), v);
+                let header_style = self.header_style;
+                let mut add = |key: &'static str, v: Value| value.insert(header_style.field_name(key).to_string(), v);
+                if self.json_shape == JsonShape::Array {
+                    // without the enclosing object each day needs to identify its location
+                    add("location", json!(daily_histories.location.name));
+                }
                 add("date", json!(isodate(&history.date)));
                 if self.report_selector.temperatures {
                     add("temperatureHigh", float_value(&history.temperature_high));
@@ -550,11 +1074,14 @@ pub mod json {
                 }
                 values.push(value);
             }
-            let json = json!({
-                "location": daily_histories.location.name,
-                "type": Value::String("daily_history".to_string()),
-                "history": json![values],
-            });
+            let json = match self.json_shape {
+                JsonShape::Object => json!({
+                    "location": daily_histories.location.name,
+                    "type": Value::String("daily_history".to_string()),
+                    "history": json![values],
+                }),
+                JsonShape::Array => Value::Array(values.into_iter().map(Value::Object).collect()),
+            };
             json_to_string(json, self.pretty)
         }
     }
@@ -644,6 +1171,7 @@ pub mod json {
     mod tests {
         use super::*;
         use toolslib::date_time::{get_date, get_time};
+        use weather_lib::prelude::{History, Location};
 
         #[test]
         fn datetime() {
@@ -660,6 +1188,77 @@ pub mod json {
             assert_eq!(string_value(&Some(testcase.clone())), json!(testcase));
         }
 
+        #[test]
+        fn header_style() {
+            let daily_histories = || DailyHistories {
+                location: Location {
+                    name: "Testcase".to_string(),
+                    alias: "testcase".to_string(),
+                    longitude: "0".to_string(),
+                    latitude: "0".to_string(),
+                    tz: "UTC".to_string(),
+                },
+                histories: vec![History {
+                    alias: "testcase".to_string(),
+                    date: get_date(2024, 1, 1),
+                    temperature_high: Some(50.0),
+                    ..Default::default()
+                }],
+            };
+            let report_selector = || ReportSelector { temperatures: true, ..Default::default() };
+            let testcase = Report::new(report_selector()).generate(daily_histories());
+            let json: Value = serde_json::from_str(&testcase).unwrap();
+            assert_eq!(json["history"][0]["temperatureHigh"], json!(50.0));
+            let testcase =
+                Report::new(report_selector()).with_header_style(HeaderStyle::Snake).generate(daily_histories());
+            let json: Value = serde_json::from_str(&testcase).unwrap();
+            assert_eq!(json["history"][0]["temperature_high"], json!(50.0));
+            assert_eq!(json["history"][0]["temperatureHigh"], Value::Null);
+        }
+
+        #[test]
+        fn json_shape() {
+            let daily_histories = || DailyHistories {
+                location: Location {
+                    name: "Testcase".to_string(),
+                    alias: "testcase".to_string(),
+                    longitude: "0".to_string(),
+                    latitude: "0".to_string(),
+                    tz: "UTC".to_string(),
+                },
+                histories: vec![
+                    History { alias: "testcase".to_string(), date: get_date(2024, 1, 1), ..Default::default() },
+                    History { alias: "testcase".to_string(), date: get_date(2024, 1, 2), ..Default::default() },
+                ],
+            };
+            let report_selector = || ReportSelector { temperatures: true, ..Default::default() };
+            // the object shape is the default
+            let object: Value =
+                serde_json::from_str(&Report::new(report_selector()).generate(daily_histories())).unwrap();
+            let testcase =
+                Report::new(report_selector()).with_json_shape(JsonShape::Object).generate(daily_histories());
+            let json: Value = serde_json::from_str(&testcase).unwrap();
+            assert_eq!(json, object);
+            assert!(json.is_object());
+            assert_eq!(json["location"], json!("Testcase"));
+            assert_eq!(json["type"], json!("daily_history"));
+            assert_eq!(json["history"].as_array().unwrap().len(), 2);
+            // the array shape is the daily histories with the location name added
+            let testcase =
+                Report::pretty_printed(report_selector()).with_json_shape(JsonShape::Array).generate(daily_histories());
+            let json: Value = serde_json::from_str(&testcase).unwrap();
+            assert!(json.is_array());
+            assert_eq!(json.as_array().unwrap().len(), 2);
+            assert_eq!(json[0]["location"], json!("Testcase"));
+            assert_eq!(json[1]["location"], json!("Testcase"));
+            assert_eq!(json[1]["date"], json!("2024-01-02"));
+            assert_eq!(json[1]["temperatureHigh"], object["history"][1]["temperatureHigh"]);
+            assert_eq!(JsonShape::default(), JsonShape::Object);
+            assert_eq!(JsonShape::try_from("array"), Ok(JsonShape::Array));
+            assert_eq!(JsonShape::try_from("object"), Ok(JsonShape::Object));
+            assert!(JsonShape::try_from("tree").is_err());
+        }
+
         #[test]
         fn numbers() {
             assert_eq!(float_value(&None), Value::Null);
@@ -683,6 +1282,8 @@ pub mod csv {
     pub struct Report(
         /// Controls the contents of the weather history report.
         ReportSelector,
+        /// The naming style of the column headers.
+        HeaderStyle,
     );
     impl Report {
         /// Create a new instance of the `CSV` based weather history report.
@@ -693,7 +1294,17 @@ pub mod csv {
         ///
         pub fn new(mut report_selector: ReportSelector) -> Self {
             sanitize_report_selector(&mut report_selector);
-            Self(report_selector)
+            Self(report_selector, HeaderStyle::default())
+        }
+        /// Use a specific naming style for the column headers.
+        ///
+        /// # Arguments
+        ///
+        /// - `header_style` is the naming style that will be used.
+        ///
+        pub fn with_header_style(mut self, header_style: HeaderStyle) -> Self {
+            self.1 = header_style;
+            self
         }
         /// Generates the list history CSV based report.
         ///
@@ -707,30 +1318,30 @@ pub mod csv {
             let mut writer = csv_lib::Writer::from_writer(vec![]);
             let mut labels: Vec<&str> = vec!["date"];
             if self.0.temperatures {
-                labels.push("temperatureHigh");
-                labels.push("temperatureLow");
-                labels.push("temperatureMean");
-                labels.push("dewPoint");
+                labels.push(self.1.field_name("temperatureHigh"));
+                labels.push(self.1.field_name("temperatureLow"));
+                labels.push(self.1.field_name("temperatureMean"));
+                labels.push(self.1.field_name("dewPoint"));
             }
             if self.0.precipitation {
-                labels.push("cloudCover");
-                labels.push("humidity");
-                labels.push("precip");
-                labels.push("precipChance");
-                labels.push("precipType");
+                labels.push(self.1.field_name("cloudCover"));
+                labels.push(self.1.field_name("humidity"));
+                labels.push(self.1.field_name("precip"));
+                labels.push(self.1.field_name("precipChance"));
+                labels.push(self.1.field_name("precipType"));
             }
             if self.0.conditions {
-                labels.push("windSpeed");
-                labels.push("windGust");
-                labels.push("windBearing");
-                labels.push("uvIndex");
-                labels.push("pressure");
+                labels.push(self.1.field_name("windSpeed"));
+                labels.push(self.1.field_name("windGust"));
+                labels.push(self.1.field_name("windBearing"));
+                labels.push(self.1.field_name("uvIndex"));
+                labels.push(self.1.field_name("pressure"));
             }
             if self.0.summary {
-                labels.push("sunrise");
-                labels.push("sunset");
-                labels.push("moonPhase");
-                labels.push("summary");
+                labels.push(self.1.field_name("sunrise"));
+                labels.push(self.1.field_name("sunset"));
+                labels.push(self.1.field_name("moonPhase"));
+                labels.push(self.1.field_name("summary"));
             }
             csv_write_record!(writer, &labels);
             let tz: Tz = daily_histories.location.tz.parse().unwrap();
@@ -852,6 +1463,7 @@ pub mod csv {
     mod tests {
         use super::*;
         use toolslib::date_time::{get_date, get_time};
+        use weather_lib::prelude::Location;
 
         #[test]
         fn datetime() {
@@ -868,6 +1480,29 @@ pub mod csv {
             assert_eq!(string_value(&Some(testcase.clone())), testcase);
         }
 
+        #[test]
+        fn header_style() {
+            let daily_histories = || DailyHistories {
+                location: Location {
+                    name: "Testcase".to_string(),
+                    alias: "testcase".to_string(),
+                    longitude: "0".to_string(),
+                    latitude: "0".to_string(),
+                    tz: "UTC".to_string(),
+                },
+                histories: vec![],
+            };
+            let report_selector = || ReportSelector { temperatures: true, ..Default::default() };
+            let testcase = Report::new(report_selector()).generate(daily_histories());
+            assert_eq!(testcase.lines().next().unwrap(), "date,temperatureHigh,temperatureLow,temperatureMean,dewPoint");
+            let testcase =
+                Report::new(report_selector()).with_header_style(HeaderStyle::Snake).generate(daily_histories());
+            assert_eq!(
+                testcase.lines().next().unwrap(),
+                "date,temperature_high,temperature_low,temperature_mean,dew_point"
+            );
+        }
+
         #[test]
         fn numbers() {
             assert_eq!(float_value(&None), "".to_string());
@@ -877,3 +1512,295 @@ pub mod csv {
         }
     }
 }
+
+pub mod svg {
+    //! The report history SVG line chart implementation.
+    //!
+    //! The chart plots the daily high and low temperatures with the date along the X axis and the
+    //! temperature along the Y axis. Only plain SVG elements are used so the chart can be viewed in
+    //! a browser or embedded in other documents.
+    use super::*;
+    use toolslib::date_time::isodate;
+
+    /// The width of the chart.
+    const WIDTH: f64 = 800.0;
+    /// The height of the chart.
+    const HEIGHT: f64 = 400.0;
+    /// The space around the plot area used for the title and axis labels.
+    const MARGIN: f64 = 50.0;
+
+    /// The `SVG` based weather history line chart.
+    #[derive(Debug, Default)]
+    pub struct Report;
+    impl Report {
+        /// Generates a chart of the daily high and low temperatures.
+        ///
+        /// Each temperature is plotted as its own line, days without a temperature are skipped.
+        ///
+        /// # Arguments
+        ///
+        /// * `daily_histories` is the locations weather history that will be charted.
+        pub fn generate(&self, daily_histories: DailyHistories) -> String {
+            let series = |temperature: fn(&History) -> Option<f64>| -> Vec<(NaiveDate, f64)> {
+                daily_histories
+                    .histories
+                    .iter()
+                    .filter_map(|history| temperature(history).map(|value| (history.date, value)))
+                    .collect()
+            };
+            let highs = series(|history| history.temperature_high);
+            let lows = series(|history| history.temperature_low);
+            let dates = || highs.iter().chain(lows.iter()).map(|(date, _)| *date);
+            let temperatures = || highs.iter().chain(lows.iter()).map(|(_, value)| *value);
+            let first_date = dates().min().unwrap_or_default();
+            let last_date = dates().max().unwrap_or_default();
+            let min_temperature = temperatures().reduce(f64::min).unwrap_or_default().floor();
+            let max_temperature = temperatures().reduce(f64::max).unwrap_or_default().ceil();
+            // avoid dividing by zero when there is a single day or temperature
+            let days = ((last_date - first_date).num_days() as f64).max(1.0);
+            let degrees = (max_temperature - min_temperature).max(1.0);
+            let plot_width = WIDTH - 2.0 * MARGIN;
+            let plot_height = HEIGHT - 2.0 * MARGIN;
+            let x = |date: NaiveDate| MARGIN + (date - first_date).num_days() as f64 / days * plot_width;
+            let y = |value: f64| MARGIN + (max_temperature - value) / degrees * plot_height;
+            let points = |series: &[(NaiveDate, f64)]| {
+                series.iter().map(|(date, value)| format!("{:.1},{:.1}", x(*date), y(*value))).collect::<Vec<_>>()
+            };
+            let bottom = HEIGHT - MARGIN;
+            let right = WIDTH - MARGIN;
+            let label_x = MARGIN - 5.0;
+            let label_y = bottom + 20.0;
+            let mut svg = vec![
+                format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}">"#),
+                format!(
+                    r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+                    WIDTH / 2.0,
+                    MARGIN / 2.0,
+                    escape(&daily_histories.location.name)
+                ),
+                format!(r#"<line x1="{MARGIN}" y1="{bottom}" x2="{right}" y2="{bottom}" stroke="black"/>"#),
+                format!(r#"<line x1="{MARGIN}" y1="{MARGIN}" x2="{MARGIN}" y2="{bottom}" stroke="black"/>"#),
+                format!(r#"<text x="{MARGIN}" y="{label_y}">{}</text>"#, isodate(&first_date)),
+                format!(r#"<text x="{right}" y="{label_y}" text-anchor="end">{}</text>"#, isodate(&last_date)),
+                format!(r#"<text x="{}" y="{}" text-anchor="middle">Date</text>"#, WIDTH / 2.0, HEIGHT - 5.0),
+                format!(r#"<text x="{label_x}" y="{MARGIN}" text-anchor="end">{max_temperature}</text>"#),
+                format!(r#"<text x="{label_x}" y="{bottom}" text-anchor="end">{min_temperature}</text>"#),
+                format!(
+                    r#"<text x="15" y="{0}" text-anchor="middle" transform="rotate(-90 15 {0})">Temperature</text>"#,
+                    HEIGHT / 2.0
+                ),
+            ];
+            for (points, color) in [(points(&highs), "red"), (points(&lows), "blue")] {
+                svg.push(format!(r#"<polyline fill="none" stroke="{color}" points="{}"/>"#, points.join(" ")));
+            }
+            svg.push("</svg>".to_string());
+            svg.join("\n")
+        }
+    }
+
+    /// Replace the characters that cannot be used in `SVG` text content.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` is the content that will be escaped.
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toolslib::date_time::get_date;
+    use weather_lib::prelude::Location;
+
+    fn daily_histories() -> DailyHistories {
+        let temperatures = [(80.0, 60.0), (90.0, 62.0), (70.0, 64.0), (100.0, 50.0)];
+        DailyHistories {
+            location: Location {
+                name: "Testcase".to_string(),
+                alias: "testcase".to_string(),
+                longitude: "0".to_string(),
+                latitude: "0".to_string(),
+                tz: "UTC".to_string(),
+            },
+            histories: temperatures
+                .iter()
+                .enumerate()
+                .map(|(day, (high, low))| History {
+                    alias: "testcase".to_string(),
+                    date: get_date(2024, 7, day as u32 + 1),
+                    temperature_high: Some(*high),
+                    temperature_low: Some(*low),
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn anomalies() {
+        let raw = daily_histories();
+        let (baseline, testcase) = temperature_anomalies(daily_histories());
+        assert_eq!(baseline.high, Some(85.0));
+        assert_eq!(baseline.low, Some(59.0));
+        for (raw, anomaly) in raw.histories.iter().zip(testcase.histories.iter()) {
+            assert_eq!(anomaly.temperature_high, Some(raw.temperature_high.unwrap() - 85.0));
+            assert_eq!(anomaly.temperature_low, Some(raw.temperature_low.unwrap() - 59.0));
+        }
+        let classes: Vec<&str> = testcase.histories.iter().map(|history| baseline.classify(history)).collect();
+        assert_eq!(classes, vec!["", "", "", "hot"]);
+    }
+
+    #[test]
+    fn high_temperature_filter() {
+        let fixture = || {
+            let mut daily_histories = daily_histories();
+            let history = History { date: get_date(2024, 7, 5), temperature_high: None, ..Default::default() };
+            daily_histories.histories.push(history);
+            daily_histories
+        };
+        let dates = |min: Option<f64>, max: Option<f64>| {
+            let daily_histories = filter_high_temperature(fixture(), min, max);
+            daily_histories.histories.iter().map(|history| history.date.to_string()).collect::<Vec<String>>()
+        };
+        assert_eq!(dates(Some(80.0), Some(90.0)), vec!["2024-07-01", "2024-07-02"]);
+        assert_eq!(dates(Some(90.0), None), vec!["2024-07-02", "2024-07-04"]);
+        assert_eq!(dates(None, Some(75.0)), vec!["2024-07-03"]);
+        // days without a high temperature are only kept when there is no range
+        assert_eq!(dates(None, None).len(), 5);
+    }
+
+    #[test]
+    fn sample() {
+        let fixture = || {
+            let date_range = DateRange::new(get_date(2024, 7, 1), get_date(2024, 7, 10));
+            super::fill_gaps(daily_histories(), &date_range, &[])
+        };
+        let days = |daily_histories: DailyHistories| {
+            daily_histories.histories.iter().map(|history| history.date.day()).collect::<Vec<u32>>()
+        };
+        assert_eq!(days(sample_histories(fixture(), 3)), vec![1, 4, 7, 10]);
+        assert_eq!(days(sample_histories(fixture(), 7)), vec![1, 8]);
+        assert_eq!(days(sample_histories(fixture(), 20)), vec![1]);
+        assert_eq!(days(sample_histories(fixture(), 1)).len(), 10);
+        assert_eq!(days(sample_histories(fixture(), 0)).len(), 10);
+        // the sampled histories keep their content
+        let testcase = sample_histories(fixture(), 3);
+        assert_eq!(testcase.histories[1].temperature_high, Some(100.0));
+    }
+
+    #[test]
+    fn utc() {
+        let fixture = || {
+            let mut daily_histories = daily_histories();
+            daily_histories.location.tz = "America/Phoenix".to_string();
+            daily_histories.histories[0].sunrise = Some(get_date(2024, 7, 1).and_hms_opt(12, 30, 0).unwrap());
+            daily_histories
+        };
+        let sunrise = |daily_histories: DailyHistories| {
+            let report = text::Report::new(ReportSelector { summary: true, ..Default::default() });
+            report.generate(daily_histories).into_iter().map(|row| row.to_string()).nth(2).unwrap()
+        };
+        assert!(sunrise(fixture()).contains("05:30"));
+        assert!(sunrise(utc_times(fixture())).contains("12:30"));
+    }
+
+    #[test]
+    fn fill_gaps() {
+        let fixture = || {
+            let mut fixture = daily_histories();
+            fixture.histories.remove(1);
+            fixture
+        };
+        let date_range = DateRange::new(get_date(2024, 7, 1), get_date(2024, 7, 5));
+        // days that were filtered out are not filled in
+        let testcase = super::fill_gaps(fixture(), &date_range, &[get_date(2024, 7, 2)]);
+        let days: Vec<u32> = testcase.histories.iter().map(|history| history.date.day()).collect();
+        assert_eq!(days, vec![1, 3, 4, 5]);
+        let history_dates: Vec<NaiveDate> = fixture().histories.iter().map(|history| history.date).collect();
+        let testcase = super::fill_gaps(fixture(), &date_range, &history_dates);
+        let dates: Vec<NaiveDate> = testcase.histories.iter().map(|history| history.date).collect();
+        assert_eq!(dates, date_range.iter().collect::<Vec<NaiveDate>>());
+        let highs: Vec<Option<f64>> = testcase.histories.iter().map(|history| history.temperature_high).collect();
+        assert_eq!(highs, vec![Some(80.0), None, Some(70.0), Some(100.0), None]);
+        assert_eq!(testcase.histories[1].alias, "testcase");
+        // the placeholder is an empty row in the reports
+        let report = super::csv::Report::new(ReportSelector::default()).generate(testcase);
+        assert_eq!(report.lines().nth(2), Some("2024-07-02,,,,"));
+    }
+
+    #[test]
+    fn svg_chart() {
+        let mut fixture = daily_histories();
+        fixture.histories[1].temperature_low = None;
+        let testcase = svg::Report::default().generate(fixture);
+        assert!(testcase.starts_with("<svg"));
+        assert!(testcase.ends_with("</svg>"));
+        assert!(testcase.contains(">Testcase</text>"));
+        assert!(testcase.contains(">2024-07-01</text>") && testcase.contains(">2024-07-04</text>"));
+        let polylines: Vec<&str> = testcase.lines().filter(|line| line.starts_with("<polyline")).collect();
+        assert_eq!(polylines.len(), 2);
+        let point_count = |polyline: &str| polyline.split("points=\"").nth(1).unwrap().split_whitespace().count();
+        assert_eq!(point_count(polylines[0]), 4);
+        assert_eq!(point_count(polylines[1]), 3);
+        // the hottest day is at the top of the plot area
+        assert!(polylines[0].contains("750.0,50.0"));
+    }
+
+    #[test]
+    fn temperature_stats() {
+        let testcase = TemperatureStats::new(&daily_histories().histories);
+        assert_eq!(testcase.days, 4);
+        assert_eq!(testcase.high_mean, Some(85.0));
+        assert_eq!(testcase.high_min, Some(70.0));
+        assert_eq!(testcase.high_max, Some(100.0));
+        assert_eq!(testcase.low_mean, Some(59.0));
+        assert_eq!(testcase.low_min, Some(50.0));
+        assert_eq!(testcase.low_max, Some(64.0));
+        assert_eq!(TemperatureStats::new(&[]), TemperatureStats::default());
+        // days without temperatures are counted but not part of the statistics
+        let mut fixture = daily_histories();
+        fixture.histories.push(History { date: get_date(2024, 7, 5), ..Default::default() });
+        let testcase = TemperatureStats::new(&fixture.histories);
+        assert_eq!(testcase.days, 5);
+        assert_eq!(testcase.high_mean, Some(85.0));
+        let lines = text::Report::new(ReportSelector::default()).generate_stats(fixture);
+        assert_eq!(lines[0], "Testcase 2024-07-01 to 2024-07-05 (5 days)");
+        assert!(lines[2].starts_with("High") && lines[2].contains("85.0") && lines[2].ends_with("100.0"));
+        assert!(lines[3].starts_with("Low") && lines[3].contains("59.0") && lines[3].ends_with("64.0"));
+    }
+
+    #[test]
+    fn empty_baseline() {
+        assert_eq!(TemperatureBaseline::new(&[]), TemperatureBaseline::default());
+        let history = History { temperature_high: Some(10.0), temperature_low: Some(-10.0), ..Default::default() };
+        assert_eq!(TemperatureBaseline::default().classify(&history), "");
+    }
+
+    #[test]
+    fn anomaly_report() {
+        let (baseline, histories) = temperature_anomalies(daily_histories());
+        let report = text::Report::new(ReportSelector::default()).with_anomaly(baseline).generate(histories);
+        let rows: Vec<String> = report.into_iter().map(|row| row.to_string()).collect();
+        assert!(rows[1].contains("Anomaly"));
+        assert!(rows[2].contains("-5.0"));
+        assert!(rows[3].contains("+5.0"));
+        assert!(rows[5].contains("+15.0"));
+        assert!(rows[5].trim_end().ends_with("hot"));
+    }
+
+    #[test]
+    fn report_precision() {
+        let rows = |report: text::Report| -> Vec<String> {
+            report.generate(daily_histories()).into_iter().map(|row| row.to_string()).collect()
+        };
+        let testcase = rows(text::Report::new(ReportSelector::default()));
+        assert!(testcase[2].contains("80.0"));
+        assert!(testcase[2].contains("60.0"));
+        let testcase = rows(text::Report::new(ReportSelector::default()).with_precision(0));
+        assert!(testcase[2].contains("80"));
+        assert!(testcase[2].contains("60"));
+        assert!(!testcase.iter().skip(2).any(|row| row.contains('.')));
+    }
+}
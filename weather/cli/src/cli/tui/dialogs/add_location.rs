@@ -23,7 +23,10 @@ impl AddLocation {
     ///
     pub fn new(weather_data: Rc<WeatherData>) -> Self {
         let buttons = ButtonBar::new(vec![ok_button().with_active(), cancel_button()]).with_auto_select(true);
-        let window = LocationEditor::new();
+        let mut window = LocationEditor::new();
+        if let Some(timezone) = weather_data.default_timezone() {
+            window.fields.get_mut(TZ_ID).unwrap().set_text(timezone);
+        }
         let dialog = ButtonDialog::new(buttons, window).with_title(" Add Location ");
         Self { dialog, weather_data }
     }
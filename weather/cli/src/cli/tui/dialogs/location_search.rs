@@ -194,7 +194,7 @@ impl SearchWindow {
     /// - `locations` provides the contents of the search results window.
     ///
     fn initialize(&mut self, locations: Vec<Location>) {
-        let report = reports::text::Report::default().with_skip_alias().generate(&locations);
+        let report = reports::text::Report::default().with_skip_alias().with_details().generate(&locations);
         let view = ReportView::new(report, None).with_show_selected(true);
         self.locations_view.replace(LocationsView { locations, view });
     }
@@ -72,7 +72,7 @@ impl LocationsWindow {
             report_win: None,
             weather_data,
         };
-        me.refresh()?;
+        me.refresh().map_err(|err| Error::new(ErrorKind::Other, err))?;
         Ok(me)
     }
     /// Dispatch a key pressed event to the popup menu. [ControlFlow::Continue] will be returned if the
@@ -197,7 +197,7 @@ impl DialogWindow for LocationsWindow {
         self.locations_view.take();
         match self.weather_data.get_locations(DataCriteria::default()) {
             Ok(locations) => {
-                let report = reports::list_locations::text::Report::default().generate(&locations);
+                let report = reports::list_locations::text::Report::default().with_details().generate(&locations);
                 let view = ReportView::new(report, None).with_show_selected(true).with_active(self.active);
                 self.locations_view.replace(LocationsView { locations, view });
                 Ok(())
@@ -27,7 +27,7 @@ impl SummaryWindow {
     ///
     pub fn new(weather_data: Rc<WeatherData>) -> Result<Self> {
         let mut fles = Self { active: false, report: None, weather_data };
-        fles.refresh()?;
+        fles.refresh().map_err(|err| Error::new(ErrorKind::Other, err))?;
         Ok(fles)
     }
 }
@@ -5,6 +5,7 @@ use std::io::Write;
 use weather_lib::prelude::*;
 
 mod add_history;
+mod diff_history;
 mod list_history;
 mod list_locations;
 mod list_summary;
@@ -21,6 +22,7 @@ impl User {
             list_summary::command(),
             report_history::command(),
             add_history::command(),
+            diff_history::command(),
         ]
     }
     /// Run the associated command.
@@ -37,6 +39,7 @@ impl User {
             list_summary::COMMAND_NAME => list_summary::execute(weather_data, args),
             report_history::COMMAND_NAME => report_history::execute(weather_data, args),
             add_history::COMMAND_NAME => add_history::execute(weather_data, args),
+            diff_history::COMMAND_NAME => diff_history::execute(weather_data, args),
             _ => unreachable!("User command should not be here..."),
         }
     }
@@ -54,6 +57,35 @@ fn validate_location(name: &str) -> std::result::Result<String, String> {
     }
 }
 
+/// Parse the location argument into one or more location names.
+///
+/// An argument starting with `@` names a file containing location names, one per line. Blank lines
+/// and lines starting with `#` are skipped.
+///
+/// # Arguments
+///
+/// * `arg` is the command line argument that should be a location name or `@` file.
+fn parse_locations(arg: &str) -> std::result::Result<Vec<String>, String> {
+    match arg.strip_prefix('@') {
+        None => Ok(vec![validate_location(arg)?]),
+        Some(filename) => match std::fs::read_to_string(filename) {
+            Ok(content) => {
+                let locations = content
+                    .lines()
+                    .map(|line| line.trim())
+                    .filter(|line| !(line.is_empty() || line.starts_with('#')))
+                    .map(validate_location)
+                    .collect::<std::result::Result<Vec<String>, String>>()?;
+                match locations.is_empty() {
+                    true => Err(format!("{} does not have any locations.", filename)),
+                    false => Ok(locations),
+                }
+            }
+            Err(err) => Err(format!("Error reading {} ({}).", filename, err)),
+        },
+    }
+}
+
 /// Parse an argument turning it into a [NaiveDate].
 ///
 /// # Arguments
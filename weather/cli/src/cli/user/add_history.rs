@@ -3,7 +3,7 @@
 use super::*;
 
 /// The add weather data history command name.
-pub const COMMAND_NAME: &'static str = "ah";
+pub const COMMAND_NAME: &str = "ah";
 
 pub use v4::{command, execute};
 mod v4 {
@@ -12,13 +12,16 @@ mod v4 {
     use chrono::NaiveDate;
 
     /// The location argument id.
-    const LOCATION: &'static str = "LOCATION";
+    const LOCATION: &str = "LOCATION";
 
     /// The history from date argument id.
-    const FROM: &'static str = "FROM";
+    const FROM: &str = "FROM";
 
     /// The history thru date argument id.
-    const THRU: &'static str = "THRU";
+    const THRU: &str = "THRU";
+
+    /// The dry run argument id.
+    const DRY_RUN: &str = "DRY_RUN";
 
     /// Create a new instance of the add history command arguments.
     ///
@@ -49,6 +52,12 @@ mod v4 {
                     .value_name("THRU")
                     .help("The weather history ending date."),
             )
+            .arg(
+                Arg::new(DRY_RUN)
+                    .long("dry-run")
+                    .action(ArgAction::SetTrue)
+                    .help("Show the dates that would be added without getting or adding weather history."),
+            )
             .arg_required_else_help(true)
     }
 
@@ -61,30 +70,47 @@ mod v4 {
     ///
     pub fn execute(weather_data: &WeatherData, args: ArgMatches) -> Result<()> {
         macro_rules! error {
-            ($reason:expr) => {
-                Err(Error::from($reason.to_string()))
+            ($kind:expr, $reason:expr) => {
+                Err(Error::new($kind, $reason))
             };
         }
         let location = args.get_one::<String>(LOCATION).unwrap().clone();
-        match weather_data.get_locations(DataCriteria { filters: vec![location.clone()], icase: true, sort: false }) {
-            Err(error) => error!(format!("Error getting location '{}' information ({}).", location, error)),
+        match weather_data.get_locations(DataCriteria {
+            filters: vec![location.clone()],
+            icase: true,
+            sort: false,
+            alias_only: false,
+        }) {
+            Err(error) => error!(
+                ErrorKind::from(error.kind()),
+                format!("Error getting location '{}' information ({}).", location, error)
+            ),
             Ok(mut locations) => {
                 let len = locations.len();
                 if len == 0 {
-                    error!(format!("Location '{}' was not found.", location))
+                    error!(ErrorKind::NotFound, format!("Location '{}' was not found.", location))
                 } else if len > 1 {
-                    error!(format!("Multiple locations were found for '{}'.", location))
+                    error!(ErrorKind::Invalid, format!("Multiple locations were found for '{}'.", location))
                 } else {
                     let location = locations.pop().unwrap();
                     let from = args.get_one::<NaiveDate>(FROM).unwrap();
                     let to = args.get_one::<NaiveDate>(THRU).map_or(from, |d| d);
                     let date_range = DateRange { from: from.clone(), to: to.clone() };
                     match weather_data.get_history_client() {
-                        Err(error) => error!(error.to_string()),
+                        Err(error) => error!(ErrorKind::from(error.kind()), error.to_string()),
+                        Ok(_) if args.get_flag(DRY_RUN) => {
+                            let dates = missing_dates(weather_data, &location.alias, &date_range)?;
+                            let mut writer = toolslib::text::get_writer(&None, false)?;
+                            for date in &dates {
+                                writeln!(writer, "{}", date)?;
+                            }
+                            writeln!(writer, "{} histories would be added.", dates.len())?;
+                            Ok(())
+                        }
                         Ok(client) => {
                             let daily_histories = get_histories(&client, location, date_range)?;
                             let histories_found = daily_histories.histories.len();
-                            let histories_added = weather_data.add_histories(daily_histories)?;
+                            let histories_added = add_histories(weather_data, daily_histories)?;
                             println!("\n{} histories found, {} histories added.", histories_found, histories_added);
                             Ok(())
                         }
@@ -95,6 +121,45 @@ mod v4 {
     }
 }
 
+/// Get the dates in a range that do not have weather history.
+///
+/// Weather data is only read, nothing will be added.
+///
+/// # Arguments
+///
+/// - `weather_data` is the weather library API used to check for histories.
+/// - `alias` identifies the location.
+/// - `date_range` are the dates that will be checked.
+///
+fn missing_dates(weather_data: &WeatherData, alias: &str, date_range: &DateRange) -> Result<Vec<NaiveDate>> {
+    let criteria = DataCriteria { filters: vec![alias.to_string()], icase: false, sort: false, alias_only: true };
+    let history_dates = weather_data.get_history_dates(criteria)?;
+    let history_ranges = history_dates.iter().flat_map(|history_dates| history_dates.history_dates.iter());
+    let covered = |date: &NaiveDate| history_ranges.clone().any(|range| range.covers(date));
+    Ok(date_range.iter().filter(|date| !covered(date)).collect())
+}
+
+/// Add the histories to weather data showing progress when `stderr` is a terminal.
+///
+/// # Arguments
+///
+/// - `weather_data` is the weather library API used to add histories.
+/// - `daily_histories` are the histories that will be added.
+///
+fn add_histories(weather_data: &WeatherData, daily_histories: DailyHistories) -> Result<usize> {
+    use std::io::{stderr, IsTerminal};
+    let histories_added = if stderr().is_terminal() {
+        let added = weather_data.add_histories_with_progress(daily_histories, &mut |added, total| {
+            eprint!("\radded {}/{}", added, total);
+        })?;
+        eprintln!();
+        added
+    } else {
+        weather_data.add_histories(daily_histories)?
+    };
+    Ok(histories_added)
+}
+
 /// This function manages calling the history client and providing a hint on the request progress.
 ///
 /// # Arguments
@@ -105,7 +170,8 @@ mod v4 {
 ///
 fn get_histories(client: &Box<dyn HistoryClient>, location: Location, date_range: DateRange) -> Result<DailyHistories> {
     use std::io::stdout;
-    use std::thread::sleep;
+    use std::sync::atomic::Ordering;
+    use std::thread::{sleep, spawn};
     use std::time::{Duration, SystemTime};
     client.execute(&location, &date_range)?;
     let timeout = SystemTime::now() + Duration::new(30, 0);
@@ -114,7 +180,7 @@ fn get_histories(client: &Box<dyn HistoryClient>, location: Location, date_range
     // this loop could use some tender love
     loop {
         if SystemTime::now() > timeout {
-            Err(Error::from("Client history timed out"))?;
+            Err(Error::new(ErrorKind::Io, "Client history timed out"))?;
         }
         if (loop_cnt % 20) == 0 {
             write!(stdout().lock(), ".").unwrap();
@@ -126,9 +192,50 @@ fn get_histories(client: &Box<dyn HistoryClient>, location: Location, date_range
         }
         sleep(pause);
     }
-    // poll() breaks the loop so this will not hang the commandline
+    // poll() breaks the loop but retries of a failed request must also stop when the time is up
+    let cancel = client.cancel_handle();
+    let remaining = timeout.duration_since(SystemTime::now()).unwrap_or_default();
+    spawn(move || {
+        sleep(remaining);
+        cancel.store(true, Ordering::Relaxed);
+    });
     match client.get() {
         Ok(daily_histories) => Ok(daily_histories),
         Err(error) => Err(Error::from(error)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use toolslib::date_time::get_date;
+    use weather_lib::test_support::MockDataAPI;
+
+    #[test]
+    fn dry_run() {
+        let location = Location {
+            name: "Northern City".to_string(),
+            alias: "north".to_string(),
+            longitude: "0".to_string(),
+            latitude: "0".to_string(),
+            tz: "UTC".to_string(),
+        };
+        let history = |date| History { alias: "north".to_string(), date, ..Default::default() };
+        let weather_data = WeatherData::from(
+            MockDataAPI::new()
+                .with_location(location)
+                .with_histories(vec![history(get_date(2024, 1, 2)), history(get_date(2024, 1, 3))]),
+        );
+        let date_range = DateRange::new(get_date(2024, 1, 1), get_date(2024, 1, 4));
+        let testcase = missing_dates(&weather_data, "north", &date_range).unwrap();
+        assert_eq!(testcase, vec![get_date(2024, 1, 1), get_date(2024, 1, 4)]);
+        // weather data was not changed
+        let history_dates = weather_data.get_history_dates(DataCriteria::default()).unwrap();
+        assert_eq!(history_dates.len(), 1);
+        let dates: Vec<NaiveDate> = history_dates[0].history_dates.iter().flat_map(|range| range.iter()).collect();
+        assert_eq!(dates, vec![get_date(2024, 1, 2), get_date(2024, 1, 3)]);
+        // the flag is optional
+        let args = command().try_get_matches_from([COMMAND_NAME, "north", "2024-01-01", "--dry-run"]).unwrap();
+        assert!(args.get_flag("DRY_RUN"));
+    }
+}
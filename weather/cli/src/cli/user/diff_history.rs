@@ -0,0 +1,111 @@
+//! # The implementation for history differences (`diff`).
+//!
+//! The history differences command compares the high and low temperatures of two locations.
+//! Only the dates both locations have weather history for are reported.
+//!
+use super::*;
+
+/// The history differences command name.
+pub(super) const COMMAND_NAME: &str = "diff";
+
+pub(super) use v4::{command, execute};
+mod v4 {
+    //! The current implementation of the history differences command.
+    use super::*;
+    use reports::diff_history as reports;
+
+    /// The first location argument id.
+    ///
+    const LHS: &str = "LHS";
+
+    /// The second location argument id.
+    ///
+    const RHS: &str = "RHS";
+
+    /// The history from date argument id.
+    ///
+    const FROM: &str = "FROM";
+
+    /// The history thru date argument id.
+    ///
+    const THRU: &str = "THRU";
+
+    /// Create the history differences command.
+    ///
+    pub fn command() -> Command {
+        let cmd_args = [
+            Arg::new(LHS)
+                .action(ArgAction::Set)
+                .required(true)
+                .value_name("LOCATION")
+                .value_parser(validate_location)
+                .help("The first location weather history."),
+            Arg::new(RHS)
+                .action(ArgAction::Set)
+                .required(true)
+                .value_name("LOCATION")
+                .value_parser(validate_location)
+                .help("The location weather history compared to the first location."),
+            Arg::new(FROM)
+                .action(ArgAction::Set)
+                .required(true)
+                .value_parser(date_parser)
+                .value_name("FROM")
+                .help("The weather history starting date."),
+            Arg::new(THRU)
+                .action(ArgAction::Set)
+                .required(false)
+                .value_parser(date_parser)
+                .value_name("THRU")
+                .help("The weather history ending date."),
+        ];
+        Command::new(COMMAND_NAME)
+            .about("Compare the high and low temperatures of two locations.")
+            .args(cmd_args)
+            .args(ReportArgs::get())
+            .group(ReportArgs::arg_group())
+            .arg_required_else_help(true)
+    }
+
+    /// Executes the history differences command.
+    ///
+    /// # Arguments
+    ///
+    /// * `weather_data` is the weather library API used by the command.
+    /// * `args` contains the history differences command arguments.
+    ///
+    pub fn execute(weather_data: &WeatherData, args: ArgMatches) -> Result<()> {
+        let from = *args.get_one::<NaiveDate>(FROM).unwrap();
+        let to = args.get_one::<NaiveDate>(THRU).map_or(from, |date| *date);
+        let daily_histories = |id: &str| {
+            let location = args.get_one::<String>(id).unwrap().clone();
+            let criteria = DataCriteria { filters: vec![location], icase: true, sort: false, alias_only: false };
+            weather_data.get_daily_history(criteria, DateRange::new(from, to))
+        };
+        let history_deltas = reports::HistoryDeltas::new(daily_histories(LHS)?, daily_histories(RHS)?);
+        let report_args = ReportArgs::new(&args);
+        report_args.check_empty(history_deltas.deltas.is_empty(), "history differences")?;
+        let report = if report_args.csv() {
+            report_args.csv_report(reports::csv::Report.generate(history_deltas))
+        } else if report_args.json() {
+            match report_args.pretty() {
+                true => reports::json::Report::pretty_printed(),
+                false => reports::json::Report::default(),
+            }
+            .generate(history_deltas)
+        } else {
+            reports::text::Report::default()
+                .with_title_separator()
+                .generate(history_deltas)
+                .into_iter()
+                .map(|row| trim_row_end!(row.to_string()))
+                .collect::<Vec<String>>()
+                .join("\n")
+        };
+        let mut writer = get_writer(&report_args)?;
+        match writer.write_all(report.as_bytes()) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Error::from(err)),
+        }
+    }
+}
@@ -23,6 +23,7 @@ mod v4 {
         Command::new(COMMAND_NAME)
             .about("List the dates of weather history available by location.")
             .args(ReportArgs::get())
+            .arg(ReportArgs::date_format_arg())
             .group(ReportArgs::arg_group())
             .args(CriteriaArgs::get())
     }
@@ -39,14 +40,16 @@ mod v4 {
             filters: CriteriaArgs::new(&args).locations().clone(),
             icase: true,
             sort: true,
+            alias_only: false,
         })?;
+        ReportArgs::new(&args).check_empty(histories.is_empty(), "history dates")?;
         match histories.is_empty() {
             true => Ok(()),
             false => {
                 let report_args = ReportArgs::new(&args);
                 let mut writer = get_writer(&report_args)?;
                 let report = if report_args.csv() {
-                    reports::csv::Report::default().generate(histories)
+                    report_args.csv_report(reports::csv::Report::default().generate(histories))
                 } else if report_args.json() {
                     let report = match report_args.pretty() {
                         true => reports::json::Report::pretty_printed(),
@@ -56,7 +59,7 @@ mod v4 {
                 } else {
                     reports::text::Report::default()
                         .with_title_separator()
-                        .with_date_format("%b-%d-%Y")
+                        .with_date_format(report_args.date_format().map_or("%b-%d-%Y", |format| format.as_str()))
                         .generate(histories)
                         .into_iter()
                         .map(|row| trim_row_end!(row.to_string()))
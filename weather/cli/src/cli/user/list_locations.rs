@@ -16,12 +16,34 @@ mod v4 {
     //! * longitude and latitude
     //! * timezone
     //!
+    //! `CSV` and `JSON` reports always include every field. Text reports only include the
+    //! longitude, latitude, and timezone when `--verbose` is used.
+    //!
     //! The command allows locations to be filtered. The filtering is case-insensitive
-    //! and will match either the start of the location name or alias.
+    //! and will match either the start of the location name or alias. A regular
+    //! expression can be used instead to filter locations (`--regex`).
     //!
     use super::*;
     use reports::list_locations as reports;
 
+    /// The verbose argument id.
+    const VERBOSE: &str = "VERBOSE";
+
+    /// The regular expression filter argument id.
+    const REGEX: &str = "REGEX";
+
+    /// Used by `clap` to validate the location regular expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` is the regular expression entered on the command line.
+    fn parse_regex(pattern: &str) -> std::result::Result<String, String> {
+        match regex::Regex::new(pattern) {
+            Ok(_) => Ok(pattern.to_string()),
+            Err(err) => Err(format!("'{}' is not a valid regular expression ({}).", pattern, err)),
+        }
+    }
+
     /// Create the list locations command.
     ///
     pub fn command() -> Command {
@@ -30,6 +52,21 @@ mod v4 {
             .args(ReportArgs::get())
             .group(ReportArgs::arg_group())
             .args(CriteriaArgs::get())
+            .arg(
+                Arg::new(VERBOSE)
+                    .long("verbose")
+                    .action(ArgAction::SetTrue)
+                    .help("Include the location coordinates and timezone in text reports."),
+            )
+            .arg(
+                Arg::new(REGEX)
+                    .long("regex")
+                    .action(ArgAction::Set)
+                    .value_name("PATTERN")
+                    .value_parser(parse_regex)
+                    .conflicts_with(CriteriaArgs::LOCATIONS)
+                    .help("Filter output to locations whose name or alias match the regular expression."),
+            )
     }
 
     /// Executes the list locations command.
@@ -40,18 +77,24 @@ mod v4 {
     /// * `args` contains the list locations command arguments.
     ///
     pub fn execute(weather_data: &WeatherData, args: ArgMatches) -> Result<()> {
-        let locations = weather_data.get_locations(DataCriteria {
-            filters: CriteriaArgs::new(&args).locations().clone(),
-            icase: true,
-            sort: true,
-        })?;
+        let locations = match args.get_one::<String>(REGEX) {
+            Some(pattern) => weather_data.get_locations_regex(pattern, true, true)?,
+            None => weather_data.get_locations(DataCriteria {
+                filters: CriteriaArgs::new(&args).locations().clone(),
+                icase: true,
+                sort: true,
+                alias_only: false,
+            })?,
+        };
+        ReportArgs::new(&args).check_empty(locations.is_empty(), "locations")?;
         match locations.is_empty() {
             true => Ok(()),
             false => {
                 let report_args = ReportArgs::new(&args);
+                let verbose = args.get_flag(VERBOSE);
                 let mut writer = get_writer(&report_args)?;
                 let report = if report_args.csv() {
-                    reports::csv::Report::default().generate(locations)
+                    report_args.csv_report(reports::csv::Report::default().generate(locations))
                 } else if report_args.json() {
                     let report = match report_args.pretty() {
                         true => reports::json::Report::pretty_printed(),
@@ -59,8 +102,12 @@ mod v4 {
                     };
                     report.generate(locations)
                 } else {
-                    reports::text::Report::default()
-                        .with_title_separator()
+                    let report = reports::text::Report::default().with_title_separator();
+                    let report = match verbose {
+                        true => report.with_details(),
+                        false => report,
+                    };
+                    report
                         .generate(&locations)
                         .into_iter()
                         .map(|row| trim_row_end!(row.to_string()))
@@ -74,4 +121,30 @@ mod v4 {
             }
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn verbose() {
+            let testcase = command().try_get_matches_from(vec!["ll"]).unwrap();
+            assert!(!testcase.get_flag(VERBOSE));
+            let testcase = command().try_get_matches_from(vec!["ll", "--verbose", "tucson"]).unwrap();
+            assert!(testcase.get_flag(VERBOSE));
+            assert_eq!(CriteriaArgs::new(&testcase).locations(), vec!["tucson".to_string()]);
+        }
+
+        #[test]
+        fn regex() {
+            let testcase = command().try_get_matches_from(vec!["ll"]).unwrap();
+            assert!(testcase.get_one::<String>(REGEX).is_none());
+            let testcase = command().try_get_matches_from(vec!["ll", "--regex", "^Las"]).unwrap();
+            assert_eq!(testcase.get_one::<String>(REGEX), Some(&"^Las".to_string()));
+            // the regular expression is validated when parsed
+            assert!(command().try_get_matches_from(vec!["ll", "--regex", "^(Las"]).is_err());
+            // the regular expression replaces the location filters
+            assert!(command().try_get_matches_from(vec!["ll", "--regex", "^Las", "tucson"]).is_err());
+        }
+    }
 }
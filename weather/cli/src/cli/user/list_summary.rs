@@ -9,6 +9,9 @@
 //! * the total size of raw data
 //! * the size of the data when compressed
 //!
+//! Locations with fewer history entries than the threshold, if one is given, will be flagged.
+//! Locations can be ordered largest to smallest by either their raw or compressed size.
+//!
 //! The command allows locations_win to be filtered. The filtering is case-insensitive
 //! and will match either the start of the location name or alias.
 //!
@@ -23,6 +26,12 @@ mod v4 {
     use super::*;
     use reports::list_summary as reports;
 
+    /// The threshold argument id.
+    const THRESHOLD: &str = "THRESHOLD";
+
+    /// The sort by size argument id.
+    const SORT_BY_SIZE: &str = "SORT_BY_SIZE";
+
     /// create the list summary command.
     pub fn command() -> Command {
         Command::new(COMMAND_NAME)
@@ -30,6 +39,22 @@ mod v4 {
             .args(ReportArgs::get())
             .group(ReportArgs::arg_group())
             .args(CriteriaArgs::get())
+            .arg(
+                Arg::new(THRESHOLD)
+                    .long("threshold")
+                    .action(ArgAction::Set)
+                    .value_name("N")
+                    .value_parser(clap::value_parser!(usize))
+                    .help("Flag locations with fewer than N days of history."),
+            )
+            .arg(
+                Arg::new(SORT_BY_SIZE)
+                    .long("sort-by-size")
+                    .action(ArgAction::Set)
+                    .value_name("SIZE")
+                    .value_parser(["raw", "compressed"])
+                    .help("Order locations from largest to smallest raw or compressed size."),
+            )
     }
 
     /// Executes the list summary command.
@@ -39,31 +64,50 @@ mod v4 {
     /// * `weather_data` is the weather library API used by the command.
     /// * `args` contains the list summary command arguments.
     pub fn execute(weather_data: &WeatherData, args: ArgMatches) -> Result<()> {
-        let history_summaries = weather_data.get_history_summary(DataCriteria {
+        let mut history_summaries = weather_data.get_history_summary(DataCriteria {
             filters: CriteriaArgs::new(&args).locations().clone(),
             icase: true,
             sort: true,
+            alias_only: false,
         })?;
+        ReportArgs::new(&args).check_empty(history_summaries.is_empty(), "history summaries")?;
         match history_summaries.is_empty() {
             true => Ok(()),
             false => {
                 let report_args = ReportArgs::new(&args);
+                let threshold = args.get_one::<usize>(THRESHOLD).copied();
+                if let Some(size_order) = size_order(&args) {
+                    reports::sort_by_size(&mut history_summaries, size_order);
+                }
                 let report = if report_args.csv() {
-                    reports::csv::Report::default().generate(history_summaries)
+                    let report = reports::csv::Report::default();
+                    let report = match threshold {
+                        Some(threshold) => report.with_threshold(threshold),
+                        None => report,
+                    }
+                    .generate(history_summaries);
+                    report_args.csv_report(report)
                 } else if report_args.json() {
                     let report = match report_args.pretty() {
                         true => reports::json::Report::pretty_printed(),
                         false => reports::json::Report::default(),
                     };
-                    report.generate(history_summaries)
+                    match threshold {
+                        Some(threshold) => report.with_threshold(threshold),
+                        None => report,
+                    }
+                    .generate(history_summaries)
                 } else {
-                    reports::text::Report::default()
-                        .with_title_separator()
-                        .generate(history_summaries)
-                        .into_iter()
-                        .map(|row| trim_row_end!(row.to_string()))
-                        .collect::<Vec<String>>()
-                        .join("\n")
+                    let report = reports::text::Report::default().with_title_separator();
+                    match threshold {
+                        Some(threshold) => report.with_threshold(threshold),
+                        None => report,
+                    }
+                    .generate(history_summaries)
+                    .into_iter()
+                    .map(|row| trim_row_end!(row.to_string()))
+                    .collect::<Vec<String>>()
+                    .join("\n")
                 };
                 let mut writer = get_writer(&report_args)?;
                 match writer.write_all(report.as_bytes()) {
@@ -73,4 +117,42 @@ mod v4 {
             }
         }
     }
+
+    /// Get the size that locations will be ordered by, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `args` contains the list summary command arguments.
+    fn size_order(args: &ArgMatches) -> Option<reports::SizeOrder> {
+        args.get_one::<String>(SORT_BY_SIZE).map(|size| match size.as_str() {
+            "raw" => reports::SizeOrder::Raw,
+            _ => reports::SizeOrder::Compressed,
+        })
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn threshold() {
+            let testcase = command().try_get_matches_from(vec!["ls"]).unwrap();
+            assert!(testcase.get_one::<usize>(THRESHOLD).is_none());
+            let testcase = command().try_get_matches_from(vec!["ls", "--threshold", "30"]).unwrap();
+            assert_eq!(testcase.get_one::<usize>(THRESHOLD), Some(&30));
+            assert!(command().try_get_matches_from(vec!["ls", "--threshold", "many"]).is_err());
+        }
+
+        #[test]
+        fn sort_by_size() {
+            let testcase = command().try_get_matches_from(vec!["ls"]).unwrap();
+            assert!(size_order(&testcase).is_none());
+            let testcase = command().try_get_matches_from(vec!["ls", "--sort-by-size", "raw"]).unwrap();
+            assert_eq!(size_order(&testcase), Some(reports::SizeOrder::Raw));
+            let testcase = command().try_get_matches_from(vec!["ls", "--sort-by-size", "compressed"]).unwrap();
+            assert_eq!(size_order(&testcase), Some(reports::SizeOrder::Compressed));
+            assert!(command().try_get_matches_from(vec!["ls", "--sort-by-size"]).is_err());
+            assert!(command().try_get_matches_from(vec!["ls", "--sort-by-size", "overall"]).is_err());
+        }
+    }
 }
@@ -4,7 +4,9 @@
 //! The details shown depend on what command line flags are supplied.
 //! The command will show the high and low temperatures for a date by default.
 //!
-//! Currently only 1 location can be used.
+//! More than 1 location can be reported. The location reports are merged into a single report, where
+//! `JSON` reports become an array and `CSV` reports share one header, unless an output directory is used
+//! to write each location report to its own file.
 //!
 use super::*;
 
@@ -15,7 +17,7 @@ pub(super) use v4::{command, execute};
 mod v4 {
     //! The current implementation of the report history command.
     use super::*;
-    use crate::cli::reports::report_history::ReportSelector;
+    use crate::cli::reports::report_history::{HeaderStyle, JsonShape, ReportSelector};
     use reports::report_history as reports;
 
     /// The report temperature argument id.
@@ -54,33 +56,151 @@ mod v4 {
         }
     }
 
+    /// The report temperature anomaly argument id.
+    ///
+    const ANOMALY: &str = "ANOMALY";
+
+    /// The minimum high temperature argument id.
+    ///
+    const MIN_TEMP: &str = "MIN_TEMP";
+
+    /// The maximum high temperature argument id.
+    ///
+    const MAX_TEMP: &str = "MAX_TEMP";
+
+    /// The text report decimal places argument id.
+    ///
+    const ROUND: &str = "ROUND";
+
+    /// The report times in UTC argument id.
+    ///
+    const UTC: &str = "UTC";
+
+    /// The report every day in the date range argument id.
+    ///
+    const FILL_GAPS: &str = "FILL_GAPS";
+
+    /// The report every Nth day argument id.
+    ///
+    const SAMPLE: &str = "SAMPLE";
+
+    /// The text report high temperature sparkline argument id.
+    ///
+    const GRAPH: &str = "GRAPH";
+
+    /// The temperature statistics argument id.
+    ///
+    const STATS_ONLY: &str = "STATS_ONLY";
+
+    /// The SVG temperature chart argument id.
+    ///
+    const EXPORT_CHART: &str = "EXPORT_CHART";
+
+    /// The per-location report directory argument id.
+    ///
+    const OUTPUT_DIR: &str = "OUTPUT_DIR";
+
+    /// The report anomalous history values argument id.
+    ///
+    const VALIDATE: &str = "VALIDATE";
+
+    /// The group id of the arguments that say where the SVG temperature chart is written.
+    ///
+    const CHART_OUTPUT: &str = "CHART_OUTPUT";
+
     /// The location argument id.
     ///
     const LOCATION: &'static str = "LOCATION";
 
-    fn get_location(args: &ArgMatches) -> String {
-        args.get_one::<String>(LOCATION).map(|location| location.clone()).unwrap()
+    fn get_locations(args: &ArgMatches) -> Vec<String> {
+        args.get_one::<Vec<String>>(LOCATION).map(|locations| locations.clone()).unwrap()
     }
 
     /// The history from date argument id.
     ///
     const FROM: &'static str = "FROM";
 
+    /// The history since date argument id.
+    ///
+    const SINCE: &str = "SINCE";
+
+    /// The group id of the history starting date arguments.
+    ///
+    const STARTING: &str = "STARTING";
+
     fn get_from(args: &ArgMatches) -> NaiveDate {
-        args.get_one::<NaiveDate>(FROM).unwrap().clone()
+        match args.get_one::<NaiveDate>(FROM) {
+            None => *args.get_one::<NaiveDate>(SINCE).unwrap(),
+            Some(date) => *date,
+        }
     }
 
     /// The history thru date argument id.
     ///
     const THRU: &'static str = "THRU";
 
+    /// The history until date argument id.
+    ///
+    const UNTIL: &str = "UNTIL";
+
     fn get_thru(args: &ArgMatches) -> NaiveDate {
-        match args.get_one::<NaiveDate>(THRU) {
+        match args.get_one::<NaiveDate>(THRU).or(args.get_one::<NaiveDate>(UNTIL)) {
             None => get_from(args),
-            Some(date) => date.clone(),
+            Some(date) => *date,
         }
     }
 
+    /// An internal helper that resolves the history dates from either the positional or named arguments.
+    ///
+    /// # Arguments
+    ///
+    /// - `args` is the collection of command line arguments.
+    ///
+    pub(super) fn get_date_range(args: &ArgMatches) -> DateRange {
+        DateRange::new(get_from(args), get_thru(args))
+    }
+
+    /// The CSV and JSON field names style argument id.
+    ///
+    const HEADER_STYLE: &str = "HEADER_STYLE";
+
+    fn parse_header_style(style: &str) -> std::result::Result<HeaderStyle, String> {
+        HeaderStyle::try_from(style)
+    }
+
+    fn get_header_style(args: &ArgMatches) -> HeaderStyle {
+        args.get_one::<HeaderStyle>(HEADER_STYLE).copied().unwrap_or_default()
+    }
+
+    /// The JSON document layout argument id.
+    ///
+    const JSON_SHAPE: &str = "JSON_SHAPE";
+
+    /// Parse the JSON document layout argument.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape` is the layout name, either `object` or `array`.
+    ///
+    fn parse_json_shape(shape: &str) -> std::result::Result<JsonShape, String> {
+        JsonShape::try_from(shape)
+    }
+
+    pub(super) fn get_json_shape(args: &ArgMatches) -> JsonShape {
+        args.get_one::<JsonShape>(JSON_SHAPE).copied().unwrap_or_default()
+    }
+
+    /// An internal helper that gets the text report decimal places, the report default is used when
+    /// the argument is missing.
+    ///
+    /// # Arguments
+    ///
+    /// - `args` is the collection of command line arguments.
+    ///
+    pub(super) fn get_precision(args: &ArgMatches) -> usize {
+        args.get_one::<u8>(ROUND).map_or(reports::text::DEFAULT_PRECISION, |precision| *precision as usize)
+    }
+
     /// Create the report history command.
     ///
     pub fn command() -> Command {
@@ -114,15 +234,73 @@ mod v4 {
                 .long("all")
                 .action(ArgAction::SetTrue)
                 .help("Include all weather information in the report."),
+            Arg::new(ANOMALY)
+                .long("anomaly")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([ReportArgs::CSV, ReportArgs::JSON, EXPORT_CHART])
+                .help("Report high and low temperatures as the difference from their mean over the dates (text only)."),
+            Arg::new(MIN_TEMP)
+                .long("min-temp")
+                .action(ArgAction::Set)
+                .value_name("TEMP")
+                .value_parser(clap::value_parser!(f64))
+                .allow_negative_numbers(true)
+                .help("Only report days whose high temperature is at least TEMP."),
+            Arg::new(MAX_TEMP)
+                .long("max-temp")
+                .action(ArgAction::Set)
+                .value_name("TEMP")
+                .value_parser(clap::value_parser!(f64))
+                .allow_negative_numbers(true)
+                .help("Only report days whose high temperature is at most TEMP."),
+            Arg::new(ROUND)
+                .long("round")
+                .action(ArgAction::Set)
+                .value_name("N")
+                .value_parser(clap::value_parser!(u8).range(0..=2))
+                .help("The decimal places (0-2) of temperatures, wind, and pressure in the text report (default 1)."),
+            Arg::new(UTC)
+                .long("utc")
+                .action(ArgAction::SetTrue)
+                .help("Report times in UTC instead of the location timezone."),
+            Arg::new(FILL_GAPS)
+                .long("fill-gaps")
+                .action(ArgAction::SetTrue)
+                .help("Include an empty row for days that do not have weather history."),
+            Arg::new(SAMPLE)
+                .long("sample")
+                .action(ArgAction::Set)
+                .value_name("N")
+                .value_parser(clap::value_parser!(u32).range(1..))
+                .help("Only report every Nth day starting with the first day."),
+            Arg::new(GRAPH)
+                .long("graph")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([ReportArgs::CSV, ReportArgs::JSON])
+                .help("Graph the daily high temperatures as a sparkline instead of a text table."),
+            Arg::new(STATS_ONLY)
+                .long("stats-only")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([GRAPH, ANOMALY, FILL_GAPS, ReportArgs::CSV, ReportArgs::JSON, EXPORT_CHART])
+                .help("Report the mean, min, and max high and low temperatures instead of each day (text only)."),
+            Arg::new(EXPORT_CHART)
+                .long("export-chart")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all([GRAPH, STATS_ONLY, ReportArgs::CSV, ReportArgs::JSON])
+                .requires(CHART_OUTPUT)
+                .help("Chart the daily high and low temperatures as an SVG line chart to the report file or DIR."),
+            Arg::new(VALIDATE)
+                .long("validate")
+                .action(ArgAction::SetTrue)
+                .help("Report a warning to stderr for weather history values that are out of bounds."),
             Arg::new(LOCATION)
                 .action(ArgAction::Set)
                 .required(true)
                 .value_name("LOCATION")
-                .value_parser(validate_location)
-                .help("The location to use for the weather history."),
+                .value_parser(parse_locations)
+                .help("The location to use for the weather history (@FILE reads locations from a file)."),
             Arg::new(FROM)
                 .action(ArgAction::Set)
-                .required(true)
                 .value_parser(date_parser)
                 .value_name("FROM")
                 .help("The weather history starting date."),
@@ -131,12 +309,49 @@ mod v4 {
                 .required(false)
                 .value_parser(date_parser)
                 .value_name("THRU")
+                .conflicts_with_all([SINCE, UNTIL])
                 .help("The weather history ending date."),
+            Arg::new(SINCE)
+                .long("since")
+                .action(ArgAction::Set)
+                .value_parser(date_parser)
+                .value_name("DATE")
+                .help("The weather history starting date (alternative to FROM)."),
+            Arg::new(UNTIL)
+                .long("until")
+                .action(ArgAction::Set)
+                .value_parser(date_parser)
+                .value_name("DATE")
+                .requires(SINCE)
+                .help("The weather history ending date (alternative to THRU)."),
+            Arg::new(OUTPUT_DIR)
+                .long("output-dir")
+                .action(ArgAction::Set)
+                .value_name("DIR")
+                .value_parser(parse_dirname)
+                .help("Write each location report to DIR as <alias>.<ext> (overrides the report filename)."),
+            Arg::new(HEADER_STYLE)
+                .long("header-style")
+                .alias("csv-header-style")
+                .action(ArgAction::Set)
+                .value_name("STYLE")
+                .value_parser(parse_header_style)
+                .help("The CSV and JSON field name style, either camel (default) or snake."),
+            Arg::new(JSON_SHAPE)
+                .long("json-shape")
+                .action(ArgAction::Set)
+                .value_name("SHAPE")
+                .value_parser(parse_json_shape)
+                .requires(ReportArgs::JSON)
+                .help("The JSON document layout, either object (default) or a top-level array of days."),
         ];
         Command::new(COMMAND_NAME)
             .about("Generate a weather history report for a location.")
             .args(cmd_args)
+            .group(ArgGroup::new(STARTING).args([FROM, SINCE]).required(true).multiple(false))
+            .group(ArgGroup::new(CHART_OUTPUT).args([ReportArgs::REPORT_FILE, OUTPUT_DIR]).multiple(true))
             .args(ReportArgs::get())
+            .arg(ReportArgs::date_format_arg())
             .group(ReportArgs::arg_group())
             .arg_required_else_help(true)
     }
@@ -149,33 +364,522 @@ mod v4 {
     /// * `args` contains the report history command arguments.
     ///
     pub fn execute(weather_data: &WeatherData, args: ArgMatches) -> Result<()> {
-        let location = get_location(&args);
-        let criteria = DataCriteria { filters: vec![location], icase: true, sort: false };
-        let date_range = DateRange { from: get_from(&args), to: get_thru(&args) };
-        let histories = weather_data.get_daily_history(criteria, date_range)?;
-        let report_selector = create_report_selector(&args);
         let report_args = ReportArgs::new(&args);
-        let report = if report_args.csv() {
-            reports::csv::Report::new(report_selector).generate(histories)
+        let header_style = get_header_style(&args);
+        let locations = get_locations(&args);
+        if args.get_flag(EXPORT_CHART) && locations.len() > 1 && !args.contains_id(OUTPUT_DIR) {
+            let reason = "A chart can only be exported for multiple locations with --output-dir.";
+            return Err(Error::new(ErrorKind::Invalid, reason));
+        }
+        let location_reports = location_reports(locations, |location| {
+            let criteria = DataCriteria { filters: vec![location], icase: true, sort: false, alias_only: false };
+            let (histories, failures, warnings) = match args.get_flag(VALIDATE) {
+                true => weather_data.get_validated_daily_history(criteria, get_date_range(&args))?,
+                false => {
+                    let (histories, failures) =
+                        weather_data.get_daily_history_with_failures(criteria, get_date_range(&args))?;
+                    (histories, failures, vec![])
+                }
+            };
+            let alias = histories.location.alias.clone();
+            for line in failures_report(&alias, &failures) {
+                eprintln!("{}", line);
+            }
+            for warning in warnings {
+                eprintln!("{}: {}", alias, warning);
+            }
+            let history_dates: Vec<NaiveDate> = histories.histories.iter().map(|history| history.date).collect();
+            let min_temp = args.get_one::<f64>(MIN_TEMP).copied();
+            let max_temp = args.get_one::<f64>(MAX_TEMP).copied();
+            let histories = reports::filter_high_temperature(histories, min_temp, max_temp);
+            report_args.check_empty(histories.histories.is_empty(), &format!("{} histories", alias))?;
+            let histories = match args.get_flag(UTC) {
+                true => reports::utc_times(histories),
+                false => histories,
+            };
+            let histories = match args.get_flag(FILL_GAPS) {
+                true => reports::fill_gaps(histories, &get_date_range(&args), &history_dates),
+                false => histories,
+            };
+            let report_selector = create_report_selector(&args);
+            // the baseline is calculated before sampling so it covers every day, anomalies are only in text reports
+            let text_report = !(args.get_flag(EXPORT_CHART) || report_args.csv() || report_args.json());
+            let (baseline, histories) = match args.get_flag(ANOMALY) && text_report {
+                true => {
+                    let (baseline, histories) = reports::temperature_anomalies(histories);
+                    (Some(baseline), histories)
+                }
+                false => (None, histories),
+            };
+            let histories = match args.get_one::<u32>(SAMPLE) {
+                Some(every) => reports::sample_histories(histories, *every as usize),
+                None => histories,
+            };
+            let report = if args.get_flag(EXPORT_CHART) {
+                reports::svg::Report.generate(histories)
+            } else if report_args.csv() {
+                let report =
+                    reports::csv::Report::new(report_selector).with_header_style(header_style).generate(histories);
+                report_args.csv_report(report)
+            } else if report_args.json() {
+                match report_args.pretty() {
+                    true => reports::json::Report::pretty_printed(report_selector),
+                    false => reports::json::Report::new(report_selector),
+                }
+                .with_header_style(header_style)
+                .with_json_shape(get_json_shape(&args))
+                .generate(histories)
+            } else {
+                let report = reports::text::Report::new(report_selector)
+                    .with_title_separator()
+                    .with_precision(get_precision(&args));
+                let report = match baseline {
+                    Some(baseline) => report.with_anomaly(baseline),
+                    None => report,
+                };
+                let report = match report_args.date_format() {
+                    Some(date_format) => report.with_date_format(date_format),
+                    None => report,
+                };
+                if args.get_flag(STATS_ONLY) {
+                    report.generate_stats(histories).join("\n")
+                } else if args.get_flag(GRAPH) {
+                    report.generate_graph(histories).join("\n")
+                } else {
+                    report
+                        .generate(histories)
+                        .into_iter()
+                        .map(|row| trim_row_end!(row.to_string()))
+                        .collect::<Vec<String>>()
+                        .join("\n")
+                }
+            };
+            Ok((alias, report))
+        })?;
+        write_location_reports(&report_args, args.get_one::<PathBuf>(OUTPUT_DIR), location_reports)
+    }
+
+    /// An internal helper that describes the histories that could not be read so the report can be
+    /// identified as partial.
+    ///
+    /// # Arguments
+    ///
+    /// - `alias` is the location alias.
+    /// - `failures` are the history dates that could not be read and the reason why.
+    ///
+    pub(super) fn failures_report(alias: &str, failures: &HistoryFailures) -> Vec<String> {
+        match failures.is_empty() {
+            true => vec![],
+            false => {
+                let mut lines = vec![format!("Warning: {} {} histories could not be read.", failures.len(), alias)];
+                lines.extend(failures.iter().map(|(date, reason)| format!("  {}: {}", date, reason)));
+                lines
+            }
+        }
+    }
+
+    /// An internal helper that writes the location reports.
+    ///
+    /// When there is an output directory each report is written to its own file, otherwise the reports
+    /// are merged into a single report.
+    ///
+    /// # Arguments
+    ///
+    /// - `report_args` has the command line arguments surrounding report generation.
+    /// - `output_dir` is the optional directory where location reports will be written.
+    /// - `location_reports` are the location alias and report pairs.
+    ///
+    pub(super) fn write_location_reports(
+        report_args: &ReportArgs,
+        output_dir: Option<&PathBuf>,
+        location_reports: Vec<(String, String)>,
+    ) -> Result<()> {
+        match output_dir {
+            Some(output_dir) => {
+                for (alias, report) in location_reports {
+                    let mut writer = get_location_writer(report_args, output_dir, &alias)?;
+                    writer.write_all(report.as_bytes())?;
+                }
+                Ok(())
+            }
+            None => {
+                let reports: Vec<String> = location_reports.into_iter().map(|(_, report)| report).collect();
+                let mut writer = get_writer(report_args)?;
+                match writer.write_all(merge_reports(report_args, reports).as_bytes()) {
+                    Ok(_) => Ok(()),
+                    Err(err) => Err(Error::from(err)),
+                }
+            }
+        }
+    }
+
+    /// An internal helper that merges location reports into a single report.
+    ///
+    /// Multiple `JSON` reports become a `JSON` array and only the first `CSV` report keeps its header
+    /// so the merged report is still a valid document.
+    ///
+    /// # Arguments
+    ///
+    /// - `report_args` has the command line arguments surrounding report generation.
+    /// - `reports` are the location reports.
+    ///
+    pub(super) fn merge_reports(report_args: &ReportArgs, reports: Vec<String>) -> String {
+        if reports.len() < 2 {
+            reports.join("\n")
         } else if report_args.json() {
-            match report_args.pretty() {
-                true => reports::json::Report::pretty_printed(report_selector),
-                false => reports::json::Report::new(report_selector),
+            format!("[{}]", reports.join(","))
+        } else if report_args.csv() {
+            let mut merged = String::default();
+            for (index, report) in reports.into_iter().enumerate() {
+                let rows = match index {
+                    0 => report.as_str(),
+                    _ => report.split_once('\n').map_or("", |(_, rows)| rows),
+                };
+                if !(merged.is_empty() || merged.ends_with('\n')) {
+                    merged.push('\n');
+                }
+                merged.push_str(rows);
             }
-            .generate(histories)
+            merged
         } else {
-            reports::text::Report::new(report_selector)
-                .with_title_separator()
-                .generate(histories)
-                .into_iter()
-                .map(|row| trim_row_end!(row.to_string()))
-                .collect::<Vec<String>>()
-                .join("\n")
+            reports.join("\n")
+        }
+    }
+
+    /// An internal helper that generates a report for each location.
+    ///
+    /// # Arguments
+    ///
+    /// - `locations` are the locations that will be reported.
+    /// - `report` generates the report for a location.
+    ///
+    pub(super) fn location_reports<F, T>(locations: Vec<String>, report: F) -> Result<Vec<T>>
+    where
+        F: FnMut(String) -> Result<T>,
+    {
+        locations.into_iter().map(report).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date_range(args: &[&str]) -> DateRange {
+        let args = v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args)).unwrap();
+        v4::get_date_range(&args)
+    }
+
+    #[test]
+    fn failures_report() {
+        assert!(v4::failures_report("north", &vec![]).is_empty());
+        let failures = vec![(toolslib::date_time::get_date(2024, 1, 2), "bad json".to_string())];
+        let testcase = v4::failures_report("north", &failures);
+        assert_eq!(testcase, vec!["Warning: 1 north histories could not be read.", "  2024-01-02: bad json"]);
+    }
+
+    #[test]
+    fn named_dates() {
+        let positional = date_range(&["loc", "2024-01-01", "2024-01-31"]);
+        let named = date_range(&["loc", "--since", "2024-01-01", "--until", "2024-01-31"]);
+        assert_eq!(named.from, positional.from);
+        assert_eq!(named.to, positional.to);
+        let positional = date_range(&["loc", "2024-01-01"]);
+        let named = date_range(&["loc", "--since", "2024-01-01"]);
+        assert_eq!(named.from, positional.from);
+        assert_eq!(named.to, positional.to);
+        assert!(named.is_one_day());
+    }
+
+    #[test]
+    fn named_dates_conflict() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(matches(&["loc", "2024-01-01", "--since", "2024-01-01"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "2024-01-31", "--until", "2024-01-31"]).is_err());
+        assert!(matches(&["loc", "--until", "2024-01-31"]).is_err());
+        assert!(matches(&["loc"]).is_err());
+    }
+
+    #[test]
+    fn location_file() {
+        let filename = std::env::temp_dir().join(format!("rh-locations-{}.txt", std::process::id()));
+        std::fs::write(&filename, "# the test locations\nnorth\n\n  south  \n").unwrap();
+        let location_arg = format!("@{}", filename.display());
+        let args = v4::command().try_get_matches_from([COMMAND_NAME, &location_arg, "2024-01-01"]);
+        std::fs::remove_file(&filename).unwrap();
+        let args = args.unwrap();
+        let locations = args.get_one::<Vec<String>>("LOCATION").unwrap().clone();
+        assert_eq!(locations, vec!["north", "south"]);
+        let reports = v4::location_reports(locations, |location| Ok(format!("report {}", location))).unwrap();
+        assert_eq!(reports, vec!["report north", "report south"]);
+        // a missing file is an error
+        assert!(v4::command().try_get_matches_from([COMMAND_NAME, "@/no/such/file.txt", "2024-01-01"]).is_err());
+    }
+
+    #[test]
+    fn header_style() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(matches(&["loc", "2024-01-01", "--header-style", "snake"]).is_ok());
+        assert!(matches(&["loc", "2024-01-01", "--csv-header-style", "camel"]).is_ok());
+        assert!(matches(&["loc", "2024-01-01", "--header-style", "kebab"]).is_err());
+    }
+
+    #[test]
+    fn json_shape() {
+        use reports::report_history::JsonShape;
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert_eq!(v4::get_json_shape(&matches(&["loc", "2024-01-01"]).unwrap()), JsonShape::Object);
+        let testcase = matches(&["loc", "2024-01-01", "--json", "--json-shape", "array"]).unwrap();
+        assert_eq!(v4::get_json_shape(&testcase), JsonShape::Array);
+        let testcase = matches(&["loc", "2024-01-01", "--json", "--json-shape", "object"]).unwrap();
+        assert_eq!(v4::get_json_shape(&testcase), JsonShape::Object);
+        assert!(matches(&["loc", "2024-01-01", "--json", "--json-shape", "tree"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--json-shape", "array"]).is_err());
+    }
+
+    #[test]
+    fn anomaly() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(!matches(&["loc", "2024-01-01"]).unwrap().get_flag("ANOMALY"));
+        assert!(matches(&["loc", "2024-01-01", "2024-01-31", "--anomaly"]).unwrap().get_flag("ANOMALY"));
+        // the temperatures are only replaced in text reports
+        assert!(matches(&["loc", "2024-01-01", "--anomaly", "--csv"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--anomaly", "--json"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--anomaly", "--export-chart"]).is_err());
+    }
+
+    #[test]
+    fn temperature_range() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        let testcase = matches(&["loc", "2024-01-01", "--min-temp", "-5.5", "--max-temp", "90"]).unwrap();
+        assert_eq!(testcase.get_one::<f64>("MIN_TEMP"), Some(&-5.5));
+        assert_eq!(testcase.get_one::<f64>("MAX_TEMP"), Some(&90.0));
+        let testcase = matches(&["loc", "2024-01-01"]).unwrap();
+        assert!(testcase.get_one::<f64>("MIN_TEMP").is_none());
+        assert!(matches(&["loc", "2024-01-01", "--max-temp", "hot"]).is_err());
+    }
+
+    #[test]
+    fn round() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert_eq!(v4::get_precision(&matches(&["loc", "2024-01-01"]).unwrap()), 1);
+        assert_eq!(v4::get_precision(&matches(&["loc", "2024-01-01", "--round", "0"]).unwrap()), 0);
+        assert_eq!(v4::get_precision(&matches(&["loc", "2024-01-01", "--round", "2"]).unwrap()), 2);
+        assert!(matches(&["loc", "2024-01-01", "--round", "3"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--round", "-1"]).is_err());
+    }
+
+    #[test]
+    fn utc() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(!matches(&["loc", "2024-01-01"]).unwrap().get_flag("UTC"));
+        assert!(matches(&["loc", "2024-01-01", "--utc"]).unwrap().get_flag("UTC"));
+    }
+
+    #[test]
+    fn fill_gaps() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(!matches(&["loc", "2024-01-01"]).unwrap().get_flag("FILL_GAPS"));
+        assert!(matches(&["loc", "2024-01-01", "2024-01-31", "--fill-gaps"]).unwrap().get_flag("FILL_GAPS"));
+    }
+
+    #[test]
+    fn sample() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(matches(&["loc", "2024-01-01"]).unwrap().get_one::<u32>("SAMPLE").is_none());
+        let testcase = matches(&["loc", "2024-01-01", "2024-12-31", "--sample", "7"]).unwrap();
+        assert_eq!(testcase.get_one::<u32>("SAMPLE"), Some(&7));
+        assert!(matches(&["loc", "2024-01-01", "--sample", "0"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--sample", "weekly"]).is_err());
+    }
+
+    #[test]
+    fn graph() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(!matches(&["loc", "2024-01-01"]).unwrap().get_flag("GRAPH"));
+        assert!(matches(&["loc", "2024-01-01", "2024-01-31", "--graph"]).unwrap().get_flag("GRAPH"));
+        assert!(matches(&["loc", "2024-01-01", "--graph", "--csv"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--graph", "--json"]).is_err());
+    }
+
+    #[test]
+    fn stats_only() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(!matches(&["loc", "2024-01-01"]).unwrap().get_flag("STATS_ONLY"));
+        assert!(matches(&["loc", "2024-01-01", "2024-01-31", "--stats-only"]).unwrap().get_flag("STATS_ONLY"));
+        assert!(matches(&["loc", "2024-01-01", "--stats-only", "--graph"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--stats-only", "--anomaly"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--stats-only", "--fill-gaps"]).is_err());
+        // the statistics are only a text report
+        assert!(matches(&["loc", "2024-01-01", "--stats-only", "--csv"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--stats-only", "--json"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--stats-only", "--export-chart", "-r", "chart.svg"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--stats-only", "--text"]).is_ok());
+    }
+
+    #[test]
+    fn date_format() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(matches(&["loc", "2024-01-15", "--date-format", ""]).is_err());
+        assert!(matches(&["loc", "2024-01-15", "--date-format", "%Y-%m-%d %H:%M"]).is_err());
+        let args = matches(&["loc", "2024-01-15"]).unwrap();
+        assert!(ReportArgs::new(&args).date_format().is_none());
+        let args = matches(&["loc", "2024-01-15", "--date-format", "%m/%d/%Y"]).unwrap();
+        let date_format = ReportArgs::new(&args).date_format().unwrap().clone();
+        let daily_histories = DailyHistories {
+            location: Location {
+                name: "Testcase".to_string(),
+                alias: "testcase".to_string(),
+                longitude: "0".to_string(),
+                latitude: "0".to_string(),
+                tz: "UTC".to_string(),
+            },
+            histories: vec![History {
+                alias: "testcase".to_string(),
+                date: v4::get_date_range(&args).from,
+                ..Default::default()
+            }],
         };
-        let mut writer = get_writer(&report_args)?;
-        match writer.write_all(report.as_bytes()) {
-            Ok(_) => Ok(()),
-            Err(err) => Err(Error::from(err)),
+        let report = reports::report_history::text::Report::new(Default::default())
+            .with_date_format(&date_format)
+            .generate(daily_histories);
+        let rows: Vec<String> = report.into_iter().map(|row| row.to_string()).collect();
+        assert!(rows[2].starts_with("01/15/2024"));
+    }
+
+    #[test]
+    fn export_chart() {
+        let matches = |args: &[&str]| v4::command().try_get_matches_from([COMMAND_NAME].iter().chain(args));
+        assert!(!matches(&["loc", "2024-01-01"]).unwrap().get_flag("EXPORT_CHART"));
+        assert!(matches(&["loc", "2024-01-01", "--export-chart", "-r", "chart.svg"]).unwrap().get_flag("EXPORT_CHART"));
+        assert!(matches(&["loc", "2024-01-01", "--export-chart", "--graph"]).is_err());
+        // the chart is written to a file and is not a CSV or JSON report
+        assert!(matches(&["loc", "2024-01-01", "--export-chart"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--export-chart", "--output-dir", "."]).is_ok());
+        assert!(matches(&["loc", "2024-01-01", "--export-chart", "-r", "chart.svg", "--csv"]).is_err());
+        assert!(matches(&["loc", "2024-01-01", "--export-chart", "-r", "chart.svg", "--json"]).is_err());
+        // multiple locations need a file for each chart
+        let locations_file = std::env::temp_dir().join(format!("rh-chart-{}.txt", std::process::id()));
+        std::fs::write(&locations_file, "north\nsouth\n").unwrap();
+        let locations = format!("@{}", locations_file.display());
+        let args = matches(&[&locations, "2024-01-01", "--export-chart", "-r", "chart.svg"]).unwrap();
+        std::fs::remove_file(&locations_file).unwrap();
+        let weather_data = WeatherData::from(weather_lib::test_support::MockDataAPI::new());
+        let error = v4::execute(&weather_data, args).unwrap_err();
+        assert!(error.to_string().contains("--output-dir"));
+    }
+
+    #[test]
+    fn merge_reports() {
+        let args = |format: &str| v4::command().try_get_matches_from([COMMAND_NAME, "loc", "2024-01-01", format]);
+        let reports = |reports: &[&str]| reports.iter().map(|report| report.to_string()).collect::<Vec<_>>();
+        let json = args("--json").unwrap();
+        let testcase = v4::merge_reports(&ReportArgs::new(&json), reports(&[r#"{"a":1}"#, r#"{"a":2}"#]));
+        assert_eq!(testcase, r#"[{"a":1},{"a":2}]"#);
+        let value: serde_json::Value = serde_json::from_str(&testcase).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 2);
+        // a single location is not changed
+        assert_eq!(v4::merge_reports(&ReportArgs::new(&json), reports(&[r#"{"a":1}"#])), r#"{"a":1}"#);
+        let csv = args("--csv").unwrap();
+        let testcase = v4::merge_reports(&ReportArgs::new(&csv), reports(&["date,high\n1,2\n", "date,high\n3,4\n"]));
+        assert_eq!(testcase, "date,high\n1,2\n3,4\n");
+        let testcase = v4::merge_reports(&ReportArgs::new(&csv), reports(&["date,high\n1,2", "date,high\n3,4"]));
+        assert_eq!(testcase, "date,high\n1,2\n3,4");
+        let text = args("--text").unwrap();
+        assert_eq!(v4::merge_reports(&ReportArgs::new(&text), reports(&["north", "south"])), "north\nsouth");
+    }
+
+    #[test]
+    fn output_dir() {
+        let output_dir = std::env::temp_dir().join(format!("rh-output-{}", std::process::id()));
+        std::fs::create_dir_all(&output_dir).unwrap();
+        let dirname = output_dir.display().to_string();
+        let args = v4::command()
+            .try_get_matches_from([COMMAND_NAME, "loc", "2024-01-01", "--csv", "--output-dir", &dirname])
+            .unwrap();
+        let location_reports = vec![
+            ("north".to_string(), "north report".to_string()),
+            ("south".to_string(), "south report".to_string()),
+        ];
+        let result = v4::write_location_reports(&ReportArgs::new(&args), Some(&output_dir), location_reports);
+        let north = std::fs::read_to_string(output_dir.join("north.csv"));
+        let south = std::fs::read_to_string(output_dir.join("south.csv"));
+        std::fs::remove_dir_all(&output_dir).unwrap();
+        result.unwrap();
+        assert_eq!(north.unwrap(), "north report");
+        assert_eq!(south.unwrap(), "south report");
+        // the directory must exist
+        let args = [COMMAND_NAME, "loc", "2024-01-01", "--output-dir", "/no/such/dir"];
+        assert!(v4::command().try_get_matches_from(args).is_err());
+    }
+
+    #[test]
+    fn fail_on_empty() {
+        use toolslib::date_time::get_date;
+        use weather_lib::test_support::MockDataAPI;
+        let location = Location {
+            name: "Northern City".to_string(),
+            alias: "north".to_string(),
+            longitude: "0".to_string(),
+            latitude: "0".to_string(),
+            tz: "UTC".to_string(),
+        };
+        let history = History { alias: "north".to_string(), date: get_date(2024, 1, 2), ..Default::default() };
+        let weather_data = WeatherData::from(MockDataAPI::new().with_location(location).with_histories(vec![history]));
+        let report_file = std::env::temp_dir().join(format!("rh-empty-{}.csv", std::process::id()));
+        let filename = report_file.display().to_string();
+        let execute = |args: &[&str]| {
+            let args = v4::command()
+                .try_get_matches_from([COMMAND_NAME, "north", "--csv", "-r", &filename].iter().chain(args))
+                .unwrap();
+            v4::execute(&weather_data, args)
+        };
+        // an empty report is not an error without the flag
+        let empty = execute(&["2024-02-01", "2024-02-02"]);
+        let with_flag = execute(&["2024-02-01", "2024-02-02", "--fail-on-empty"]);
+        let not_empty = execute(&["2024-01-01", "2024-01-02", "--fail-on-empty"]);
+        let _ = std::fs::remove_file(&report_file);
+        assert!(empty.is_ok());
+        match with_flag {
+            Err(error) => assert_eq!(error.kind(), ErrorKind::NotFound),
+            Ok(_) => unreachable!("an empty report should fail with --fail-on-empty"),
         }
+        assert!(not_empty.is_ok());
+    }
+
+    #[test]
+    fn anomaly_sample() {
+        use toolslib::date_time::get_date;
+        use weather_lib::test_support::MockDataAPI;
+        let location = Location {
+            name: "Northern City".to_string(),
+            alias: "north".to_string(),
+            longitude: "0".to_string(),
+            latitude: "0".to_string(),
+            tz: "UTC".to_string(),
+        };
+        let histories = [10.0, 20.0, 30.0, 40.0]
+            .into_iter()
+            .zip(1..)
+            .map(|(high, day)| History {
+                alias: "north".to_string(),
+                date: get_date(2024, 1, day),
+                temperature_high: Some(high),
+                ..Default::default()
+            })
+            .collect();
+        let weather_data = WeatherData::from(MockDataAPI::new().with_location(location).with_histories(histories));
+        let report_file = std::env::temp_dir().join(format!("rh-anomaly-{}.txt", std::process::id()));
+        let filename = report_file.display().to_string();
+        let args = [COMMAND_NAME, "north", "2024-01-01", "2024-01-04", "--anomaly", "--sample", "2", "-r", &filename];
+        let result = v4::execute(&weather_data, v4::command().try_get_matches_from(args).unwrap());
+        let report = std::fs::read_to_string(&report_file);
+        let _ = std::fs::remove_file(&report_file);
+        result.unwrap();
+        // the baseline mean (25) includes the days that were not sampled
+        let report = report.unwrap();
+        assert!(report.contains("-15.0"), "{}", report);
+        assert!(report.contains("+5.0"), "{}", report);
+        assert!(!report.contains("2024-01-02"), "{}", report);
     }
 }
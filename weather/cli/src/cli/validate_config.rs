@@ -0,0 +1,110 @@
+//! The validate configuration command implementation.
+use super::*;
+use reports::report_history::{text::DEFAULT_PRECISION, HeaderStyle, JsonShape};
+use std::io::Write;
+use toolslib::{rptcols, rptrow, text::Report};
+use weather_lib::prelude::ConfigCheck;
+
+/// The validate configuration command name.
+pub const COMMAND_NAME: &str = "validate-config";
+
+pub use v1::{command, execute};
+mod v1 {
+    //! # The implementation for validate configuration (`validate-config`).
+    //!
+    //! The command loads the configuration file, applies the command line overrides, and reports
+    //! the settings that will be used along with the report format defaults. Weather data is not
+    //! opened so the configuration can be checked even when the weather data directory is missing.
+    //!
+    use super::*;
+
+    /// Create the validate configuration command.
+    ///
+    pub fn command() -> Command {
+        Command::new(COMMAND_NAME).about("Report the resolved configuration and any values that are not valid.")
+    }
+
+    /// Executes the validate configuration command.
+    ///
+    /// An invalid configuration is reported as an error after the settings have been written.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_check` is the resolved configuration.
+    /// * `_args` contains the validate configuration command arguments.
+    ///
+    pub fn execute(config_check: ConfigCheck, _args: ArgMatches) -> Result<()> {
+        let mut writer = toolslib::text::get_writer(&None, false)?;
+        for line in report(&config_check) {
+            writeln!(writer, "{}", line)?;
+        }
+        match config_check.is_valid() {
+            true => Ok(()),
+            false => Err(Error::new(ErrorKind::Invalid, "The configuration is not valid.")),
+        }
+    }
+
+    /// Create the configuration report.
+    ///
+    /// # Arguments
+    ///
+    /// * `config_check` is the resolved configuration.
+    ///
+    fn report(config_check: &ConfigCheck) -> Vec<String> {
+        let mut report = Report::from(rptcols!(<, <));
+        report.header(rptrow!(^ "Setting", ^ "Value")).separator("-");
+        report.text(rptrow!("Directory", &config_check.directory));
+        report.text(rptrow!("Backend", &config_check.backend));
+        report.text(rptrow!("Read only", config_check.read_only));
+        report.text(rptrow!("History cache", config_check.history_cache));
+        report.text(rptrow!("Timezone", config_check.timezone.as_deref().unwrap_or("")));
+        report.text(rptrow!("Units", &config_check.units));
+        report.text(rptrow!("End-point", &config_check.endpoint));
+        report.text(rptrow!("US Cities", &config_check.us_cities));
+        report.text(rptrow!("Report format", "text"));
+        report.text(rptrow!("Decimal places", DEFAULT_PRECISION));
+        report.text(rptrow!("Header style", HeaderStyle::default().name()));
+        report.text(rptrow!("JSON shape", JsonShape::default().name()));
+        let mut lines = report.into_iter().map(|line| line.trim_end().to_string()).collect::<Vec<_>>();
+        lines.push(String::default());
+        match config_check.is_valid() {
+            true => lines.push("The configuration is valid.".to_string()),
+            false => {
+                lines.push("The configuration is not valid.".to_string());
+                lines.extend(config_check.problems.iter().map(|problem| format!("  {}", problem)));
+            }
+        }
+        lines
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn report() {
+            let config_check = |timezone: &str, problems: Vec<String>| ConfigCheck {
+                directory: "weather_data".to_string(),
+                backend: "db".to_string(),
+                read_only: false,
+                history_cache: false,
+                timezone: Some(timezone.to_string()),
+                units: "us".to_string(),
+                endpoint: "http://end/point".to_string(),
+                us_cities: "uscities.csv".to_string(),
+                problems,
+            };
+            assert!(command().try_get_matches_from(vec![COMMAND_NAME]).is_ok());
+            let testcase = super::report(&config_check("America/Denver", vec![]));
+            assert!(testcase.iter().any(|line| line.starts_with("Timezone") && line.ends_with("America/Denver")));
+            assert!(testcase.iter().any(|line| line.starts_with("Units") && line.ends_with("us")));
+            assert!(testcase.iter().any(|line| line.starts_with("Report format") && line.ends_with("text")));
+            assert!(testcase.iter().any(|line| line.starts_with("Header style") && line.ends_with("camel")));
+            assert_eq!(testcase.last().unwrap(), "The configuration is valid.");
+            let problem = "The timezone 'America/Nowhere' is not valid.".to_string();
+            let testcase = super::report(&config_check("America/Nowhere", vec![problem.clone()]));
+            assert_eq!(testcase[testcase.len() - 2], "The configuration is not valid.");
+            assert_eq!(testcase[testcase.len() - 1], format!("  {}", problem));
+        }
+    }
+}
@@ -4,10 +4,11 @@ mod cli;
 /// The weather cli entry point.
 fn main() -> ExitCode {
     let args = cli::get().get_matches();
-    if let Err(error) = cli::initialize_and_run(args) {
-        eprintln!("Error: {}", error);
-        ExitCode::FAILURE
-    } else {
-        ExitCode::SUCCESS
+    match cli::initialize_and_run(args) {
+        Ok(_) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            ExitCode::from(error.kind().exit_code())
+        }
     }
 }
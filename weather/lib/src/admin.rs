@@ -1,5 +1,5 @@
 //! The weather data administration API.
-use super::{backend, Result};
+use super::{backend, Error, ErrorKind, Result};
 use std::path::PathBuf;
 
 /// Create an instance of the weather data administration `API`.
@@ -19,14 +19,19 @@ pub fn weather_admin(dirname: Option<PathBuf>) -> Result<WeatherAdmin> {
 pub use api::WeatherAdmin;
 mod api {
     //! The administration commands are scoped to this module.
-    use super::{admin_entities::UsCitiesInfo, *};
-    use crate::entities::DataCriteria;
+    use super::{
+        admin_entities::{
+            BatchLocationReport, BatchReport, CompactionReport, IntegrityIssue, NormalizeReport, UsCitiesInfo,
+        },
+        *,
+    };
+    use crate::entities::{DailyHistories, DataCriteria, History};
     use admin_entities::{Components, DbMode};
     use backend::{
         db::admin as db_admin,
         filesys::{admin as fs_admin, weather_dir, MigrateConfig, WeatherDir},
     };
-    use std::path::PathBuf;
+    use std::{collections::HashMap, path::PathBuf};
     use toolslib::{fmt::commafy, stopwatch::StopWatch};
 
     /// The weather data administration `API`.
@@ -44,6 +49,25 @@ mod api {
         pub(super) fn new(dirname: &str) -> Result<Self> {
             Ok(WeatherAdmin(weather_dir(dirname)?))
         }
+        /// Control if the administration commands are allowed to modify weather data.
+        ///
+        /// # Arguments
+        ///
+        /// * `read_only` when `true` will cause commands that modify weather data to fail.
+        pub fn with_read_only(self, read_only: bool) -> Self {
+            WeatherAdmin(self.0.with_read_only(read_only))
+        }
+        /// Get an error if weather data cannot be modified.
+        ///
+        /// # Arguments
+        ///
+        /// * `command` describes what would modify weather data.
+        fn writable(&self, command: &str) -> Result<()> {
+            match self.0.is_read_only() {
+                true => Err(Error::new(ErrorKind::ReadOnly, format!("Weather data is read-only, cannot {}.", command))),
+                false => Ok(()),
+            }
+        }
         /// Initialize the weather database using the supplied database configuration.
         ///
         /// # Arguments
@@ -52,6 +76,7 @@ mod api {
         /// * `drop` when `true` will delete the schema before initialization.
         /// * `load` when `true` will load weather data into the database.
         pub fn init(&self, db_mode: DbMode, drop: bool, load: bool, threads: usize) -> Result<()> {
+            self.writable("initialize the database")?;
             db_admin::init_db(&self.0, db_mode, drop, load, threads)?;
             Ok(())
         }
@@ -61,6 +86,7 @@ mod api {
         ///
         /// * `delete` when `true` will delete the database file.
         pub fn drop(&self, delete: bool) -> Result<()> {
+            self.writable("drop the database")?;
             db_admin::drop_db(&self.0, delete)?;
             Ok(())
         }
@@ -79,6 +105,7 @@ mod api {
         /// * `retain` indicates existing converted archives should not be deleted before adding documents.
         /// * `criteria` identifies what location archives should be converted.
         pub fn migrate(&self, into: PathBuf, create: bool, retain: bool, criteria: DataCriteria) -> Result<usize> {
+            self.writable("migrate archives")?;
             let count = fs_admin::migrate_history(MigrateConfig { source: &self.0, create, retain, criteria }, into)?;
             Ok(count)
         }
@@ -88,15 +115,95 @@ mod api {
         ///
         /// * `criteria` identifies the locations that will be reloaded.
         pub fn reload(&self, criteria: DataCriteria) -> Result<usize> {
+            self.writable("reload the database")?;
             let locations = db_admin::reload(&self.0, criteria)?;
             Ok(locations.len())
         }
+        /// Rewrite location weather history archives using the maximum compression level.
+        ///
+        /// # Arguments
+        ///
+        /// * `criteria` identifies the locations that will be compacted.
+        pub fn compact(&self, criteria: DataCriteria) -> Result<Vec<CompactionReport>> {
+            self.writable("compact archives")?;
+            fs_admin::compact_archives(&self.0, criteria)
+        }
+        /// Rewrite location weather history archives so histories are in ascending date order.
+        ///
+        /// # Arguments
+        ///
+        /// * `criteria` identifies the locations that will be normalized.
+        pub fn normalize(&self, criteria: DataCriteria) -> Result<Vec<NormalizeReport>> {
+            self.writable("normalize archives")?;
+            fs_admin::normalize_archives(&self.0, criteria)
+        }
+        /// Add weather histories to multiple locations.
+        ///
+        /// Locations that fail are included in the report and do not stop the remaining locations
+        /// from being updated. Histories are added the same way weather data adds them so the database
+        /// is updated along with the archives.
+        ///
+        /// # Arguments
+        ///
+        /// * `histories` are the histories that will be added keyed by location alias.
+        pub fn add_histories(&self, histories: HashMap<String, Vec<History>>) -> Result<BatchReport> {
+            self.writable("add histories")?;
+            let data_api = backend::data_api(None, Some(self.0.path().to_path_buf()), false, false)?;
+            let mut histories: Vec<(String, Vec<History>)> = histories.into_iter().collect();
+            histories.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+            let mut locations = Vec::with_capacity(histories.len());
+            for (alias, histories) in histories {
+                let count = histories.len();
+                let result = data_api.get_location_by_id(&alias).and_then(|location| {
+                    data_api.add_daily_histories(DailyHistories { location, histories }, &mut |_, _| ())
+                });
+                let location_report = match result {
+                    Ok(added) => BatchLocationReport { alias, added, skipped: count - added, error: None },
+                    Err(err) => {
+                        log::error!("Batch add to '{}' failed ({}).", alias, err);
+                        BatchLocationReport { alias, added: 0, skipped: 0, error: Some(err.to_string()) }
+                    }
+                };
+                locations.push(location_report);
+            }
+            Ok(BatchReport { locations })
+        }
+        /// Change the name and alias of a location.
+        ///
+        /// History filenames include the location alias so changing the alias moves the weather
+        /// histories into a new archive. The database location is updated if there is one.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the current alias of the location.
+        /// * `name` is the new location name.
+        /// * `new_alias` is the new location alias.
+        pub fn rename_location(&self, alias: &str, name: &str, new_alias: &str) -> Result<()> {
+            self.writable("rename a location")?;
+            let new_alias = new_alias.to_lowercase();
+            let previous_name = fs_admin::rename_location(&self.0, alias, name, &new_alias)?;
+            if let Err(err) = db_admin::rename_location(&self.0, alias, name, &new_alias) {
+                // put the archive and locations document back so they agree with the database
+                if let Err(undo_err) = fs_admin::rename_location(&self.0, &new_alias, &previous_name, alias) {
+                    log::error!("Could not restore the '{}' location ({}).", alias, undo_err);
+                }
+                return Err(err);
+            }
+            Ok(())
+        }
+        /// Verify the weather history archives of all locations can be read.
+        ///
+        /// Problems are collected rather than stopping at the first one found.
+        pub fn scan_integrity(&self) -> Result<Vec<IntegrityIssue>> {
+            fs_admin::scan_integrity(&self.0)
+        }
         /// Load the US Cities database.
         ///
         /// # Arguments
         ///
         /// * `uscities_path` is the US Cities `CSV` file that will populate the database.
         pub fn uscities_load(&self, uscities_path: &PathBuf) -> Result<()> {
+            self.writable("load US Cities")?;
             let stopwatch = StopWatch::start_new();
             let count = db_admin::uscities_load(&self.0, uscities_path)?;
             log::debug!("Loaded {} US Cities in {}", commafy(count), stopwatch);
@@ -104,6 +211,7 @@ mod api {
         }
         /// Delete the US Cities database.
         pub fn uscities_delete(&self) -> Result<()> {
+            self.writable("delete US Cities")?;
             db_admin::uscities_delete(&self.0)?;
             Ok(())
         }
@@ -177,6 +285,75 @@ pub(crate) mod admin_entities {
         pub histories: usize,
     }
 
+    /// The results of compacting a weather history archive.
+    #[derive(Debug)]
+    pub struct CompactionReport {
+        /// The location alias name.
+        pub alias: String,
+        /// The number of weather histories in the archive.
+        pub histories: usize,
+        /// The size of the archive before it was compacted.
+        pub before: u64,
+        /// The size of the archive after it was compacted.
+        pub after: u64,
+    }
+
+    /// The results of normalizing a weather history archive.
+    #[derive(Debug)]
+    pub struct NormalizeReport {
+        /// The location alias name.
+        pub alias: String,
+        /// The number of weather histories in the archive.
+        pub histories: usize,
+        /// Indicates the archive was rewritten because histories were not in date order.
+        pub rewritten: bool,
+    }
+
+    /// The results of adding weather histories to a location as part of a batch.
+    #[derive(Debug)]
+    pub struct BatchLocationReport {
+        /// The location alias name.
+        pub alias: String,
+        /// The number of weather histories added.
+        pub added: usize,
+        /// The number of weather histories that already existed.
+        pub skipped: usize,
+        /// A description of the problem if the location could not be updated.
+        pub error: Option<String>,
+    }
+
+    /// The results of adding weather histories to multiple locations.
+    #[derive(Debug)]
+    pub struct BatchReport {
+        /// The results of each location.
+        pub locations: Vec<BatchLocationReport>,
+    }
+    impl BatchReport {
+        /// The total number of weather histories added.
+        pub fn added(&self) -> usize {
+            self.locations.iter().map(|location| location.added).sum()
+        }
+        /// The total number of weather histories that already existed.
+        pub fn skipped(&self) -> usize {
+            self.locations.iter().map(|location| location.skipped).sum()
+        }
+        /// The number of locations that could not be updated.
+        pub fn failed(&self) -> usize {
+            self.locations.iter().filter(|location| location.error.is_some()).count()
+        }
+    }
+
+    /// A problem found verifying a weather history archive.
+    #[derive(Debug)]
+    pub struct IntegrityIssue {
+        /// The location alias name.
+        pub alias: String,
+        /// The archive entry with the problem or `None` if the archive itself has a problem.
+        pub entry: Option<String>,
+        /// A description of the problem.
+        pub reason: String,
+    }
+
     #[derive(Debug)]
     pub struct UsCitiesInfo {
         pub db_size: usize,
@@ -3,20 +3,41 @@
 pub mod db;
 pub mod filesys;
 mod history;
+#[cfg(any(test, feature = "test-support"))]
+pub mod mock;
 
 pub use config::Config;
 mod config;
 
 use super::*;
 use crate::entities::{
-    DailyHistories, DataCriteria, DateRange, History, HistoryDates, HistorySummaries, Location, LocationCriteria,
+    validate_history, ConfigCheck, DailyHistories, DataCriteria, DateRange, History, HistoryDates, HistoryFailures,
+    HistorySummaries, HistorySummary, Location, LocationCriteria,
 };
+use chrono::NaiveDate;
 use std::path::{Path, PathBuf};
 
 impl From<rusqlite::Error> for Error {
     /// Add support to convert rusqlite database errors.
     fn from(err: rusqlite::Error) -> Self {
-        Error::from(format!("SQL: {}", err))
+        use rusqlite::{ffi, ErrorCode};
+        let kind = match &err {
+            rusqlite::Error::QueryReturnedNoRows => ErrorKind::NotFound,
+            rusqlite::Error::FromSqlConversionFailure(..) | rusqlite::Error::InvalidColumnType(..) => ErrorKind::Decode,
+            rusqlite::Error::SqliteFailure(failure, _) => match failure.code {
+                ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase => ErrorKind::Corrupt,
+                ErrorCode::ReadOnly => ErrorKind::ReadOnly,
+                ErrorCode::CannotOpen | ErrorCode::SystemIoFailure => ErrorKind::Io,
+                ErrorCode::DiskFull | ErrorCode::PermissionDenied => ErrorKind::Io,
+                ErrorCode::ConstraintViolation => match failure.extended_code {
+                    ffi::SQLITE_CONSTRAINT_UNIQUE | ffi::SQLITE_CONSTRAINT_PRIMARYKEY => ErrorKind::AlreadyExists,
+                    _ => ErrorKind::Other,
+                },
+                _ => ErrorKind::Other,
+            },
+            _ => ErrorKind::Other,
+        };
+        Error::new(kind, format!("SQL: {}", err))
     }
 }
 
@@ -27,11 +48,14 @@ impl From<rusqlite::Error> for Error {
 /// * `config_file` is the weather data configuration filename.
 /// * `dirname` is the weather data directory name override.
 /// * `no_db` is used to force using the archive implementation of weather data.
-pub fn data_api(config_file: Option<PathBuf>, dirname: Option<PathBuf>, no_db: bool) -> Result<DataAPI> {
-    let mut config = Config::new(config_file)?;
-    if let Some(path) = dirname {
-        config.weather_data.directory = path.display().to_string();
-    }
+/// * `read_only` when `true` prevents the weather data from being modified.
+pub fn data_api(
+    config_file: Option<PathBuf>,
+    dirname: Option<PathBuf>,
+    no_db: bool,
+    read_only: bool,
+) -> Result<DataAPI> {
+    let config = resolve_config(config_file, dirname, read_only)?;
     let weather_dir = filesys::WeatherDir::try_from(&config)?;
     let data_adapter = if no_db || db::db_file(&weather_dir).is_none() {
         filesys::data_adapter(config)
@@ -41,7 +65,69 @@ pub fn data_api(config_file: Option<PathBuf>, dirname: Option<PathBuf>, no_db: b
     Ok(DataAPI(data_adapter))
 }
 
+/// Get the weather data configuration that would be used by the backend without opening weather data.
+///
+/// # Arguments
+///
+/// * `config_file` is the weather data configuration filename.
+/// * `dirname` is the weather data directory name override.
+/// * `no_db` is used to force using the archive implementation of weather data.
+/// * `read_only` when `true` prevents the weather data from being modified.
+pub fn check_config(
+    config_file: Option<PathBuf>,
+    dirname: Option<PathBuf>,
+    no_db: bool,
+    read_only: bool,
+) -> Result<ConfigCheck> {
+    let config = resolve_config(config_file, dirname, read_only)?;
+    let problems = config.problems();
+    // weather data that cannot be opened does not have a database
+    let use_db = match filesys::WeatherDir::try_from(&config) {
+        Ok(weather_dir) => !no_db && db::db_file(&weather_dir).is_some(),
+        Err(_) => false,
+    };
+    Ok(ConfigCheck {
+        directory: config.weather_data.directory,
+        backend: if use_db { "db" } else { "archive" }.to_string(),
+        read_only: config.weather_data.read_only,
+        history_cache: config.weather_data.history_cache,
+        timezone: config.weather_data.timezone,
+        units: crate::history_client::UNITS.to_string(),
+        endpoint: config.visual_crossing.endpoint,
+        us_cities: config.us_cities.filename,
+        problems,
+    })
+}
+
+/// Load the weather data configuration and apply the command line overrides.
+///
+/// # Arguments
+///
+/// * `config_file` is the weather data configuration filename.
+/// * `dirname` is the weather data directory name override.
+/// * `read_only` when `true` prevents the weather data from being modified.
+fn resolve_config(config_file: Option<PathBuf>, dirname: Option<PathBuf>, read_only: bool) -> Result<Config> {
+    let mut config = Config::new(config_file)?;
+    if let Some(path) = dirname {
+        config.weather_data.directory = path.display().to_string();
+    }
+    if read_only {
+        config.weather_data.read_only = true;
+    }
+    Ok(config)
+}
+
+/// The location id used for the summary of all locations.
+pub const TOTAL_SUMMARY_ID: &str = "*";
+
 pub struct DataAPI(Box<dyn DataAdapter>);
+#[cfg(any(test, feature = "test-support"))]
+impl From<mock::MockDataAPI> for DataAPI {
+    /// Create the backend API using the in-memory weather data.
+    fn from(mock_data_api: mock::MockDataAPI) -> Self {
+        DataAPI(Box::new(mock_data_api))
+    }
+}
 /// The backend API for weather data.
 impl DataAPI {
     /// Get the weather data configuration.
@@ -54,9 +140,14 @@ impl DataAPI {
     /// # Arguments
     ///
     /// - `daily_histories` contains the historical weather data that will be added.
-    ///
-    pub fn add_daily_histories(&self, daily_histories: DailyHistories) -> Result<usize> {
-        self.0.add_daily_histories(&daily_histories)
+    /// - `progress` is called with the count of histories added and the count being added.
+    ///
+    pub fn add_daily_histories(
+        &self,
+        daily_histories: DailyHistories,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<usize> {
+        self.0.add_daily_histories(&daily_histories, progress)
     }
     /// Get daily weather history for a location.
     ///
@@ -71,6 +162,99 @@ impl DataAPI {
         let location = self.get_location(&criteria)?;
         self.0.daily_histories(location, history_range)
     }
+    /// Get daily weather history for a location along with the histories that could not be decoded.
+    ///
+    /// It is an error if more than 1 location is found.
+    ///
+    /// # Arguments
+    ///
+    /// - `criteria` identifies the location.
+    /// - `history_range` covers the history dates returned.
+    ///
+    pub fn get_daily_history_with_failures(
+        &self,
+        criteria: DataCriteria,
+        history_range: DateRange,
+    ) -> Result<(DailyHistories, HistoryFailures)> {
+        let location = self.get_location(&criteria)?;
+        self.0.daily_histories_with_failures(location, history_range)
+    }
+    /// Get daily weather history for a location and check the histories for anomalous values.
+    ///
+    /// It is an error if more than 1 location is found. Values that are out of bounds are returned as
+    /// warnings along with the histories and the histories that could not be decoded.
+    ///
+    /// # Arguments
+    ///
+    /// - `criteria` identifies the location.
+    /// - `history_range` covers the history dates returned.
+    ///
+    pub fn get_validated_daily_history(
+        &self,
+        criteria: DataCriteria,
+        history_range: DateRange,
+    ) -> Result<(DailyHistories, HistoryFailures, Vec<String>)> {
+        let (daily_histories, failures) = self.get_daily_history_with_failures(criteria, history_range)?;
+        let warnings = daily_histories.histories.iter().flat_map(validate_history).collect();
+        Ok((daily_histories, failures, warnings))
+    }
+    /// Check if a location has weather history for a date without getting the history.
+    ///
+    /// # Arguments
+    ///
+    /// - `location_id` is the location identifier (alias).
+    /// - `date` is the weather history date.
+    ///
+    pub fn contains_history(&self, location_id: &str, date: &NaiveDate) -> Result<bool> {
+        self.0.contains_history(location_id, date)
+    }
+    /// Get the number of weather histories a location has without getting the history dates.
+    ///
+    /// # Arguments
+    ///
+    /// - `location_id` is the location identifier (alias).
+    ///
+    pub fn count_histories(&self, location_id: &str) -> Result<usize> {
+        self.0.count_histories(location_id)
+    }
+    /// Get the names of the history fields that have a value in at least one of a locations histories.
+    ///
+    /// It is an error if more than 1 location is found.
+    ///
+    /// # Arguments
+    ///
+    /// - `criteria` identifies the location.
+    ///
+    pub fn available_fields(&self, criteria: DataCriteria) -> Result<Vec<String>> {
+        let location = self.get_location(&criteria)?;
+        let alias_criteria =
+            DataCriteria { filters: vec![location.alias.clone()], icase: false, sort: false, alias_only: true };
+        let date_ranges: Vec<DateRange> = self
+            .0
+            .history_dates(alias_criteria)?
+            .into_iter()
+            .flat_map(|history_dates| history_dates.history_dates)
+            .collect();
+        let from = date_ranges.iter().map(|date_range| date_range.from).min();
+        let to = date_ranges.iter().map(|date_range| date_range.to).max();
+        let mut populated = [false; History::OPTIONAL_FIELDS.len()];
+        if let (Some(from), Some(to)) = (from, to) {
+            for history in self.0.daily_histories(location, DateRange::new(from, to))?.histories {
+                for (available, is_some) in populated.iter_mut().zip(history.populated()) {
+                    *available |= is_some;
+                }
+            }
+        }
+        let fields = History::OPTIONAL_FIELDS
+            .iter()
+            .zip(populated)
+            .filter_map(|(field, available)| match available {
+                true => Some(field.to_string()),
+                false => None,
+            })
+            .collect();
+        Ok(fields)
+    }
     /// Get the history dates for locations.
     ///
     /// # Arguments
@@ -89,6 +273,38 @@ impl DataAPI {
     pub fn get_history_summary(&self, criteria: DataCriteria) -> Result<Vec<HistorySummaries>> {
         self.0.history_summaries(criteria)
     }
+    /// Get a single summary of the weather data for all the locations.
+    ///
+    /// The counts and sizes of each location summary are added together. A size will be `None`
+    /// only if it is not available for any of the locations.
+    ///
+    /// # Arguments
+    ///
+    /// - `criteria` identifies the locations.
+    ///
+    pub fn get_total_summary(&self, criteria: DataCriteria) -> Result<HistorySummary> {
+        let history_summaries = self.get_history_summary(criteria)?;
+        let total_summary = history_summaries.into_iter().fold(
+            HistorySummary {
+                location_id: TOTAL_SUMMARY_ID.to_string(),
+                count: 0,
+                overall_size: None,
+                raw_size: None,
+                compressed_size: None,
+            },
+            |total, summary| {
+                total
+                    + HistorySummary {
+                        location_id: summary.location.alias,
+                        count: summary.count,
+                        overall_size: summary.overall_size,
+                        raw_size: summary.raw_size,
+                        compressed_size: summary.store_size,
+                    }
+            },
+        );
+        Ok(total_summary)
+    }
     /// Get the weather location metadata.
     ///
     /// # Arguments
@@ -98,6 +314,34 @@ impl DataAPI {
     pub fn get_locations(&self, criteria: DataCriteria) -> Result<Vec<Location>> {
         self.0.locations(criteria)
     }
+    /// Get the weather locations whose name or alias match a regular expression.
+    ///
+    /// # Arguments
+    ///
+    /// - `pattern` is the regular expression locations must match.
+    /// - `icase` when `true` the regular expression will ignore case.
+    /// - `sort` when `true` the locations will be sorted by name.
+    ///
+    pub fn get_locations_regex(&self, pattern: &str, icase: bool, sort: bool) -> Result<Vec<Location>> {
+        let locations = self.0.locations(DataCriteria { filters: vec![], icase, sort, alias_only: false })?;
+        filter_locations_regex(locations, pattern, icase)
+    }
+    /// Get a weather location by its identifier (alias).
+    ///
+    /// Unlike the location criteria the identifier must match exactly.
+    ///
+    /// # Arguments
+    ///
+    /// - `id` is the location identifier.
+    ///
+    pub fn get_location_by_id(&self, id: &str) -> Result<Location> {
+        let locations =
+            self.0.locations(DataCriteria { filters: vec![], icase: false, sort: false, alias_only: false })?;
+        match locations.into_iter().find(|location| location.alias == id) {
+            Some(location) => Ok(location),
+            None => Err(Error::from(format!("Location id '{}' was not found.", id))),
+        }
+    }
     pub fn add_location(&self, location: Location) -> Result<()> {
         self.0.add_location(location)
     }
@@ -121,6 +365,7 @@ impl DataAPI {
             filters: criteria.filters.clone(),
             icase: criteria.icase,
             sort: criteria.sort,
+            alias_only: criteria.alias_only,
         })?;
         match locations.len() {
             1 => Ok(locations.pop().unwrap()),
@@ -130,6 +375,24 @@ impl DataAPI {
     }
 }
 
+/// Keep the locations whose name or alias match a regular expression.
+///
+/// # Arguments
+///
+/// - `locations` are the locations that will be filtered.
+/// - `pattern` is the regular expression locations must match.
+/// - `icase` when `true` the regular expression will ignore case.
+///
+fn filter_locations_regex(locations: Vec<Location>, pattern: &str, icase: bool) -> Result<Vec<Location>> {
+    match regex::RegexBuilder::new(pattern).case_insensitive(icase).build() {
+        Ok(regex) => Ok(locations
+            .into_iter()
+            .filter(|location| regex.is_match(&location.name) || regex.is_match(&location.alias))
+            .collect()),
+        Err(err) => Err(Error::from(format!("Location regex '{}' is not valid ({}).", pattern, err))),
+    }
+}
+
 /// The `API` common to all the backend implementations.
 trait DataAdapter {
     /// Get the data adapter configuration.
@@ -140,8 +403,9 @@ trait DataAdapter {
     /// # Arguments
     ///
     /// - `histories` has the location and histories to add.
+    /// - `progress` is called with the count of histories added and the count being added.
     ///
-    fn add_daily_histories(&self, histories: &DailyHistories) -> Result<usize>;
+    fn add_daily_histories(&self, histories: &DailyHistories, progress: &mut dyn FnMut(usize, usize)) -> Result<usize>;
     /// Reports if all histories were added or not.
     ///
     /// # Arguments
@@ -173,6 +437,36 @@ trait DataAdapter {
     /// - `history_range` specifies the date range that should be used.
     ///
     fn daily_histories(&self, location: Location, date_range: DateRange) -> Result<DailyHistories>;
+    /// Returns the daily weather data history for a location along with the histories that could not
+    /// be decoded. Histories that are not kept in archives do not have decode failures.
+    ///
+    /// # Arguments
+    ///
+    /// - `location` identifies what location should be used.
+    /// - `date_range` specifies the date range that should be used.
+    ///
+    fn daily_histories_with_failures(
+        &self,
+        location: Location,
+        date_range: DateRange,
+    ) -> Result<(DailyHistories, HistoryFailures)> {
+        Ok((self.daily_histories(location, date_range)?, vec![]))
+    }
+    /// Check if a location has weather history for a date.
+    ///
+    /// # Arguments
+    ///
+    /// - `alias` is the location identifier.
+    /// - `date` is the weather history date.
+    ///
+    fn contains_history(&self, alias: &str, date: &NaiveDate) -> Result<bool>;
+    /// Get the number of weather histories for a location.
+    ///
+    /// # Arguments
+    ///
+    /// - `alias` is the location identifier.
+    ///
+    fn count_histories(&self, alias: &str) -> Result<usize>;
     /// Get the weather history dates for locations.
     ///
     /// # Arguments
@@ -302,3 +596,199 @@ mod testlib {
         std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("resources").join("tests")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sql_error_kinds() {
+        let conn = rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE testcase (id INTEGER PRIMARY KEY, name TEXT UNIQUE)", []).unwrap();
+        conn.execute("INSERT INTO testcase (name) VALUES ('one')", []).unwrap();
+        let sql_error = |sql: &str| Error::from(conn.query_row(sql, [], |row| row.get::<_, i64>(0)).unwrap_err());
+        assert_eq!(sql_error("SELECT id FROM testcase WHERE name = 'two'").kind(), ErrorKind::NotFound);
+        assert_eq!(sql_error("SELECT name FROM testcase").kind(), ErrorKind::Decode);
+        let testcase = sql_error("INSERT INTO testcase (name) VALUES ('one') RETURNING id");
+        assert_eq!(testcase.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(sql_error("SELECT * FROM missing").kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn location_by_id() {
+        let fixture = testlib::TestFixture::create();
+        fixture.copy_resources(&testlib::test_resources().join("filesys"));
+        let testcase = data_api(None, Some(PathBuf::from(&fixture)), true, false).unwrap();
+        // the substring filter matches multiple locations
+        let criteria = DataCriteria { filters: vec!["*th*".to_string()], icase: true, sort: true, alias_only: false };
+        assert_eq!(testcase.get_locations(criteria).unwrap().len(), 2);
+        // the id only matches one
+        let location = testcase.get_location_by_id("north").unwrap();
+        assert_eq!(location.name, "Northern City");
+        let location = testcase.get_location_by_id("south").unwrap();
+        assert_eq!(location.name, "Southern City");
+        // ids are not patterns or substrings
+        assert!(testcase.get_location_by_id("nor").is_err());
+        assert!(testcase.get_location_by_id("*th*").is_err());
+        assert!(testcase.get_location_by_id("North").is_err());
+    }
+
+    #[test]
+    fn locations_regex() {
+        let location = |name: &str, alias: &str| Location {
+            name: name.to_string(),
+            alias: alias.to_string(),
+            longitude: "0".to_string(),
+            latitude: "0".to_string(),
+            tz: "UTC".to_string(),
+        };
+        let locations =
+            || vec![location("Las Vegas", "vegas"), location("Atlas", "atlas"), location("Dallas", "big_d")];
+        let names = |locations: Vec<Location>| locations.into_iter().map(|location| location.name).collect::<Vec<_>>();
+        let testcase = filter_locations_regex(locations(), "^Las", false).unwrap();
+        assert_eq!(names(testcase), vec!["Las Vegas"]);
+        let testcase = filter_locations_regex(locations(), "las$", false).unwrap();
+        assert_eq!(names(testcase), vec!["Atlas", "Dallas"]);
+        // the alias is matched too
+        let testcase = filter_locations_regex(locations(), "^big_", false).unwrap();
+        assert_eq!(names(testcase), vec!["Dallas"]);
+        // case is ignored when asked
+        assert!(filter_locations_regex(locations(), "^las", false).unwrap().is_empty());
+        assert_eq!(filter_locations_regex(locations(), "^las", true).unwrap().len(), 1);
+        assert!(filter_locations_regex(locations(), "^(Las", true).is_err());
+        // the data api uses the weather data locations
+        let fixture = testlib::TestFixture::create();
+        fixture.copy_resources(&testlib::test_resources().join("filesys"));
+        let testcase = data_api(None, Some(PathBuf::from(&fixture)), true, false).unwrap();
+        assert_eq!(names(testcase.get_locations_regex("^north", true, true).unwrap()), vec!["Northern City"]);
+    }
+
+    #[test]
+    fn check_config() {
+        let fixture = testlib::TestFixture::create();
+        fixture.copy_resources(&testlib::test_resources().join("filesys"));
+        let config_file = PathBuf::from(&fixture).join("weather.toml");
+        let write_config = |timezone: &str| {
+            let config = format!("[weather-data]\ndirectory = '{}'\ntimezone = '{timezone}'", fixture);
+            std::fs::write(&config_file, config).unwrap();
+            super::check_config(Some(config_file.clone()), None, false, false).unwrap()
+        };
+        let testcase = write_config("America/Los_Angeles");
+        assert!(testcase.is_valid());
+        assert_eq!(testcase.directory, fixture.to_string());
+        assert_eq!(testcase.backend, "archive");
+        assert!(!testcase.read_only);
+        assert_eq!(testcase.timezone.as_deref(), Some("America/Los_Angeles"));
+        assert_eq!(testcase.units, "us");
+        let testcase = write_config("Mars/Olympus_Mons");
+        assert!(!testcase.is_valid());
+        assert_eq!(testcase.problems, vec!["The timezone 'Mars/Olympus_Mons' is not valid.".to_string()]);
+        // command line overrides are applied
+        let testcase = super::check_config(Some(config_file.clone()), Some(PathBuf::from("no/such/dir")), true, true);
+        let testcase = testcase.unwrap();
+        assert_eq!(testcase.directory, "no/such/dir");
+        assert_eq!(testcase.backend, "archive");
+        assert!(testcase.read_only);
+        assert_eq!(testcase.problems.len(), 2);
+        // a directory that cannot be opened does not have a database
+        let testcase = super::check_config(Some(config_file), Some(PathBuf::from("no/such/dir")), false, false);
+        assert_eq!(testcase.unwrap().backend, "archive");
+    }
+
+    #[test]
+    fn read_only() {
+        let fixture = testlib::TestFixture::create();
+        fixture.copy_resources(&testlib::test_resources().join("filesys"));
+        let testcase = data_api(None, Some(PathBuf::from(&fixture)), true, true).unwrap();
+        assert!(testcase.get_config().weather_data.read_only);
+        let location = testcase.get_location_by_id("north").unwrap();
+        let archive = PathBuf::from(&fixture).join("north.zip");
+        let size = archive.metadata().unwrap().len();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let history = History { alias: location.alias.clone(), date, ..Default::default() };
+        let daily_histories = DailyHistories { location, histories: vec![history] };
+        match testcase.add_daily_histories(daily_histories, &mut |_, _| ()) {
+            Err(error) => assert_eq!(error.kind(), ErrorKind::ReadOnly),
+            Ok(_) => unreachable!("the weather data is read-only"),
+        }
+        assert_eq!(archive.metadata().unwrap().len(), size);
+    }
+
+    #[test]
+    fn contains_history() {
+        let fixture = testlib::TestFixture::create();
+        fixture.copy_resources(&testlib::test_resources().join("filesys"));
+        let testcase = data_api(None, Some(PathBuf::from(&fixture)), true, false).unwrap();
+        assert!(testcase.contains_history("north", &NaiveDate::from_ymd_opt(2015, 4, 1).unwrap()).unwrap());
+        assert!(!testcase.contains_history("north", &NaiveDate::from_ymd_opt(1999, 4, 1).unwrap()).unwrap());
+        assert!(testcase.contains_history("unknown", &NaiveDate::from_ymd_opt(2015, 4, 1).unwrap()).is_err());
+    }
+
+    #[test]
+    fn one_day_history() {
+        let fixture = testlib::TestFixture::create();
+        fixture.copy_resources(&testlib::test_resources().join("filesys"));
+        let testcase = data_api(None, Some(PathBuf::from(&fixture)), true, false).unwrap();
+        let criteria =
+            || DataCriteria { filters: vec!["north".to_string()], icase: false, sort: false, alias_only: false };
+        let date = NaiveDate::from_ymd_opt(2015, 4, 1).unwrap();
+        let daily_histories = testcase.get_daily_history(criteria(), DateRange::new(date, date)).unwrap();
+        assert_eq!(daily_histories.histories.len(), 1);
+        assert_eq!(daily_histories.histories[0].date, date);
+        let date = NaiveDate::from_ymd_opt(1999, 4, 1).unwrap();
+        let daily_histories = testcase.get_daily_history(criteria(), DateRange::new(date, date)).unwrap();
+        assert!(daily_histories.histories.is_empty());
+    }
+
+    #[test]
+    fn count_histories() {
+        let fixture = testlib::TestFixture::create();
+        fixture.copy_resources(&testlib::test_resources().join("filesys"));
+        let testcase = data_api(None, Some(PathBuf::from(&fixture)), true, false).unwrap();
+        assert_eq!(testcase.count_histories("testmd").unwrap(), 28);
+        assert!(testcase.count_histories("unknown").is_err());
+    }
+
+    #[test]
+    fn total_summary() {
+        let fixture = testlib::TestFixture::create();
+        fixture.copy_resources(&testlib::test_resources().join("filesys"));
+        let testcase = data_api(None, Some(PathBuf::from(&fixture)), true, false).unwrap();
+        let criteria = || DataCriteria { filters: vec![], icase: false, sort: false, alias_only: false };
+        let history_summaries = testcase.get_history_summary(criteria()).unwrap();
+        assert!(history_summaries.len() > 1);
+        let total_summary = testcase.get_total_summary(criteria()).unwrap();
+        assert_eq!(total_summary.location_id, TOTAL_SUMMARY_ID);
+        assert_eq!(total_summary.count, history_summaries.iter().map(|summary| summary.count).sum::<usize>());
+        let size = |f: fn(&HistorySummaries) -> Option<usize>| history_summaries.iter().filter_map(f).sum::<usize>();
+        assert_eq!(total_summary.overall_size, Some(size(|summary| summary.overall_size)));
+        assert_eq!(total_summary.raw_size, Some(size(|summary| summary.raw_size)));
+        assert_eq!(total_summary.compressed_size, Some(size(|summary| summary.store_size)));
+    }
+
+    #[test]
+    fn daily_history_with_failures() {
+        use std::io::Write;
+        use zip::{write::FileOptions, ZipWriter};
+        let fixture = testlib::TestFixture::create();
+        fixture.copy_resources(&testlib::test_resources().join("filesys"));
+        let date_range = || DateRange::new(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap(), NaiveDate::MAX);
+        let criteria =
+            || DataCriteria { filters: vec!["north".to_string()], icase: true, sort: false, alias_only: false };
+        let data_api = || data_api(None, Some(PathBuf::from(&fixture)), true, false).unwrap();
+        let count = data_api().get_daily_history(criteria(), date_range()).unwrap().histories.len();
+        let corrupt_date = NaiveDate::from_ymd_opt(2099, 12, 31).unwrap();
+        {
+            let weather_dir = filesys::WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let mut writer = ZipWriter::new_append(weather_dir.archive("north").writer().unwrap()).unwrap();
+            writer.start_file("north/north-20991231.json", FileOptions::default()).unwrap();
+            writer.write_all(b"not a history").unwrap();
+            writer.finish().unwrap();
+        }
+        let (daily_histories, failures) = data_api().get_daily_history_with_failures(criteria(), date_range()).unwrap();
+        assert_eq!(daily_histories.location.alias, "north");
+        assert_eq!(daily_histories.histories.len(), count);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, corrupt_date);
+    }
+}
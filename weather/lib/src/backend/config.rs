@@ -25,6 +25,30 @@ impl Config {
     pub fn new(optional_path: Option<PathBuf>) -> Result<Config> {
         config_file::load(optional_path)
     }
+    /// Get the descriptions of configuration values that are not valid.
+    ///
+    /// An empty collection indicates the configuration can be used.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = vec![];
+        let directory = Path::new(&self.weather_data.directory);
+        if !directory.exists() {
+            problems.push(format!("The weather data directory '{}' does not exist.", self.weather_data.directory));
+        } else if !directory.is_dir() {
+            problems.push(format!("The weather data directory '{}' is not a directory.", self.weather_data.directory));
+        }
+        if let Some(timezone) = &self.weather_data.timezone {
+            if !chrono_tz::TZ_VARIANTS.iter().any(|tz| tz.name() == timezone) {
+                problems.push(format!("The timezone '{}' is not valid.", timezone));
+            }
+        }
+        problems
+    }
+}
+impl Default for Config {
+    /// Create the configuration using the default properties.
+    fn default() -> Self {
+        Config::from(config_file::ConfigDocument::default())
+    }
 }
 impl TryFrom<&str> for Config {
     type Error = Error;
@@ -131,6 +155,9 @@ mod config_file {
             let config = r#"
                 [weather-data]
                 directory = "directory/name"
+                read-only = true
+                history-cache = true
+                timezone = "America/Denver"
 
                 [visual-crossing]
                 endpoint = "http://end/point"
@@ -141,6 +168,9 @@ mod config_file {
                 "#;
             let testcase = load_str(config).unwrap();
             assert_eq!(as_ref!(testcase.weather_data).directory, some!("directory/name"));
+            assert_eq!(as_ref!(testcase.weather_data).read_only, Some(true));
+            assert_eq!(as_ref!(testcase.weather_data).history_cache, Some(true));
+            assert_eq!(as_ref!(testcase.weather_data).timezone, some!("America/Denver"));
             assert_eq!(as_ref!(testcase.visual_crossing).endpoint, some!("http://end/point"));
             assert_eq!(as_ref!(testcase.visual_crossing).api_key, some!("api-key"));
             assert_eq!(as_ref!(testcase.us_cities).filename, some!("filename.csv"));
@@ -150,6 +180,9 @@ mod config_file {
                 "#;
             let testcase = load_str(config).unwrap();
             assert!(testcase.weather_data.is_some());
+            assert_eq!(as_ref!(testcase.weather_data).history_cache, None);
+            assert!(!Config::from(testcase).weather_data.history_cache);
+            let testcase = load_str(config).unwrap();
             assert!(testcase.visual_crossing.is_none());
             assert!(testcase.us_cities.is_none());
             let config = r#"
@@ -171,6 +204,23 @@ mod config_file {
             assert!(testcase.us_cities.is_some());
         }
 
+        #[test]
+        fn problems() {
+            let directory = env!("CARGO_MANIFEST_DIR");
+            let config = format!("[weather-data]\ndirectory = '{directory}'\ntimezone = 'America/Denver'");
+            assert!(Config::try_from(config.as_str()).unwrap().problems().is_empty());
+            let config = format!("[weather-data]\ndirectory = '{directory}'");
+            assert!(Config::try_from(config.as_str()).unwrap().problems().is_empty());
+            let config = format!("[weather-data]\ndirectory = '{directory}'\ntimezone = 'America/Nowhere'");
+            let testcase = Config::try_from(config.as_str()).unwrap().problems();
+            assert_eq!(testcase, vec!["The timezone 'America/Nowhere' is not valid.".to_string()]);
+            let config = "[weather-data]\ndirectory = 'no/such/directory'\ntimezone = 'utc'";
+            let testcase = Config::try_from(config).unwrap().problems();
+            assert_eq!(testcase.len(), 2);
+            assert!(testcase[0].contains("does not exist"));
+            assert!(testcase[1].contains("'utc'"));
+        }
+
         #[test]
         fn env() {
             // isolate env setting here to avoid threaded test failures
@@ -215,6 +265,12 @@ mod weather_data {
     #[derive(Debug)]
     pub struct Properties {
         pub directory: String,
+        /// When `true` the weather data will not be modified.
+        pub read_only: bool,
+        /// When `true` decoded weather histories are cached in the weather data directory.
+        pub history_cache: bool,
+        /// The timezone suggested for new locations.
+        pub timezone: Option<String>,
     }
     impl From<Option<Document>> for Properties {
         /// Convert the document into the configuration table.
@@ -222,9 +278,13 @@ mod weather_data {
             match value {
                 Some(dict) => {
                     let directory = dict.directory.unwrap_or_else(default_dirname);
-                    Properties { directory }
+                    let read_only = dict.read_only.unwrap_or(false);
+                    let history_cache = dict.history_cache.unwrap_or(false);
+                    Properties { directory, read_only, history_cache, timezone: dict.timezone }
+                }
+                None => {
+                    Properties { directory: default_dirname(), read_only: false, history_cache: false, timezone: None }
                 }
-                None => Properties { directory: default_dirname() },
             }
         }
     }
@@ -233,6 +293,14 @@ mod weather_data {
     #[derive(Debug, Default, Serialize, Deserialize)]
     pub struct Document {
         pub directory: Option<String>,
+        /// Open the weather data without write access.
+        #[serde(rename = "read-only")]
+        pub read_only: Option<bool>,
+        /// Cache decoded weather histories in the weather data directory.
+        #[serde(rename = "history-cache")]
+        pub history_cache: Option<bool>,
+        /// The timezone suggested for new locations.
+        pub timezone: Option<String>,
     }
 
     /// Gets the default API key from the process environment if [ENV_DIRNAME] is defined.
@@ -15,21 +15,28 @@ use crate::admin::admin_entities::DbMode;
 use crate::db_conn;
 use crate::entities;
 use chrono::NaiveDate;
-use filesys::{archive_history_collector, ArchiveMd, WeatherArchive, WeatherDir, WeatherFile, WeatherHistoryUpdate};
-use rusqlite::{named_params, Connection, Transaction};
+use filesys::{
+    archive_history_collector, ArchiveMd, WeatherArchive, WeatherDir, WeatherFile, WeatherHistory, WeatherHistoryUpdate,
+};
+use rusqlite::{named_params, Connection, OpenFlags, Transaction};
 use std::cmp;
 use toolslib::stopwatch::StopWatch;
 
 /// The name of the database
-const DB_FILENAME: &str = "weather_data.db";
+pub(crate) const DB_FILENAME: &str = "weather_data.db";
 
 /// Create a database connection.
 ///
 /// # Arguments
 ///
-/// * `optional_file` is the database file, if `None` an in-memory database will be used.
+/// * `optional_file` is the database file, if `None` an in-memory database will be used. A read-only file
+/// will open the database without write access.
 pub fn db_connection(optional_file: Option<WeatherFile>) -> Result<Connection> {
     match optional_file {
+        Some(file) if file.is_read_only() => {
+            let flags = OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX | OpenFlags::SQLITE_OPEN_URI;
+            Ok(Connection::open_with_flags(file.to_string(), flags)?)
+        }
         Some(file) => Ok(Connection::open(file.to_string())?),
         None => Ok(Connection::open_in_memory()?),
     }
@@ -110,3 +117,31 @@ pub(crate) fn db_file(weather_dir: &WeatherDir) -> Option<WeatherFile> {
         }
     }
 }
+
+/// Check if a location has weather history for a date.
+///
+/// The weather history archive is always updated, regardless of the database mode, so it is used
+/// to check for the date instead of querying the database.
+///
+/// # Arguments
+///
+/// * `weather_dir` is the weather data directory.
+/// * `alias` is the location identifier.
+/// * `date` is the weather history date.
+fn archive_contains(weather_dir: &WeatherDir, alias: &str, date: &NaiveDate) -> Result<bool> {
+    let archive = WeatherHistory::new(alias, weather_dir.archive(alias))?;
+    archive.contains(date)
+}
+
+/// Get the number of weather histories for a location.
+///
+/// Like [archive_contains], the weather history archive is used rather than the database.
+///
+/// # Arguments
+///
+/// * `weather_dir` is the weather data directory.
+/// * `alias` is the location identifier.
+fn archive_count(weather_dir: &WeatherDir, alias: &str) -> Result<usize> {
+    let archive = WeatherHistory::new(alias, weather_dir.archive(alias))?;
+    archive.count()
+}
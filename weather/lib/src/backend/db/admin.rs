@@ -3,7 +3,8 @@
 use super::*;
 
 pub use v3::{
-    database_configuration, db_details, drop_db, init_db, reload, uscities_delete, uscities_info, uscities_load,
+    database_configuration, db_details, drop_db, init_db, reload, rename_location, uscities_delete, uscities_info,
+    uscities_load,
 };
 mod v3 {
     //! The implementation of weather data administration of a database.
@@ -112,7 +113,8 @@ mod v3 {
         if let Some(db_file) = db_file(weather_dir) {
             let mut conn = db_connection(Some(db_file))?;
             let db_mode = database_configuration(&conn)?;
-            for location in locations::get(&conn, &criteria.filters, true)? {
+            let criteria = DataCriteria { sort: true, ..criteria };
+            for location in locations::get(&conn, &criteria)? {
                 match db_mode {
                     DbMode::Hybrid => {
                         hybrid_db::reload(&mut conn, weather_dir, &location.alias)?;
@@ -152,6 +154,24 @@ mod v3 {
         }
     }
 
+    /// Change the name and alias of a location in the database.
+    ///
+    /// Nothing is done if the weather data is not configured to use a database.
+    ///
+    /// # Arguments
+    ///
+    /// * `weather_dir` is the weather data directory.
+    /// * `alias` is the current alias of the location.
+    /// * `name` is the new location name.
+    /// * `new_alias` is the new location alias.
+    pub fn rename_location(weather_dir: &WeatherDir, alias: &str, name: &str, new_alias: &str) -> Result<()> {
+        if let Some(db_file) = db_file(weather_dir) {
+            let conn = db_connection(Some(db_file))?;
+            locations::rename(&conn, alias, name, new_alias)?;
+        }
+        Ok(())
+    }
+
     /// Delete the US Cities database.
     ///
     /// # Arguments
@@ -12,15 +12,17 @@ mod v3 {
     ///
     /// * `weather_dir` is the weather archive directory.
     /// * `daily_histories` is the collection of histories that will be added.
+    /// * `progress` is called as each history is added to the archive.
     pub fn add_histories<'dh>(
         weather_dir: &WeatherDir,
         daily_histories: &'dh DailyHistories,
+        progress: &mut dyn FnMut(usize, usize),
     ) -> Result<Vec<&'dh History>> {
         let location = &daily_histories.location;
         let histories = &daily_histories.histories;
         let file = weather_dir.archive(&location.alias);
         let mut updater = WeatherHistoryUpdate::new(&location.alias, file)?;
-        let dates_added = updater.add(histories)?;
+        let dates_added = updater.add(histories, progress)?;
         let mut histories_added = Vec::with_capacity(dates_added.len());
         histories.iter().for_each(|history| {
             if dates_added.iter().any(|date| &history.date == date) {
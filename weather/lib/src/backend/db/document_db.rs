@@ -41,9 +41,13 @@ mod v3 {
         /// # Arguments
         ///
         /// * `daily_histories` has the location and histories to add.
-        fn add_daily_histories(&self, daily_histories: &DailyHistories) -> Result<usize> {
+        fn add_daily_histories(
+            &self,
+            daily_histories: &DailyHistories,
+            progress: &mut dyn FnMut(usize, usize),
+        ) -> Result<usize> {
             // add histories to the archive
-            let file_histories = archive::add_histories(&self.weather_dir, daily_histories)?;
+            let file_histories = archive::add_histories(&self.weather_dir, daily_histories, progress)?;
             // find which histories can be added to the database
             let mut conn = db_conn!(&self.weather_dir)?;
             let (lid, histories) = metadata::examine_add_histories(&conn, daily_histories)?;
@@ -70,6 +74,23 @@ mod v3 {
             let daily_histories = query_daily_history(&conn, &location.alias, date_range, self.compress)?;
             Ok(DailyHistories { location, histories: daily_histories })
         }
+        /// Check if a location has weather history for a date.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        /// * `date` is the weather history date.
+        fn contains_history(&self, alias: &str, date: &NaiveDate) -> Result<bool> {
+            archive_contains(&self.weather_dir, alias, date)
+        }
+        /// Get the number of weather histories for a location.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        fn count_histories(&self, alias: &str) -> Result<usize> {
+            archive_count(&self.weather_dir, alias)
+        }
         /// Get the weather history dates for locations.
         ///
         /// # Arguments
@@ -117,7 +138,7 @@ mod v3 {
         /// * `criteria` identifies the locations of interest.
         fn locations(&self, criteria: DataCriteria) -> Result<Vec<Location>> {
             let conn = db_conn!(&self.weather_dir)?;
-            locations::get(&conn, &criteria.filters, criteria.sort)
+            locations::get(&conn, &criteria)
         }
         /// Search for locations.
         ///
@@ -5,7 +5,6 @@ pub(super) use v3::{data_adapter, load, reload};
 mod v3 {
     //! The current implementation of a hybrid database
     use super::*;
-    use crate::backend::filesys::WeatherHistory;
 
     /// Create the *hybrid* version of the data adapter.
     pub fn data_adapter(config: Config, weather_dir: WeatherDir) -> Result<Box<dyn DataAdapter>> {
@@ -30,9 +29,13 @@ mod v3 {
         /// # Arguments
         ///
         /// * `daily_histories` has the location and histories to add.
-        fn add_daily_histories(&self, daily_histories: &DailyHistories) -> Result<usize> {
+        fn add_daily_histories(
+            &self,
+            daily_histories: &DailyHistories,
+            progress: &mut dyn FnMut(usize, usize),
+        ) -> Result<usize> {
             // add histories to the archive
-            let file_histories = archive::add_histories(&self.weather_dir, daily_histories)?;
+            let file_histories = archive::add_histories(&self.weather_dir, daily_histories, progress)?;
             // audit the histories to see which ones can be added
             let mut conn = db_conn!(&self.weather_dir)?;
             let (lid, histories) = metadata::examine_add_histories(&conn, daily_histories)?;
@@ -66,6 +69,39 @@ mod v3 {
             let daily_histories = archive.daily_histories(&history_range)?;
             Ok(DailyHistories { location, histories: daily_histories })
         }
+        /// Returns the daily weather data history for a location along with the histories that could not
+        /// be decoded.
+        ///
+        /// # Arguments
+        ///
+        /// * `location` identifies what location should be used.
+        /// * `history_range` specifies the date range that should be used.
+        fn daily_histories_with_failures(
+            &self,
+            location: Location,
+            history_range: DateRange,
+        ) -> Result<(DailyHistories, HistoryFailures)> {
+            let archive = WeatherHistory::new(&location.alias, self.weather_dir.archive(&location.alias))?;
+            let (histories, failures) = archive.daily_histories_with_failures(&history_range)?;
+            Ok((DailyHistories { location, histories }, failures))
+        }
+        /// Check if a location has weather history for a date.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        /// * `date` is the weather history date.
+        fn contains_history(&self, alias: &str, date: &NaiveDate) -> Result<bool> {
+            archive_contains(&self.weather_dir, alias, date)
+        }
+        /// Get the number of weather histories for a location.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        fn count_histories(&self, alias: &str) -> Result<usize> {
+            archive_count(&self.weather_dir, alias)
+        }
         /// Get the weather history dates for locations.
         ///
         /// # Arguments
@@ -113,7 +149,7 @@ mod v3 {
         /// * `criteria` identifies the locations of interest.
         fn locations(&self, criteria: DataCriteria) -> Result<Vec<Location>> {
             let conn = db_conn!(&self.weather_dir)?;
-            locations::get(&conn, &criteria.filters, criteria.sort)
+            locations::get(&conn, &criteria)
         }
         /// Search for locations.
         ///
@@ -1,7 +1,7 @@
 //! Provide database support for weather data locations.
 use super::*;
 
-pub(super) use v3::{add, search, load, get, id_aliases, location_id};
+pub(super) use v3::{add, search, load, get, id_aliases, location_id, rename};
 mod v3 {
     //! The current version of locations for the various database modes.
     use super::*;
@@ -80,10 +80,9 @@ mod v3 {
     /// # Arguments
     ///
     /// * `conn` is the database connection that will be used.
-    /// * `filters` determines what locations will be returned.
-    /// * `sort` when true the locations will be in location name order.
-    pub fn get(conn: &Connection, filters: &Vec<String>, sort: bool) -> Result<Vec<Location>> {
-        let mut stmt = conn.prepare(&query_sql(filters, sort))?;
+    /// * `criteria` determines what locations will be returned and if they are in location name order.
+    pub fn get(conn: &Connection, criteria: &DataCriteria) -> Result<Vec<Location>> {
+        let mut stmt = conn.prepare(&query_sql(criteria))?;
         let mut rows = stmt.query([])?;
         let mut locations = vec![];
         while let Some(row) = rows.next()? {
@@ -103,12 +102,19 @@ mod v3 {
     ///
     /// # Arguments
     ///
-    /// * `filters` has the location names or aliases that restrict what locations are returned.
-    /// * `sort` when true will sort the returned collection by location name.
-    fn query_sql(filters: &Vec<String>, sort: bool) -> String {
+    /// * `criteria` has the location names or aliases that restrict what locations are returned. Alias
+    ///   only filters do not support wildcards and ignore case only if the criteria says so.
+    fn query_sql(criteria: &DataCriteria) -> String {
+        let DataCriteria { filters, icase, sort, alias_only } = criteria;
+        let alias_only = *alias_only;
+        let quote = |value: String| format!("'{}'", value.replace("'", "''"));
         macro_rules! condition {
             ($filter:expr) => {
-                or!([like!("name", $filter), like!("alias", $filter)])
+                match (alias_only, icase) {
+                    (true, true) => equals!("lower(alias)", &quote($filter.to_lowercase())),
+                    (true, false) => equals!("alias", &quote($filter.to_string())),
+                    (false, _) => or!([like!("name", $filter), like!("alias", $filter)]),
+                }
             };
         }
         let mut sql = r#"
@@ -121,18 +127,18 @@ mod v3 {
             0 => (),
             1 => {
                 let filter = &filters[0];
-                if filter != "*" {
+                if alias_only || filter != "*" {
                     sql.push_str(&format!(" WHERE {}", condition!(filter)));
                 }
             }
             _ => {
-                if filters.iter().find(|filter| *filter == "*").is_none() {
+                if alias_only || filters.iter().find(|filter| *filter == "*").is_none() {
                     let conditions: Vec<Condition> = filters.iter().map(|filter| condition!(filter)).collect();
                     sql.push_str(&format!(" WHERE {}", or!(conditions)));
                 }
             }
         }
-        if sort {
+        if *sort {
             sql.push_str(" ORDER BY name ASC");
         }
         // log::trace!("{}", sql);
@@ -156,6 +162,28 @@ mod v3 {
         Ok(id_aliases)
     }
 
+    /// Change the name and alias of a location.
+    ///
+    /// Histories reference the location identifier so only the location row needs to be updated.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` is the database connection that will be used.
+    /// * `alias` is the current alias of the location.
+    /// * `name` is the new location name.
+    /// * `new_alias` is the new location alias.
+    pub fn rename(conn: &Connection, alias: &str, name: &str, new_alias: &str) -> Result<()> {
+        const SQL: &str = "UPDATE locations SET name = :name, alias = :new_alias WHERE alias = :alias";
+        match conn.execute(SQL, named_params! {":name": name, ":new_alias": new_alias, ":alias": alias}) {
+            Ok(0) => Err(Error::from(format!("Location '{}' was not found.", alias))),
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let reason = format!("Error renaming location '{}' ({}).", alias, err);
+                Err(Error::from(reason))
+            }
+        }
+    }
+
     /// Get the locations database identifier.
     ///
     /// # Arguments
@@ -173,7 +201,7 @@ mod v3 {
     }
 
 //     use crate::backend::filesys;
-    use conditions::{between, equals, like, or, Condition, Like, Or};
+    use conditions::{equals, like, or, Condition, Equals, Like, Or};
     mod conditions {
         //! Helpers that dynamically build `SQL WHERE` clause conditions.
         use super::*;
@@ -183,7 +211,6 @@ mod v3 {
         #[derive(Debug)]
         pub enum Condition {
             /// A `SQL` *equals* condition.
-            #[allow(unused)]
             Equals(Equals),
             /// A `SQL` *like* condition.
             Like(Like),
@@ -312,6 +339,7 @@ mod v3 {
                 Condition::Between(Between::column($column).from($from).thru($thru))
             };
         }
+        #[allow(unused)]
         pub(super) use between;
 
         #[derive(Debug, Default)]
@@ -403,21 +431,61 @@ mod v3 {
             db_conn!(&weather_dir).unwrap()
         }
 
+        fn criteria(filters: &[&str], alias_only: bool, sort: bool) -> DataCriteria {
+            let filters = filters.iter().map(|filter| filter.to_string()).collect();
+            DataCriteria { filters, icase: true, sort, alias_only }
+        }
+
         #[test]
         fn query_locations() {
             let fixture = testlib::TestFixture::create();
             let conn = testenv(&fixture);
-            let locations = get(&conn, &vec![], true).unwrap();
+            let locations = get(&conn, &criteria(&[], false, true)).unwrap();
             assert_eq!(locations.len(), 3);
             for (location, expected_alias) in locations.iter().zip(["between", "north", "south"].iter()) {
                 assert_eq!(location.alias, *expected_alias);
             }
-            let locations = get(&conn, &vec!["south".to_string(), "north".to_string()], true).unwrap();
+            let locations = get(&conn, &criteria(&["south", "north"], false, true)).unwrap();
             assert_eq!(locations.len(), 2);
             for (location, expected_alias) in locations.iter().zip(["north", "south"].iter()) {
                 assert_eq!(location.alias, *expected_alias);
             }
         }
+
+        #[test]
+        fn query_locations_by_alias() {
+            let fixture = testlib::TestFixture::create();
+            let conn = testenv(&fixture);
+            // name substrings only match when aliases are not required
+            let filters = ["*th*", "Between City"];
+            assert_eq!(get(&conn, &criteria(&filters, false, true)).unwrap().len(), 3);
+            assert!(get(&conn, &criteria(&filters, true, true)).unwrap().is_empty());
+            // aliases must match exactly
+            let filters = criteria(&["North", "sout"], true, true);
+            let locations = get(&conn, &filters).unwrap();
+            assert_eq!(locations.len(), 1);
+            assert_eq!(locations[0].alias, "north");
+            // aliases are case-sensitive unless case is ignored
+            let filters = DataCriteria { icase: false, ..filters };
+            assert!(get(&conn, &filters).unwrap().is_empty());
+            let filters = DataCriteria { filters: vec!["north".to_string()], ..filters };
+            assert_eq!(get(&conn, &filters).unwrap().len(), 1);
+        }
+        #[test]
+        fn rename_location() {
+            let fixture = testlib::TestFixture::create();
+            let conn = testenv(&fixture);
+            let id = location_id(&conn, "north").unwrap();
+            assert!(rename(&conn, "north", "Southern City", "renamed").is_err());
+            assert!(rename(&conn, "missing", "Missing City", "missing").is_err());
+            rename(&conn, "north", "Renamed City", "renamed").unwrap();
+            assert!(location_id(&conn, "north").is_err());
+            assert_eq!(location_id(&conn, "renamed").unwrap(), id);
+            let locations = get(&conn, &criteria(&["renamed"], true, false)).unwrap();
+            assert_eq!(locations.len(), 1);
+            assert_eq!(locations[0].name, "Renamed City");
+        }
+
         #[test]
         fn locations_sql() {
             macro_rules! normalize {
@@ -426,25 +494,33 @@ mod v3 {
                 };
             }
             assert_eq!(
-                normalize!(query_sql(&vec![], false)),
+                normalize!(query_sql(&criteria(&[], false, false))),
                 "SELECT name, alias, longitude, latitude, tz FROM locations"
             );
             assert_eq!(
-                normalize!(query_sql(&vec!["*".to_string()], true)),
+                normalize!(query_sql(&criteria(&["*"], false, true))),
                 "SELECT name, alias, longitude, latitude, tz FROM locations ORDER BY name ASC"
             );
             assert_eq!(
-                normalize!(query_sql(&vec!["*ern*".to_string()], true)),
+                normalize!(query_sql(&criteria(&["*ern*"], false, true))),
                 "SELECT name, alias, longitude, latitude, tz FROM locations WHERE (name LIKE '%ern%' OR alias LIKE '%ern%') ORDER BY name ASC"
             );
             assert_eq!(
-                normalize!(query_sql(&vec!["*ern*".to_string(), "bet*".to_string()], false)),
+                normalize!(query_sql(&criteria(&["*ern*", "bet*"], false, false))),
                 "SELECT name, alias, longitude, latitude, tz FROM locations WHERE ((name LIKE '%ern%' OR alias LIKE '%ern%') OR (name LIKE 'bet%' OR alias LIKE 'bet%'))"
             );
             assert_eq!(
-                normalize!(query_sql(&vec!["*ern*".to_string(), "*".to_string()], false)),
+                normalize!(query_sql(&criteria(&["*ern*", "*"], false, false))),
                 "SELECT name, alias, longitude, latitude, tz FROM locations"
             );
+            assert_eq!(
+                normalize!(query_sql(&criteria(&["North", "*"], true, true))),
+                "SELECT name, alias, longitude, latitude, tz FROM locations WHERE (lower(alias) = 'north' OR lower(alias) = '*') ORDER BY name ASC"
+            );
+            assert_eq!(
+                normalize!(query_sql(&DataCriteria { icase: false, ..criteria(&["North", "o'k"], true, false) })),
+                "SELECT name, alias, longitude, latitude, tz FROM locations WHERE (alias = 'North' OR alias = 'o''k')"
+            );
         }
     }
 }
\ No newline at end of file
@@ -34,9 +34,13 @@ mod v3 {
         /// # Arguments
         ///
         /// * `daily_histories` contains the location histories to add.
-        fn add_daily_histories(&self, daily_histories: &DailyHistories) -> Result<usize> {
+        fn add_daily_histories(
+            &self,
+            daily_histories: &DailyHistories,
+            progress: &mut dyn FnMut(usize, usize),
+        ) -> Result<usize> {
             // add histories to the archive first
-            let file_histories = archive::add_histories(&self.weather_dir, daily_histories)?;
+            let file_histories = archive::add_histories(&self.weather_dir, daily_histories, progress)?;
             // filter out histories that already exist in the db
             let mut conn = db_conn!(&self.weather_dir)?;
             let (lid, db_histories) = metadata::examine_add_histories(&conn, daily_histories)?;
@@ -74,6 +78,23 @@ mod v3 {
             let daily_histories = query_history(&conn, &location.alias, date_range)?;
             Ok(DailyHistories { location, histories: daily_histories })
         }
+        /// Check if a location has weather history for a date.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        /// * `date` is the weather history date.
+        fn contains_history(&self, alias: &str, date: &NaiveDate) -> Result<bool> {
+            archive_contains(&self.weather_dir, alias, date)
+        }
+        /// Get the number of weather histories for a location.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        fn count_histories(&self, alias: &str) -> Result<usize> {
+            archive_count(&self.weather_dir, alias)
+        }
         /// Get the weather history dates for locations.
         ///
         /// # Arguments
@@ -121,7 +142,7 @@ mod v3 {
         /// * `criteria` identifies the locations of interest.
         fn locations(&self, criteria: DataCriteria) -> Result<Vec<Location>> {
             let conn = db_conn!(&self.weather_dir)?;
-            locations::get(&conn, &criteria.filters, criteria.sort)
+            locations::get(&conn, &criteria)
         }
         /// Search for locations.
         ///
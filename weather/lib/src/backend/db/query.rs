@@ -16,10 +16,11 @@ mod v3 {
     /// * `criteria` is the location data criteria.
     pub fn history_dates(conn: &Connection, criteria: DataCriteria) -> Result<Vec<HistoryDates>> {
         // collect up the locations that match the criteria
-        let mut history_dates: Vec<HistoryDates> = locations::get(conn, &criteria.filters, criteria.sort)?
-            .into_iter()
-            .map(|location| HistoryDates { location, history_dates: vec![] })
-            .collect();
+        let mut history_dates: Vec<HistoryDates> =
+            locations::get(conn, &criteria)?
+                .into_iter()
+                .map(|location| HistoryDates { location, history_dates: vec![] })
+                .collect();
         // if the data criteria didn't match anything don't bother with a query
         if history_dates.len() > 0 {
             // collect the location aliases
@@ -93,16 +94,17 @@ mod v3 {
     /// * `criteria` is the location data criteria.
     #[allow(unused)]
     pub fn history_sizes(conn: &Connection, criteria: DataCriteria) -> Result<Vec<HistorySummaries>> {
-        let mut history_summaries: Vec<HistorySummaries> = locations::get(conn, &criteria.filters, criteria.sort)?
-            .into_iter()
-            .map(|location| HistorySummaries {
-                location,
-                count: 0,
-                overall_size: None,
-                raw_size: None,
-                store_size: None,
-            })
-            .collect();
+        let mut history_summaries: Vec<HistorySummaries> =
+            locations::get(conn, &criteria)?
+                .into_iter()
+                .map(|location| HistorySummaries {
+                    location,
+                    count: 0,
+                    overall_size: None,
+                    raw_size: None,
+                    store_size: None,
+                })
+                .collect();
         let aliases: Vec<&str> = history_summaries.iter().map(|h| h.location.alias.as_str()).collect();
         for (alias, count, store_size, raw_size) in query_history_sizes(conn, aliases)? {
             for history in &mut history_summaries {
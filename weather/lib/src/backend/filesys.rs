@@ -4,7 +4,7 @@ use super::*;
 pub(crate) use admin::MigrateConfig;
 pub(crate) mod admin;
 
-pub(crate) use files::{WeatherDir, WeatherFile};
+pub(crate) use files::{FileHandle, WeatherDir, WeatherFile};
 pub(crate) mod files;
 
 pub(super) use archives::{archive_history_collector, ArchiveMd, WeatherArchive, WeatherHistory, WeatherHistoryUpdate};
@@ -13,6 +13,9 @@ pub(crate) mod archives;
 pub(super) use locations::weather_locations;
 mod locations;
 
+use history_cache::HistoryCache;
+mod history_cache;
+
 /// Get a [WeatherDir] instance.
 pub(crate) fn weather_dir(dirname: &str) -> Result<WeatherDir> {
     let weather_dir = if dirname.len() > 0 {
@@ -34,6 +37,7 @@ mod v1 {
         DailyHistories, DataCriteria, DateRange, HistoryDates, HistorySummaries, Location, LocationCriteria,
     };
 
+    use chrono::NaiveDate;
     use locations::search_locations;
     use toolslib::stopwatch::StopWatch;
 
@@ -48,8 +52,13 @@ mod v1 {
     // pub fn create(config: Config) -> Result<Box<dyn DataAdapter>> {
     pub fn create(config: Config) -> Result<Box<dyn DataAdapter>> {
         log::debug!("DataAdapter");
-        let weather_dir = weather_dir(&config.weather_data.directory)?;
-        Ok(Box::new(ArchiveDataAdapter { config, weather_dir }))
+        let weather_dir = weather_dir(&config.weather_data.directory)?.with_read_only(config.weather_data.read_only);
+        // the history cache is written into the weather directory so it is not used with read-only weather data
+        let history_cache = match config.weather_data.history_cache && !weather_dir.is_read_only() {
+            true => Some(HistoryCache::new(&weather_dir)),
+            false => None,
+        };
+        Ok(Box::new(ArchiveDataAdapter { config, weather_dir, history_cache }))
     }
 
     /// Consolidate logging elapsed time here.
@@ -67,6 +76,8 @@ mod v1 {
         config: Config,
         /// The directory containing weather data files.rs
         weather_dir: WeatherDir,
+        /// The on-disk cache of decoded weather histories, if it has been enabled.
+        history_cache: Option<HistoryCache>,
     }
     impl ArchiveDataAdapter {
         /// Used internally to get the archive manager for some location.
@@ -94,11 +105,16 @@ mod v1 {
         /// # Arguments
         ///
         /// * `daily_histories` has the location and histories to add.
-        fn add_daily_histories(&self, daily_histories: &DailyHistories) -> Result<usize> {
+        /// * `progress` is called as each history is added.
+        fn add_daily_histories(
+            &self,
+            daily_histories: &DailyHistories,
+            progress: &mut dyn FnMut(usize, usize),
+        ) -> Result<usize> {
             let location = &daily_histories.location;
             let file = self.weather_dir.archive(&location.alias);
             let mut archive_updater = WeatherHistoryUpdate::new(&location.alias, file)?;
-            let additions = archive_updater.add(&daily_histories.histories)?;
+            let additions = archive_updater.add(&daily_histories.histories, progress)?;
             Ok(additions.len())
         }
         /// Returns the daily weather data history for a location.
@@ -109,11 +125,58 @@ mod v1 {
         /// * `history_range` specifies the date range that should be used.
         fn daily_histories(&self, location: Location, history_range: DateRange) -> Result<DailyHistories> {
             let stopwatch = StopWatch::start_new();
-            let archive = self.get_archive(&location.alias)?;
-            let daily_histories = archive.daily_histories(&history_range)?;
+            let archive_histories = |filter: &DateRange| self.get_archive(&location.alias)?.daily_histories(filter);
+            let daily_histories = match &self.history_cache {
+                Some(history_cache) => {
+                    let file = self.weather_dir.archive(&location.alias);
+                    let everything = DateRange::new(NaiveDate::MIN, NaiveDate::MAX);
+                    history_cache.daily_histories(&location.alias, &file, &history_range, || {
+                        archive_histories(&everything)
+                    })?
+                }
+                None if history_range.is_one_day() => {
+                    self.get_archive(&location.alias)?.history(history_range.from)?.into_iter().collect()
+                }
+                None => archive_histories(&history_range)?,
+            };
             log_elapsed!("daily_histories", &stopwatch);
             Ok(DailyHistories { location, histories: daily_histories })
         }
+        /// Returns the daily weather data history for a location along with the histories that could not
+        /// be decoded. The history cache is not used so decode failures are always found.
+        ///
+        /// # Arguments
+        ///
+        /// * `location` identifies what location should be used.
+        /// * `history_range` specifies the date range that should be used.
+        fn daily_histories_with_failures(
+            &self,
+            location: Location,
+            history_range: DateRange,
+        ) -> Result<(DailyHistories, HistoryFailures)> {
+            let archive = self.get_archive(&location.alias)?;
+            let (histories, failures) = archive.daily_histories_with_failures(&history_range)?;
+            Ok((DailyHistories { location, histories }, failures))
+        }
+        /// Check if a location has weather history for a date.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        /// * `date` is the weather history date.
+        fn contains_history(&self, alias: &str, date: &NaiveDate) -> Result<bool> {
+            let archive = self.get_archive(alias)?;
+            archive.contains(date)
+        }
+        /// Get the number of weather histories for a location.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        fn count_histories(&self, alias: &str) -> Result<usize> {
+            let archive = self.get_archive(alias)?;
+            archive.count()
+        }
         /// Get the weather history dates for locations.
         ///
         /// # Arguments
@@ -171,7 +234,10 @@ mod v1 {
         fn locations(&self, criteria: DataCriteria) -> Result<Vec<Location>> {
             let stopwatch = StopWatch::start_new();
             let locations = weather_locations(&self.weather_dir)?;
-            let locations = locations.as_iter(&criteria.filters, !criteria.icase, criteria.sort).collect();
+            let locations = match criteria.alias_only {
+                true => locations.as_alias_iter(&criteria.filters, !criteria.icase, criteria.sort).collect(),
+                false => locations.as_iter(&criteria.filters, !criteria.icase, criteria.sort).collect(),
+            };
             log_elapsed!("locations", &stopwatch);
             Ok(locations)
         }
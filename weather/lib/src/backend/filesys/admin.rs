@@ -1,15 +1,18 @@
 //! Isolates the administration API from the weather API.
 use super::*;
 
-pub(crate) use v2::{filesys_details, migrate_history, MigrateConfig};
+pub(crate) use v2::{
+    compact_archives, filesys_details, migrate_history, normalize_archives, rename_location, scan_integrity,
+    MigrateConfig,
+};
 mod v2 {
     //! The current implementation of administration for the file system.
     use super::*;
     use crate::{
-        admin::admin_entities::{FilesysDetails, LocationDetails},
+        admin::admin_entities::{CompactionReport, FilesysDetails, IntegrityIssue, LocationDetails, NormalizeReport},
         entities::{DataCriteria, History, Location},
     };
-    use chrono::{DateTime, NaiveDate};
+    use chrono::NaiveDate;
     use std::{
         env, fs,
         io::Read,
@@ -31,21 +34,85 @@ mod v2 {
         for location in locations.as_iter(&vec![], false, false) {
             let file = weather_dir.archive(&location.alias);
             archives_size += file.size();
-            let mut histories: usize = 0;
-            let mut history_dates = vec![];
-            let size = WeatherArchive::open(&location.alias, file)?
-                .iter_date_range(None, false, ArchiveMd::new)?
-                .map(|md| {
-                    histories += 1;
-                    history_dates.push(md.date);
-                    md.compressed_size as usize
-                })
-                .sum::<usize>();
+            let entry_sizes = WeatherHistory::new(&location.alias, file)?.entry_sizes()?;
+            let histories = entry_sizes.len();
+            let size = entry_sizes.iter().map(|(_, _, compressed_size)| *compressed_size as usize).sum::<usize>();
             location_details.push(LocationDetails { alias: location.alias.clone(), size, histories })
         }
         Ok(FilesysDetails { size: archives_size as usize, location_details })
     }
 
+    /// Compact the weather history archives of locations.
+    ///
+    /// # Arguments
+    ///
+    /// * `weather_dir` is the weather data directory.
+    /// * `criteria` identifies the locations that will be compacted.
+    pub fn compact_archives(weather_dir: &WeatherDir, criteria: DataCriteria) -> Result<Vec<CompactionReport>> {
+        let locations = weather_locations(weather_dir)?;
+        let mut reports = vec![];
+        for location in locations.as_iter(&criteria.filters, criteria.icase, criteria.sort) {
+            let mut archive = WeatherArchive::open(&location.alias, weather_dir.archive(&location.alias))?;
+            reports.push(archive.compact()?);
+        }
+        Ok(reports)
+    }
+
+    /// Rewrite the weather history archives of locations so histories are in ascending date order.
+    ///
+    /// # Arguments
+    ///
+    /// * `weather_dir` is the weather data directory.
+    /// * `criteria` identifies the locations that will be normalized.
+    pub fn normalize_archives(weather_dir: &WeatherDir, criteria: DataCriteria) -> Result<Vec<NormalizeReport>> {
+        let locations = weather_locations(weather_dir)?;
+        let mut reports = vec![];
+        for location in locations.as_iter(&criteria.filters, criteria.icase, criteria.sort) {
+            let mut archive = WeatherArchive::open(&location.alias, weather_dir.archive(&location.alias))?;
+            reports.push(archive.normalize()?);
+        }
+        Ok(reports)
+    }
+
+    /// Change the name and alias of a location. The previous location name is returned so the
+    /// change can be reversed.
+    ///
+    /// # Arguments
+    ///
+    /// * `weather_dir` is the weather data directory.
+    /// * `alias` is the current alias of the location.
+    /// * `name` is the new location name.
+    /// * `new_alias` is the new location alias.
+    pub fn rename_location(weather_dir: &WeatherDir, alias: &str, name: &str, new_alias: &str) -> Result<String> {
+        let mut locations = weather_locations(weather_dir)?;
+        let previous = locations.as_alias_iter(&vec![alias.to_string()], true, false).next();
+        locations.rename(alias, name, new_alias, weather_dir)?;
+        Ok(previous.map_or(Default::default(), |location| location.name))
+    }
+
+    /// Verify the weather history archives of all locations.
+    ///
+    /// Archives that cannot be opened are reported as an issue and the scan continues.
+    ///
+    /// # Arguments
+    ///
+    /// * `weather_dir` is the weather data directory.
+    pub fn scan_integrity(weather_dir: &WeatherDir) -> Result<Vec<IntegrityIssue>> {
+        let locations = weather_locations(weather_dir)?;
+        let mut issues = vec![];
+        for location in locations.as_iter(&vec![], false, true) {
+            let alias = location.alias;
+            match WeatherArchive::open(&alias, weather_dir.archive(&alias)) {
+                Ok(archive) => match archive.integrity_issues() {
+                    Ok(archive_issues) => issues.extend(archive_issues),
+                    Err(err) => issues.push(IntegrityIssue { alias, entry: None, reason: err.to_string() }),
+                },
+                Err(err) => issues.push(IntegrityIssue { alias, entry: None, reason: err.to_string() }),
+            }
+        }
+        Ok(issues)
+    }
+
     #[derive(Debug)]
     /// The metadata surrounding migrating old data to [History].
     pub struct MigrateConfig<'w> {
@@ -127,7 +194,6 @@ mod v2 {
     mod darksky {
         //! Isolate the Darksky conversion to this module.
         use super::*;
-        use serde::{Deserialize, Serialize};
 
         /// The entry point to migrate *DarkSky* weather history to [History].
         ///
@@ -193,8 +259,7 @@ mod v2 {
             let migrations: Vec<MigrationData> = source.iter_date_range(None, false, MigrationData::new)?.collect();
             let mut histories: Vec<History> = Vec::with_capacity(migrations.len());
             for md in migrations {
-                let darksky = md.to_darksky()?;
-                histories.push(darksky.as_history(alias, &md.date));
+                histories.push(md.to_history()?);
             }
             let mut archive_writer = target.archive_writer();
             archive_writer.write(histories.iter().map(|h| h).collect())?;
@@ -230,431 +295,126 @@ mod v2 {
                     Ok(Self { alias: alias.to_string(), date: date.clone(), data })
                 }
             }
-            /// Deserialize the history data into [DarkskyHistory].
-            fn to_darksky(&self) -> Result<DarkskyHistory> {
-                match serde_json::from_slice::<DarkskyHistory>(&self.data) {
+            /// Convert the *DarkSky* history data into [History].
+            fn to_history(&self) -> Result<History> {
+                match history::from_darksky(&self.alias, &self.date, &self.data) {
                     Ok(history) => Ok(history),
                     Err(err) => {
-                        let reason = format!(
-                            "MigrationData ({}): {} error creating Darksky history ({})",
-                            self.alias, self.date, err
-                        );
+                        let reason = format!("MigrationData ({}): {} {}", self.alias, self.date, err);
                         error!(reason)
                     }
                 }
             }
         }
+    }
 
-        #[derive(Debug, Serialize, Deserialize)]
-        /// The *DarkSky* document.
-        struct DarkskyHistory {
-            daily: DarkskyDaily,
-            hourly: DarkskyHourly,
-            latitude: f64,
-            longitude: f64,
-            offset: i64,
-            timezone: String,
-        }
-        /// Returns a reference to the daily weather history.
-        macro_rules! daily {
-            ($self:expr) => {
-                &$self.daily.data[0]
-            };
-        }
-        /// Returns an iterator to the hourly weather history.
-        macro_rules! hourly_iter {
-            ($self:expr) => {
-                $self.hourly.data.iter()
-            };
-        }
-        /// Consolidate what happens if weather history cannot be derived.
-        macro_rules! map_or_default {
-            ($option:expr, $what:literal) => {
-                match $option {
-                    Some(value) => Some(value),
-                    None => {
-                        log::trace!("{} has no value, using default.", $what);
-                        None
-                    }
-                }
-            };
-        }
-        impl DarkskyHistory {
-            /// Convert *DarkSky* weather history into [History].
-            ///
-            /// # Arguments
-            ///
-            /// * `alias` is the location alias name.
-            /// * `date` is the weather history date.
-            fn as_history(&self, alias: &str, date: &NaiveDate) -> History {
-                let daily = daily!(self);
-                History {
-                    alias: alias.to_string(),
-                    date: date.clone(),
-                    temperature_high: self.temperature_high(),
-                    temperature_low: self.temperature_low(),
-                    temperature_mean: self.temperature_mean(),
-                    dew_point: self.dew_point(),
-                    humidity: self.humidity(),
-                    precipitation_chance: self.precipitation_chance(),
-                    precipitation_type: daily.precipType.clone(),
-                    precipitation_amount: self.precip(),
-                    wind_speed: self.wind_speed(),
-                    wind_gust: self.wind_gust(),
-                    wind_direction: self.wind_bearing(),
-                    cloud_cover: self.cloud_cover(),
-                    pressure: self.pressure(),
-                    uv_index: self.uv_index(),
-                    sunrise: daily
-                        .sunriseTime
-                        .map_or(None, |ts| DateTime::from_timestamp(ts, 0).map_or(None, |dt| Some(dt.naive_utc()))),
-                    sunset: daily
-                        .sunsetTime
-                        .map_or(None, |ts| DateTime::from_timestamp(ts, 0).map_or(None, |dt| Some(dt.naive_utc()))),
-                    moon_phase: daily.moonPhase,
-                    visibility: self.visibility(),
-                    description: daily.summary.clone(),
-                }
-            }
-            /// Extracts the daily high temperature
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            ///
-            /// * daily `temperatureHigh`
-            /// * daily `temperatureMax`
-            /// * daily `apparentTemperatureHigh`
-            /// * daily `apparentTemperatureMax`
-            /// * hourly `temperature`
-            fn temperature_high(&self) -> Option<f64> {
-                let daily = daily!(self);
-                match daily.temperatureHigh {
-                    Some(t) => Some(t),
-                    None => match daily.temperatureMax {
-                        Some(t) => Some(t),
-                        None => match daily.apparentTemperatureHigh {
-                            Some(t) => Some(t),
-                            None => match daily.apparentTemperatureMax {
-                                Some(t) => Some(t),
-                                None => {
-                                    let temp = hourly_iter!(self).filter_map(|h| h.temperature).reduce(f64::max);
-                                    map_or_default!(temp, "temperature_max")
-                                }
-                            },
-                        },
-                    },
-                }
-            }
-            /// Extracts the daily low temperature
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            ///
-            /// * daily `temperatureLow`
-            /// * daily `temperatureMin`
-            /// * daily `apparentTemperatureMin`
-            /// * daily `apparentTemperatureMin`
-            /// * hourly `temperature`
-            fn temperature_low(&self) -> Option<f64> {
-                let daily = daily!(self);
-                match daily.temperatureLow {
-                    Some(t) => Some(t),
-                    None => match daily.temperatureMin {
-                        Some(t) => Some(t),
-                        None => match daily.apparentTemperatureMin {
-                            Some(t) => Some(t),
-                            None => match daily.apparentTemperatureMin {
-                                Some(t) => Some(t),
-                                None => {
-                                    let temp = hourly_iter!(self).filter_map(|h| h.temperature).reduce(f64::min);
-                                    map_or_default!(temp, "temperature_min")
-                                }
-                            },
-                        },
-                    },
-                }
-            }
-            /// Calculate the daily mean temperature from hourly history.
-            fn temperature_mean(&self) -> Option<f64> {
-                let temps: Vec<f64> = hourly_iter!(self).filter_map(|h| h.temperature).collect();
-                if temps.is_empty() {
-                    log::trace!("temperature_mean has no value, using default");
-                    None
-                } else {
-                    let mean_temp = temps.iter().sum::<f64>() / temps.len() as f64;
-                    Some((mean_temp * 100.0).round() / 100.0)
-                }
-            }
-            /// Extract the daily dew point.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `dewPoint`
-            /// * hourly `dewPoint`
-            fn dew_point(&self) -> Option<f64> {
-                match daily!(self).dewPoint {
-                    Some(dew_point) => Some(dew_point),
-                    None => {
-                        let dew_point = hourly_iter!(self).filter_map(|h| h.dewPoint).reduce(f64::max);
-                        map_or_default!(dew_point, "dew_point")
-                    }
-                }
-            }
-            /// Extract the daily humidity.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `humidity`
-            /// * hourly `humidity`
-            fn humidity(&self) -> Option<f64> {
-                match daily!(self).humidity {
-                    Some(humidity) => Some(humidity),
-                    None => {
-                        let humidity = hourly_iter!(self).filter_map(|h| h.humidity).reduce(f64::max);
-                        map_or_default!(humidity, "humidity")
-                    }
-                }
-            }
-            /// Extract the chance of precipitation.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `precipProbability`
-            /// * hourly `precipProbability`
-            fn precipitation_chance(&self) -> Option<f64> {
-                match daily!(self).precipProbability {
-                    Some(chance) => Some(chance),
-                    None => {
-                        let probabilities: Vec<f64> = hourly_iter!(self).filter_map(|h| h.precipProbability).collect();
-                        if probabilities.is_empty() {
-                            log::trace!("probabilities has no value, using default.");
-                            None
-                        } else {
-                            let chance = probabilities.iter().sum::<f64>() / probabilities.len() as f64;
-                            Some((chance * 100.0).round() / 100.0)
-                        }
-                    }
-                }
-            }
-            /// Extract the amount of precipitation.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `precipIntensity`
-            /// * hourly `precipIntensity`
-            fn precip(&self) -> Option<f64> {
-                let precip = match daily!(self).precipIntensity {
-                    Some(p) => p * 24f64,
-                    None => hourly_iter!(self).filter_map(|h| h.precipIntensity).sum::<f64>(),
-                };
-                Some(precip)
-            }
-            /// Extract the daily wind speed.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `windSpeed`
-            /// * hourly `windSpeed`
-            fn wind_speed(&self) -> Option<f64> {
-                match daily!(self).windSpeed {
-                    Some(speed) => Some(speed),
-                    None => {
-                        let speeds = hourly_iter!(self).filter_map(|md| md.windSpeed).collect::<Vec<f64>>();
-                        if speeds.is_empty() {
-                            log::trace!("wind_speed has no value, using default.");
-                            None
-                        } else {
-                            let speed = speeds.iter().sum::<f64>() / speeds.len() as f64;
-                            Some((speed * 100.0).round() / 100.0)
-                        }
-                    }
-                }
-            }
-            /// Extract the daily wind gust speed.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `windGust`
-            /// * hourly `windGust`
-            fn wind_gust(&self) -> Option<f64> {
-                match daily!(self).windGust {
-                    Some(gust) => Some(gust),
-                    None => {
-                        let gust = hourly_iter!(self).filter_map(|md| md.windGust).reduce(f64::max);
-                        map_or_default!(gust, "wind_gust")
-                    }
-                }
-            }
-            /// Extract the daily wind bearing.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `windBearing`
-            /// * hourly `windBearing`
-            fn wind_bearing(&self) -> Option<i64> {
-                match daily!(self).windBearing {
-                    Some(bearing) => Some(bearing),
-                    None => {
-                        let bearings = hourly_iter!(self).filter_map(|md| md.windBearing).collect::<Vec<i64>>();
-                        if bearings.is_empty() {
-                            log::trace!("wind_bearing has no value, using default.");
-                            None
-                        } else {
-                            let bearing = bearings.iter().sum::<i64>() as f64 / bearings.len() as f64;
-                            Some(bearing.round() as i64)
-                        }
-                    }
-                }
-            }
-            /// Extract the daily cloud cover.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `cloudCover`
-            /// * hourly `cloudCover`
-            fn cloud_cover(&self) -> Option<f64> {
-                match daily!(self).cloudCover {
-                    Some(cover) => Some(cover),
-                    None => {
-                        let covers = hourly_iter!(self).filter_map(|md| md.cloudCover).collect::<Vec<f64>>();
-                        if covers.is_empty() {
-                            log::trace!("cloud_cover has no value, using default.");
-                            None
-                        } else {
-                            let cover = covers.iter().sum::<f64>() / covers.len() as f64;
-                            Some((cover * 100.0).round() / 100.0)
-                        }
-                    }
-                }
-            }
-            /// Extract the daily atmospheric pressure.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `pressure`
-            /// * hourly `pressure`
-            fn pressure(&self) -> Option<f64> {
-                match daily!(self).pressure {
-                    Some(pressure) => Some(pressure),
-                    None => {
-                        let pressures = hourly_iter!(self).filter_map(|md| md.pressure).collect::<Vec<f64>>();
-                        if pressures.is_empty() {
-                            log::trace!("pressure has no value, using default.");
-                            None
-                        } else {
-                            let pressure = pressures.iter().sum::<f64>() / pressures.len() as f64;
-                            Some((pressure * 10000.0).round() / 10000.0)
-                        }
-                    }
-                }
-            }
-            /// Extract the daily atmospheric pressure.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `uvIndex`
-            /// * hourly `uvIndex`
-            fn uv_index(&self) -> Option<f64> {
-                match daily!(self).uvIndex {
-                    Some(uv_index) => Some(uv_index as f64),
-                    None => {
-                        let uv_indexes = hourly_iter!(self).filter_map(|md| md.uvIndex).collect::<Vec<i64>>();
-                        if uv_indexes.is_empty() {
-                            log::trace!("uv_index has no value, using default.");
-                            None
-                        } else {
-                            let uv_index = uv_indexes.iter().sum::<i64>() as f64 / uv_indexes.len() as f64;
-                            Some((uv_index * 100.0).round() / 100.0)
-                        }
-                    }
-                }
-            }
-            /// Extract the daily atmospheric pressure.
-            ///
-            /// *DarkSky* history is sparse so the following attributes are examined from first to last.
-            /// * daily `visibility`
-            /// * hourly `visibility`
-            fn visibility(&self) -> Option<f64> {
-                match daily!(self).visibility {
-                    Some(visibility) => Some(visibility),
-                    None => {
-                        let visibilities = hourly_iter!(self).filter_map(|md| md.visibility).collect::<Vec<f64>>();
-                        if visibilities.is_empty() {
-                            log::trace!("visibility has no value, using default.");
-                            None
-                        } else {
-                            let visibility = visibilities.iter().sum::<f64>() / visibilities.len() as f64;
-                            Some((visibility * 100.0).round() / 100.0)
-                        }
-                    }
-                }
-            }
-        }
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::backend::{data_api, testlib};
+        use std::collections::HashMap;
 
-        #[derive(Debug, Serialize, Deserialize)]
-        /// The *DarkSky* hourly weather history data.
-        struct DarkskyHourly {
-            data: Vec<HourlyMd>,
+        #[test]
+        fn rename() {
+            let fixture = testlib::TestFixture::create();
+            fixture.copy_resources(&testlib::test_resources().join("filesys"));
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let date = NaiveDate::from_ymd_opt(2015, 4, 1).unwrap();
+            let histories = WeatherHistory::new("north", weather_dir.archive("north")).unwrap().count().unwrap();
+            // the alias of another location cannot be used
+            assert!(rename_location(&weather_dir, "north", "Northern City", "south").is_err());
+            assert!(rename_location(&weather_dir, "missing", "Missing City", "missing").is_err());
+            assert!(weather_dir.archive("north").exists());
+            assert_eq!(rename_location(&weather_dir, "north", "Renamed City", "Renamed").unwrap(), "Northern City");
+            let locations = weather_locations(&weather_dir).unwrap();
+            assert_eq!(locations.cnt(), 3);
+            assert!(locations.as_alias_iter(&vec!["north".to_string()], false, false).next().is_none());
+            let location = locations.as_alias_iter(&vec!["renamed".to_string()], false, false).next().unwrap();
+            assert_eq!(location.name, "Renamed City");
+            assert!(!weather_dir.archive("north").exists());
+            let testcase = WeatherHistory::new("renamed", weather_dir.archive("renamed")).unwrap();
+            assert_eq!(testcase.count().unwrap(), histories);
+            let history = testcase.history(date).unwrap().unwrap();
+            assert_eq!(history.alias, "renamed");
+            assert_eq!(history.date, date);
+            // changing only the name leaves the archive alone
+            rename_location(&weather_dir, "renamed", "Renamed Town", "renamed").unwrap();
+            let locations = weather_locations(&weather_dir).unwrap();
+            assert_eq!(locations.as_iter(&vec!["Renamed Town".to_string()], true, false).count(), 1);
+            assert!(WeatherHistory::new("renamed", weather_dir.archive("renamed")).unwrap().contains(&date).unwrap());
         }
 
-        #[allow(non_snake_case)]
-        #[derive(Debug, Serialize, Deserialize)]
-        /// The *DarkSky* hourly weather metadata.
-        struct HourlyMd {
-            apparentTemperature: Option<f64>,
-            cloudCover: Option<f64>,
-            dewPoint: Option<f64>,
-            humidity: Option<f64>,
-            icon: Option<String>,
-            precipIntensity: Option<f64>,
-            precipProbability: Option<f64>,
-            pressure: Option<f64>,
-            summary: Option<String>,
-            temperature: Option<f64>,
-            time: Option<i64>,
-            uvIndex: Option<i64>,
-            visibility: Option<f64>,
-            windBearing: Option<i64>,
-            windGust: Option<f64>,
-            windSpeed: Option<f64>,
+        #[test]
+        fn batch_add_admin() {
+            let fixture = testlib::TestFixture::create();
+            fixture.copy_resources(&testlib::test_resources().join("filesys"));
+            let weather_admin = crate::admin::create_weather_admin(&fixture.to_string()).unwrap();
+            weather_admin.init(crate::admin::admin_entities::DbMode::Hybrid, false, true, 1).unwrap();
+            let histories = |alias: &str, days: &[u32]| {
+                let histories = days
+                    .iter()
+                    .map(|day| History {
+                        alias: alias.to_string(),
+                        date: NaiveDate::from_ymd_opt(2024, 1, *day).unwrap(),
+                        ..Default::default()
+                    })
+                    .collect::<Vec<History>>();
+                (alias.to_string(), histories)
+            };
+            let data_api = || data_api(None, Some(PathBuf::from(&fixture)), false, false).unwrap();
+            let north = data_api().count_histories("north").unwrap();
+            let south = data_api().count_histories("south").unwrap();
+            let batch = HashMap::from([histories("north", &[1, 2]), histories("south", &[1])]);
+            let testcase = weather_admin.add_histories(batch).unwrap();
+            assert_eq!(testcase.locations.len(), 2);
+            assert_eq!((testcase.added(), testcase.skipped(), testcase.failed()), (3, 0, 0));
+            // the database is updated along with the archives
+            assert_eq!(data_api().count_histories("north").unwrap(), north + 2);
+            assert_eq!(data_api().count_histories("south").unwrap(), south + 1);
+            // existing histories are skipped and a failed location does not stop the batch
+            let batch = HashMap::from([histories("north", &[2, 3]), histories("missing", &[1])]);
+            let testcase = weather_admin.add_histories(batch).unwrap();
+            assert_eq!((testcase.added(), testcase.skipped(), testcase.failed()), (1, 1, 1));
+            assert_eq!(testcase.locations[0].alias, "missing");
+            assert!(testcase.locations[0].error.is_some());
+            assert_eq!(testcase.locations[1].alias, "north");
+            assert_eq!((testcase.locations[1].added, testcase.locations[1].skipped), (1, 1));
+            assert_eq!(data_api().count_histories("north").unwrap(), north + 3);
         }
 
-        #[derive(Debug, Serialize, Deserialize)]
-        /// The *DarkSky* daily weather history data.
-        struct DarkskyDaily {
-            data: Vec<DailyMd>,
+        #[test]
+        fn rename_admin_rollback() {
+            let fixture = testlib::TestFixture::create();
+            fixture.copy_resources(&testlib::test_resources().join("filesys"));
+            // an empty database does not have a locations table so the database rename will fail
+            std::fs::File::create(PathBuf::from(&fixture).join(crate::backend::db::DB_FILENAME)).unwrap();
+            let weather_admin = crate::admin::create_weather_admin(&fixture.to_string()).unwrap();
+            assert!(weather_admin.rename_location("north", "Renamed City", "renamed").is_err());
+            let data_api = data_api(None, Some(PathBuf::from(&fixture)), true, false).unwrap();
+            let location = data_api.get_location_by_id("north").unwrap();
+            assert_eq!(location.name, "Northern City");
+            assert!(data_api.get_location_by_id("renamed").is_err());
+            assert!(data_api.count_histories("north").unwrap() > 0);
         }
 
-        #[allow(non_snake_case)]
-        #[derive(Debug, Serialize, Deserialize)]
-        /// The *DarkSky* daily weather metadata.
-        struct DailyMd {
-            apparentTemperatureHigh: Option<f64>,
-            apparentTemperatureHighTime: Option<i64>,
-            apparentTemperatureLow: Option<f64>,
-            apparentTemperatureLowTime: Option<i64>,
-            apparentTemperatureMax: Option<f64>,
-            apparentTemperatureMaxTime: Option<i64>,
-            apparentTemperatureMin: Option<f64>,
-            apparentTemperatureMinTime: Option<i64>,
-            cloudCover: Option<f64>,
-            dewPoint: Option<f64>,
-            humidity: Option<f64>,
-            icon: Option<String>,
-            moonPhase: Option<f64>,
-            precipIntensity: Option<f64>,
-            precipIntensityMax: Option<f64>,
-            precipIntensityMaxTime: Option<f64>,
-            precipProbability: Option<f64>,
-            precipType: Option<String>,
-            pressure: Option<f64>,
-            summary: Option<String>,
-            sunriseTime: Option<i64>,
-            sunsetTime: Option<i64>,
-            temperatureHigh: Option<f64>,
-            temperatureHighTime: Option<i64>,
-            temperatureLow: Option<f64>,
-            temperatureLowTime: Option<i64>,
-            temperatureMax: Option<f64>,
-            temperatureMaxTime: Option<i64>,
-            temperatureMin: Option<f64>,
-            temperatureMinTime: Option<i64>,
-            time: Option<i64>,
-            uvIndex: Option<i64>,
-            uvIndexTime: Option<i64>,
-            visibility: Option<f64>,
-            windBearing: Option<i64>,
-            windGust: Option<f64>,
-            windGustTime: Option<i64>,
-            windSpeed: Option<f64>,
+        #[test]
+        fn read_only_admin() {
+            let fixture = testlib::TestFixture::create();
+            fixture.copy_resources(&testlib::test_resources().join("filesys"));
+            let criteria = || DataCriteria { filters: vec![], icase: false, sort: false, alias_only: false };
+            let weather_admin = crate::admin::create_weather_admin(&fixture.to_string()).unwrap().with_read_only(true);
+            let error = weather_admin.compact(criteria()).unwrap_err();
+            assert!(matches!(error.kind(), crate::ErrorKind::ReadOnly));
+            assert!(weather_admin.normalize(criteria()).is_err());
+            assert!(weather_admin.reload(criteria()).is_err());
+            assert!(weather_admin.drop(false).is_err());
+            let into = PathBuf::from(&fixture).join("migrated");
+            assert!(weather_admin.migrate(into.clone(), true, false, criteria()).is_err());
+            assert!(!into.exists());
+            // weather data can still be read
+            assert!(weather_admin.scan_integrity().is_ok());
+            let weather_admin = weather_admin.with_read_only(false);
+            assert!(weather_admin.compact(criteria()).is_ok());
         }
     }
 }
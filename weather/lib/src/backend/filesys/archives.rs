@@ -10,10 +10,13 @@ mod v2 {
     //! The implementation does not manage multi-client file access. That concern is left
     //! to the consumer of the module.
     use super::*;
-    use crate::prelude::{DateRange, DateRanges, History, HistorySummary};
+    use crate::{
+        admin::admin_entities::{CompactionReport, IntegrityIssue, NormalizeReport},
+        prelude::{DateRange, DateRanges, History, HistoryFailures, HistorySummary},
+    };
     use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
     use std::{
-        fs::{self, File, OpenOptions},
+        fs::{self, File},
         io::{BufReader, Read, Write},
     };
     use toolslib::{fmt::commafy, stopwatch::StopWatch};
@@ -21,8 +24,8 @@ mod v2 {
 
     /// The [WeatherArchive] error builder.
     macro_rules! archive_err {
-        ($id:expr, $reason:expr) => {
-            Error::from(format!("WeatherArchive ({}): {}", $id, $reason))
+        ($kind:ident, $id:expr, $reason:expr) => {
+            Error::new(ErrorKind::$kind, format!("WeatherArchive ({}): {}", $id, $reason))
         };
     }
 
@@ -39,9 +42,13 @@ mod v2 {
     }
 
     /// The definition of the `ZipArchive` reader.
-    type ZipArchiveReader = ZipArchive<BufReader<File>>;
+    type ZipArchiveReader = ZipArchive<BufReader<FileHandle>>;
 
     /// The public view of a weather archive file.
+    ///
+    /// The weather history does not keep an archive reader between calls. Each query opens its own reader
+    /// and there is no shared mutable state so the weather history is `Send + Sync`. A single instance can
+    /// be shared across threads (for instance using an `Arc`) and queried concurrently.
     #[derive(Debug)]
     pub struct WeatherHistory(
         /// The managed weather archive.
@@ -77,6 +84,14 @@ mod v2 {
                 compressed_size: Some(compressed_size as usize),
             })
         }
+        /// Get the raw and compressed size of each weather history in the archive.
+        ///
+        /// The sizes are returned in date order as `(date, raw size, compressed size)`.
+        pub fn entry_sizes(&self) -> Result<Vec<(NaiveDate, u64, u64)>> {
+            let iter = self.0.iter_date_range(None, true, ArchiveMd::new)?;
+            let entry_sizes = iter.map(|md| (md.date, md.size, md.compressed_size)).collect();
+            Ok(entry_sizes)
+        }
         /// Get the weather history dates that are available.
         pub fn dates(&self) -> Result<DateRanges> {
             let stopwatch = StopWatch::start_new();
@@ -86,6 +101,40 @@ mod v2 {
             let date_ranges = DateRange::from_dates(dates);
             Ok(DateRanges { location_id: self.0.alias.to_string(), date_ranges })
         }
+        /// Check if the archive has weather history for a date without reading the history.
+        ///
+        /// # Arguments
+        ///
+        /// * `date` is the weather history date.
+        pub fn contains(&self, date: &NaiveDate) -> Result<bool> {
+            let inner = self.0.file.reader()?;
+            match ZipArchive::new(BufReader::new(inner)) {
+                Ok(mut reader) => match reader.by_name(&WeatherArchive::date_to_filename(&self.0.alias, date)) {
+                    Ok(_) => Ok(true),
+                    Err(err) => match ziperror_descr(err) {
+                        None => Ok(false),
+                        Some(descr) => {
+                            let reason = format!("ZipArchive::by_name() error ({}).", descr);
+                            Err(archive_err!(Corrupt, &self.0.alias, reason))
+                        }
+                    },
+                },
+                Err(err) => Err(archive_err!(Corrupt, &self.0.alias, &format!("contains reader error ({}).", &err))),
+            }
+        }
+        /// Count the weather histories without reading them.
+        ///
+        /// Only archive filenames that contain a valid history date are counted.
+        pub fn count(&self) -> Result<usize> {
+            let inner = self.0.file.reader()?;
+            match ZipArchive::new(BufReader::new(inner)) {
+                Ok(reader) => {
+                    let is_history = |name: &&str| WeatherArchive::filename_to_date(name).is_ok();
+                    Ok(reader.file_names().filter(is_history).count())
+                }
+                Err(err) => Err(archive_err!(Corrupt, &self.0.alias, &format!("count reader error ({}).", &err))),
+            }
+        }
         /// Get an iterator of daily weather history for a location.
         ///
         /// # Arguments
@@ -97,6 +146,67 @@ mod v2 {
             let histories = iter.collect();
             Ok(histories)
         }
+        /// Get the daily weather history for a location along with the dates that could not be decoded.
+        ///
+        /// Unlike [daily_histories](Self::daily_histories) a history that fails to decode is not silently
+        /// dropped, its date and the reason it failed are returned alongside the decoded histories.
+        ///
+        /// # Arguments
+        ///
+        /// * `filter` restricts the range of the historical weather data.
+        ///
+        pub fn daily_histories_with_failures(&self, filter: &DateRange) -> Result<(Vec<History>, HistoryFailures)> {
+            let iter = self.0.iter_date_range(Some(filter), true, history_result)?;
+            let mut histories = vec![];
+            let mut failures = vec![];
+            for (date, result) in iter {
+                match result {
+                    Ok(history) => histories.push(history),
+                    Err(err) => failures.push((date, err.to_string())),
+                }
+            }
+            Ok((histories, failures))
+        }
+        /// Get the weather history for a single date.
+        ///
+        /// `None` is returned if the archive does not have history for the date.
+        ///
+        /// # Arguments
+        ///
+        /// * `date` is the weather history date.
+        ///
+        pub fn history(&self, date: NaiveDate) -> Result<Option<History>> {
+            let mut iter = self.0.iter_dates(vec![date], history_decoder)?;
+            Ok(iter.next())
+        }
+    }
+
+    /// How weather history is compressed when it is written to an archive.
+    ///
+    /// The compression level is optional and when `None` the default level of the method will be used.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Compression {
+        /// The history is stored without compression.
+        #[allow(unused)]
+        Stored,
+        /// The history is compressed using `Deflate` (levels 0-9).
+        Deflated(Option<i32>),
+    }
+    impl Default for Compression {
+        /// Weather history has always been written using `Deflate` and the default level.
+        fn default() -> Self {
+            Compression::Deflated(None)
+        }
+    }
+    impl Compression {
+        /// Get the zip file options for the compression.
+        fn file_options(&self) -> FileOptions {
+            let (method, level) = match *self {
+                Compression::Stored => (zip::CompressionMethod::Stored, None),
+                Compression::Deflated(level) => (zip::CompressionMethod::Deflated, level),
+            };
+            FileOptions::default().compression_method(method).compression_level(level)
+        }
     }
 
     /// The weather archive file updater.
@@ -104,6 +214,8 @@ mod v2 {
     pub struct WeatherHistoryUpdate(
         /// The weather archive that will be updated.
         WeatherArchive,
+        /// How added histories will be compressed.
+        Compression,
     );
     impl WeatherHistoryUpdate {
         /// Create a new instance of the weather history updater.
@@ -114,14 +226,32 @@ mod v2 {
         /// * `file` is the weather archive file.
         pub fn new(alias: &str, file: WeatherFile) -> Result<Self> {
             let archive = WeatherArchive::open(alias, file)?;
-            Ok(Self(archive))
+            Ok(Self(archive, Compression::default()))
+        }
+        /// Set how added histories will be compressed.
+        ///
+        /// # Arguments
+        ///
+        /// * `compression` is the compression method and level.
+        #[allow(unused)]
+        pub fn with_compression(mut self, compression: Compression) -> Self {
+            self.1 = compression;
+            self
         }
         /// Add histories to the weather archive that don't already exist.
         ///
         /// # Arguments
         ///
         /// * `histories` are the histories that will be added.
-        pub fn add(&mut self, histories: &Vec<History>) -> Result<Vec<NaiveDate>> {
+        /// * `progress` is called with the count of histories written and the count being added.
+        pub fn add(
+            &mut self,
+            histories: &Vec<History>,
+            progress: &mut dyn FnMut(usize, usize),
+        ) -> Result<Vec<NaiveDate>> {
+            if self.0.file.is_read_only() {
+                return Err(archive_err!(ReadOnly, &self.0.alias, "Opened read-only..."));
+            }
             // find histories dates that already exist
             let mut stopwatch = StopWatch::start_new();
             let mut already_exists: Vec<NaiveDate> = Vec::with_capacity(histories.len());
@@ -147,8 +277,8 @@ mod v2 {
             stopwatch.start();
             let dates_added: Vec<NaiveDate> = okay_to_add.iter().map(|h| h.date).collect();
             if !okay_to_add.is_empty() {
-                let mut writer = self.0.archive_writer();
-                writer.write(okay_to_add)?;
+                let mut writer = self.0.archive_writer().with_compression(self.1);
+                writer.write_with_progress(okay_to_add, progress)?;
             }
             stopwatch.stop();
             if !already_exists.is_empty() {
@@ -181,14 +311,14 @@ mod v2 {
             let stopwatch = StopWatch::start_new();
             file.refresh();
             let result = if !file.exists() {
-                Err(archive_err!(alias, format!("'{}' does not exist...", &file)))
+                Err(archive_err!(NotFound, alias, format!("'{}' does not exist...", &file)))
             } else {
                 // use a buffer reader here otherwise opening the archive is really slow....
                 let reader = BufReader::new(file.reader()?);
                 match ZipArchive::new(reader) {
                     // unfortunately you have to drop the zip archive which makes open/create expensive
                     Ok(_) => Ok(Self { alias: alias.to_string(), file }),
-                    Err(error) => Err(archive_err!(alias, &error)),
+                    Err(error) => Err(archive_err!(Corrupt, alias, &error)),
                 }
             };
             log::trace!("WeatherArchive: open {} {}us", alias, commafy(stopwatch.elapsed().as_micros()));
@@ -204,18 +334,24 @@ mod v2 {
         /// * `file` is the container of weather data.
         pub fn create(alias: &str, mut file: WeatherFile) -> Result<Self> {
             file.refresh();
-            if file.exists() {
-                Err(archive_err!(&alias, format!("'{}' already exists...", &file)))
+            if file.is_read_only() {
+                Err(archive_err!(ReadOnly, &alias, format!("'{}' opened read-only...", &file)))
+            } else if file.exists() {
+                Err(archive_err!(AlreadyExists, &alias, format!("'{}' already exists...", &file)))
             } else {
                 // touch the file so the writer can be returned.
-                if let Err(err) = OpenOptions::new().create(true).write(true).open(&file.to_string()) {
-                    Err(archive_err!(alias, &format!("Error creating archive file ({}), {}", &file, &err)))
+                if let Err(err) = file.touch() {
+                    Err(archive_err!(Io, alias, &format!("Error creating archive file ({}), {}", &file, &err)))
                 } else {
                     let writer = file.writer()?;
                     let mut archive = ZipWriter::new(writer);
                     match archive.finish() {
-                        Ok(_) => Self::open(alias, file),
-                        Err(err) => Err(archive_err!(alias, &err)),
+                        Ok(writer) => {
+                            // make sure the empty archive has been saved before opening it
+                            drop(writer);
+                            Self::open(alias, file)
+                        }
+                        Err(err) => Err(archive_err!(Io, alias, &err)),
                     }
                 }
             }
@@ -244,7 +380,7 @@ mod v2 {
                     }
                     Ok(ArchiveIter::new(&self.alias, reader, history_dates, builder))
                 }
-                Err(err) => Err(archive_err!(&self.alias, &format!("get_reader error ({}).", &err))),
+                Err(err) => Err(archive_err!(Corrupt, &self.alias, &format!("get_reader error ({}).", &err))),
             }
         }
 
@@ -267,7 +403,7 @@ mod v2 {
                                 Err(err) => {
                                     if let Some(descr) = ziperror_descr(err) {
                                         let reason = format!("ZipArchive::by_name() error ({}).", descr);
-                                        log::error!("{}", archive_err!(self.alias, reason));
+                                        log::error!("{}", archive_err!(Corrupt, self.alias, reason));
                                     }
                                     None
                                 }
@@ -278,7 +414,7 @@ mod v2 {
                 }
                 Err(err) => {
                     let reason = format!("ZipArchive error ({})", err);
-                    Err(archive_err!(&self.alias, reason))
+                    Err(archive_err!(Corrupt, &self.alias, reason))
                 }
             }
         }
@@ -286,10 +422,212 @@ mod v2 {
         pub fn archive_writer(&mut self) -> ArchiveWriter {
             ArchiveWriter::new(self)
         }
+        /// Rewrite the weather history in the archive using the maximum compression level.
+        ///
+        /// The compacted archive replaces the original archive the same way history is added to
+        /// the archive. The last modified time of each history is preserved.
+        pub fn compact(&mut self) -> Result<CompactionReport> {
+            let stopwatch = StopWatch::start_new();
+            self.file.refresh();
+            let before = self.file.size();
+            let mut reader = self.zip_reader("compact")?;
+            let mut history_dates = self.filter_history(&mut reader, None);
+            history_dates.sort();
+            self.rewrite(|archive_writer, zip_writer| {
+                let alias = &archive_writer.archive.alias;
+                for date in &history_dates {
+                    let history_name = Self::date_to_filename(alias, date);
+                    let (mtime, data) = match reader.by_name(&history_name) {
+                        Ok(mut zipfile) => {
+                            let mut data: Vec<u8> = Vec::with_capacity(zipfile.size() as usize);
+                            match zipfile.read_to_end(&mut data) {
+                                Ok(_) => (zipfile.last_modified(), data),
+                                Err(err) => {
+                                    let reason = format!("error reading {} history ({})", date, err);
+                                    Err(archive_err!(Io, alias, reason))?
+                                }
+                            }
+                        }
+                        Err(err) => {
+                            let reason = format!("ZipArchive::by_name() ({}).", err);
+                            Err(archive_err!(Corrupt, alias, reason))?
+                        }
+                    };
+                    let compression = Compression::Deflated(Some(ArchiveWriter::MAX_COMPRESSION_LEVEL));
+                    let options = compression.file_options().last_modified_time(mtime);
+                    archive_writer.write_file(zip_writer, date, &data[..], options)?;
+                }
+                Ok(())
+            })?;
+            let after = self.file.size();
+            log::debug!("WeatherArchive: compact {} {}", self.alias, stopwatch);
+            Ok(CompactionReport { alias: self.alias.clone(), histories: history_dates.len(), before, after })
+        }
+        /// Rewrite the archive so weather histories are stored in ascending date order.
+        ///
+        /// Histories are copied into the replacement archive as is. The archive is only rewritten if
+        /// the histories are not already in date order so normalizing an archive again does nothing.
+        /// Files whose name is not a history date are logged and kept after the histories.
+        pub fn normalize(&mut self) -> Result<NormalizeReport> {
+            if self.file.is_read_only() {
+                return Err(archive_err!(ReadOnly, &self.alias, "Opened read-only..."));
+            }
+            let mut reader = self.zip_reader("normalize")?;
+            let mut filenames: Vec<(Option<NaiveDate>, String)> = Vec::with_capacity(reader.len());
+            for index in 0..reader.len() {
+                let filename = match reader.by_index_raw(index) {
+                    Ok(zipfile) => zipfile.name().to_string(),
+                    Err(err) => {
+                        let reason = format!("normalize ZipArchive::by_index_raw() ({}).", err);
+                        return Err(archive_err!(Corrupt, &self.alias, reason));
+                    }
+                };
+                match Self::filename_to_date(&filename) {
+                    Ok(date) => filenames.push((Some(date), filename)),
+                    Err(err) => {
+                        let reason = format!("normalize keeping '{}' ({}).", filename, err);
+                        log::warn!("{}", archive_err!(Decode, self.alias, reason));
+                        filenames.push((None, filename));
+                    }
+                }
+            }
+            let mut sorted_filenames = filenames.clone();
+            sorted_filenames.sort_by_key(|(date, _)| (date.is_none(), *date));
+            let rewritten = filenames != sorted_filenames;
+            if rewritten {
+                let stopwatch = StopWatch::start_new();
+                self.rewrite(|archive_writer, zip_writer| {
+                    let alias = &archive_writer.archive.alias;
+                    for (_, filename) in &sorted_filenames {
+                        match reader.by_name(filename) {
+                            Ok(zipfile) => {
+                                if let Err(err) = zip_writer.raw_copy_file(zipfile) {
+                                    let reason = format!("error copying {} ({})", filename, err);
+                                    return Err(archive_err!(Io, alias, reason));
+                                }
+                            }
+                            Err(err) => {
+                                let reason = format!("ZipArchive::by_name() ({}).", err);
+                                return Err(archive_err!(Corrupt, alias, reason));
+                            }
+                        }
+                    }
+                    Ok(())
+                })?;
+                log::debug!("WeatherArchive: normalize {} {}", self.alias, stopwatch);
+            }
+            let histories = sorted_filenames.iter().filter(|(date, _)| date.is_some()).count();
+            Ok(NormalizeReport { alias: self.alias.clone(), histories, rewritten })
+        }
+        /// Open the archive for reading.
+        ///
+        /// # Arguments
+        ///
+        /// * `what` describes why the archive is being read.
+        fn zip_reader(&self, what: &str) -> Result<ZipArchiveReader> {
+            let inner = self.file.reader()?;
+            match ZipArchive::new(BufReader::new(inner)) {
+                Ok(reader) => Ok(reader),
+                Err(err) => Err(archive_err!(Corrupt, &self.alias, &format!("{} reader error ({}).", what, &err))),
+            }
+        }
+        /// Replace the archive with a new archive.
+        ///
+        /// The new archive is written alongside the archive and then replaces it the same way history
+        /// is added to the archive, restoring the original if it cannot be replaced.
+        ///
+        /// # Arguments
+        ///
+        /// * `write` adds the content of the new archive.
+        fn rewrite<F>(&mut self, write: F) -> Result<()>
+        where
+            F: FnOnce(&ArchiveWriter, &mut ZipWriter<FileHandle>) -> Result<()>,
+        {
+            let archive_writer = ArchiveWriter::new(self);
+            let mut zip_writer = archive_writer.create()?;
+            write(&archive_writer, &mut zip_writer)?;
+            archive_writer.close(zip_writer)?;
+            drop(archive_writer);
+            self.file.refresh();
+            Ok(())
+        }
+        /// Copy the weather histories into a new archive for a different location alias.
+        ///
+        /// History filenames include the location alias so each history is copied as is into the new
+        /// archive under a filename that uses the new alias. This archive is not changed.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier of the new archive.
+        /// * `file` is the new archive, it must not already exist.
+        pub fn copy_as(&self, alias: &str, file: WeatherFile) -> Result<WeatherArchive> {
+            let inner = self.file.reader()?;
+            let mut reader = match ZipArchive::new(BufReader::new(inner)) {
+                Ok(reader) => reader,
+                Err(err) => Err(archive_err!(Corrupt, &self.alias, &format!("copy reader error ({}).", &err)))?,
+            };
+            let history_dates = self.filter_history(&mut reader, None);
+            let stopwatch = StopWatch::start_new();
+            let mut target = Self::create(alias, file)?;
+            let archive_writer = ArchiveWriter::new(&target);
+            let mut zip_writer = archive_writer.create()?;
+            for date in &history_dates {
+                let history_name = Self::date_to_filename(&self.alias, date);
+                match reader.by_name(&history_name) {
+                    Ok(zipfile) => {
+                        let copy_name = Self::date_to_filename(alias, date);
+                        if let Err(err) = zip_writer.raw_copy_file_rename(zipfile, copy_name) {
+                            let reason = format!("error copying {} history ({})", date, err);
+                            return Err(archive_err!(Io, alias, reason));
+                        }
+                    }
+                    Err(err) => {
+                        let reason = format!("ZipArchive::by_name() ({}).", err);
+                        return Err(archive_err!(Corrupt, &self.alias, reason));
+                    }
+                }
+            }
+            archive_writer.close(zip_writer)?;
+            drop(archive_writer);
+            target.file.refresh();
+            log::debug!("WeatherArchive: copy {} to {} {}", self.alias, alias, stopwatch);
+            Ok(target)
+        }
+        /// Verify each history in the archive can be decoded and matches the date of its filename.
+        ///
+        /// Problems with histories are collected, an error is returned only if the archive cannot be read.
+        pub fn integrity_issues(&self) -> Result<Vec<IntegrityIssue>> {
+            let inner = self.file.reader()?;
+            let mut reader = match ZipArchive::new(BufReader::new(inner)) {
+                Ok(reader) => reader,
+                Err(err) => Err(archive_err!(Corrupt, &self.alias, &format!("integrity reader error ({}).", &err)))?,
+            };
+            let history_names: Vec<String> = reader.file_names().map(|name| name.to_string()).collect();
+            let mut issues = vec![];
+            for history_name in history_names {
+                let reason = match Self::filename_to_date(&history_name) {
+                    Err(err) => Some(err.to_string()),
+                    Ok(date) => match reader.by_name(&history_name) {
+                        Err(err) => Some(format!("ZipArchive::by_name() ({}).", err)),
+                        Ok(zipfile) => match history_decoder(&self.alias, &date, zipfile) {
+                            Ok(history) if history.date != date => {
+                                Some(format!("history date {} does not match {}.", history.date, date))
+                            }
+                            Ok(_) => None,
+                            Err(err) => Some(err.to_string()),
+                        },
+                    },
+                };
+                if let Some(reason) = reason {
+                    issues.push(IntegrityIssue { alias: self.alias.clone(), entry: Some(history_name), reason });
+                }
+            }
+            Ok(issues)
+        }
         /// Get the weather history dates in the archive.
         ///
         /// If `filter` is provided, only dates covered by the history range are returned.
-        /// The returned date collection is not guaranteed to be ordered.
+        /// The returned date collection is in the order histories are stored in the archive.
         ///
         /// # Arguments
         ///
@@ -297,13 +635,19 @@ mod v2 {
         /// * `filter` is used to restrict the dates that will be returned.
         fn filter_history(&self, reader: &mut ZipArchiveReader, filter: Option<&DateRange>) -> Vec<NaiveDate> {
             let stopwatch = StopWatch::start_new();
-            let dates = reader
-                .file_names()
-                .filter_map(|filename| match WeatherArchive::filename_to_date(filename) {
-                    Ok(date) => Some(date),
+            let dates = (0..reader.len())
+                .filter_map(|index| match reader.by_index_raw(index) {
+                    Ok(zipfile) => match WeatherArchive::filename_to_date(zipfile.name()) {
+                        Ok(date) => Some(date),
+                        Err(err) => {
+                            let reason = format!("filter_history ({}).", err);
+                            log::error!("{}", archive_err!(Decode, self.alias, reason));
+                            None
+                        }
+                    },
                     Err(err) => {
-                        let reason = format!("filter_history ({}).", err);
-                        log::error!("{}", archive_err!(self.alias, reason));
+                        let reason = format!("filter_history ZipArchive::by_index_raw() ({}).", err);
+                        log::error!("{}", archive_err!(Corrupt, self.alias, reason));
                         None
                     }
                 })
@@ -334,19 +678,19 @@ mod v2 {
         fn filename_to_date(history_name: &str) -> Result<NaiveDate> {
             let ymd_offset = "yyyymmdd.json".len();
             if ymd_offset > history_name.len() {
-                Err(Error::from(format!("malformed history name ({}).", history_name)))
+                Err(Error::new(ErrorKind::Decode, format!("malformed history name ({}).", history_name)))
             } else {
                 let ymd_index = history_name.len() - ymd_offset;
                 let ymd: &str = &history_name[ymd_index..ymd_index + 8];
                 if !ymd.chars().all(char::is_numeric) {
-                    Err(Error::from(format!("history date not found ({}).", history_name)))
+                    Err(Error::new(ErrorKind::Decode, format!("history date not found ({}).", history_name)))
                 } else {
                     let year = ymd[..4].parse().unwrap();
                     let month = ymd[4..6].parse().unwrap();
                     let day = ymd[6..].parse().unwrap();
                     match NaiveDate::from_ymd_opt(year, month, day) {
                         Some(date) => Ok(date),
-                        None => Err(Error::from(format!("illegal history date ({}).", history_name))),
+                        None => Err(Error::new(ErrorKind::Decode, format!("illegal history date ({}).", history_name))),
                     }
                 }
             }
@@ -411,12 +755,12 @@ mod v2 {
                         datetime.and_utc().timestamp_millis()
                     } else {
                         let reason = format!("NaiveTime error for HHMMSS ({:02}{:02}{:02})", hour, minute, second);
-                        log::error!("{}", &archive_err!(alias, reason));
+                        log::error!("{}", &archive_err!(Decode, alias, reason));
                         0
                     }
                 } else {
                     let reason = format!("NaiveDate error for YYYYMMDD ({:04}{:02}{:02})", year, month, day);
-                    log::error!("{}", &archive_err!(alias, reason));
+                    log::error!("{}", &archive_err!(Decode, alias, reason));
                     0
                 }
             }
@@ -440,11 +784,22 @@ mod v2 {
             Ok(_) => history::from_bytes(alias, &data),
             Err(err) => {
                 let reason = format!("error reading {} history ({})", date, err);
-                Err(archive_err!(alias, reason))
+                Err(archive_err!(Io, alias, reason))
             }
         }
     }
 
+    /// The [HistoryBuilder] used to get [History] from the archive that keeps decode failures.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` is the location alias name.
+    /// * `date` is the weather history date.
+    /// * `zipfile` is the archive weather history file.
+    pub fn history_result(alias: &str, date: &NaiveDate, zipfile: ZipFile) -> Result<(NaiveDate, Result<History>)> {
+        Ok((*date, history_decoder(alias, date, zipfile)))
+    }
+
     /// The [HistoryBuilder] used to collect history from the archive.
     ///
     /// # Arguments
@@ -463,7 +818,7 @@ mod v2 {
             }
             Err(err) => {
                 let reason = format!("error reading {} history ({})", date, err);
-                Err(archive_err!(alias, reason))
+                Err(archive_err!(Io, alias, reason))
             }
         }
     }
@@ -510,13 +865,13 @@ mod v2 {
                         }
                         Err(err) => {
                             let reason = format!("HistoryBuilder ({}).", err);
-                            log::error!("{}", archive_err!(self.alias, reason));
+                            log::error!("{}", archive_err!(Decode, self.alias, reason));
                         }
                     },
                     Err(err) => {
                         if let Some(descr) = ziperror_descr(err) {
                             let reason = format!("ZipArchive::by_name() ({}).", descr);
-                            log::error!("{}", archive_err!(self.alias, reason));
+                            log::error!("{}", archive_err!(Corrupt, self.alias, reason));
                         }
                     }
                 }
@@ -532,12 +887,16 @@ mod v2 {
         archive: &'a WeatherArchive,
         /// The pathname of the archive that will actually have data added to it.
         writable: PathBuf,
+        /// How histories will be compressed.
+        compression: Compression,
     }
     impl<'a> ArchiveWriter<'a> {
         /// The extension that identifies a writable archive.
         const UPDATE_EXT: &'static str = "upd";
         /// The extension that identifies an archive backup.
         const BACKUP_EXT: &'static str = "bu";
+        /// The maximum compression level used by the `Deflated` compression method.
+        const MAX_COMPRESSION_LEVEL: i32 = 9;
         /// Create a new instance of the archive writer.
         ///
         /// # Arguments
@@ -545,7 +904,16 @@ mod v2 {
         /// `archive` is what will be updated with new history.
         fn new(archive: &'a WeatherArchive) -> Self {
             let writable = archive.file.path().with_extension(Self::UPDATE_EXT);
-            Self { archive, writable }
+            Self { archive, writable, compression: Compression::default() }
+        }
+        /// Set how histories will be compressed.
+        ///
+        /// # Arguments
+        ///
+        /// * `compression` is the compression method and level.
+        pub fn with_compression(mut self, compression: Compression) -> Self {
+            self.compression = compression;
+            self
         }
         /// Adds history to the archive.
         ///
@@ -553,10 +921,25 @@ mod v2 {
         ///
         /// `histories` is what will be added to the archive.
         pub fn write(&mut self, histories: Vec<&History>) -> Result<()> {
+            self.write_with_progress(histories, &mut |_, _| ())
+        }
+        /// Write histories into the archive reporting progress as each history is written.
+        ///
+        /// # Arguments
+        ///
+        /// * `histories` are the histories that will be written.
+        /// * `progress` is called with the count of histories written and the total count.
+        pub fn write_with_progress(
+            &mut self,
+            histories: Vec<&History>,
+            progress: &mut dyn FnMut(usize, usize),
+        ) -> Result<()> {
             let mut writer = self.open()?;
-            for history in histories {
+            let total = histories.len();
+            for (index, history) in histories.into_iter().enumerate() {
                 let data = history::to_bytes(history)?;
                 self.write_history(&mut writer, &history.date, &data[..])?;
+                progress(index + 1, total);
             }
             self.close(writer)
         }
@@ -567,7 +950,7 @@ mod v2 {
         /// * `writer` will be used to add the history.
         /// * `date` is the data associated with the history.
         /// * `data` is the history serialized into a sequence of bytes.
-        fn write_history(&self, writer: &mut ZipWriter<File>, date: &NaiveDate, data: &[u8]) -> Result<()> {
+        fn write_history(&self, writer: &mut ZipWriter<FileHandle>, date: &NaiveDate, data: &[u8]) -> Result<()> {
             let now = Utc::now().naive_utc();
             let mtime = DateTime::from_date_and_time(
                 now.year() as u16,
@@ -578,15 +961,31 @@ mod v2 {
                 now.second() as u8,
             )
             .unwrap();
+            let options = self.compression.file_options().last_modified_time(mtime);
+            self.write_file(writer, date, data, options)
+        }
+        /// Writes a history file into the archive.
+        ///
+        /// # Arguments
+        ///
+        /// * `writer` will be used to add the history.
+        /// * `date` is the data associated with the history.
+        /// * `data` is the history serialized into a sequence of bytes.
+        /// * `options` controls how the history file will be written.
+        fn write_file(
+            &self,
+            writer: &mut ZipWriter<FileHandle>,
+            date: &NaiveDate,
+            data: &[u8],
+            options: FileOptions,
+        ) -> Result<()> {
             let filename = WeatherArchive::date_to_filename(&self.archive.alias, date);
-            let options =
-                FileOptions::default().compression_method(zip::CompressionMethod::Deflated).last_modified_time(mtime);
             if let Err(err) = writer.start_file(filename, options) {
                 let reason = format!("{} start_file error ({}).", date, &err);
-                Err(archive_err!(&self.archive.alias, reason))
+                Err(archive_err!(Io, &self.archive.alias, reason))
             } else if let Err(err) = writer.write_all(data) {
                 let reason = format!("{} write_all err ({}).", date, &err);
-                Err(archive_err!(&self.archive.alias, reason))
+                Err(archive_err!(Io, &self.archive.alias, reason))
             } else {
                 Ok(())
             }
@@ -595,19 +994,44 @@ mod v2 {
         ///
         /// In order to add data the archive is first copied to the writable path. When done adding history the
         /// archive will be restored when the [ZipWriter] is closed.
-        fn open(&self) -> Result<ZipWriter<File>> {
-            self.copy(self.archive.file.path(), &self.writable)?;
-            match File::options().read(true).write(true).open(&self.writable) {
+        fn open(&self) -> Result<ZipWriter<FileHandle>> {
+            let writable = if self.archive.file.is_in_memory() {
+                let mut content = vec![];
+                match self.archive.file.reader()?.read_to_end(&mut content) {
+                    Ok(_) => Ok(FileHandle::detached(content)),
+                    Err(err) => Err(err),
+                }
+            } else {
+                self.copy(self.archive.file.path(), &self.writable)?;
+                File::options().read(true).write(true).open(&self.writable).map(FileHandle::Disk)
+            };
+            match writable {
                 Ok(file) => match ZipWriter::new_append(file) {
                     Ok(zip_writer) => Ok(zip_writer),
                     Err(err) => {
                         let reason = format!("'{}' zip writer error ({}).", self.archive.file.filename, err);
-                        Err(archive_err!(&self.archive.alias, reason))
+                        Err(archive_err!(Corrupt, &self.archive.alias, reason))
                     }
                 },
                 Err(err) => {
                     let reason = format!("error open writable archive ({}).", &err);
-                    Err(archive_err!(&self.archive.alias, reason))
+                    Err(archive_err!(Io, &self.archive.alias, reason))
+                }
+            }
+        }
+        /// Creates a [ZipWriter] for an empty archive that will replace the existing archive.
+        ///
+        /// The empty archive is created at the writable path. When done adding history the archive will be
+        /// replaced when the [ZipWriter] is closed.
+        fn create(&self) -> Result<ZipWriter<FileHandle>> {
+            if self.archive.file.is_in_memory() {
+                return Ok(ZipWriter::new(FileHandle::detached(vec![])));
+            }
+            match File::create(&self.writable) {
+                Ok(file) => Ok(ZipWriter::new(FileHandle::Disk(file))),
+                Err(err) => {
+                    let reason = format!("error creating writable archive ({}).", &err);
+                    Err(archive_err!(Io, &self.archive.alias, reason))
                 }
             }
         }
@@ -619,7 +1043,14 @@ mod v2 {
         /// # Arguments
         ///
         /// * `writer` is what was used to update the archive histories.
-        fn close(&self, writer: ZipWriter<File>) -> Result<()> {
+        fn close(&self, mut writer: ZipWriter<FileHandle>) -> Result<()> {
+            if self.archive.file.is_in_memory() {
+                // there isn't a backup, the content is simply replaced
+                return match writer.finish() {
+                    Ok(writable) => self.archive.file.replace_content(writable.into_content().unwrap_or_default()),
+                    Err(err) => Err(archive_err!(Io, &self.archive.alias, format!("error closing archive ({}).", err))),
+                };
+            }
             drop(writer);
             // try to safely replace the updated archive
             let backup = self.archive.file.path().with_extension(Self::BACKUP_EXT);
@@ -643,16 +1074,16 @@ mod v2 {
                             Ok(_) => log::info!("{}: original archive restored.", self.archive.alias),
                             Err(err) => {
                                 let reason = format!("could not restore backup ({}).", err);
-                                log::error!("{}", archive_err!(self.archive.alias, reason));
+                                log::error!("{}", archive_err!(Io, self.archive.alias, reason));
                             }
                         };
                         let reason = format!("error replacing updated archive ({})", err);
-                        Err(archive_err!(&self.archive.alias, reason))
+                        Err(archive_err!(Io, &self.archive.alias, reason))
                     }
                 },
                 Err(err) => {
                     let reason = format!("error creating archive backup {} ({})", backup.display(), err);
-                    Err(archive_err!(&self.archive.alias, reason))
+                    Err(archive_err!(Io, &self.archive.alias, reason))
                 }
             }
         }
@@ -669,7 +1100,7 @@ mod v2 {
                     let from_name = from.file_name().unwrap().to_str().unwrap();
                     let to_name = to.file_name().unwrap().to_str().unwrap();
                     let reason = format!("error copying {} to {} ({})", from_name, to_name, err);
-                    Err(archive_err!(&self.archive.alias, reason))
+                    Err(archive_err!(Io, &self.archive.alias, reason))
                 }
             }
         }
@@ -678,6 +1109,10 @@ mod v2 {
         /// If something bad happens adding history, this attempts to clean up files.rs that might be
         /// left hanging around.
         fn drop(&mut self) {
+            // there is nothing on the filesystem to clean up for archives in memory
+            if self.archive.file.is_in_memory() {
+                return;
+            }
             // do your best to clean up
             if self.writable.exists() {
                 match fs::remove_file(&self.writable) {
@@ -723,6 +1158,37 @@ mod v2 {
             assert!(WeatherArchive::create(alias, get_file!()).is_err());
         }
 
+        #[test]
+        fn error_kinds() {
+            let fixture = testlib::TestFixture::create();
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let alias = "kinds";
+            match WeatherArchive::open(alias, weather_dir.archive(alias)) {
+                Err(err) => assert_eq!(err.kind(), ErrorKind::NotFound),
+                Ok(_) => unreachable!("the archive does not exist"),
+            }
+            WeatherArchive::create(alias, weather_dir.archive(alias)).unwrap();
+            match WeatherArchive::create(alias, weather_dir.archive(alias)) {
+                Err(err) => {
+                    assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+                    // the error description is unchanged
+                    assert!(err.to_string().starts_with("WeatherArchive (kinds): "));
+                }
+                Ok(_) => unreachable!("the archive already exists"),
+            }
+            // an archive that is not a zip file is corrupt
+            let corrupt = "corrupt";
+            std::fs::write(weather_dir.archive(corrupt).path(), "not a zip file").unwrap();
+            match WeatherArchive::open(corrupt, weather_dir.archive(corrupt)) {
+                Err(err) => assert_eq!(err.kind(), ErrorKind::Corrupt),
+                Ok(_) => unreachable!("the archive is not valid"),
+            }
+            match WeatherArchive::filename_to_date("a0230705.json") {
+                Err(err) => assert_eq!(err.kind(), ErrorKind::Decode),
+                Ok(_) => unreachable!("the history name is not valid"),
+            }
+        }
+
         #[test]
         fn weather_data_iterator() {
             // don't copy files.rs use the test resources... just don't update files.rs!!!
@@ -775,6 +1241,108 @@ mod v2 {
             assert_eq!(testcase.compressed_size, Some(39510));
         }
 
+        #[test]
+        fn entry_sizes() {
+            let alias = "testmd";
+            let resources = testlib::test_resources().join("filesys");
+            let weather_dir = WeatherDir::new(resources).unwrap();
+            let weather_history = WeatherHistory::new(alias, weather_dir.archive(alias)).unwrap();
+            let summary = weather_history.summary().unwrap();
+            let testcase = weather_history.entry_sizes().unwrap();
+            assert_eq!(testcase.len(), summary.count);
+            assert!(testcase.windows(2).all(|pair| pair[0].0 < pair[1].0));
+            let raw_size: u64 = testcase.iter().map(|(_, size, _)| size).sum();
+            let compressed_size: u64 = testcase.iter().map(|(_, _, compressed_size)| compressed_size).sum();
+            assert_eq!(Some(raw_size as usize), summary.raw_size);
+            assert_eq!(Some(compressed_size as usize), summary.compressed_size);
+        }
+
+        #[test]
+        fn count() {
+            let alias = "testmd";
+            let resources = testlib::test_resources().join("filesys");
+            let weather_dir = WeatherDir::new(resources).unwrap();
+            let weather_history = WeatherHistory::new(alias, weather_dir.archive(alias)).unwrap();
+            assert_eq!(weather_history.count().unwrap(), 28);
+        }
+
+        #[test]
+        fn contains() {
+            let alias = "north";
+            let resources = testlib::test_resources().join("filesys");
+            let weather_dir = WeatherDir::new(resources).unwrap();
+            let weather_history = WeatherHistory::new(alias, weather_dir.archive(alias)).unwrap();
+            assert!(weather_history.contains(&get_date(2015, 4, 1)).unwrap());
+            assert!(!weather_history.contains(&get_date(1999, 4, 1)).unwrap());
+        }
+
+        #[test]
+        fn in_memory() {
+            let alias = "memory";
+            let file = WeatherFile::in_memory("memory.zip");
+            assert!(WeatherArchive::open(alias, file.clone()).is_err());
+            let archive = WeatherArchive::create(alias, file.clone()).unwrap();
+            assert!(archive.file.is_in_memory());
+            assert!(WeatherArchive::create(alias, file.clone()).is_err());
+            // write a day into the archive
+            let date = get_date(2024, 1, 15);
+            let history =
+                History { alias: alias.to_string(), date, temperature_high: Some(50.5), ..Default::default() };
+            let mut weather_history_update = WeatherHistoryUpdate::new(alias, file.clone()).unwrap();
+            let added = weather_history_update.add(&vec![history], &mut |_, _| ()).unwrap();
+            assert_eq!(added, vec![date]);
+            // read it back
+            let weather_history = WeatherHistory::new(alias, file.clone()).unwrap();
+            assert_eq!(weather_history.count().unwrap(), 1);
+            assert!(weather_history.contains(&date).unwrap());
+            let testcase = weather_history.history(date).unwrap().unwrap();
+            assert_eq!(testcase.alias, alias);
+            assert_eq!(testcase.date, date);
+            assert_eq!(testcase.temperature_high, Some(50.5));
+            // nothing was written to the filesystem
+            assert!(!PathBuf::from("memory.zip").exists());
+            assert!(!PathBuf::from("memory.upd").exists());
+        }
+
+        #[test]
+        fn history() {
+            let alias = "north";
+            let resources = testlib::test_resources().join("filesys");
+            let weather_dir = WeatherDir::new(resources).unwrap();
+            let weather_history = WeatherHistory::new(alias, weather_dir.archive(alias)).unwrap();
+            let history = weather_history.history(get_date(2015, 4, 1)).unwrap().unwrap();
+            assert_eq!(history.alias, alias);
+            assert_eq!(history.date, get_date(2015, 4, 1));
+            assert!(weather_history.history(get_date(1999, 4, 1)).unwrap().is_none());
+        }
+
+        #[test]
+        fn concurrent_readers() {
+            fn assert_send_sync<T: Send + Sync>() {}
+            assert_send_sync::<WeatherHistory>();
+            let alias = "testmd";
+            let resources = testlib::test_resources().join("filesys");
+            let weather_dir = WeatherDir::new(resources).unwrap();
+            let weather_history = std::sync::Arc::new(WeatherHistory::new(alias, weather_dir.archive(alias)).unwrap());
+            let filter = DateRange::new(get_date(2014, 4, 1), get_date(2014, 4, 7));
+            let expected: Vec<NaiveDate> =
+                weather_history.daily_histories(&filter).unwrap().iter().map(|history| history.date).collect();
+            assert_eq!(expected.len(), 7);
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    let weather_history = weather_history.clone();
+                    std::thread::spawn(move || {
+                        let filter = DateRange::new(get_date(2014, 4, 1), get_date(2014, 4, 7));
+                        let histories = weather_history.daily_histories(&filter).unwrap();
+                        histories.iter().map(|history| history.date).collect::<Vec<NaiveDate>>()
+                    })
+                })
+                .collect();
+            for handle in handles {
+                assert_eq!(handle.join().unwrap(), expected);
+            }
+        }
+
         #[test]
         fn writer() {
             // set up the testcase
@@ -808,6 +1376,218 @@ mod v2 {
             assert!(iter.next().is_none());
         }
 
+        #[test]
+        fn compression() {
+            let fixture = testlib::TestFixture::create();
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let history = |alias: &str| History {
+                alias: alias.to_string(),
+                date: get_date(2024, 3, 1),
+                description: Some("The same weather description, over and over and over again. ".repeat(20)),
+                ..Default::default()
+            };
+            let compressed_size = |alias: &str, compression: Compression| {
+                WeatherArchive::create(alias, weather_dir.archive(alias)).unwrap();
+                let updater = WeatherHistoryUpdate::new(alias, weather_dir.archive(alias)).unwrap();
+                let mut updater = updater.with_compression(compression);
+                updater.add(&vec![history(alias)], &mut |_, _| ()).unwrap();
+                let archive = WeatherArchive::open(alias, weather_dir.archive(alias)).unwrap();
+                let md = archive.iter_date_range(None, true, ArchiveMd::new).unwrap().next().unwrap();
+                assert_eq!(md.date, get_date(2024, 3, 1));
+                (md.compressed_size, md.size)
+            };
+            let (stored, stored_size) = compressed_size("stored", Compression::Stored);
+            let (deflated, deflated_size) = compressed_size("deflated", Compression::Deflated(Some(9)));
+            assert_eq!(stored, stored_size);
+            assert_eq!(stored_size, deflated_size);
+            assert!(stored > deflated, "stored {} deflated {}", stored, deflated);
+            assert_eq!(Compression::default(), Compression::Deflated(None));
+        }
+
+        #[test]
+        fn add_progress() {
+            let fixture = testlib::TestFixture::create();
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let alias = "progress";
+            WeatherArchive::create(alias, weather_dir.archive(alias)).unwrap();
+            let histories: Vec<History> = DateRange::new(get_date(2024, 1, 1), get_date(2024, 1, 5))
+                .into_iter()
+                .map(|date| History { alias: alias.to_string(), date, ..Default::default() })
+                .collect();
+            let mut events: Vec<(usize, usize)> = vec![];
+            let mut updater = WeatherHistoryUpdate::new(alias, weather_dir.archive(alias)).unwrap();
+            let dates_added = updater.add(&histories, &mut |added, total| events.push((added, total))).unwrap();
+            assert_eq!(dates_added.len(), 5);
+            assert_eq!(events, vec![(1, 5), (2, 5), (3, 5), (4, 5), (5, 5)]);
+            // histories that already exist are not reported
+            let mut event_cnt = 0;
+            let mut updater = WeatherHistoryUpdate::new(alias, weather_dir.archive(alias)).unwrap();
+            let dates_added = updater.add(&histories, &mut |_, _| event_cnt += 1).unwrap();
+            assert!(dates_added.is_empty());
+            assert_eq!(event_cnt, 0);
+        }
+
+        #[test]
+        fn read_only_add() {
+            let fixture = testlib::TestFixture::create();
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let alias = "readonly";
+            WeatherArchive::create(alias, weather_dir.archive(alias)).unwrap();
+            let size = weather_dir.archive(alias).size();
+            let weather_dir = weather_dir.with_read_only(true);
+            let histories = vec![History { alias: alias.to_string(), date: get_date(2024, 1, 1), ..Default::default() }];
+            let mut event_cnt = 0;
+            let mut updater = WeatherHistoryUpdate::new(alias, weather_dir.archive(alias)).unwrap();
+            match updater.add(&histories, &mut |_, _| event_cnt += 1) {
+                Err(err) => assert_eq!(err.kind(), ErrorKind::ReadOnly),
+                Ok(_) => unreachable!("the archive is read-only"),
+            }
+            assert_eq!(event_cnt, 0);
+            assert_eq!(weather_dir.archive(alias).size(), size);
+            // archives cannot be created either
+            let alias = "created";
+            match WeatherArchive::create(alias, weather_dir.archive(alias)) {
+                Err(err) => assert_eq!(err.kind(), ErrorKind::ReadOnly),
+                Ok(_) => unreachable!("the weather directory is read-only"),
+            }
+            assert!(!weather_dir.archive(alias).exists());
+        }
+
+        #[test]
+        fn compact() {
+            let alias = "testmd";
+            let fixture = testlib::TestFixture::create();
+            fixture.copy_resources(&testlib::test_resources().join("filesys").join(format!("{}.zip", alias)));
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let mut archive = WeatherArchive::open(alias, weather_dir.archive(alias)).unwrap();
+            let metadata_of = |archive: &WeatherArchive| {
+                archive
+                    .iter_date_range(None, true, ArchiveMd::new)
+                    .unwrap()
+                    .map(|md| (md.date, md.size, md.mtime))
+                    .collect::<Vec<(NaiveDate, u64, i64)>>()
+            };
+            let original_md = metadata_of(&archive);
+            let testcase = archive.compact().unwrap();
+            assert_eq!(testcase.alias, alias);
+            assert_eq!(testcase.histories, original_md.len());
+            assert!(testcase.after <= testcase.before);
+            assert_eq!(testcase.after, archive.file.size());
+            assert_eq!(metadata_of(&archive), original_md);
+            assert!(!archive.file.path().with_extension(ArchiveWriter::UPDATE_EXT).exists());
+            assert!(!archive.file.path().with_extension(ArchiveWriter::BACKUP_EXT).exists());
+        }
+
+        #[test]
+        fn normalize() {
+            let fixture = testlib::TestFixture::create();
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let alias = "normalize";
+            let mut archive = WeatherArchive::create(alias, weather_dir.archive(alias)).unwrap();
+            let dates: Vec<NaiveDate> =
+                DateRange::new(get_date(2024, 3, 1), get_date(2024, 3, 5)).into_iter().collect();
+            let archive_writer = ArchiveWriter::new(&archive);
+            let mut zip_writer = archive_writer.open().unwrap();
+            for date in dates.iter().rev() {
+                let history = History { alias: alias.to_string(), date: *date, ..Default::default() };
+                archive_writer.write_history(&mut zip_writer, date, &history::to_bytes(&history).unwrap()).unwrap();
+            }
+            archive_writer.close(zip_writer).unwrap();
+            drop(archive_writer);
+            let dates_of = |archive: &WeatherArchive| {
+                archive.iter_date_range(None, false, ArchiveMd::new).unwrap().map(|md| md.date).collect::<Vec<_>>()
+            };
+            assert_eq!(dates_of(&archive), dates.iter().rev().cloned().collect::<Vec<NaiveDate>>());
+            let testcase = archive.normalize().unwrap();
+            assert_eq!(testcase.alias, alias);
+            assert_eq!(testcase.histories, dates.len());
+            assert!(testcase.rewritten);
+            assert_eq!(dates_of(&archive), dates);
+            assert!(!archive.file.path().with_extension(ArchiveWriter::UPDATE_EXT).exists());
+            assert!(!archive.file.path().with_extension(ArchiveWriter::BACKUP_EXT).exists());
+            // normalizing again leaves the archive alone
+            let testcase = archive.normalize().unwrap();
+            assert_eq!(testcase.histories, dates.len());
+            assert!(!testcase.rewritten);
+            assert_eq!(dates_of(&archive), dates);
+            // files that are not histories are kept after the histories
+            {
+                let file = weather_dir.archive(alias).writer().unwrap();
+                let mut writer = ZipWriter::new_append(file).unwrap();
+                writer.start_file("notes.txt", FileOptions::default()).unwrap();
+                writer.write_all(b"not a history").unwrap();
+                let date = get_date(2024, 2, 29);
+                let history = history::to_bytes(&History { alias: alias.to_string(), date, ..Default::default() });
+                writer.start_file(WeatherArchive::date_to_filename(alias, &date), FileOptions::default()).unwrap();
+                writer.write_all(&history.unwrap()).unwrap();
+                writer.finish().unwrap();
+            }
+            archive.file.refresh();
+            let testcase = archive.normalize().unwrap();
+            assert_eq!(testcase.histories, dates.len() + 1);
+            assert!(testcase.rewritten);
+            assert_eq!(dates_of(&archive)[0], get_date(2024, 2, 29));
+            let mut reader = archive.zip_reader("test").unwrap();
+            assert_eq!(reader.len(), dates.len() + 2);
+            assert_eq!(reader.by_index_raw(dates.len() + 1).unwrap().name(), "notes.txt");
+        }
+
+        #[test]
+        fn integrity_issues() {
+            let alias = "testmd";
+            let fixture = testlib::TestFixture::create();
+            fixture.copy_resources(&testlib::test_resources().join("filesys").join(format!("{}.zip", alias)));
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let archive = WeatherArchive::open(alias, weather_dir.archive(alias)).unwrap();
+            assert!(archive.integrity_issues().unwrap().is_empty());
+            // add a history that cannot be decoded
+            let corrupt_name = WeatherArchive::date_to_filename(alias, &get_date(2099, 12, 31));
+            {
+                let file = weather_dir.archive(alias).writer().unwrap();
+                let mut writer = ZipWriter::new_append(file).unwrap();
+                writer.start_file(&corrupt_name, FileOptions::default()).unwrap();
+                writer.write_all(b"not a history").unwrap();
+                writer.finish().unwrap();
+            }
+            let archive = WeatherArchive::open(alias, weather_dir.archive(alias)).unwrap();
+            let testcase = archive.integrity_issues().unwrap();
+            assert_eq!(testcase.len(), 1);
+            assert_eq!(testcase[0].alias, alias);
+            assert_eq!(testcase[0].entry, Some(corrupt_name));
+        }
+
+        #[test]
+        fn daily_histories_with_failures() {
+            let alias = "testmd";
+            let fixture = testlib::TestFixture::create();
+            fixture.copy_resources(&testlib::test_resources().join("filesys").join(format!("{}.zip", alias)));
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let history = WeatherHistory::new(alias, weather_dir.archive(alias)).unwrap();
+            let date_range = DateRange::new(get_date(2000, 1, 1), get_date(2099, 12, 31));
+            let (histories, failures) = history.daily_histories_with_failures(&date_range).unwrap();
+            let count = histories.len();
+            assert!(count > 0);
+            assert!(failures.is_empty());
+            // add a history that cannot be decoded
+            let corrupt_date = get_date(2099, 12, 31);
+            let corrupt_name = WeatherArchive::date_to_filename(alias, &corrupt_date);
+            {
+                let file = weather_dir.archive(alias).writer().unwrap();
+                let mut writer = ZipWriter::new_append(file).unwrap();
+                writer.start_file(&corrupt_name, FileOptions::default()).unwrap();
+                writer.write_all(b"not a history").unwrap();
+                writer.finish().unwrap();
+            }
+            let history = WeatherHistory::new(alias, weather_dir.archive(alias)).unwrap();
+            let (histories, failures) = history.daily_histories_with_failures(&date_range).unwrap();
+            assert_eq!(histories.len(), count);
+            assert_eq!(failures.len(), 1);
+            assert_eq!(failures[0].0, corrupt_date);
+            assert!(!failures[0].1.is_empty());
+            // the corrupt history is silently skipped otherwise
+            assert_eq!(history.daily_histories(&date_range).unwrap().len(), count);
+        }
+
         #[allow(unused)]
         // of course this is hard coded to my workstation
         const SOURCE_WEATHER_DATA: &str = r"C:\Users\rncru\dev\weather_data";
@@ -2,14 +2,16 @@
 //!
 use super::*;
 
-pub use v2::{WeatherDir, WeatherFile};
+pub use v2::{FileHandle, WeatherDir, WeatherFile};
 mod v2 {
     //! The current implementation of filesystem access.
     use super::*;
 
     use std::{
         fs::{remove_file, rename, File, Metadata, OpenOptions},
-        io::ErrorKind,
+        io::{self, Cursor, ErrorKind, Read, Seek, SeekFrom, Write},
+        sync::{Arc, Mutex},
+        time::SystemTime,
     };
 
     /// The [WeatherDir] error builder.
@@ -24,6 +26,8 @@ mod v2 {
     pub struct WeatherDir(
         /// The directory managed by the weather directory.
         PathBuf,
+        /// When `true` files within the directory cannot be modified.
+        bool,
     );
 
     impl std::fmt::Display for WeatherDir {
@@ -52,7 +56,8 @@ mod v2 {
     impl TryFrom<&Config> for WeatherDir {
         type Error = Error;
         fn try_from(config: &Config) -> std::prelude::v1::Result<Self, Self::Error> {
-            WeatherDir::new(PathBuf::from(&config.weather_data.directory))
+            let weather_dir = WeatherDir::new(PathBuf::from(&config.weather_data.directory))?;
+            Ok(weather_dir.with_read_only(config.weather_data.read_only))
         }
     }
 
@@ -66,7 +71,7 @@ mod v2 {
         /// * `directory_name` is the name of the directory.
         pub fn new(path: PathBuf) -> Result<WeatherDir> {
             match path.is_dir() {
-                true => Ok(WeatherDir(path)),
+                true => Ok(WeatherDir(path, false)),
                 false => Err(dir_err!(path.display().to_string(), "Not a directory...")),
             }
         }
@@ -76,11 +81,24 @@ mod v2 {
         ///
         /// * `filename` is the name of the file within the weather directory.
         pub fn file(&self, filename: &str) -> WeatherFile {
-            WeatherFile::new(self.0.join(filename))
+            WeatherFile::new(self.0.join(filename)).with_read_only(self.1)
         }
         pub fn archive(&self, alias: &str) -> WeatherFile {
             let archive_name = self.0.join(alias).with_extension("zip");
-            WeatherFile::new(archive_name)
+            WeatherFile::new(archive_name).with_read_only(self.1)
+        }
+        /// Control if files within the weather directory can be modified.
+        ///
+        /// # Arguments
+        ///
+        /// * `read_only` when `true` will prevent weather files from being modified.
+        pub fn with_read_only(mut self, read_only: bool) -> Self {
+            self.1 = read_only;
+            self
+        }
+        /// Indicates if files within the weather directory cannot be modified.
+        pub fn is_read_only(&self) -> bool {
+            self.1
         }
         /// Get the weather directory path.
         pub fn path(&self) -> &Path {
@@ -95,8 +113,18 @@ mod v2 {
     };
 }
 
+    /// The error returned when a read-only [WeatherFile] would be modified.
+    macro_rules! read_only_err {
+        ($id:expr) => {
+            Err(Error::new(crate::ErrorKind::ReadOnly, format!("WeatherFile {}: Opened read-only...", $id)))
+        };
+    }
+
+    /// The content of a weather file kept in memory, `None` indicates the file does not exist.
+    type MemoryContent = Arc<Mutex<Option<Vec<u8>>>>;
+
     /// The manager of a file within the weather directory.
-    #[derive(Debug)]
+    #[derive(Clone, Debug)]
     pub struct WeatherFile {
         /// The file name within the weather directory.
         pub filename: String,
@@ -104,6 +132,10 @@ mod v2 {
         path: PathBuf,
         /// The filesystem metadata for the file.
         fs_metadata: Option<Metadata>,
+        /// When `true` the file cannot be modified.
+        read_only: bool,
+        /// When available the file content is kept in memory instead of the filesystem.
+        memory: Option<MemoryContent>,
     }
     impl std::fmt::Display for WeatherFile {
         /// Use the trait to get the pathname of the file.
@@ -129,10 +161,44 @@ mod v2 {
                     None
                 }
             };
-            WeatherFile { filename, path, fs_metadata }
+            WeatherFile { filename, path, fs_metadata, read_only: false, memory: None }
+        }
+        /// Create a weather file whose content is kept in memory instead of the filesystem.
+        ///
+        /// The file does not exist until it has been created. Clones of the weather file share the
+        /// same content.
+        ///
+        /// # Arguments
+        ///
+        /// * `filename` is the name of the weather file.
+        #[cfg(test)]
+        pub fn in_memory(filename: &str) -> Self {
+            WeatherFile {
+                filename: filename.to_string(),
+                path: PathBuf::from(filename),
+                fs_metadata: None,
+                read_only: false,
+                memory: Some(Arc::new(Mutex::new(None))),
+            }
+        }
+        /// Indicates if the weather file content is kept in memory.
+        pub fn is_in_memory(&self) -> bool {
+            self.memory.is_some()
+        }
+        /// Control if the weather file can be modified.
+        fn with_read_only(mut self, read_only: bool) -> Self {
+            self.read_only = read_only;
+            self
+        }
+        /// Indicates if the weather file cannot be modified.
+        pub fn is_read_only(&self) -> bool {
+            self.read_only
         }
         /// Refresh the filesystem metadata.
         pub fn refresh(&mut self) {
+            if self.is_in_memory() {
+                return;
+            }
             match self.path.metadata() {
                 Ok(metadata) => self.fs_metadata.replace(metadata),
                 Err(err) => {
@@ -145,35 +211,87 @@ mod v2 {
         }
         /// Indicates if the file exists or does not.
         pub fn exists(&self) -> bool {
-            self.fs_metadata.is_some()
+            match &self.memory {
+                Some(memory) => memory.lock().unwrap().is_some(),
+                None => self.fs_metadata.is_some(),
+            }
         }
         /// Get the size of the file.
         pub fn size(&self) -> u64 {
-            match &self.fs_metadata {
-                Some(md) => md.len(),
-                None => 0,
+            match (&self.memory, &self.fs_metadata) {
+                (Some(memory), _) => memory.lock().unwrap().as_ref().map_or(0, |content| content.len() as u64),
+                (None, Some(md)) => md.len(),
+                (None, None) => 0,
+            }
+        }
+        /// Get when the file was last modified.
+        ///
+        /// `None` is returned if the file does not exist or its content is kept in memory.
+        pub fn modified(&self) -> Option<SystemTime> {
+            match (&self.memory, &self.fs_metadata) {
+                (None, Some(md)) => md.modified().ok(),
+                _ => None,
             }
         }
         /// Get the writer that can be used to update a Zip archive.
-        pub fn writer(&self) -> Result<File> {
+        pub fn writer(&self) -> Result<FileHandle> {
+            if self.read_only {
+                return read_only_err!(&self.filename);
+            }
+            if let Some(memory) = &self.memory {
+                return match memory.lock().unwrap().as_ref() {
+                    Some(content) => Ok(FileHandle::Memory(Cursor::new(content.clone()), Some(memory.clone()))),
+                    None => Err(file_err!(&self.filename, "open read/write error (does not exist).")),
+                };
+            }
             match File::options().read(true).write(true).open(&self.path) {
-                Ok(file) => Ok(file),
+                Ok(file) => Ok(FileHandle::Disk(file)),
                 Err(err) => Err(file_err!(&self.filename, &format!("open read/write error ({}).", &err))),
             }
         }
         /// Get the reader that can be used to read the contents of a Zip archive.
-        pub fn reader(&self) -> Result<File> {
+        pub fn reader(&self) -> Result<FileHandle> {
+            if let Some(memory) = &self.memory {
+                return match memory.lock().unwrap().as_ref() {
+                    Some(content) => Ok(FileHandle::detached(content.clone())),
+                    None => Err(file_err!(&self.filename, "open read error (does not exist)...")),
+                };
+            }
             match OpenOptions::new().read(true).open(&self.path) {
-                Ok(file) => Ok(file),
+                Ok(file) => Ok(FileHandle::Disk(file)),
                 Err(err) => Err(file_err!(&self.filename, &format!("open read error ({})...", &err))),
             }
         }
+        /// Replace the content of a weather file kept in memory.
+        ///
+        /// # Arguments
+        ///
+        /// * `content` is the new file content.
+        pub fn replace_content(&self, content: Vec<u8>) -> Result<()> {
+            if self.read_only {
+                return read_only_err!(&self.filename);
+            }
+            match &self.memory {
+                Some(memory) => {
+                    memory.lock().unwrap().replace(content);
+                    Ok(())
+                }
+                None => Err(file_err!(&self.filename, "Content can only be replaced in memory...")),
+            }
+        }
         /// Get the weather file as a [Path].
         pub fn path(&self) -> &Path {
             self.path.as_path()
         }
         /// Remove the weather file from the filesystem.
         pub fn remove(&mut self) -> Result<()> {
+            if self.read_only {
+                return read_only_err!(&self.filename);
+            }
+            if let Some(memory) = &self.memory {
+                memory.lock().unwrap().take();
+                return Ok(());
+            }
             self.refresh();
             match self.exists() {
                 true => match remove_file(self.path()) {
@@ -195,6 +313,13 @@ mod v2 {
         }
         /// Either update the existing file access time or create the file.
         pub fn touch(&mut self) -> Result<()> {
+            if self.read_only {
+                return read_only_err!(&self.filename);
+            }
+            if let Some(memory) = &self.memory {
+                memory.lock().unwrap().get_or_insert_with(Vec::new);
+                return Ok(());
+            }
             self.refresh();
             let result = if self.exists() {
                 OpenOptions::new().read(true).open(self.path())
@@ -215,6 +340,18 @@ mod v2 {
         }
         /// Rename the weather file to another weather file.
         pub fn rename(&mut self, to: &mut WeatherFile) -> Result<()> {
+            if self.read_only || to.read_only {
+                return read_only_err!(&self.filename);
+            }
+            match (&self.memory, &to.memory) {
+                (Some(from_memory), Some(to_memory)) => {
+                    let content = from_memory.lock().unwrap().take();
+                    *to_memory.lock().unwrap() = content;
+                    return Ok(());
+                }
+                (None, None) => (),
+                _ => return Err(file_err!(self.filename, format!("Cannot rename to {} (mixed storage).", to))),
+            }
             match rename(self.path(), to.path()) {
                 Ok(_) => {
                     self.refresh();
@@ -230,6 +367,79 @@ mod v2 {
         }
     }
 
+    /// The reader and writer of weather file content.
+    #[derive(Debug)]
+    pub enum FileHandle {
+        /// The content is in a filesystem file.
+        Disk(File),
+        /// The content is in memory and when available where the content is saved when flushed.
+        Memory(Cursor<Vec<u8>>, Option<MemoryContent>),
+    }
+    impl FileHandle {
+        /// Create a handle to content in memory that is not saved anywhere.
+        ///
+        /// # Arguments
+        ///
+        /// * `content` is the initial content of the handle.
+        pub fn detached(content: Vec<u8>) -> Self {
+            Self::Memory(Cursor::new(content), None)
+        }
+        /// Get the content of a handle in memory, `None` is returned for filesystem files.
+        pub fn into_content(mut self) -> Option<Vec<u8>> {
+            match &mut self {
+                Self::Disk(_) => None,
+                Self::Memory(cursor, memory) => {
+                    // don't let drop save the content that was taken
+                    memory.take();
+                    Some(std::mem::take(cursor.get_mut()))
+                }
+            }
+        }
+    }
+    impl Read for FileHandle {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match self {
+                Self::Disk(file) => file.read(buf),
+                Self::Memory(cursor, _) => cursor.read(buf),
+            }
+        }
+    }
+    impl Write for FileHandle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            match self {
+                Self::Disk(file) => file.write(buf),
+                Self::Memory(cursor, _) => cursor.write(buf),
+            }
+        }
+        /// Memory content is saved when it is flushed.
+        fn flush(&mut self) -> io::Result<()> {
+            match self {
+                Self::Disk(file) => file.flush(),
+                Self::Memory(cursor, Some(memory)) => {
+                    memory.lock().unwrap().replace(cursor.get_ref().clone());
+                    Ok(())
+                }
+                Self::Memory(_, None) => Ok(()),
+            }
+        }
+    }
+    impl Seek for FileHandle {
+        fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+            match self {
+                Self::Disk(file) => file.seek(pos),
+                Self::Memory(cursor, _) => cursor.seek(pos),
+            }
+        }
+    }
+    impl Drop for FileHandle {
+        /// Make sure written memory content is saved.
+        fn drop(&mut self) {
+            if let Self::Memory(cursor, Some(memory)) = self {
+                memory.lock().unwrap().replace(std::mem::take(cursor.get_mut()));
+            }
+        }
+    }
+
     #[cfg(test)]
     mod test {
         use super::*;
@@ -257,6 +467,7 @@ mod v2 {
             assert_eq!(testcase.filename, filename);
             assert!(testcase.exists());
             assert_eq!(testcase.size(), content.len() as u64);
+            assert!(testcase.modified().is_some());
             // verify reading the file content
             {
                 let mut reader = testcase.reader().unwrap();
@@ -266,6 +477,32 @@ mod v2 {
             }
         }
 
+        #[test]
+        fn in_memory() {
+            let mut testcase = WeatherFile::in_memory("memory.dat");
+            assert!(testcase.is_in_memory());
+            assert!(!testcase.exists());
+            assert!(testcase.reader().is_err());
+            assert!(testcase.writer().is_err());
+            testcase.touch().unwrap();
+            assert!(testcase.exists());
+            assert_eq!(testcase.size(), 0);
+            assert!(testcase.modified().is_none());
+            // written content is shared with clones
+            let content = "testcase file content...";
+            testcase.writer().unwrap().write_all(content.as_bytes()).unwrap();
+            let other = testcase.clone();
+            assert_eq!(other.size(), content.len() as u64);
+            let mut file_content = String::new();
+            other.reader().unwrap().read_to_string(&mut file_content).unwrap();
+            assert_eq!(&file_content, content);
+            // readers don't change the content
+            other.reader().unwrap().write_all(b"ignored").unwrap();
+            assert_eq!(testcase.size(), content.len() as u64);
+            testcase.remove().unwrap();
+            assert!(!other.exists());
+        }
+
         #[test]
         fn weather_dir() {
             // set up the test case
@@ -278,6 +515,23 @@ mod v2 {
             let file = testcase.file(filename);
             assert!(file.exists());
             assert_eq!(file.size(), 515);
+            assert!(!testcase.is_read_only());
+            assert!(!file.is_read_only());
+        }
+
+        #[test]
+        fn read_only() {
+            let fixture = testlib::TestFixture::create();
+            let filename = "test_file.dat";
+            let testcase = WeatherDir::try_from(fixture.to_string()).unwrap().with_read_only(true);
+            assert!(testcase.is_read_only());
+            let mut file = testcase.file(filename);
+            assert!(file.is_read_only());
+            let error = file.touch().unwrap_err();
+            assert_eq!(error.kind(), crate::ErrorKind::ReadOnly);
+            assert!(!PathBuf::from(&fixture).join(filename).exists());
+            assert_eq!(file.writer().unwrap_err().kind(), crate::ErrorKind::ReadOnly);
+            assert_eq!(file.remove().unwrap_err().kind(), crate::ErrorKind::ReadOnly);
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,316 @@
+//! An optional on-disk cache of decoded weather history.
+use super::*;
+
+pub use v1::HistoryCache;
+mod v1 {
+    //! The first implementation of the weather history cache.
+    //!
+    //! The cache is a sidecar `sqlite` database in the weather directory. Each history is keyed by the
+    //! location alias, the history date, and the modification time of the archive it was read from. When
+    //! an archive is modified the cached histories no longer match and they are reloaded from the archive.
+    //! The archives that have been cached are recorded separately so archives without histories are cached too.
+    use super::*;
+    use crate::{
+        backend::db::db_connection,
+        prelude::{DateRange, History},
+    };
+    use rusqlite::{named_params, Connection};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use toolslib::stopwatch::StopWatch;
+
+    /// The name of the history cache database in the weather data directory.
+    pub const CACHE_FILENAME: &str = "history_cache.db";
+
+    /// The history cache schema.
+    const SCHEMA_SQL: &str = r#"
+        CREATE TABLE IF NOT EXISTS history_cache (
+            alias TEXT NOT NULL,
+            date TEXT NOT NULL,
+            mtime INTEGER NOT NULL,
+            history TEXT NOT NULL,
+            PRIMARY KEY (alias, date, mtime)
+        );
+        CREATE TABLE IF NOT EXISTS history_cache_archives (
+            alias TEXT NOT NULL PRIMARY KEY,
+            mtime INTEGER NOT NULL
+        );
+    "#;
+
+    /// The manager of decoded weather histories kept in the weather directory.
+    #[derive(Debug)]
+    pub struct HistoryCache {
+        /// The history cache database file.
+        file: WeatherFile,
+        /// Indicates the cache schema has been created.
+        schema_created: AtomicBool,
+    }
+    impl HistoryCache {
+        /// Create the history cache manager for a weather directory.
+        ///
+        /// # Arguments
+        ///
+        /// * `weather_dir` is the weather data directory that holds the cache.
+        pub fn new(weather_dir: &WeatherDir) -> Self {
+            Self { file: weather_dir.file(CACHE_FILENAME), schema_created: AtomicBool::new(false) }
+        }
+        /// Get the daily weather history of an archive.
+        ///
+        /// If the archive histories have not been cached or the archive has been modified since they were,
+        /// `load` is called to get every history in the archive and the cache is refreshed. Archives without a
+        /// modification time, such as those kept in memory, are not cached. A cache that cannot be read or
+        /// written is logged and the histories are loaded from the archive.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        /// * `file` is the location weather archive.
+        /// * `filter` restricts the range of the historical weather data.
+        /// * `load` returns all the weather histories in the archive.
+        pub fn daily_histories<F>(
+            &self,
+            alias: &str,
+            file: &WeatherFile,
+            filter: &DateRange,
+            load: F,
+        ) -> Result<Vec<History>>
+        where
+            F: FnOnce() -> Result<Vec<History>>,
+        {
+            let stopwatch = StopWatch::start_new();
+            let mtime = match file.modified().and_then(mtime_key) {
+                Some(mtime) => mtime,
+                None => return Ok(filter_histories(load()?, filter)),
+            };
+            match self.cached(alias, mtime, filter) {
+                Ok(Some(histories)) => {
+                    log::trace!("HistoryCache: {} cache hit {}", alias, &stopwatch);
+                    return Ok(histories);
+                }
+                Ok(None) => log::trace!("HistoryCache: {} cache miss", alias),
+                Err(err) => log::warn!("HistoryCache: {} read error ({}).", alias, err),
+            }
+            let histories = load()?;
+            if let Err(err) = self.replace(alias, mtime, &histories) {
+                log::warn!("HistoryCache: {} write error ({}).", alias, err);
+            }
+            log::trace!("HistoryCache: {} cache load {}", alias, &stopwatch);
+            Ok(filter_histories(histories, filter))
+        }
+        /// Get a connection to the cache database, the schema is created the first time a connection is made.
+        fn connection(&self) -> Result<Connection> {
+            let conn = db_connection(Some(self.file.clone()))?;
+            if !self.schema_created.load(Ordering::Relaxed) {
+                conn.execute_batch(SCHEMA_SQL)?;
+                self.schema_created.store(true, Ordering::Relaxed);
+            }
+            Ok(conn)
+        }
+        /// Get the cached histories for an archive, `None` is returned if the archive has not been cached.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        /// * `mtime` is the archive modification time key.
+        /// * `filter` restricts the range of the historical weather data.
+        fn cached(&self, alias: &str, mtime: i64, filter: &DateRange) -> Result<Option<Vec<History>>> {
+            const EXISTS_SQL: &str = r#"
+                SELECT EXISTS(SELECT 1 FROM history_cache_archives WHERE alias = :alias AND mtime = :mtime)
+            "#;
+            const SELECT_SQL: &str = r#"
+                SELECT history FROM history_cache
+                WHERE alias = :alias AND mtime = :mtime AND date BETWEEN :from AND :thru
+                ORDER BY date
+            "#;
+            let conn = self.connection()?;
+            let exists: bool =
+                conn.query_row(EXISTS_SQL, named_params! {":alias": alias, ":mtime": mtime}, |row| row.get(0))?;
+            if !exists {
+                return Ok(None);
+            }
+            let mut stmt = conn.prepare(SELECT_SQL)?;
+            let params = named_params! {":alias": alias, ":mtime": mtime, ":from": filter.from, ":thru": filter.to};
+            let rows = stmt.query_map(params, |row| row.get::<_, String>(0))?;
+            let mut histories = vec![];
+            for row in rows {
+                histories.push(history::from_bytes(alias, row?.as_bytes())?);
+            }
+            Ok(Some(histories))
+        }
+        /// Replace the cached histories for an archive.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the location identifier.
+        /// * `mtime` is the archive modification time key.
+        /// * `histories` are all the weather histories in the archive.
+        fn replace(&self, alias: &str, mtime: i64, histories: &[History]) -> Result<()> {
+            const DELETE_SQL: &str = "DELETE FROM history_cache WHERE alias = :alias";
+            const ARCHIVE_SQL: &str = r#"
+                INSERT OR REPLACE INTO history_cache_archives (alias, mtime) VALUES (:alias, :mtime)
+            "#;
+            const INSERT_SQL: &str = r#"
+                INSERT INTO history_cache (alias, date, mtime, history) VALUES (:alias, :date, :mtime, :history)
+            "#;
+            let mut conn = self.connection()?;
+            let tx = conn.transaction()?;
+            tx.execute(DELETE_SQL, named_params! {":alias": alias})?;
+            tx.execute(ARCHIVE_SQL, named_params! {":alias": alias, ":mtime": mtime})?;
+            {
+                let mut stmt = tx.prepare_cached(INSERT_SQL)?;
+                for history in histories {
+                    let json = history::to_json(history)?;
+                    let params =
+                        named_params! {":alias": alias, ":date": history.date, ":mtime": mtime, ":history": json};
+                    stmt.execute(params)?;
+                }
+            }
+            tx.commit()?;
+            Ok(())
+        }
+    }
+
+    /// Convert the archive modification time into the cache key.
+    ///
+    /// # Arguments
+    ///
+    /// * `modified` is when the archive was last modified.
+    fn mtime_key(modified: SystemTime) -> Option<i64> {
+        modified.duration_since(UNIX_EPOCH).ok().map(|duration| duration.as_nanos() as i64)
+    }
+
+    /// Keep the histories that are within the date range.
+    ///
+    /// # Arguments
+    ///
+    /// * `histories` are the weather histories that will be filtered.
+    /// * `filter` restricts the range of the historical weather data.
+    fn filter_histories(histories: Vec<History>, filter: &DateRange) -> Vec<History> {
+        histories.into_iter().filter(|history| filter.covers(&history.date)).collect()
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use chrono::NaiveDate;
+        use filetime::{set_file_mtime, FileTime};
+        use std::cell::Cell;
+
+        fn get_date(year: i32, month: u32, day: u32) -> NaiveDate {
+            NaiveDate::from_ymd_opt(year, month, day).unwrap()
+        }
+
+        #[test]
+        fn daily_histories() {
+            let fixture = testlib::TestFixture::create();
+            fixture.copy_resources(&testlib::test_resources().join("filesys").join("testmd.zip"));
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let testcase = HistoryCache::new(&weather_dir);
+            let opens = Cell::new(0);
+            let load = || {
+                opens.set(opens.get() + 1);
+                let archive = WeatherHistory::new("testmd", weather_dir.archive("testmd"))?;
+                archive.daily_histories(&DateRange::new(NaiveDate::MIN, NaiveDate::MAX))
+            };
+            let filter = DateRange::new(get_date(2014, 4, 1), get_date(2014, 4, 5));
+            let expected =
+                WeatherHistory::new("testmd", weather_dir.archive("testmd")).unwrap().daily_histories(&filter).unwrap();
+            let dates = |histories: &Vec<History>| histories.iter().map(|history| history.date).collect::<Vec<_>>();
+            // the cold cache loads the archive
+            let histories = testcase.daily_histories("testmd", &weather_dir.archive("testmd"), &filter, load).unwrap();
+            assert_eq!(opens.get(), 1);
+            assert_eq!(dates(&histories), dates(&expected));
+            assert!(PathBuf::from(&fixture).join(CACHE_FILENAME).exists());
+            // the warm cache does not open the archive
+            let histories = testcase.daily_histories("testmd", &weather_dir.archive("testmd"), &filter, load).unwrap();
+            assert_eq!(opens.get(), 1);
+            assert_eq!(dates(&histories), dates(&expected));
+            for (history, expected) in histories.iter().zip(expected.iter()) {
+                assert_eq!(history.alias, expected.alias);
+                assert_eq!(history.temperature_high, expected.temperature_high);
+                assert_eq!(history.temperature_low, expected.temperature_low);
+                assert_eq!(history.sunrise, expected.sunrise);
+                assert_eq!(history.description, expected.description);
+            }
+            // other date ranges come from the cache as well
+            let filter = DateRange::new(get_date(2014, 4, 6), get_date(2014, 4, 6));
+            let histories = testcase.daily_histories("testmd", &weather_dir.archive("testmd"), &filter, load).unwrap();
+            assert_eq!(opens.get(), 1);
+            assert_eq!(dates(&histories), vec![get_date(2014, 4, 6)]);
+        }
+
+        #[test]
+        fn modified_archive() {
+            let fixture = testlib::TestFixture::create();
+            fixture.copy_resources(&testlib::test_resources().join("filesys").join("testmd.zip"));
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let testcase = HistoryCache::new(&weather_dir);
+            let opens = Cell::new(0);
+            let load = || {
+                opens.set(opens.get() + 1);
+                let archive = WeatherHistory::new("testmd", weather_dir.archive("testmd"))?;
+                archive.daily_histories(&DateRange::new(NaiveDate::MIN, NaiveDate::MAX))
+            };
+            let filter = DateRange::new(NaiveDate::MIN, NaiveDate::MAX);
+            let archive = PathBuf::from(&fixture).join("testmd.zip");
+            set_file_mtime(&archive, FileTime::from_unix_time(1_700_000_000, 0)).unwrap();
+            let histories = testcase.daily_histories("testmd", &weather_dir.archive("testmd"), &filter, load).unwrap();
+            let count = histories.len();
+            assert_eq!(count, 28);
+            assert_eq!(opens.get(), 1);
+            testcase.daily_histories("testmd", &weather_dir.archive("testmd"), &filter, load).unwrap();
+            assert_eq!(opens.get(), 1);
+            // changing the archive invalidates the cache
+            set_file_mtime(&archive, FileTime::from_unix_time(1_700_000_060, 0)).unwrap();
+            let histories = testcase.daily_histories("testmd", &weather_dir.archive("testmd"), &filter, load).unwrap();
+            assert_eq!(opens.get(), 2);
+            assert_eq!(histories.len(), count);
+            testcase.daily_histories("testmd", &weather_dir.archive("testmd"), &filter, load).unwrap();
+            assert_eq!(opens.get(), 2);
+            // the stale histories were replaced
+            let conn = testcase.connection().unwrap();
+            let rows: usize = conn.query_row("SELECT COUNT(*) FROM history_cache", [], |row| row.get(0)).unwrap();
+            assert_eq!(rows, count);
+        }
+
+        #[test]
+        fn empty_archive() {
+            let fixture = testlib::TestFixture::create();
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            std::fs::write(PathBuf::from(&fixture).join("empty.zip"), "").unwrap();
+            let testcase = HistoryCache::new(&weather_dir);
+            let opens = Cell::new(0);
+            let load = || -> Result<Vec<History>> {
+                opens.set(opens.get() + 1);
+                Ok(vec![])
+            };
+            let filter = DateRange::new(NaiveDate::MIN, NaiveDate::MAX);
+            let archive = weather_dir.archive("empty");
+            assert!(testcase.daily_histories("empty", &archive, &filter, load).unwrap().is_empty());
+            assert!(testcase.daily_histories("empty", &archive, &filter, load).unwrap().is_empty());
+            // the archive without histories was cached
+            assert_eq!(opens.get(), 1);
+        }
+
+        #[test]
+        fn in_memory() {
+            let fixture = testlib::TestFixture::create();
+            let weather_dir = WeatherDir::new(PathBuf::from(&fixture)).unwrap();
+            let testcase = HistoryCache::new(&weather_dir);
+            let opens = Cell::new(0);
+            let history =
+                |day: u32| History { alias: "memory".to_string(), date: get_date(2024, 3, day), ..Default::default() };
+            let load = || -> Result<Vec<History>> {
+                opens.set(opens.get() + 1);
+                Ok(vec![history(1), history(2), history(3)])
+            };
+            let file = WeatherFile::in_memory("memory.zip");
+            let filter = DateRange::new(get_date(2024, 3, 2), get_date(2024, 3, 3));
+            assert_eq!(testcase.daily_histories("memory", &file, &filter, load).unwrap().len(), 2);
+            assert_eq!(testcase.daily_histories("memory", &file, &filter, load).unwrap().len(), 2);
+            // archives kept in memory are always loaded
+            assert_eq!(opens.get(), 2);
+            assert!(!PathBuf::from(&fixture).join(CACHE_FILENAME).exists());
+        }
+    }
+}
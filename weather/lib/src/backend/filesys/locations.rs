@@ -123,6 +123,26 @@ mod v2 {
             }
             LocationsIter::new(locations)
         }
+        /// Creates an iterator returning weather data locations whose alias exactly matches a filter.
+        ///
+        /// # Arguments
+        ///
+        /// * `aliases` are the location aliases that will be returned.
+        /// * `icase` will make aliases case-sensitive (`true`) or ignore case (`false`).
+        /// * `sort` will order the matching locations by their name.
+        pub fn as_alias_iter(&self, aliases: &Vec<String>, icase: bool, sort: bool) -> LocationsIter {
+            let mut locations: Vec<&LocationMd> = if aliases.is_empty() {
+                self.0.iter().collect()
+            } else {
+                let prepare = |text: &str| if icase { text.to_string() } else { text.to_lowercase() };
+                let aliases: Vec<String> = aliases.iter().map(|alias| prepare(alias)).collect();
+                self.0.iter().filter(|location| aliases.contains(&prepare(&location.alias))).collect()
+            };
+            if sort {
+                locations.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+            }
+            LocationsIter::new(locations)
+        }
         /// Add a location to the locations document.
         ///
         /// # Arguments
@@ -147,6 +167,65 @@ mod v2 {
                 }
             }
         }
+        /// Change the name and alias of a location in the locations document.
+        ///
+        /// History filenames include the location alias. If the alias changes the histories are copied
+        /// into an archive for the new alias before the locations document is saved. The previous
+        /// archive is removed only after the document has been updated.
+        ///
+        /// # Arguments
+        ///
+        /// * `alias` is the current alias of the location.
+        /// * `name` is the new location name.
+        /// * `new_alias` is the new location alias.
+        /// * `weather_dir` is the weather data directory.
+        pub fn rename(&mut self, alias: &str, name: &str, new_alias: &str, weather_dir: &WeatherDir) -> Result<()> {
+            // make sure the alias is lowercase
+            let new_alias = new_alias.to_lowercase();
+            if name.is_empty() {
+                return error!("The location name cannot be empty.");
+            } else if new_alias.is_empty() {
+                return error!("The alias name cannot be empty.");
+            }
+            let index = match self.0.iter().position(|md| md.alias == alias) {
+                Some(index) => index,
+                None => return error!(format!("The '{}' location was not found.", alias)),
+            };
+            for (other_index, md) in self.0.iter().enumerate() {
+                if other_index == index {
+                    continue;
+                } else if md.alias == new_alias {
+                    return error!(format!("{} already uses the '{}' alias name", md.name, md.alias));
+                } else if md.name == name {
+                    return error!(format!("The '{}' location name is already being used.", md.name));
+                }
+            }
+            let renamed = new_alias != alias;
+            if renamed {
+                let archive = WeatherArchive::open(alias, weather_dir.archive(alias))?;
+                if let Err(err) = archive.copy_as(&new_alias, weather_dir.archive(&new_alias)) {
+                    // don't leave behind a partial archive unless it belongs to someone else
+                    if err.kind() != ErrorKind::AlreadyExists {
+                        weather_dir.archive(&new_alias).remove()?;
+                    }
+                    return Err(err);
+                }
+            }
+            let previous = self.0[index].clone();
+            self.0[index].name = name.to_string();
+            self.0[index].alias = new_alias.clone();
+            if let Err(err) = save_locations(weather_dir, &self.0) {
+                self.0[index] = previous;
+                if renamed {
+                    weather_dir.archive(&new_alias).remove()?;
+                }
+                return Err(err);
+            }
+            if renamed {
+                weather_dir.archive(alias).remove()?;
+            }
+            Ok(())
+        }
     }
 
     /// Do a high level validation of the new location.
@@ -400,6 +479,32 @@ mod v2 {
             assert!(result.next().is_none());
         }
 
+        #[test]
+        fn as_alias_iter() {
+            let resources = testlib::test_resources().join("filesys");
+            let weather_dir = WeatherDir::new(resources).unwrap();
+            let testcase = LocationsDocument::new(weather_dir.file("locations.json")).unwrap();
+            // no filters
+            assert_eq!(testcase.as_alias_iter(&vec![], false, true).count(), 3);
+            // names and wildcards do not match
+            let patterns = vec!["Northern City".to_string(), "nor".to_string(), "*th*".to_string()];
+            assert_eq!(testcase.as_iter(&patterns, false, true).count(), 2);
+            assert!(testcase.as_alias_iter(&patterns, false, true).next().is_none());
+            // aliases ignore case unless they are case-sensitive
+            let patterns = vec!["North".to_string()];
+            assert_eq!(testcase.as_alias_iter(&patterns, false, true).count(), 1);
+            assert!(testcase.as_alias_iter(&patterns, true, true).next().is_none());
+            // exact alias
+            let patterns = vec!["north".to_string(), "South".to_string()];
+            let mut result = testcase.as_alias_iter(&patterns, true, true);
+            assert_eq!(&result.next().unwrap().name, "Northern City");
+            assert!(result.next().is_none());
+            let mut result = testcase.as_alias_iter(&patterns, false, true);
+            assert_eq!(&result.next().unwrap().name, "Northern City");
+            assert_eq!(&result.next().unwrap().name, "Southern City");
+            assert!(result.next().is_none());
+        }
+
         #[test]
         fn from() {
             let md = LocationMd {
@@ -2,13 +2,17 @@
 //!
 use super::*;
 
-pub use converters::{from_bytes, to_bytes, to_json};
+mod darksky;
+
+pub use converters::{from_bytes, from_darksky, to_bytes, to_json};
 mod converters {
     //! Convert [History] to and from a `JSON` byte stream.
     //!
     use super::*;
     use chrono::{DateTime, NaiveDate};
+    use darksky::DarkskyHistory;
     use serde::{Deserialize, Serialize};
+    use serde_json::Value;
 
     /// This is the structure used to serialize and deserialize [History].
     #[derive(Debug, Deserialize, Serialize)]
@@ -150,22 +154,87 @@ mod converters {
         Ok(to_json(history)?.into_bytes())
     }
 
+    /// The layouts of weather history documents found in archives.
+    #[derive(Debug, PartialEq)]
+    enum DocFormat {
+        /// The document is a [HistoryDoc].
+        History,
+        /// The document is a legacy *DarkSky* document.
+        Darksky,
+    }
+    impl DocFormat {
+        /// Parse a weather history document and detect its layout.
+        ///
+        /// Only *DarkSky* documents have `daily` and `hourly` history, anything else is treated as a
+        /// [HistoryDoc]. The parsed document is returned so the bytes are only parsed once.
+        ///
+        /// # Arguments
+        ///
+        /// * `bytes` is the weather history document.
+        fn parse(bytes: &[u8]) -> serde_json::Result<(Self, Value)> {
+            let document = serde_json::from_slice::<Value>(bytes)?;
+            match document.get("daily").is_some() && document.get("hourly").is_some() {
+                true => Ok((Self::Darksky, document)),
+                false => Ok((Self::History, document)),
+            }
+        }
+    }
+
     /// Convert a sequence of bytes into a [History].
     ///
+    /// Both the current and legacy *DarkSky* document layouts are supported.
+    ///
     /// # Arguments
     ///
     /// * `alias` is the locations alias name.
     /// * `bytes` will be converted to a [History] instance.
     pub fn from_bytes(alias: &str, bytes: &[u8]) -> Result<History> {
-        match serde_json::from_slice::<HistoryDoc>(bytes) {
-            Ok(history_doc) => Ok(history_doc.to_history(alias)),
-            Err(err) => {
-                let reason = format!("Yikes... Error creating History for {} ({})", alias, err);
-                Err(Error::from(reason))
+        let history_error = |err: serde_json::Error| {
+            let reason = format!("Yikes... Error creating History for {} ({})", alias, err);
+            Error::from(reason)
+        };
+        match DocFormat::parse(bytes).map_err(history_error)? {
+            (DocFormat::History, document) => match serde_json::from_value::<HistoryDoc>(document) {
+                Ok(history_doc) => Ok(history_doc.to_history(alias)),
+                Err(err) => Err(history_error(err)),
+            },
+            (DocFormat::Darksky, document) => {
+                let darksky = DarkskyHistory::from_value(document).map_err(|err| {
+                    let reason = format!("Error creating DarkSky history for {} ({})", alias, err);
+                    Error::from(reason)
+                })?;
+                match darksky.date() {
+                    Some(date) => Ok(darksky.as_history(alias, &date)),
+                    None => Err(Error::from(format!("DarkSky history for {} does not have a date.", alias))),
+                }
             }
         }
     }
 
+    /// Convert a sequence of *DarkSky* bytes into a [History].
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` is the locations alias name.
+    /// * `date` is the weather history date.
+    /// * `bytes` is the *DarkSky* document that will be converted to a [History] instance.
+    pub fn from_darksky(alias: &str, date: &NaiveDate, bytes: &[u8]) -> Result<History> {
+        Ok(darksky_doc(alias, bytes)?.as_history(alias, date))
+    }
+
+    /// Deserialize a sequence of bytes into a *DarkSky* document.
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` is the locations alias name.
+    /// * `bytes` is the *DarkSky* document.
+    fn darksky_doc(alias: &str, bytes: &[u8]) -> Result<DarkskyHistory> {
+        DarkskyHistory::from_bytes(bytes).map_err(|err| {
+            let reason = format!("Error creating DarkSky history for {} ({})", alias, err);
+            Error::from(reason)
+        })
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -222,5 +291,39 @@ mod converters {
             assert_eq!(history.visibility, testcase.visibility);
             assert_eq!(history.description, testcase.description);
         }
+
+        #[test]
+        fn document_formats() {
+            let timeline = r#"{
+                "date": "2023-09-12", "sunrise": 1694526300, "sunset": 1694572080, "moon": 0.9,
+                "tempmax": 77.0, "tempmin": 56.0, "tempmean": 65.0, "dewpoint": 50.3, "precipprob": 0.08,
+                "precip": 0.75, "preciptype": "rain", "humidity": 0.43, "pressure": 1015.2, "cloud": 0.07,
+                "uv": 5.0, "vis": 10.0, "wind": 6.0, "windgust": 8.0, "winddir": 337, "summary": "Partly cloudy."
+            }"#;
+            let darksky = r#"{
+                "latitude": 47.5, "longitude": -122.3, "offset": -7, "timezone": "America/Los_Angeles",
+                "daily": {"data": [{
+                    "time": 1694502000, "sunriseTime": 1694526300, "sunsetTime": 1694572080, "moonPhase": 0.9,
+                    "temperatureHigh": 77.0, "temperatureLow": 56.0, "dewPoint": 50.3, "precipProbability": 0.08,
+                    "precipIntensity": 0.03125, "precipType": "rain", "humidity": 0.43, "pressure": 1015.2,
+                    "cloudCover": 0.07, "uvIndex": 5, "visibility": 10.0, "windSpeed": 6.0, "windGust": 8.0,
+                    "windBearing": 337, "summary": "Partly cloudy."
+                }]},
+                "hourly": {"data": [{"temperature": 60.0}, {"temperature": 70.0}]}
+            }"#;
+            let format = |bytes: &[u8]| DocFormat::parse(bytes).map(|(format, _)| format);
+            assert_eq!(format(timeline.as_bytes()).unwrap(), DocFormat::History);
+            assert_eq!(format(darksky.as_bytes()).unwrap(), DocFormat::Darksky);
+            assert!(format(b"not json").is_err());
+            assert!(from_bytes("test", b"not json").unwrap_err().to_string().contains("Error creating History"));
+            let timeline = from_bytes("test", timeline.as_bytes()).unwrap();
+            let darksky = from_bytes("test", darksky.as_bytes()).unwrap();
+            assert_eq!(darksky.date, get_date(2023, 9, 12));
+            assert_eq!(format!("{:?}", darksky), format!("{:?}", timeline));
+            // darksky documents must have daily history
+            let empty = r#"{"latitude": 0, "longitude": 0, "offset": 0, "timezone": "UTC", "daily": {"data": []},
+                "hourly": {"data": []}}"#;
+            assert!(from_bytes("test", empty.as_bytes()).is_err());
+        }
     }
 }
@@ -0,0 +1,455 @@
+//! The legacy *DarkSky* weather history document.
+//!
+//! Weather history archives created before the *Timeline* API was used contain *DarkSky* documents.
+//! *DarkSky* history is sparse so values missing from the daily history are derived from the hourly history.
+use super::*;
+use chrono::{DateTime, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+/// The *DarkSky* document.
+pub(super) struct DarkskyHistory {
+    daily: DarkskyDaily,
+    hourly: DarkskyHourly,
+    latitude: f64,
+    longitude: f64,
+    offset: i64,
+    timezone: String,
+}
+/// Returns a reference to the daily weather history.
+macro_rules! daily {
+    ($self:expr) => {
+        &$self.daily.data[0]
+    };
+}
+/// Returns an iterator to the hourly weather history.
+macro_rules! hourly_iter {
+    ($self:expr) => {
+        $self.hourly.data.iter()
+    };
+}
+/// Consolidate what happens if weather history cannot be derived.
+macro_rules! map_or_default {
+    ($option:expr, $what:literal) => {
+        match $option {
+            Some(value) => Some(value),
+            None => {
+                log::trace!("{} has no value, using default.", $what);
+                None
+            }
+        }
+    };
+}
+impl DarkskyHistory {
+    /// Deserialize a sequence of bytes into the *DarkSky* document.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` is the *DarkSky* `JSON` document.
+    pub(super) fn from_bytes(bytes: &[u8]) -> std::result::Result<Self, String> {
+        Self::checked(serde_json::from_slice::<DarkskyHistory>(bytes))
+    }
+    /// Deserialize an already parsed `JSON` value into the *DarkSky* document.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` is the *DarkSky* `JSON` document.
+    pub(super) fn from_value(value: serde_json::Value) -> std::result::Result<Self, String> {
+        Self::checked(serde_json::from_value::<DarkskyHistory>(value))
+    }
+    /// Verify the deserialized *DarkSky* document has daily history.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` is the outcome of deserializing the document.
+    fn checked(result: serde_json::Result<Self>) -> std::result::Result<Self, String> {
+        match result {
+            Ok(darksky) if darksky.daily.data.is_empty() => Err("daily history is missing".to_string()),
+            Ok(darksky) => Ok(darksky),
+            Err(err) => Err(err.to_string()),
+        }
+    }
+    /// Get the weather history date from the daily time.
+    ///
+    /// *DarkSky* daily time is midnight at the location so the timezone offset is added to get the local date.
+    pub(super) fn date(&self) -> Option<NaiveDate> {
+        let time = daily!(self).time?;
+        DateTime::from_timestamp(time + self.offset * 3600, 0).map(|dt| dt.date_naive())
+    }
+    /// Convert *DarkSky* weather history into [History].
+    ///
+    /// # Arguments
+    ///
+    /// * `alias` is the location alias name.
+    /// * `date` is the weather history date.
+    pub(super) fn as_history(&self, alias: &str, date: &NaiveDate) -> History {
+        let daily = daily!(self);
+        History {
+            alias: alias.to_string(),
+            date: *date,
+            temperature_high: self.temperature_high(),
+            temperature_low: self.temperature_low(),
+            temperature_mean: self.temperature_mean(),
+            dew_point: self.dew_point(),
+            humidity: self.humidity(),
+            precipitation_chance: self.precipitation_chance(),
+            precipitation_type: daily.precipType.clone(),
+            precipitation_amount: self.precip(),
+            wind_speed: self.wind_speed(),
+            wind_gust: self.wind_gust(),
+            wind_direction: self.wind_bearing(),
+            cloud_cover: self.cloud_cover(),
+            pressure: self.pressure(),
+            uv_index: self.uv_index(),
+            sunrise: daily
+                .sunriseTime
+                .and_then(|ts| DateTime::from_timestamp(ts, 0).map(|dt| dt.naive_utc())),
+            sunset: daily
+                .sunsetTime
+                .and_then(|ts| DateTime::from_timestamp(ts, 0).map(|dt| dt.naive_utc())),
+            moon_phase: daily.moonPhase,
+            visibility: self.visibility(),
+            description: daily.summary.clone(),
+        }
+    }
+    /// Extracts the daily high temperature
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    ///
+    /// * daily `temperatureHigh`
+    /// * daily `temperatureMax`
+    /// * daily `apparentTemperatureHigh`
+    /// * daily `apparentTemperatureMax`
+    /// * hourly `temperature`
+    fn temperature_high(&self) -> Option<f64> {
+        let daily = daily!(self);
+        match daily.temperatureHigh {
+            Some(t) => Some(t),
+            None => match daily.temperatureMax {
+                Some(t) => Some(t),
+                None => match daily.apparentTemperatureHigh {
+                    Some(t) => Some(t),
+                    None => match daily.apparentTemperatureMax {
+                        Some(t) => Some(t),
+                        None => {
+                            let temp = hourly_iter!(self).filter_map(|h| h.temperature).reduce(f64::max);
+                            map_or_default!(temp, "temperature_max")
+                        }
+                    },
+                },
+            },
+        }
+    }
+    /// Extracts the daily low temperature
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    ///
+    /// * daily `temperatureLow`
+    /// * daily `temperatureMin`
+    /// * daily `apparentTemperatureLow`
+    /// * daily `apparentTemperatureMin`
+    /// * hourly `temperature`
+    fn temperature_low(&self) -> Option<f64> {
+        let daily = daily!(self);
+        match daily.temperatureLow {
+            Some(t) => Some(t),
+            None => match daily.temperatureMin {
+                Some(t) => Some(t),
+                None => match daily.apparentTemperatureLow {
+                    Some(t) => Some(t),
+                    None => match daily.apparentTemperatureMin {
+                        Some(t) => Some(t),
+                        None => {
+                            let temp = hourly_iter!(self).filter_map(|h| h.temperature).reduce(f64::min);
+                            map_or_default!(temp, "temperature_min")
+                        }
+                    },
+                },
+            },
+        }
+    }
+    /// Calculate the daily mean temperature from hourly history.
+    fn temperature_mean(&self) -> Option<f64> {
+        let temps: Vec<f64> = hourly_iter!(self).filter_map(|h| h.temperature).collect();
+        if temps.is_empty() {
+            log::trace!("temperature_mean has no value, using default");
+            None
+        } else {
+            let mean_temp = temps.iter().sum::<f64>() / temps.len() as f64;
+            Some((mean_temp * 100.0).round() / 100.0)
+        }
+    }
+    /// Extract the daily dew point.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `dewPoint`
+    /// * hourly `dewPoint`
+    fn dew_point(&self) -> Option<f64> {
+        match daily!(self).dewPoint {
+            Some(dew_point) => Some(dew_point),
+            None => {
+                let dew_point = hourly_iter!(self).filter_map(|h| h.dewPoint).reduce(f64::max);
+                map_or_default!(dew_point, "dew_point")
+            }
+        }
+    }
+    /// Extract the daily humidity.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `humidity`
+    /// * hourly `humidity`
+    fn humidity(&self) -> Option<f64> {
+        match daily!(self).humidity {
+            Some(humidity) => Some(humidity),
+            None => {
+                let humidity = hourly_iter!(self).filter_map(|h| h.humidity).reduce(f64::max);
+                map_or_default!(humidity, "humidity")
+            }
+        }
+    }
+    /// Extract the chance of precipitation.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `precipProbability`
+    /// * hourly `precipProbability`
+    fn precipitation_chance(&self) -> Option<f64> {
+        match daily!(self).precipProbability {
+            Some(chance) => Some(chance),
+            None => {
+                let probabilities: Vec<f64> = hourly_iter!(self).filter_map(|h| h.precipProbability).collect();
+                if probabilities.is_empty() {
+                    log::trace!("probabilities has no value, using default.");
+                    None
+                } else {
+                    let chance = probabilities.iter().sum::<f64>() / probabilities.len() as f64;
+                    Some((chance * 100.0).round() / 100.0)
+                }
+            }
+        }
+    }
+    /// Extract the amount of precipitation.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `precipIntensity`
+    /// * hourly `precipIntensity`
+    fn precip(&self) -> Option<f64> {
+        let precip = match daily!(self).precipIntensity {
+            Some(p) => p * 24f64,
+            None => hourly_iter!(self).filter_map(|h| h.precipIntensity).sum::<f64>(),
+        };
+        Some(precip)
+    }
+    /// Extract the daily wind speed.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `windSpeed`
+    /// * hourly `windSpeed`
+    fn wind_speed(&self) -> Option<f64> {
+        match daily!(self).windSpeed {
+            Some(speed) => Some(speed),
+            None => {
+                let speeds = hourly_iter!(self).filter_map(|md| md.windSpeed).collect::<Vec<f64>>();
+                if speeds.is_empty() {
+                    log::trace!("wind_speed has no value, using default.");
+                    None
+                } else {
+                    let speed = speeds.iter().sum::<f64>() / speeds.len() as f64;
+                    Some((speed * 100.0).round() / 100.0)
+                }
+            }
+        }
+    }
+    /// Extract the daily wind gust speed.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `windGust`
+    /// * hourly `windGust`
+    fn wind_gust(&self) -> Option<f64> {
+        match daily!(self).windGust {
+            Some(gust) => Some(gust),
+            None => {
+                let gust = hourly_iter!(self).filter_map(|md| md.windGust).reduce(f64::max);
+                map_or_default!(gust, "wind_gust")
+            }
+        }
+    }
+    /// Extract the daily wind bearing.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `windBearing`
+    /// * hourly `windBearing`
+    fn wind_bearing(&self) -> Option<i64> {
+        match daily!(self).windBearing {
+            Some(bearing) => Some(bearing),
+            None => {
+                let bearings = hourly_iter!(self).filter_map(|md| md.windBearing).collect::<Vec<i64>>();
+                if bearings.is_empty() {
+                    log::trace!("wind_bearing has no value, using default.");
+                    None
+                } else {
+                    let bearing = bearings.iter().sum::<i64>() as f64 / bearings.len() as f64;
+                    Some(bearing.round() as i64)
+                }
+            }
+        }
+    }
+    /// Extract the daily cloud cover.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `cloudCover`
+    /// * hourly `cloudCover`
+    fn cloud_cover(&self) -> Option<f64> {
+        match daily!(self).cloudCover {
+            Some(cover) => Some(cover),
+            None => {
+                let covers = hourly_iter!(self).filter_map(|md| md.cloudCover).collect::<Vec<f64>>();
+                if covers.is_empty() {
+                    log::trace!("cloud_cover has no value, using default.");
+                    None
+                } else {
+                    let cover = covers.iter().sum::<f64>() / covers.len() as f64;
+                    Some((cover * 100.0).round() / 100.0)
+                }
+            }
+        }
+    }
+    /// Extract the daily atmospheric pressure.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `pressure`
+    /// * hourly `pressure`
+    fn pressure(&self) -> Option<f64> {
+        match daily!(self).pressure {
+            Some(pressure) => Some(pressure),
+            None => {
+                let pressures = hourly_iter!(self).filter_map(|md| md.pressure).collect::<Vec<f64>>();
+                if pressures.is_empty() {
+                    log::trace!("pressure has no value, using default.");
+                    None
+                } else {
+                    let pressure = pressures.iter().sum::<f64>() / pressures.len() as f64;
+                    Some((pressure * 10000.0).round() / 10000.0)
+                }
+            }
+        }
+    }
+    /// Extract the daily UV index.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    ///
+    /// * daily `uvIndex`
+    /// * hourly `uvIndex`
+    fn uv_index(&self) -> Option<f64> {
+        match daily!(self).uvIndex {
+            Some(uv_index) => Some(uv_index as f64),
+            None => {
+                let uv_indexes = hourly_iter!(self).filter_map(|md| md.uvIndex).collect::<Vec<i64>>();
+                if uv_indexes.is_empty() {
+                    log::trace!("uv_index has no value, using default.");
+                    None
+                } else {
+                    let uv_index = uv_indexes.iter().sum::<i64>() as f64 / uv_indexes.len() as f64;
+                    Some((uv_index * 100.0).round() / 100.0)
+                }
+            }
+        }
+    }
+    /// Extract the daily atmospheric pressure.
+    ///
+    /// *DarkSky* history is sparse so the following attributes are examined from first to last.
+    /// * daily `visibility`
+    /// * hourly `visibility`
+    fn visibility(&self) -> Option<f64> {
+        match daily!(self).visibility {
+            Some(visibility) => Some(visibility),
+            None => {
+                let visibilities = hourly_iter!(self).filter_map(|md| md.visibility).collect::<Vec<f64>>();
+                if visibilities.is_empty() {
+                    log::trace!("visibility has no value, using default.");
+                    None
+                } else {
+                    let visibility = visibilities.iter().sum::<f64>() / visibilities.len() as f64;
+                    Some((visibility * 100.0).round() / 100.0)
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+/// The *DarkSky* hourly weather history data.
+struct DarkskyHourly {
+    data: Vec<HourlyMd>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+/// The *DarkSky* hourly weather metadata.
+struct HourlyMd {
+    apparentTemperature: Option<f64>,
+    cloudCover: Option<f64>,
+    dewPoint: Option<f64>,
+    humidity: Option<f64>,
+    icon: Option<String>,
+    precipIntensity: Option<f64>,
+    precipProbability: Option<f64>,
+    pressure: Option<f64>,
+    summary: Option<String>,
+    temperature: Option<f64>,
+    time: Option<i64>,
+    uvIndex: Option<i64>,
+    visibility: Option<f64>,
+    windBearing: Option<i64>,
+    windGust: Option<f64>,
+    windSpeed: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+/// The *DarkSky* daily weather history data.
+struct DarkskyDaily {
+    data: Vec<DailyMd>,
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize, Deserialize)]
+/// The *DarkSky* daily weather metadata.
+struct DailyMd {
+    apparentTemperatureHigh: Option<f64>,
+    apparentTemperatureHighTime: Option<i64>,
+    apparentTemperatureLow: Option<f64>,
+    apparentTemperatureLowTime: Option<i64>,
+    apparentTemperatureMax: Option<f64>,
+    apparentTemperatureMaxTime: Option<i64>,
+    apparentTemperatureMin: Option<f64>,
+    apparentTemperatureMinTime: Option<i64>,
+    cloudCover: Option<f64>,
+    dewPoint: Option<f64>,
+    humidity: Option<f64>,
+    icon: Option<String>,
+    moonPhase: Option<f64>,
+    precipIntensity: Option<f64>,
+    precipIntensityMax: Option<f64>,
+    precipIntensityMaxTime: Option<f64>,
+    precipProbability: Option<f64>,
+    precipType: Option<String>,
+    pressure: Option<f64>,
+    summary: Option<String>,
+    sunriseTime: Option<i64>,
+    sunsetTime: Option<i64>,
+    temperatureHigh: Option<f64>,
+    temperatureHighTime: Option<i64>,
+    temperatureLow: Option<f64>,
+    temperatureLowTime: Option<i64>,
+    temperatureMax: Option<f64>,
+    temperatureMaxTime: Option<i64>,
+    temperatureMin: Option<f64>,
+    temperatureMinTime: Option<i64>,
+    time: Option<i64>,
+    uvIndex: Option<i64>,
+    uvIndexTime: Option<i64>,
+    visibility: Option<f64>,
+    windBearing: Option<i64>,
+    windGust: Option<f64>,
+    windGustTime: Option<i64>,
+    windSpeed: Option<f64>,
+}
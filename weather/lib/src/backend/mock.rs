@@ -0,0 +1,341 @@
+//! An in-memory implementation of weather data.
+//!
+//! The mock allows the weather data `API` to be exercised without reading or writing the filesystem.
+use super::*;
+use chrono::NaiveDate;
+use std::cell::RefCell;
+
+/// The in-memory weather data backend.
+#[derive(Debug, Default)]
+pub struct MockDataAPI {
+    /// The weather data configuration.
+    config: Config,
+    /// The weather data locations.
+    locations: RefCell<Vec<Location>>,
+    /// The weather data histories for all locations.
+    histories: RefCell<Vec<History>>,
+}
+impl MockDataAPI {
+    /// Create an empty instance of the mock weather data.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Seed the mock with a location.
+    ///
+    /// # Arguments
+    ///
+    /// - `location` is the location that will be added.
+    ///
+    pub fn with_location(self, location: Location) -> Self {
+        self.locations.borrow_mut().push(location);
+        self
+    }
+    /// Seed the mock with weather histories. The history alias identifies the location.
+    ///
+    /// # Arguments
+    ///
+    /// - `histories` are the weather histories that will be added.
+    ///
+    pub fn with_histories(self, histories: Vec<History>) -> Self {
+        self.histories.borrow_mut().extend(histories);
+        self
+    }
+    /// Get the dates of the histories for a location.
+    ///
+    /// # Arguments
+    ///
+    /// - `alias` is the location identifier.
+    ///
+    fn dates(&self, alias: &str) -> Vec<NaiveDate> {
+        self.histories.borrow().iter().filter(|history| history.alias == alias).map(|history| history.date).collect()
+    }
+}
+impl DataAdapter for MockDataAPI {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+    fn add_daily_histories(&self, histories: &DailyHistories, progress: &mut dyn FnMut(usize, usize)) -> Result<usize> {
+        let dates = self.dates(&histories.location.alias);
+        let additions: Vec<&History> =
+            histories.histories.iter().filter(|history| !dates.contains(&history.date)).collect();
+        let total = additions.len();
+        for (added, history) in additions.into_iter().enumerate() {
+            self.histories.borrow_mut().push(History { alias: histories.location.alias.clone(), ..history.clone() });
+            progress(added + 1, total);
+        }
+        Ok(total)
+    }
+    fn daily_histories(&self, location: Location, date_range: DateRange) -> Result<DailyHistories> {
+        let mut histories: Vec<History> = self
+            .histories
+            .borrow()
+            .iter()
+            .filter(|history| history.alias == location.alias && date_range.covers(&history.date))
+            .cloned()
+            .collect();
+        histories.sort_by_key(|history| history.date);
+        Ok(DailyHistories { location, histories })
+    }
+    fn contains_history(&self, alias: &str, date: &NaiveDate) -> Result<bool> {
+        Ok(self.dates(alias).contains(date))
+    }
+    fn count_histories(&self, alias: &str) -> Result<usize> {
+        Ok(self.dates(alias).len())
+    }
+    fn history_dates(&self, criteria: DataCriteria) -> Result<Vec<HistoryDates>> {
+        let history_dates = self
+            .locations(criteria)?
+            .into_iter()
+            .map(|location| {
+                let history_dates = DateRange::from_dates(self.dates(&location.alias));
+                HistoryDates { location, history_dates }
+            })
+            .collect();
+        Ok(history_dates)
+    }
+    fn history_summaries(&self, criteria: DataCriteria) -> Result<Vec<HistorySummaries>> {
+        let history_summaries = self
+            .locations(criteria)?
+            .into_iter()
+            .map(|location| {
+                let count = self.dates(&location.alias).len();
+                HistorySummaries { location, count, overall_size: None, raw_size: None, store_size: None }
+            })
+            .collect();
+        Ok(history_summaries)
+    }
+    fn add_location(&self, location: Location) -> Result<()> {
+        let mut locations = self.locations.borrow_mut();
+        match locations.iter().any(|md| md.alias == location.alias) {
+            true => Err(Error::from(format!("{} already uses the '{}' alias name", location.name, location.alias))),
+            false => {
+                locations.push(location);
+                Ok(())
+            }
+        }
+    }
+    fn locations(&self, criteria: DataCriteria) -> Result<Vec<Location>> {
+        let prepare = |text: &str| if criteria.icase { text.to_lowercase() } else { text.to_string() };
+        let patterns: Vec<String> = criteria.filters.iter().map(|filter| prepare(filter)).collect();
+        let mut locations: Vec<Location> = self
+            .locations
+            .borrow()
+            .iter()
+            .filter(|location| {
+                let name = prepare(&location.name);
+                let alias = prepare(&location.alias);
+                match criteria.alias_only {
+                    true => patterns.is_empty() || patterns.contains(&alias),
+                    false => patterns.is_empty() || patterns.iter().any(|pattern| is_match(&name, &alias, pattern)),
+                }
+            })
+            .cloned()
+            .collect();
+        if criteria.sort {
+            locations.sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+        }
+        Ok(locations)
+    }
+    fn search(&self, _criteria: LocationCriteria) -> Result<Vec<Location>> {
+        Ok(vec![])
+    }
+}
+
+/// Test if a location name or alias matches a pattern.
+///
+/// The pattern can include a leading and/or trailing `*` to match any characters.
+///
+/// # Arguments
+///
+/// * `name` is the location name that will be matched against the pattern.
+/// * `alias` is the location alias that will be matched against the pattern.
+/// * `pattern` is what will be matched against the location name and alias.
+fn is_match(name: &str, alias: &str, pattern: &str) -> bool {
+    match (pattern.strip_prefix('*'), pattern.strip_suffix('*')) {
+        _ if pattern == "*" => true,
+        (Some(suffix), Some(_)) => {
+            let slice = &suffix[..suffix.len() - 1];
+            name.contains(slice) || alias.contains(slice)
+        }
+        (Some(suffix), None) => name.ends_with(suffix) || alias.ends_with(suffix),
+        (None, Some(prefix)) => name.starts_with(prefix) || alias.starts_with(prefix),
+        (None, None) => name == pattern || alias == pattern,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::WeatherData;
+    use toolslib::date_time::get_date;
+
+    fn location(name: &str, alias: &str) -> Location {
+        Location {
+            name: name.to_string(),
+            alias: alias.to_string(),
+            longitude: "-111.8315".to_string(),
+            latitude: "33.4152".to_string(),
+            tz: "America/Phoenix".to_string(),
+        }
+    }
+
+    fn history(alias: &str, date: NaiveDate) -> History {
+        History { alias: alias.to_string(), date, temperature_high: Some(70.0), ..Default::default() }
+    }
+
+    fn weather_data() -> WeatherData {
+        let mock = MockDataAPI::new()
+            .with_location(location("Southern City", "south"))
+            .with_location(location("Northern City", "north"))
+            .with_location(location("Middle Town", "middle"))
+            .with_histories(vec![
+                history("north", get_date(2024, 1, 3)),
+                history("north", get_date(2024, 1, 1)),
+                history("north", get_date(2024, 1, 2)),
+                history("south", get_date(2024, 1, 1)),
+            ]);
+        WeatherData::from(mock)
+    }
+
+    #[test]
+    fn locations() {
+        let testcase = weather_data();
+        let criteria = |filters: &[&str]| DataCriteria {
+            filters: filters.iter().map(|filter| filter.to_string()).collect(),
+            icase: true,
+            sort: true,
+            alias_only: false,
+        };
+        let locations = testcase.get_locations(criteria(&[])).unwrap();
+        let names: Vec<&str> = locations.iter().map(|location| location.name.as_str()).collect();
+        assert_eq!(names, vec!["Middle Town", "Northern City", "Southern City"]);
+        let locations = testcase.get_locations(criteria(&["*TH*"])).unwrap();
+        assert_eq!(locations.len(), 2);
+        let locations = testcase.get_locations(criteria(&["mid*"])).unwrap();
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].alias, "middle");
+        // alias only matches exactly
+        let locations = testcase.get_locations(criteria(&["*th*", "Middle Town"]).alias_only(true)).unwrap();
+        assert!(locations.is_empty());
+        let locations = testcase.get_locations(criteria(&["NORTH", "sou"]).alias_only(true)).unwrap();
+        assert_eq!(locations.len(), 1);
+        assert_eq!(locations[0].alias, "north");
+        assert_eq!(testcase.get_location_by_id("south").unwrap().name, "Southern City");
+        assert!(testcase.get_location_by_id("sou").is_err());
+    }
+
+    #[test]
+    fn daily_history() {
+        let testcase = weather_data();
+        let criteria = DataCriteria { filters: vec!["north".to_string()], icase: true, sort: false, alias_only: false };
+        let date_range = DateRange::new(get_date(2024, 1, 2), get_date(2024, 1, 5));
+        let daily_histories = testcase.get_daily_history(criteria, date_range).unwrap();
+        assert_eq!(daily_histories.location.alias, "north");
+        let dates: Vec<NaiveDate> = daily_histories.histories.iter().map(|history| history.date).collect();
+        assert_eq!(dates, vec![get_date(2024, 1, 2), get_date(2024, 1, 3)]);
+        assert_eq!(daily_histories.histories[0].temperature_high, Some(70.0));
+        // multiple locations are an error
+        let criteria = DataCriteria { filters: vec!["*th*".to_string()], icase: true, sort: false, alias_only: false };
+        let date_range = DateRange::new(get_date(2024, 1, 1), get_date(2024, 1, 1));
+        assert!(testcase.get_daily_history(criteria, date_range).is_err());
+    }
+
+    #[test]
+    fn histories_by_year() {
+        let testcase = weather_data();
+        let daily_histories = DailyHistories {
+            location: location("Northern City", "north"),
+            histories: vec![history("north", get_date(2023, 12, 31)), history("north", get_date(2023, 12, 30))],
+        };
+        testcase.add_histories(daily_histories).unwrap();
+        let criteria =
+            || DataCriteria { filters: vec!["north".to_string()], icase: true, sort: false, alias_only: false };
+        let date_range = DateRange::new(get_date(2023, 1, 1), get_date(2024, 12, 31));
+        let testcase_by_year = testcase.get_histories_by_year(criteria(), date_range).unwrap();
+        assert_eq!(testcase_by_year.keys().copied().collect::<Vec<i32>>(), vec![2023, 2024]);
+        assert_eq!(testcase_by_year[&2023].len(), 2);
+        assert_eq!(testcase_by_year[&2024].len(), 3);
+        assert_eq!(testcase_by_year[&2023][0].date, get_date(2023, 12, 30));
+        assert_eq!(testcase_by_year[&2024][2].date, get_date(2024, 1, 3));
+        // years without histories are not included
+        let date_range = DateRange::new(get_date(2024, 1, 2), get_date(2025, 12, 31));
+        let testcase_by_year = testcase.get_histories_by_year(criteria(), date_range).unwrap();
+        assert_eq!(testcase_by_year.keys().copied().collect::<Vec<i32>>(), vec![2024]);
+        assert_eq!(testcase_by_year[&2024].len(), 2);
+    }
+
+    #[test]
+    fn validated_daily_history() {
+        let testcase = weather_data();
+        let daily_histories = DailyHistories {
+            location: location("Middle Town", "middle"),
+            histories: vec![History { humidity: Some(1.5), ..history("middle", get_date(2024, 1, 1)) }],
+        };
+        testcase.add_histories(daily_histories).unwrap();
+        let criteria =
+            DataCriteria { filters: vec!["middle".to_string()], icase: true, sort: false, alias_only: false };
+        let date_range = DateRange::new(get_date(2024, 1, 1), get_date(2024, 1, 1));
+        // the anomalous value is a warning not an error
+        let (daily_histories, failures, warnings) =
+            testcase.get_validated_daily_history(criteria, date_range).unwrap();
+        assert!(failures.is_empty());
+        assert_eq!(daily_histories.histories.len(), 1);
+        assert_eq!(daily_histories.histories[0].humidity, Some(1.5));
+        assert_eq!(warnings, validate_history(&daily_histories.histories[0]));
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn add_histories() {
+        let testcase = weather_data();
+        let daily_histories = DailyHistories {
+            location: location("Middle Town", "middle"),
+            histories: vec![history("middle", get_date(2024, 2, 1)), history("middle", get_date(2024, 2, 2))],
+        };
+        let mut events = vec![];
+        let added = testcase
+            .add_histories_with_progress(daily_histories, &mut |added, total| events.push((added, total)))
+            .unwrap();
+        assert_eq!(added, 2);
+        assert_eq!(events, vec![(1, 2), (2, 2)]);
+        let summaries = testcase.get_history_summary(DataCriteria::default()).unwrap();
+        let counts: Vec<(&str, usize)> =
+            summaries.iter().map(|summary| (summary.location.alias.as_str(), summary.count)).collect();
+        assert_eq!(counts, vec![("middle", 2), ("north", 3), ("south", 1)]);
+        assert_eq!(testcase.count_histories("middle").unwrap(), 2);
+        let history_dates = testcase.get_history_dates(DataCriteria::default()).unwrap();
+        assert_eq!(history_dates[1].history_dates.len(), 1);
+        assert_eq!(history_dates[1].history_dates[0].from, get_date(2024, 1, 1));
+        assert_eq!(history_dates[1].history_dates[0].to, get_date(2024, 1, 3));
+        assert!(testcase.add_location(location("Middle Town", "middle")).is_err());
+    }
+
+    #[test]
+    fn available_fields() {
+        let testcase = weather_data();
+        let criteria = |filter: &str| DataCriteria {
+            filters: vec![filter.to_string()],
+            icase: true,
+            sort: false,
+            alias_only: false,
+        };
+        assert_eq!(testcase.available_fields(criteria("north")).unwrap(), vec!["temperature_high"]);
+        // a field is available if any history has a value
+        let daily_histories = DailyHistories {
+            location: location("Southern City", "south"),
+            histories: vec![History {
+                alias: "south".to_string(),
+                date: get_date(2024, 1, 2),
+                description: Some("sunny".to_string()),
+                ..Default::default()
+            }],
+        };
+        testcase.add_histories(daily_histories).unwrap();
+        let fields = testcase.available_fields(criteria("south")).unwrap();
+        assert_eq!(fields, vec!["temperature_high", "description"]);
+        assert!(!fields.contains(&"temperature_low".to_string()));
+        // locations without histories have no fields
+        assert!(testcase.available_fields(criteria("middle")).unwrap().is_empty());
+        assert!(testcase.available_fields(criteria("*th*")).is_err());
+    }
+}
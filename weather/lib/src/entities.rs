@@ -1,9 +1,12 @@
 //! Structures used by the weather data `API`s.
 
 use chrono::{NaiveDate, NaiveDateTime};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Used by front-ends to identify locations.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct DataCriteria {
     /// The locations of interest.
     pub filters: Vec<String>,
@@ -11,21 +14,28 @@ pub struct DataCriteria {
     pub icase: bool,
     /// If `true` locations will be sorted by name.
     pub sort: bool,
+    /// If `true` the location filters must exactly match a location alias, otherwise they match the name or alias.
+    pub alias_only: bool,
 }
 impl DataCriteria {
     pub fn filters(mut self, filters: Vec<String>) -> Self {
         self.filters = filters;
         self
     }
+    pub fn alias_only(mut self, alias_only: bool) -> Self {
+        self.alias_only = alias_only;
+        self
+    }
 }
 impl Default for DataCriteria {
     fn default() -> Self {
-        Self { filters: Default::default(), icase: true, sort: true }
+        Self { filters: Default::default(), icase: true, sort: true, alias_only: false }
     }
 }
 
 /// A locations daily weather history.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct DailyHistories {
     /// The location metadata.
     pub location: Location,
@@ -33,8 +43,12 @@ pub struct DailyHistories {
     pub histories: Vec<History>,
 }
 
+/// The dates of weather histories that could not be decoded along with the reason why.
+pub type HistoryFailures = Vec<(NaiveDate, String)>;
+
 /// A locations history dates.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct HistoryDates {
     /// The location metadata.
     pub location: Location,
@@ -43,6 +57,7 @@ pub struct HistoryDates {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 /// A locations history summary.
 pub struct HistorySummaries {
     pub location: Location,
@@ -58,6 +73,7 @@ pub struct HistorySummaries {
 
 /// The data that comprises a location.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Location {
     /// The name of a location.
     pub name: String,
@@ -73,6 +89,7 @@ pub struct Location {
 
 /// A locations history summary.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct HistorySummary {
     /// The location id.
     pub location_id: String,
@@ -85,9 +102,35 @@ pub struct HistorySummary {
     /// The compressed data size of weather data for a location in bytes (may or may not be available).
     pub compressed_size: Option<usize>,
 }
+/// Summaries are added together by summing their counts and sizes.
+///
+/// The location id of the left hand summary is kept. A size that is `None` is treated as `0` unless
+/// both sizes are `None`.
+impl std::ops::AddAssign for HistorySummary {
+    fn add_assign(&mut self, rhs: Self) {
+        fn add_size(lhs: Option<usize>, rhs: Option<usize>) -> Option<usize> {
+            match (lhs, rhs) {
+                (None, None) => None,
+                (lhs, rhs) => Some(lhs.unwrap_or(0) + rhs.unwrap_or(0)),
+            }
+        }
+        self.count += rhs.count;
+        self.overall_size = add_size(self.overall_size, rhs.overall_size);
+        self.raw_size = add_size(self.raw_size, rhs.raw_size);
+        self.compressed_size = add_size(self.compressed_size, rhs.compressed_size);
+    }
+}
+impl std::ops::Add for HistorySummary {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
 
 /// The weather history data.
-#[derive(Debug)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct History {
     /// The location alias name.
     pub alias: String,
@@ -132,6 +175,88 @@ pub struct History {
     /// A summary of the daily weather.
     pub description: Option<String>,
 }
+impl History {
+    /// The names of the history fields that may not have a value.
+    pub const OPTIONAL_FIELDS: [&'static str; 19] = [
+        "temperature_high",
+        "temperature_low",
+        "temperature_mean",
+        "dew_point",
+        "humidity",
+        "precipitation_chance",
+        "precipitation_type",
+        "precipitation_amount",
+        "wind_speed",
+        "wind_gust",
+        "wind_direction",
+        "cloud_cover",
+        "pressure",
+        "uv_index",
+        "sunrise",
+        "sunset",
+        "moon_phase",
+        "visibility",
+        "description",
+    ];
+    /// Get which of the optional fields have a value, in the same order as [History::OPTIONAL_FIELDS].
+    pub fn populated(&self) -> [bool; 19] {
+        [
+            self.temperature_high.is_some(),
+            self.temperature_low.is_some(),
+            self.temperature_mean.is_some(),
+            self.dew_point.is_some(),
+            self.humidity.is_some(),
+            self.precipitation_chance.is_some(),
+            self.precipitation_type.is_some(),
+            self.precipitation_amount.is_some(),
+            self.wind_speed.is_some(),
+            self.wind_gust.is_some(),
+            self.wind_direction.is_some(),
+            self.cloud_cover.is_some(),
+            self.pressure.is_some(),
+            self.uv_index.is_some(),
+            self.sunrise.is_some(),
+            self.sunset.is_some(),
+            self.moon_phase.is_some(),
+            self.visibility.is_some(),
+            self.description.is_some(),
+        ]
+    }
+}
+
+/// Check a history for values that are outside their expected bounds.
+///
+/// A description of each anomalous value is returned, the history is valid if it is empty.
+///
+/// # Arguments
+///
+/// * `history` is the weather history that will be checked.
+pub fn validate_history(history: &History) -> Vec<String> {
+    let mut warnings = vec![];
+    let mut check = |name: &str, value: Option<f64>, min: f64, max: f64| {
+        if let Some(value) = value {
+            if !(min..=max).contains(&value) {
+                warnings.push(format!("{} {} {} is out of bounds.", history.date, name, value));
+            }
+        }
+    };
+    check("humidity", history.humidity, 0.0, 1.0);
+    check("precipitation_chance", history.precipitation_chance, 0.0, 1.0);
+    check("precipitation_amount", history.precipitation_amount, 0.0, f64::MAX);
+    check("wind_speed", history.wind_speed, 0.0, f64::MAX);
+    check("wind_gust", history.wind_gust, 0.0, f64::MAX);
+    check("wind_direction", history.wind_direction.map(|direction| direction as f64), 0.0, 360.0);
+    check("cloud_cover", history.cloud_cover, 0.0, 1.0);
+    check("uv_index", history.uv_index, 0.0, f64::MAX);
+    check("moon_phase", history.moon_phase, 0.0, 1.0);
+    check("visibility", history.visibility, 0.0, f64::MAX);
+    if let (Some(high), Some(low)) = (history.temperature_high, history.temperature_low) {
+        if low > high {
+            warnings.push(format!("{} temperature_low {} is above temperature_high {}.", history.date, low, high));
+        }
+    }
+    warnings
+}
 
 /// For a given `NaiveDate` return the next day `NaiveDate`.
 macro_rules! next_day {
@@ -143,6 +268,7 @@ macro_rules! next_day {
 
 /// A locations weather data history dates.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct DateRanges {
     /// The location id.
     pub location_id: String,
@@ -157,6 +283,7 @@ impl DateRanges {
 
 /// A container for a range of dates.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct DateRange {
     /// The starting date of the range.
     pub from: NaiveDate,
@@ -187,7 +314,7 @@ impl DateRange {
     }
     /// Allow the history range to be iterated over without consuming it.
     pub fn iter(&self) -> DateRangeIterator {
-        DateRangeIterator { from: self.from, thru: self.to }
+        toolslib::date_time::date_range_iter(self.from, self.to)
     }
     /// Returns the dates as a tuple of ISO8601 formatted strings.
     pub fn as_iso8601(&self) -> (String, String) {
@@ -241,7 +368,7 @@ impl IntoIterator for DateRange {
     type Item = NaiveDate;
     type IntoIter = DateRangeIterator;
     fn into_iter(self) -> Self::IntoIter {
-        DateRangeIterator { from: self.from, thru: self.to }
+        toolslib::date_time::date_range_iter(self.from, self.to)
     }
 }
 /// Create an iterator that will return all dates within the range.
@@ -249,39 +376,16 @@ impl IntoIterator for &DateRange {
     type Item = NaiveDate;
     type IntoIter = DateRangeIterator;
     fn into_iter(self) -> Self::IntoIter {
-        DateRangeIterator { from: self.from, thru: self.to }
+        toolslib::date_time::date_range_iter(self.from, self.to)
     }
 }
 
-/// Create the DateRange iterator structure.
-#[derive(Debug)]
-///
-/// # Arguments
-///
-/// * `from` is the starting date.
-/// * `thru` is the inclusive end date.
-pub struct DateRangeIterator {
-    /// The starting date.
-    from: NaiveDate,
-    /// The inclusive end date.
-    thru: NaiveDate,
-}
-/// The implementation of iterating over the date range.
-impl Iterator for DateRangeIterator {
-    type Item = NaiveDate;
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.from > self.thru {
-            None
-        } else {
-            let date = self.from;
-            self.from = next_day!(date);
-            Some(date)
-        }
-    }
-}
+/// The iterator that returns all dates within a date range.
+pub type DateRangeIterator = toolslib::date_time::DateRangeIter;
 
 /// The search criteria for locations.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct LocationCriteria {
     /// The optional city name.
     pub name: Option<String>,
@@ -291,6 +395,36 @@ pub struct LocationCriteria {
     pub limit: usize,
 }
 
+/// The fully resolved weather data configuration.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct ConfigCheck {
+    /// The weather data directory.
+    pub directory: String,
+    /// The weather data backend that will be used (`db` or `archive`).
+    pub backend: String,
+    /// Indicates the weather data will not be modified.
+    pub read_only: bool,
+    /// Indicates decoded weather histories are cached in the weather data directory.
+    pub history_cache: bool,
+    /// The timezone suggested for new locations.
+    pub timezone: Option<String>,
+    /// The units of the weather history service data.
+    pub units: String,
+    /// The weather history service end-point.
+    pub endpoint: String,
+    /// The US Cities data filename.
+    pub us_cities: String,
+    /// The descriptions of configuration values that are not valid.
+    pub problems: Vec<String>,
+}
+impl ConfigCheck {
+    /// Query if the configuration does not have any problems.
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -353,4 +487,119 @@ mod tests {
         assert_eq!(from, "2022-07-01");
         assert_eq!(to, "2022-07-02");
     }
+
+    #[test]
+    fn validate() {
+        let history = |date: NaiveDate| History { alias: "test".to_string(), date, ..Default::default() };
+        let testcase = History {
+            temperature_high: Some(80.0),
+            temperature_low: Some(60.0),
+            humidity: Some(0.35),
+            wind_direction: Some(270),
+            moon_phase: Some(1.0),
+            ..history(get_date(2024, 7, 1))
+        };
+        assert!(validate_history(&testcase).is_empty());
+        let testcase = History {
+            temperature_high: Some(60.0),
+            temperature_low: Some(80.0),
+            humidity: Some(1.2),
+            wind_direction: Some(-10),
+            uv_index: Some(-1.0),
+            ..history(get_date(2024, 7, 2))
+        };
+        let warnings = validate_history(&testcase);
+        assert_eq!(warnings.len(), 4);
+        assert_eq!(warnings[0], "2024-07-02 humidity 1.2 is out of bounds.");
+        assert!(warnings[1].contains("wind_direction"));
+        assert!(warnings[2].contains("uv_index"));
+        assert_eq!(warnings[3], "2024-07-02 temperature_low 80 is above temperature_high 60.");
+    }
+
+    #[test]
+    fn add_history_summary() {
+        let summary = |location_id: &str, count, overall_size, raw_size, compressed_size| HistorySummary {
+            location_id: location_id.to_string(),
+            count,
+            overall_size,
+            raw_size,
+            compressed_size,
+        };
+        let testcase = summary("north", 2, Some(100), None, None) + summary("south", 3, None, Some(50), None);
+        assert_eq!(testcase.location_id, "north");
+        assert_eq!(testcase.count, 5);
+        assert_eq!(testcase.overall_size, Some(100));
+        assert_eq!(testcase.raw_size, Some(50));
+        assert_eq!(testcase.compressed_size, None);
+        let mut testcase = testcase;
+        testcase += summary("middle", 1, Some(10), Some(20), Some(5));
+        assert_eq!(testcase.count, 6);
+        assert_eq!(testcase.overall_size, Some(110));
+        assert_eq!(testcase.raw_size, Some(70));
+        assert_eq!(testcase.compressed_size, Some(5));
+        let testcase = vec![summary("a", 1, None, None, Some(1)), summary("b", 1, None, None, Some(2))]
+            .into_iter()
+            .reduce(|total, summary| total + summary)
+            .unwrap();
+        assert_eq!((testcase.count, testcase.overall_size, testcase.compressed_size), (2, None, Some(3)));
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde {
+        use super::*;
+        use serde_json::{from_str, to_string};
+
+        #[test]
+        fn location() {
+            let location = Location {
+                name: "Somewhere, USA".to_string(),
+                alias: "somewhere".to_string(),
+                longitude: "-122.3".to_string(),
+                latitude: "47.6".to_string(),
+                tz: "America/Los_Angeles".to_string(),
+            };
+            let testcase: Location = from_str(&to_string(&location).unwrap()).unwrap();
+            assert_eq!(testcase.name, location.name);
+            assert_eq!(testcase.alias, location.alias);
+            assert_eq!(testcase.longitude, location.longitude);
+            assert_eq!(testcase.latitude, location.latitude);
+            assert_eq!(testcase.tz, location.tz);
+        }
+
+        #[test]
+        fn date_range() {
+            let date_range = DateRange::new(get_date(2024, 2, 1), get_date(2024, 2, 29));
+            let json = to_string(&date_range).unwrap();
+            assert_eq!(json, r#"{"from":"2024-02-01","to":"2024-02-29"}"#);
+            let testcase: DateRange = from_str(&json).unwrap();
+            assert_eq!(testcase.from, date_range.from);
+            assert_eq!(testcase.to, date_range.to);
+        }
+
+        #[test]
+        fn history() {
+            let history = History {
+                alias: "somewhere".to_string(),
+                date: get_date(2024, 2, 1),
+                temperature_high: Some(52.5),
+                temperature_low: Some(31.0),
+                precipitation_type: Some("rain".to_string()),
+                wind_direction: Some(270),
+                sunrise: get_date(2024, 2, 1).and_hms_opt(7, 30, 0),
+                ..Default::default()
+            };
+            let json = to_string(&history).unwrap();
+            let testcase: History = from_str(&json).unwrap();
+            assert_eq!(testcase.alias, history.alias);
+            assert_eq!(testcase.date, history.date);
+            assert_eq!(testcase.temperature_high, history.temperature_high);
+            assert_eq!(testcase.temperature_low, history.temperature_low);
+            assert_eq!(testcase.temperature_mean, None);
+            assert_eq!(testcase.precipitation_type, history.precipitation_type);
+            assert_eq!(testcase.wind_direction, history.wind_direction);
+            assert_eq!(testcase.sunrise, history.sunrise);
+            assert_eq!(testcase.sunset, None);
+            assert_eq!(to_string(&testcase).unwrap(), json);
+        }
+    }
 }
@@ -1,6 +1,7 @@
 //! The source of weather history for locations.
 
 use std::fmt::Debug;
+use std::sync::{atomic::AtomicBool, Arc};
 use super::*;
 use backend::Config;
 use entities::{DailyHistories, DateRange, Location};
@@ -17,6 +18,10 @@ mod rest_client;
 
 mod timeline_client;
 
+/// The units of weather history, temperatures are Fahrenheit and precipitation is in inches.
+///
+pub(crate) const UNITS: &str = "us";
+
 /// Creates a history client.
 ///
 /// # Arguments
@@ -49,4 +54,8 @@ pub trait HistoryClient: Debug {
     /// Get the request result by blocking until it finishes.
     ///
     fn get(&self) -> Result<DailyHistories>;
+    /// Get the flag that stops the active request from being retried once it is set. The flag can be
+    /// set from another thread while [get](HistoryClient::get()) is blocked.
+    ///
+    fn cancel_handle(&self) -> Arc<AtomicBool>;
 }
@@ -11,12 +11,16 @@ mod timeline_client {
     use super::*;
     use std::cell::RefCell;
     use std::fmt::Formatter;
+    use std::sync::{atomic::AtomicBool, Arc};
+    use std::time::Duration;
 
     #[derive(Debug)]
     /// The current timeline client request location and client handle.
     struct ActiveRequest {
         /// The location associated with the request.
         location: Location,
+        /// The history dates associated with the request.
+        date_range: DateRange,
         /// The Rest client handle.
         client_handle: RestClientHandle,
     }
@@ -33,6 +37,8 @@ mod timeline_client {
         api_key: String,
         /// The currently active request.
         active_request: RefCell<Option<ActiveRequest>>,
+        /// Stops the active request from being retried, a new flag is created for each request.
+        cancel: RefCell<Arc<AtomicBool>>,
     }
     impl std::fmt::Debug for TimelineClient {
         /// Show all the attributes except the API client and API key.
@@ -44,6 +50,10 @@ mod timeline_client {
         }
     }
     impl TimelineClient {
+        /// The number of times a request will be tried before giving up.
+        const RETRY_ATTEMPTS: usize = 3;
+        /// How long to wait before the first request retry.
+        const RETRY_DELAY: Duration = Duration::from_millis(500);
         /// Creates a new instance of the HTTP client metadata.
         ///
         /// # Arguments
@@ -68,6 +78,7 @@ mod timeline_client {
                         url,
                         api_key: config.visual_crossing.api_key.clone(),
                         active_request: Default::default(),
+                        cancel: Default::default(),
                     }),
                 },
             }
@@ -97,7 +108,7 @@ mod timeline_client {
                     }
                     // add the query parameters
                     let builder = self.rest_client.get(url).query(&[
-                        ("unitGroup", "us"),
+                        ("unitGroup", UNITS),
                         ("include", "days"),
                         ("key", &self.api_key),
                     ]);
@@ -125,6 +136,7 @@ mod timeline_client {
             debug_assert!(self.active_request.borrow().is_none(), "Request already active.");
             let request = self.create_request(location, date_range)?;
             let client_handle = self.rest_client.execute(request);
+            self.cancel.replace(Arc::default());
             self.active_request.borrow_mut().replace(ActiveRequest {
                 location: Location {
                     name: location.name.to_string(),
@@ -133,6 +145,7 @@ mod timeline_client {
                     latitude: location.latitude.to_string(),
                     tz: location.tz.to_string(),
                 },
+                date_range: DateRange::new(date_range.from, date_range.to),
                 client_handle,
             });
             Ok(())
@@ -148,16 +161,54 @@ mod timeline_client {
         }
         /// Get the result by blocking until the request finishes.
         ///
+        /// Requests that fail because of a transient error are retried until the
+        /// [cancel handle](HistoryClient::cancel_handle()) is set.
+        ///
         fn get(&self) -> Result<DailyHistories> {
             match self.active_request.borrow_mut().take() {
                 // None => ControlFlow::Break(Err(Error::from("There is no active request."))),
                 None => Err(Error::from("There is no active request.")),
-                Some(active_request) => match active_request.client_handle.get() {
-                    RestClientResult::Body(body) => map_body(active_request.location, body),
-                    client_result => map_client_error(&active_request.location, client_result),
-                },
+                Some(ActiveRequest { location, date_range, client_handle }) => {
+                    let mut client_handle = Some(client_handle);
+                    let (attempts, delay, cancel) = (Self::RETRY_ATTEMPTS, Self::RETRY_DELAY, self.cancel_handle());
+                    let client_result = toolslib::retry_cancelable(attempts, delay, &cancel, |_| {
+                        let client_result = match client_handle.take() {
+                            Some(client_handle) => client_handle.get(),
+                            None => match self.create_request(&location, &date_range) {
+                                Ok(request) => self.rest_client.execute(request).get(),
+                                Err(err) => RestClientResult::ExecuteError(err.to_string()),
+                            },
+                        };
+                        match is_transient(&client_result) {
+                            true => Err(client_result),
+                            false => Ok(client_result),
+                        }
+                    });
+                    match client_result.unwrap_or_else(|client_result| client_result) {
+                        RestClientResult::Body(body) => map_body(location, body),
+                        client_result => map_client_error(&location, client_result),
+                    }
+                }
             }
         }
+        /// Get the flag that stops the active request from being retried once it is set.
+        ///
+        fn cancel_handle(&self) -> Arc<AtomicBool> {
+            self.cancel.borrow().clone()
+        }
+    }
+
+    /// Identifies Rest client results that might succeed if the request is tried again.
+    ///
+    /// # Arguments
+    ///
+    /// - `client_result` is the Rest client result.
+    fn is_transient(client_result: &RestClientResult) -> bool {
+        match client_result {
+            RestClientResult::ExecuteError(_) | RestClientResult::ResponseError(_) => true,
+            RestClientResult::HttpStatusCode(code) => *code >= 500,
+            _ => false,
+        }
     }
 
     /// Convert the response body into the daily histories.
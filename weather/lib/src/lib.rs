@@ -42,28 +42,68 @@
 /// The library result.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// The category of a library error.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ErrorKind {
+    /// The error has not been categorized.
+    #[default]
+    Other,
+    /// Something being used does not exist.
+    NotFound,
+    /// Something being created already exists.
+    AlreadyExists,
+    /// The content of something is not valid.
+    Corrupt,
+    /// There was a problem reading or writing something.
+    Io,
+    /// Data could not be converted from its stored format.
+    Decode,
+    /// The weather data was opened read-only.
+    ReadOnly,
+}
+
 /// The library error.
 #[derive(Debug)]
-pub struct Error(String);
+pub struct Error {
+    /// The category of error.
+    kind: ErrorKind,
+    /// A description of what happened.
+    message: String,
+}
+impl Error {
+    /// Create an error of a specific kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` is the category of error.
+    /// * `message` describes what happened.
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self { kind, message: message.into() }
+    }
+    /// Get the category of error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+}
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.message)
     }
 }
 impl From<String> for Error {
     /// Create an error from the provided string.
     fn from(error: String) -> Self {
-        Error(error)
+        Error::new(ErrorKind::Other, error)
     }
 }
 impl From<&str> for Error {
     /// Create an error from the provided string.
     fn from(error: &str) -> Self {
-        Error(error.to_string())
+        Error::new(ErrorKind::Other, error)
     }
 }
 
-pub use weather_data::create_weather_data;
+pub use weather_data::{check_config, create_weather_data};
 mod weather_data;
 
 mod backend;
@@ -77,19 +117,29 @@ pub mod prelude {
     pub use crate::{
         weather_data::WeatherData,
         entities::{
-            DailyHistories, DataCriteria, DateRange, DateRanges, History, HistoryDates, HistorySummaries,
-            HistorySummary, Location, LocationCriteria,
+            validate_history, ConfigCheck, DailyHistories, DataCriteria, DateRange, DateRanges, History,
+            HistoryDates, HistoryFailures, HistorySummaries, HistorySummary, Location,
+            LocationCriteria,
         },
         history_client::HistoryClient,
     };
 }
 
+/// The in-memory weather data used to test code that uses the weather data `API`.
+#[cfg(any(test, feature = "test-support"))]
+pub mod test_support {
+    pub use crate::backend::mock::MockDataAPI;
+}
+
 mod admin;
 
 /// The public administration data structures.
 pub mod admin_prelude {
     pub use super::admin::{
-        admin_entities::{Components, DbDetails, DbMode, FilesysDetails, LocationDetails, UsCitiesInfo},
+        admin_entities::{
+            BatchLocationReport, BatchReport, CompactionReport, Components, DbDetails, DbMode, FilesysDetails,
+            IntegrityIssue, LocationDetails, NormalizeReport, UsCitiesInfo,
+        },
         create_weather_admin, weather_admin, WeatherAdmin,
     };
 }
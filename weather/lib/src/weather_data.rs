@@ -1,21 +1,48 @@
 //! The new version of the weather data API.
 use super::{backend, Result};
 use crate::prelude::{
-    DailyHistories, DataCriteria, DateRange, HistoryClient, HistoryDates, HistorySummaries, Location, LocationCriteria,
+    ConfigCheck, DailyHistories, DataCriteria, DateRange, History, HistoryClient, HistoryDates, HistoryFailures,
+    HistorySummaries, HistorySummary, Location, LocationCriteria,
 };
-use std::path::PathBuf;
+use chrono::{Datelike, NaiveDate};
+use std::{collections::BTreeMap, path::PathBuf};
 use toolslib::stopwatch::StopWatch;
 
 /// Creates the weather data `API` depending on the backend configuration.
 ///
 /// # Arguments
 ///
+/// * `config_file` is the weather data configuration filename.
 /// * `dirname` is the weather data directory name.
-pub fn create_weather_data(config_file: Option<PathBuf>, dirname: Option<PathBuf>, no_db: bool) -> Result<WeatherData> {
-    let data_api = backend::data_api(config_file, dirname, no_db)?;
+/// * `no_db` is used to force using the archive implementation of weather data.
+/// * `read_only` when `true` prevents the weather data from being modified.
+pub fn create_weather_data(
+    config_file: Option<PathBuf>,
+    dirname: Option<PathBuf>,
+    no_db: bool,
+    read_only: bool,
+) -> Result<WeatherData> {
+    let data_api = backend::data_api(config_file, dirname, no_db, read_only)?;
     Ok(WeatherData(data_api))
 }
 
+/// Get the fully resolved weather data configuration without opening weather data.
+///
+/// # Arguments
+///
+/// * `config_file` is the weather data configuration filename.
+/// * `dirname` is the weather data directory name.
+/// * `no_db` is used to force using the archive implementation of weather data.
+/// * `read_only` when `true` prevents the weather data from being modified.
+pub fn check_config(
+    config_file: Option<PathBuf>,
+    dirname: Option<PathBuf>,
+    no_db: bool,
+    read_only: bool,
+) -> Result<ConfigCheck> {
+    backend::check_config(config_file, dirname, no_db, read_only)
+}
+
 macro_rules! log_elapsed {
     ($what:expr, $stopwatch:expr) => {
         log::info!("WeatherData: {} {}", $what, $stopwatch)
@@ -27,6 +54,13 @@ pub struct WeatherData(
     /// The weather data implementation.
     backend::DataAPI,
 );
+#[cfg(any(test, feature = "test-support"))]
+impl From<backend::mock::MockDataAPI> for WeatherData {
+    /// Create the weather data `API` using in-memory weather data.
+    fn from(mock_data_api: backend::mock::MockDataAPI) -> Self {
+        WeatherData(backend::DataAPI::from(mock_data_api))
+    }
+}
 impl WeatherData {
     /// Add weather data history for a location.
     ///
@@ -35,7 +69,26 @@ impl WeatherData {
     /// - `histories` has the location and histories to add.
     ///
     pub fn add_histories(&self, daily_histories: DailyHistories) -> Result<usize> {
-        self.0.add_daily_histories(daily_histories)
+        self.0.add_daily_histories(daily_histories, &mut |_, _| ())
+    }
+    /// Add weather data history for a location reporting progress as histories are written.
+    ///
+    /// # Arguments
+    ///
+    /// - `histories` has the location and histories to add.
+    /// - `progress` is called with the count of histories added and the count being added.
+    ///
+    pub fn add_histories_with_progress(
+        &self,
+        daily_histories: DailyHistories,
+        progress: &mut dyn FnMut(usize, usize),
+    ) -> Result<usize> {
+        self.0.add_daily_histories(daily_histories, progress)
+    }
+    /// Get the timezone suggested for new locations.
+    ///
+    pub fn default_timezone(&self) -> Option<&str> {
+        self.0.get_config().weather_data.timezone.as_deref()
     }
     /// Get the client that retrieves weather history for a location.
     ///
@@ -57,6 +110,109 @@ impl WeatherData {
         log_elapsed!("get_daily_history", &stopwatch);
         Ok(daily_history)
     }
+    /// Get daily weather history for a location along with the histories that could not be decoded.
+    ///
+    /// It is an error if more than 1 location is found. Histories that cannot be decoded do not fail
+    /// the request, their dates and the reason are returned instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` identifies the location.
+    /// * `history_range` covers the history dates returned.
+    ///
+    pub fn get_daily_history_with_failures(
+        &self,
+        criteria: DataCriteria,
+        history_range: DateRange,
+    ) -> Result<(DailyHistories, HistoryFailures)> {
+        let stopwatch = StopWatch::start_new();
+        let daily_history = self.0.get_daily_history_with_failures(criteria, history_range)?;
+        log_elapsed!("get_daily_history_with_failures", &stopwatch);
+        Ok(daily_history)
+    }
+    /// Get daily weather history for a location and check the histories for anomalous values.
+    ///
+    /// It is an error if more than 1 location is found. Anomalous values are returned as warnings and do
+    /// not fail the request. Like [get_daily_history_with_failures](Self::get_daily_history_with_failures)
+    /// the histories that could not be decoded are also returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` identifies the location.
+    /// * `history_range` covers the history dates returned.
+    ///
+    pub fn get_validated_daily_history(
+        &self,
+        criteria: DataCriteria,
+        history_range: DateRange,
+    ) -> Result<(DailyHistories, HistoryFailures, Vec<String>)> {
+        let stopwatch = StopWatch::start_new();
+        let daily_history = self.0.get_validated_daily_history(criteria, history_range)?;
+        log_elapsed!("get_validated_daily_history", &stopwatch);
+        Ok(daily_history)
+    }
+    /// Get daily weather history for a location grouped by calendar year.
+    ///
+    /// It is an error if more than 1 location is found. The histories for each year are in date order.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` identifies the location.
+    /// * `history_range` covers the history dates returned.
+    ///
+    pub fn get_histories_by_year(
+        &self,
+        criteria: DataCriteria,
+        history_range: DateRange,
+    ) -> Result<BTreeMap<i32, Vec<History>>> {
+        let stopwatch = StopWatch::start_new();
+        let daily_histories = self.0.get_daily_history(criteria, history_range)?;
+        let mut histories_by_year: BTreeMap<i32, Vec<History>> = BTreeMap::new();
+        for history in daily_histories.histories {
+            histories_by_year.entry(history.date.year()).or_default().push(history);
+        }
+        log_elapsed!("get_histories_by_year", &stopwatch);
+        Ok(histories_by_year)
+    }
+    /// Check if a location has weather history for a date.
+    ///
+    /// # Arguments
+    ///
+    /// * `location_id` is the location identifier (alias).
+    /// * `date` is the weather history date.
+    ///
+    pub fn contains_history(&self, location_id: &str, date: &NaiveDate) -> Result<bool> {
+        let stopwatch = StopWatch::start_new();
+        let contains = self.0.contains_history(location_id, date)?;
+        log_elapsed!("contains_history", &stopwatch);
+        Ok(contains)
+    }
+    /// Get the number of weather histories a location has.
+    ///
+    /// # Arguments
+    ///
+    /// * `location_id` is the location identifier (alias).
+    ///
+    pub fn count_histories(&self, location_id: &str) -> Result<usize> {
+        let stopwatch = StopWatch::start_new();
+        let count = self.0.count_histories(location_id)?;
+        log_elapsed!("count_histories", &stopwatch);
+        Ok(count)
+    }
+    /// Get the names of the history fields that have a value for a location.
+    ///
+    /// Histories can be sparse, fields that never have a value are not included.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` identifies the location.
+    ///
+    pub fn available_fields(&self, criteria: DataCriteria) -> Result<Vec<String>> {
+        let stopwatch = StopWatch::start_new();
+        let fields = self.0.available_fields(criteria)?;
+        log_elapsed!("available_fields", &stopwatch);
+        Ok(fields)
+    }
     /// Get the history dates for locations.
     ///
     /// # Arguments
@@ -81,6 +237,18 @@ impl WeatherData {
         log_elapsed!("get_history_summary", &stopwatch);
         Ok(history_summary)
     }
+    /// Get a single summary of the weather data across all locations.
+    ///
+    /// # Arguments
+    ///
+    /// * `criteria` identifies the locations.
+    ///
+    pub fn get_total_summary(&self, criteria: DataCriteria) -> Result<HistorySummary> {
+        let stopwatch = StopWatch::start_new();
+        let total_summary = self.0.get_total_summary(criteria)?;
+        log_elapsed!("get_total_summary", &stopwatch);
+        Ok(total_summary)
+    }
     /// Get the weather location metadata.
     ///
     /// # Arguments
@@ -93,6 +261,32 @@ impl WeatherData {
         log_elapsed!("get_locations", &stopwatch);
         Ok(locations)
     }
+    /// Get the weather locations whose name or alias match a regular expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` is the regular expression locations must match.
+    /// * `icase` when `true` the regular expression will ignore case.
+    /// * `sort` when `true` the locations will be sorted by name.
+    ///
+    pub fn get_locations_regex(&self, pattern: &str, icase: bool, sort: bool) -> Result<Vec<Location>> {
+        let stopwatch = StopWatch::start_new();
+        let locations = self.0.get_locations_regex(pattern, icase, sort)?;
+        log_elapsed!("get_locations_regex", &stopwatch);
+        Ok(locations)
+    }
+    /// Get a weather location by its identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` is the location identifier (alias) that must match exactly.
+    ///
+    pub fn get_location_by_id(&self, id: &str) -> Result<Location> {
+        let stopwatch = StopWatch::start_new();
+        let location = self.0.get_location_by_id(id)?;
+        log_elapsed!("get_location_by_id", &stopwatch);
+        Ok(location)
+    }
     /// Search for locations that can be added to weather data.
     ///
     /// # Arguments